@@ -17,7 +17,7 @@ pub fn get_connections(
         }
     }
 
-    db.list_connections(tag_filter.as_deref(), false)
+    db.list_connections(tag_filter.as_deref(), false, false)
         .map_err(|e| e.to_string())
 }
 
@@ -98,6 +98,18 @@ pub fn edit_connection(
         created_at: existing.created_at,
         last_used: existing.last_used,
         tags: Vec::new(),
+        archived: existing.archived,
+        host_key_fingerprint: existing.host_key_fingerprint,
+        expires_at: existing.expires_at,
+        ssh_config_file: existing.ssh_config_file,
+        env: existing.env,
+        send_env: existing.send_env,
+        last_error: existing.last_error,
+        last_error_at: existing.last_error_at,
+        proxy_command: existing.proxy_command,
+        auto_attach: existing.auto_attach,
+        use_count: existing.use_count,
+        password_ref: existing.password_ref,
         aliases: existing.aliases,
     };
 
@@ -110,9 +122,9 @@ pub fn edit_connection(
 
 #[tauri::command]
 pub fn remove_connection(id_or_name: String) -> Result<(), String> {
-    let (db, _config) = get_db_and_config()?;
+    let (db, config) = get_db_and_config()?;
 
-    db.remove_connection(&id_or_name)
+    db.remove_connection(&id_or_name, config.retain_sessions_on_delete)
         .map_err(|e| e.to_string())?;
     Ok(())
 }
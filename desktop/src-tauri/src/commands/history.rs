@@ -13,6 +13,6 @@ pub fn get_history(limit: Option<usize>) -> Result<Vec<SessionHistoryEntry>, Str
     let (db, config) = get_db_and_config()?;
 
     let effective_limit = limit.unwrap_or(config.max_history_size.max(1));
-    db.get_session_history(None, effective_limit, None, false)
+    db.get_session_history(None, effective_limit, None, None, false, None)
         .map_err(|e| e.to_string())
 }
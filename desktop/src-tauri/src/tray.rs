@@ -20,7 +20,7 @@ pub fn build_tray_menu(app: &AppHandle) -> tauri::Result<Menu<tauri::Wry>> {
     let mut has_connections = false;
 
     if let Ok((db, _config)) = get_db_and_config() {
-        if let Ok(conns) = db.list_connections(None, false) {
+        if let Ok(conns) = db.list_connections(None, false, false) {
             for conn in conns.iter().take(10) {
                 let id = format!("connect:{}", conn.name);
                 if let Ok(item) = MenuItemBuilder::new(&conn.name).id(id).build(app) {
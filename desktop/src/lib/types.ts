@@ -11,6 +11,15 @@ export interface Connection {
   created_at: string;
   last_used?: string;
   tags: string[];
+  archived?: boolean;
+  host_key_fingerprint?: string;
+  expires_at?: string;
+  ssh_config_file?: string;
+  env?: [string, string][];
+  send_env?: string[];
+  last_error?: string;
+  last_error_at?: string;
+  proxy_command?: string;
 }
 
 export interface EnvInfo {
@@ -25,6 +34,7 @@ export interface SessionHistoryEntry {
   status: string | { Error: string };
   exit_code?: number;
   duration?: number;
+  label?: string;
 }
 
 export interface ConnectionStats {
@@ -32,6 +42,20 @@ export interface ConnectionStats {
   most_used?: Connection;
   recently_used: Connection[];
   by_tag: Record<string, number>;
+  total_sessions: number;
+  success_rate: number;
+  average_session_secs?: number;
+  busiest_weekday?: string;
+  kerberos_sessions: number;
+  direct_sessions: number;
+  usage?: ConnectionUsage[];
+}
+
+export interface ConnectionUsage {
+  name: string;
+  week: number;
+  month: number;
+  all_time: number;
 }
 
 export interface DesktopSettings {
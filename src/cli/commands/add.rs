@@ -1,11 +1,13 @@
 use crate::config::AppConfig;
+use crate::models::Connection;
 use crate::services::SshService;
 use anyhow::Result;
+use std::path::PathBuf;
 use tracing::info;
 
 #[allow(clippy::too_many_arguments)]
 pub async fn execute(
-    name: String,
+    name: Option<String>,
     host: String,
     user: Option<String>,
     port: Option<u16>,
@@ -15,11 +17,23 @@ pub async fn execute(
     bastion_user: Option<String>,
     key: Option<String>,
     tags: Vec<String>,
+    expires: Option<chrono::Duration>,
+    ssh_config: Option<PathBuf>,
+    set_env: Vec<(String, String)>,
+    send_env: Vec<String>,
+    proxy_command: Option<String>,
+    auto_attach: Option<String>,
     config: AppConfig,
 ) -> Result<()> {
+    let ssh_service = SshService::new(config)?;
+    let derived = name.is_none();
+    let name = match name {
+        Some(name) => name,
+        None => free_name(&ssh_service, &Connection::derive_name(&host)).await?,
+    };
     info!("Adding new connection: {} -> {}", name, host);
 
-    let ssh_service = SshService::new(config)?;
+    let expires_at = expires.map(|ttl| chrono::Utc::now() + ttl);
 
     ssh_service
         .add_connection(
@@ -33,10 +47,36 @@ pub async fn execute(
             bastion_user,
             key,
             tags,
+            expires_at,
+            ssh_config,
+            set_env,
+            send_env,
+            proxy_command,
+            auto_attach,
         )
         .await?;
 
     println!("✅ Connection '{}' added successfully!", name);
+    if derived {
+        println!("   Rename it with: bssh edit {} --name <NAME>", name);
+    }
+    if let Some(at) = expires_at {
+        println!(
+            "⏳ Expires {} ('bssh prune --expired' removes it after that)",
+            at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")
+        );
+    }
 
     Ok(())
 }
+
+/// `base`, or `base-2`, `base-3`, ... when a connection already has that name
+async fn free_name(ssh_service: &SshService, base: &str) -> Result<String> {
+    let mut name = base.to_string();
+    let mut n = 1;
+    while ssh_service.get_connection(&name).await?.is_some() {
+        n += 1;
+        name = format!("{}-{}", base, n);
+    }
+    Ok(name)
+}
@@ -1,10 +1,12 @@
 //! Alias command implementation - manage connection aliases
 
-use crate::cli::utils::fuzzy_select_connection;
+use crate::cli::output::{csv_table, OutputFormat};
+use crate::cli::utils::select_target;
 use crate::config::AppConfig;
 use crate::database::Database;
 use crate::services::SshService;
 use anyhow::{bail, Result};
+use serde::Serialize;
 
 /// Execute the alias command
 pub async fn execute(action: AliasAction, config: AppConfig) -> Result<()> {
@@ -17,8 +19,8 @@ pub async fn execute(action: AliasAction, config: AppConfig) -> Result<()> {
         AliasAction::Remove { alias } => {
             remove_alias(&db, &alias)?;
         }
-        AliasAction::List { target } => {
-            list_aliases(&db, target.as_deref(), &config).await?;
+        AliasAction::List { pattern, output } => {
+            list_aliases(&db, pattern.as_deref(), output)?;
         }
     }
 
@@ -27,12 +29,50 @@ pub async fn execute(action: AliasAction, config: AppConfig) -> Result<()> {
 
 #[derive(Debug, Clone)]
 pub enum AliasAction {
-    Add { alias: String, target: String },
-    Remove { alias: String },
-    List { target: Option<String> },
+    Add {
+        alias: String,
+        target: String,
+    },
+    Remove {
+        alias: String,
+    },
+    List {
+        pattern: Option<String>,
+        output: OutputFormat,
+    },
+}
+
+/// One alias as emitted by `bssh alias list --json`
+#[derive(Debug, Serialize)]
+struct AliasRow {
+    alias: String,
+    connection: String,
+    uses: usize,
 }
 
 async fn add_alias(db: &Database, alias: &str, target: &str, config: &AppConfig) -> Result<()> {
+    ensure_alias_free(db, alias)?;
+
+    // Find the target connection
+    let ssh_service = SshService::new(config.clone())?;
+    let connection = match select_target(&ssh_service, target, "alias", true).await? {
+        Some((conn, _)) => conn,
+        None => {
+            bail!("No connection found matching '{}'", target);
+        }
+    };
+
+    // Add the alias
+    db.add_alias(alias, &connection.id.to_string())?;
+
+    println!("✅ Added alias '{}' → '{}'", alias, connection.name);
+    println!("   You can now use: bssh connect {}", alias);
+
+    Ok(())
+}
+
+/// Fail unless `alias` is neither an alias nor a connection name yet
+pub(crate) fn ensure_alias_free(db: &Database, alias: &str) -> Result<()> {
     // Check if alias already exists
     if let Some(existing) = db.get_connection_by_alias(alias)? {
         bail!(
@@ -49,27 +89,6 @@ async fn add_alias(db: &Database, alias: &str, target: &str, config: &AppConfig)
             alias
         );
     }
-
-    // Find the target connection
-    let ssh_service = SshService::new(config.clone())?;
-    let connection = if let Some(conn) = db.get_connection(target)? {
-        conn
-    } else {
-        // Try fuzzy search
-        match fuzzy_select_connection(&ssh_service, target, "alias", true).await? {
-            Some(conn) => conn,
-            None => {
-                bail!("No connection found matching '{}'", target);
-            }
-        }
-    };
-
-    // Add the alias
-    db.add_alias(alias, &connection.id.to_string())?;
-
-    println!("✅ Added alias '{}' → '{}'", alias, connection.name);
-    println!("   You can now use: bssh connect {}", alias);
-
     Ok(())
 }
 
@@ -82,61 +101,84 @@ fn remove_alias(db: &Database, alias: &str) -> Result<()> {
     Ok(())
 }
 
-async fn list_aliases(db: &Database, target: Option<&str>, config: &AppConfig) -> Result<()> {
-    if let Some(target_name) = target {
-        // List aliases for a specific connection
-        let ssh_service = SshService::new(config.clone())?;
-        let connection = if let Some(conn) = db.get_connection(target_name)? {
-            conn
-        } else {
-            match fuzzy_select_connection(&ssh_service, target_name, "show aliases for", true)
-                .await?
-            {
-                Some(conn) => conn,
-                None => {
-                    bail!("No connection found matching '{}'", target_name);
-                }
+fn list_aliases(db: &Database, pattern: Option<&str>, output: OutputFormat) -> Result<()> {
+    let usage = db.get_alias_usage_counts()?;
+    let needle = pattern.map(str::to_lowercase);
+    let rows: Vec<AliasRow> = db
+        .list_all_aliases()?
+        .into_iter()
+        .filter(|(alias, connection, _)| match &needle {
+            Some(needle) => {
+                alias.to_lowercase().contains(needle) || connection.to_lowercase().contains(needle)
             }
-        };
+            None => true,
+        })
+        .map(|(alias, connection, connection_id)| AliasRow {
+            uses: usage
+                .get(&(connection_id, alias.clone()))
+                .copied()
+                .unwrap_or(0),
+            alias,
+            connection,
+        })
+        .collect();
+
+    match output {
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&rows)?);
+            return Ok(());
+        }
+        OutputFormat::Csv => {
+            print!(
+                "{}",
+                csv_table(
+                    &["alias", "connection", "uses"],
+                    rows.iter().map(|row| vec![
+                        row.alias.clone(),
+                        row.connection.clone(),
+                        row.uses.to_string(),
+                    ]),
+                )
+            );
+            return Ok(());
+        }
+        OutputFormat::Human => {}
+    }
 
-        let aliases = db.get_aliases_for_connection(&connection.id.to_string())?;
+    match pattern {
+        Some(pattern) => println!("📝 Aliases matching '{}'\n", pattern),
+        None => println!("📝 All Connection Aliases\n"),
+    }
 
-        if aliases.is_empty() {
-            println!("📝 No aliases for connection '{}'", connection.name);
-            println!(
-                "   Use 'bssh alias add <alias> {}' to create one",
-                connection.name
-            );
+    if rows.is_empty() {
+        if pattern.is_some() {
+            println!("  No matching aliases.");
         } else {
-            println!("📝 Aliases for '{}':", connection.name);
-            for alias in &aliases {
-                println!("   • {}", alias);
-            }
+            println!("  No aliases defined yet.");
+            println!("\n  Use 'bssh alias add <alias> <connection>' to create aliases.");
         }
-    } else {
-        // List all aliases
-        let connections = db.list_connections(None, false)?;
-        let mut found_any = false;
-
-        println!("📝 All Connection Aliases\n");
-
-        for conn in connections {
-            let aliases = db.get_aliases_for_connection(&conn.id.to_string())?;
-            if !aliases.is_empty() {
-                found_any = true;
-                println!("  {} ({})", conn.name, conn.host);
-                for alias in &aliases {
-                    println!("    └─ {}", alias);
-                }
+        return Ok(());
+    }
+
+    // Rows are ordered by connection, so each group is contiguous.
+    for (i, row) in rows.iter().enumerate() {
+        if i == 0 || rows[i - 1].connection != row.connection {
+            if i > 0 {
                 println!();
             }
+            println!("  {}", row.connection);
         }
-
-        if !found_any {
-            println!("  No aliases defined yet.");
-            println!("\n  Use 'bssh alias add <alias> <connection>' to create aliases.");
-        }
+        println!("    └─ {} ({})", row.alias, format_uses(row.uses));
     }
+    println!();
 
     Ok(())
 }
+
+/// Render how many sessions were started through an alias
+fn format_uses(uses: usize) -> String {
+    match uses {
+        1 => "used once".to_string(),
+        n => format!("used {} times", n),
+    }
+}
@@ -0,0 +1,37 @@
+use crate::cli::utils::resolve_connection;
+use crate::config::AppConfig;
+use crate::services::SshService;
+use anyhow::Result;
+use tracing::info;
+
+/// Archive (`archived = true`) or restore a connection
+pub async fn execute(target: String, archived: bool, config: AppConfig) -> Result<()> {
+    let action = if archived { "archive" } else { "unarchive" };
+    info!("Setting archived={} on connection: {}", archived, target);
+
+    let ssh_service = SshService::new(config)?;
+    let connection = resolve_connection(&ssh_service, &target, action, false).await?;
+
+    if connection.archived == archived {
+        println!(
+            "ℹ️  Connection '{}' is already {}",
+            connection.name,
+            if archived { "archived" } else { "active" }
+        );
+        return Ok(());
+    }
+
+    ssh_service.set_archived(&connection, archived).await?;
+
+    if archived {
+        println!("📦 Connection '{}' archived", connection.name);
+        println!(
+            "💡 Hidden from list and search; 'bssh unarchive {}' restores it",
+            connection.name
+        );
+    } else {
+        println!("✅ Connection '{}' restored", connection.name);
+    }
+
+    Ok(())
+}
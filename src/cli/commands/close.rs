@@ -1,15 +1,29 @@
 //! Close command implementation - manage active sessions
 
-use crate::cli::utils::confirm;
+use crate::cli::utils::{assumed_yes, confirm};
 use crate::config::AppConfig;
 use crate::database::Database;
-use anyhow::Result;
+use anyhow::{bail, Result};
 use nix::sys::signal::{kill, Signal};
 use nix::unistd::Pid;
+use std::collections::HashMap;
+use std::io::{self, IsTerminal, Write};
+
+type ActiveSession = (String, String, Option<u32>, chrono::DateTime<chrono::Utc>);
+
+/// Which of several sessions to the same target to close
+#[derive(Debug, PartialEq)]
+enum Selection {
+    All,
+    /// 1-based, oldest first
+    One(usize),
+    Cancel,
+}
 
 /// Execute the close command
 pub async fn execute(
     target: Option<String>,
+    number: Option<usize>,
     all: bool,
     cleanup: bool,
     force: bool,
@@ -26,7 +40,7 @@ pub async fn execute(
     }
 
     if let Some(target) = target {
-        return close_session(&db, &target, force);
+        return close_session(&db, &target, number, force);
     }
 
     // No target specified - list active sessions
@@ -42,14 +56,27 @@ fn list_active_sessions(db: &Database) -> Result<()> {
         return Ok(());
     }
 
+    // Number each connection's sessions oldest first, as `close -n` expects
+    let mut per_connection: HashMap<&str, usize> = HashMap::new();
+    let mut numbers: Vec<usize> = sessions
+        .iter()
+        .rev()
+        .map(|(_, name, _, _)| {
+            let n = per_connection.entry(name.as_str()).or_default();
+            *n += 1;
+            *n
+        })
+        .collect();
+    numbers.reverse();
+
     println!("📋 Active Sessions\n");
     println!(
-        "{:<20} {:<10} {:<25} DURATION",
-        "CONNECTION", "PID", "STARTED"
+        "{:<20} {:<4} {:<10} {:<25} DURATION",
+        "CONNECTION", "#", "PID", "STARTED"
     );
-    println!("{}", "─".repeat(70));
+    println!("{}", "─".repeat(75));
 
-    for (conn_name, pid, started_at) in &sessions {
+    for ((_, conn_name, pid, started_at), number) in sessions.iter().zip(numbers) {
         let duration = chrono::Utc::now().signed_duration_since(*started_at);
         let duration_str = format_duration(duration);
         let pid_str = pid
@@ -68,8 +95,9 @@ fn list_active_sessions(db: &Database) -> Result<()> {
         };
 
         println!(
-            "{:<20} {:<10} {:<25} {} {}",
+            "{:<20} {:<4} {:<10} {:<25} {} {}",
             truncate(conn_name, 19),
+            number,
             pid_str,
             started_at.format("%Y-%m-%d %H:%M:%S"),
             duration_str,
@@ -78,24 +106,54 @@ fn list_active_sessions(db: &Database) -> Result<()> {
     }
 
     println!("\n💡 Use 'bssh close <connection>' to close a session");
+    println!("   Use 'bssh close <connection> -n <#>' to close one of several");
     println!("   Use 'bssh close --cleanup' to remove stale sessions");
 
     Ok(())
 }
 
 /// Close a specific session
-fn close_session(db: &Database, target: &str, force: bool) -> Result<()> {
-    let sessions = db.get_active_sessions_for_connection(target)?;
+fn close_session(db: &Database, target: &str, number: Option<usize>, force: bool) -> Result<()> {
+    let mut sessions = db.get_active_sessions_for_connection(target)?;
 
     if sessions.is_empty() {
         println!("❌ No active sessions found for '{}'", target);
         return Ok(());
     }
 
-    for (session_id, conn_name, pid, _) in sessions {
+    // Oldest first, matching the numbers in the listing
+    sessions.reverse();
+    let count = sessions.len();
+    let mut ask = !force;
+    let selection = match number {
+        Some(n) => Selection::One(n),
+        None if count > 1 && !force => {
+            print_numbered(target, &sessions);
+            // Picking from the list is the confirmation
+            ask = false;
+            ask_selection(count)?
+        }
+        None => Selection::All,
+    };
+    let selected: Vec<ActiveSession> = match selection {
+        Selection::All => sessions,
+        Selection::One(n) if (1..=count).contains(&n) => vec![sessions.swap_remove(n - 1)],
+        Selection::One(n) => bail!(
+            "There is no session #{} for '{}' ({} active)",
+            n,
+            target,
+            count
+        ),
+        Selection::Cancel => {
+            println!("Cancelled.");
+            return Ok(());
+        }
+    };
+
+    for (session_id, conn_name, pid, _) in selected {
         if let Some(p) = pid {
             if is_process_running(p) {
-                if !force
+                if ask
                     && !confirm(
                         &format!("Close session for '{}' (PID {})?", conn_name, p),
                         true,
@@ -131,6 +189,62 @@ fn close_session(db: &Database, target: &str, force: bool) -> Result<()> {
     Ok(())
 }
 
+fn print_numbered(target: &str, sessions: &[ActiveSession]) {
+    println!("{} active sessions to {}:", sessions.len(), target);
+    for (i, (_, conn_name, pid, started_at)) in sessions.iter().enumerate() {
+        let duration = chrono::Utc::now().signed_duration_since(*started_at);
+        println!(
+            "  [{}] {:<20} PID {:<8} started {} ({})",
+            i + 1,
+            truncate(conn_name, 19),
+            pid.map(|p| p.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            started_at.format("%Y-%m-%d %H:%M:%S"),
+            format_duration(duration)
+        );
+    }
+}
+
+/// Ask which of `count` sessions to close, until the answer makes sense
+fn ask_selection(count: usize) -> Result<Selection> {
+    let prompt = format!("Close [a]ll / [1-{}] / [c]ancel", count);
+
+    if assumed_yes() {
+        println!("{}: a (assumed)", prompt);
+        return Ok(Selection::All);
+    }
+    if !io::stdin().is_terminal() {
+        bail!(
+            "Choice required but stdin is not a terminal: pass --number N to close one session, or --force to close all"
+        );
+    }
+
+    loop {
+        print!("{}: ", prompt);
+        io::stdout().flush()?;
+        let mut input = String::new();
+        if io::stdin().read_line(&mut input)? == 0 {
+            return Ok(Selection::Cancel);
+        }
+        match parse_selection(&input, count) {
+            Some(selection) => return Ok(selection),
+            None => println!("Enter 'a', 'c' or a number from 1 to {}.", count),
+        }
+    }
+}
+
+fn parse_selection(input: &str, count: usize) -> Option<Selection> {
+    match input.trim().to_lowercase().as_str() {
+        "a" | "all" => Some(Selection::All),
+        "c" | "cancel" | "q" => Some(Selection::Cancel),
+        other => other
+            .parse::<usize>()
+            .ok()
+            .filter(|n| (1..=count).contains(n))
+            .map(Selection::One),
+    }
+}
+
 /// Close all active sessions
 fn close_all_sessions(db: &Database, force: bool) -> Result<()> {
     let sessions = db.get_active_sessions()?;
@@ -153,7 +267,7 @@ fn close_all_sessions(db: &Database, force: bool) -> Result<()> {
     let mut closed = 0;
     let mut cleaned = 0;
 
-    for (_conn_name, pid, _) in &sessions {
+    for (_, _conn_name, pid, _) in &sessions {
         if let Some(p) = pid {
             if is_process_running(*p) {
                 if kill(Pid::from_raw(*p as i32), Signal::SIGTERM).is_ok() {
@@ -181,14 +295,12 @@ fn cleanup_stale_sessions(db: &Database) -> Result<()> {
     let sessions = db.get_active_sessions()?;
     let mut cleaned = 0;
 
-    for (conn_name, pid, _) in &sessions {
+    for (session_id, conn_name, pid, _) in &sessions {
         if let Some(p) = pid {
             if !is_process_running(*p) {
-                if let Some(session_id) = db.get_session_id_by_pid(*p)? {
-                    db.mark_session_terminated(&session_id, -1)?;
-                    cleaned += 1;
-                    println!("🧹 Cleaned stale session: {} (PID {})", conn_name, p);
-                }
+                db.mark_session_terminated(session_id, -1)?;
+                cleaned += 1;
+                println!("🧹 Cleaned stale session: {} (PID {})", conn_name, p);
             }
         }
     }
@@ -203,12 +315,12 @@ fn cleanup_stale_sessions(db: &Database) -> Result<()> {
 }
 
 /// Check if a process is running
-fn is_process_running(pid: u32) -> bool {
+pub(crate) fn is_process_running(pid: u32) -> bool {
     // Try to send signal 0 (doesn't actually send a signal, just checks if process exists)
     kill(Pid::from_raw(pid as i32), None).is_ok()
 }
 
-fn format_duration(duration: chrono::Duration) -> String {
+pub(crate) fn format_duration(duration: chrono::Duration) -> String {
     let secs = duration.num_seconds();
     if secs < 60 {
         format!("{}s", secs)
@@ -219,10 +331,34 @@ fn format_duration(duration: chrono::Duration) -> String {
     }
 }
 
-fn truncate(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
+/// `s` cut to at most `max_len` characters, ending in `…` when shortened
+pub(crate) fn truncate(s: &str, max_len: usize) -> String {
+    if s.chars().count() <= max_len {
         s.to_string()
     } else {
-        format!("{}…", &s[..max_len - 1])
+        let kept: String = s.chars().take(max_len.saturating_sub(1)).collect();
+        format!("{kept}…")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selection_accepts_all_cancel_and_numbers_in_range() {
+        assert_eq!(parse_selection("a\n", 3), Some(Selection::All));
+        assert_eq!(parse_selection(" C ", 3), Some(Selection::Cancel));
+        assert_eq!(parse_selection("2", 3), Some(Selection::One(2)));
+        assert_eq!(parse_selection("0", 3), None);
+        assert_eq!(parse_selection("4", 3), None);
+        assert_eq!(parse_selection("yes", 3), None);
+    }
+
+    #[test]
+    fn truncation_counts_characters_not_bytes() {
+        assert_eq!(truncate("web-prod", 19), "web-prod");
+        assert_eq!(truncate("serveur-édition-01", 10), "serveur-é…");
+        assert_eq!(truncate("日本語のサーバー", 5), "日本語の…");
     }
 }
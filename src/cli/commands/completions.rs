@@ -1,15 +1,105 @@
 use crate::config::AppConfig;
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use clap::CommandFactory;
-use clap_complete::generate;
+use clap_complete::{generate, Shell};
+use std::fs;
 use std::io;
+use std::path::PathBuf;
 
-pub async fn execute(shell: clap_complete::Shell, _config: AppConfig) -> Result<()> {
+const BIN_NAME: &str = "bayesian-ssh";
+
+pub async fn execute(
+    shell: Option<Shell>,
+    install: bool,
+    force: bool,
+    _config: AppConfig,
+) -> Result<()> {
     // Get the CLI app from the current crate
     let mut app = crate::cli::Cli::command();
 
-    // Generate completion script
-    generate(shell, &mut app, "bayesian-ssh", &mut io::stdout());
+    if !install {
+        let shell = shell.context("A shell is required unless --install is given")?;
+        // Generate completion script
+        generate(shell, &mut app, BIN_NAME, &mut io::stdout());
+        return Ok(());
+    }
+
+    let shell = match shell.or_else(Shell::from_env) {
+        Some(shell) => shell,
+        None => bail!("Could not detect your shell from $SHELL; pass it explicitly (e.g. 'bssh completions --install zsh')"),
+    };
+    let path = install_path(shell)?;
+
+    if path.exists() && !force {
+        bail!(
+            "Completion file already exists at {}; use --force to overwrite it",
+            path.display()
+        );
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+
+    let mut script = Vec::new();
+    generate(shell, &mut app, BIN_NAME, &mut script);
+    fs::write(&path, script).with_context(|| format!("Failed to write {}", path.display()))?;
+
+    println!("✅ Installed {} completions to {}", shell, path.display());
+    if shell == Shell::Zsh && !zsh_fpath_dirs().iter().any(|dir| path.starts_with(dir)) {
+        let dir = path
+            .parent()
+            .map(|p| p.display().to_string())
+            .unwrap_or_default();
+        println!(
+            "💡 Add 'fpath=({} $fpath)' before 'compinit' in your ~/.zshrc",
+            dir
+        );
+    }
+    println!("💡 Restart your shell to enable tab-completion");
 
     Ok(())
 }
+
+/// Conventional per-user completion file for `shell`
+fn install_path(shell: Shell) -> Result<PathBuf> {
+    let home = dirs::home_dir().context("Could not determine home directory")?;
+
+    let path = match shell {
+        // bash-completion looks under $XDG_DATA_HOME (default ~/.local/share)
+        Shell::Bash => dirs::data_dir()
+            .unwrap_or_else(|| home.join(".local/share"))
+            .join("bash-completion/completions")
+            .join(BIN_NAME),
+        // First $fpath entry under $HOME, falling back to ~/.zfunc
+        Shell::Zsh => zsh_fpath_dirs()
+            .into_iter()
+            .find(|dir| dir.starts_with(&home))
+            .unwrap_or_else(|| home.join(".zfunc"))
+            .join(format!("_{}", BIN_NAME)),
+        Shell::Fish => dirs::config_dir()
+            .unwrap_or_else(|| home.join(".config"))
+            .join("fish/completions")
+            .join(format!("{}.fish", BIN_NAME)),
+        other => bail!(
+            "--install is not supported for {}; redirect 'bssh completions {}' to a file instead",
+            other,
+            other
+        ),
+    };
+
+    Ok(path)
+}
+
+/// Directories on zsh's `$FPATH`, when it is exported to us
+fn zsh_fpath_dirs() -> Vec<PathBuf> {
+    std::env::var("FPATH")
+        .map(|fpath| {
+            fpath
+                .split(':')
+                .filter(|dir| !dir.is_empty())
+                .map(PathBuf::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
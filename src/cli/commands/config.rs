@@ -1,5 +1,6 @@
-use crate::config::{AppConfig, AppConfigUpdates};
-use anyhow::Result;
+use crate::config::{is_valid_time_format, AppConfig, AppConfigUpdates};
+use anyhow::{bail, Result};
+use std::path::PathBuf;
 use tracing::info;
 
 #[allow(clippy::too_many_arguments)]
@@ -12,10 +13,28 @@ pub async fn execute(
     log_level: Option<String>,
     clear_bastion: bool,
     search_mode: Option<String>,
+    time_format: Option<String>,
+    connect_retries: Option<u32>,
+    theme: Option<String>,
+    default_ssh_config: Option<PathBuf>,
+    clear_default_ssh_config: bool,
+    env_file: Option<PathBuf>,
+    clear_env_file: bool,
+    retain_sessions_on_delete: Option<bool>,
+    prompt_save_adhoc: Option<bool>,
     mut config: AppConfig,
 ) -> Result<()> {
     info!("Updating application configuration");
 
+    if let Some(format) = &time_format {
+        if !is_valid_time_format(format) {
+            bail!(
+                "Invalid time format '{}': use \"relative\", \"rfc3339\" or a strftime pattern",
+                format
+            );
+        }
+    }
+
     // Handle bastion settings:
     // - If --clear-bastion is passed, set to Some(None) to clear
     // - If a value is provided, set to Some(Some(value))
@@ -42,9 +61,31 @@ pub async fn execute(
         auto_save_history: None,
         max_history_size: None,
         search_mode,
+        time_format,
+        connect_retries,
+        theme,
+        default_ssh_config_file: if clear_default_ssh_config {
+            Some(None)
+        } else {
+            default_ssh_config.map(Some)
+        },
+        env_file: if clear_env_file {
+            Some(None)
+        } else {
+            env_file.map(Some)
+        },
+        retain_sessions_on_delete,
+        prompt_save_adhoc,
     };
 
+    // Persist on top of the on-disk config so a one-off `--database`
+    // override is not written back to the environment.
+    let database_path = std::mem::replace(
+        &mut config.database_path,
+        AppConfig::load(Some(config.environment.clone()))?.database_path,
+    );
     config.update(updates)?;
+    config.database_path = database_path;
 
     println!("✅ Configuration updated successfully!");
     println!("\nCurrent settings:");
@@ -76,6 +117,95 @@ pub async fn execute(
             "(simple pattern matching)"
         }
     );
+    println!("  Time format: {}", config.time_format);
+    println!("  Connect retries: {}", config.connect_retries);
+    println!("  Theme: {}", config.theme);
+    println!(
+        "  Default SSH config (-F): {}",
+        config
+            .default_ssh_config_file
+            .as_ref()
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| "None".to_string())
+    );
+    println!(
+        "  Env file for ssh: {}",
+        config
+            .env_file
+            .as_ref()
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| "None".to_string())
+    );
+    println!(
+        "  Keep sessions of removed connections: {}",
+        if config.retain_sessions_on_delete {
+            "Yes"
+        } else {
+            "No"
+        }
+    );
+    println!(
+        "  Offer to save ad-hoc hosts: {}",
+        if config.prompt_save_adhoc {
+            "Yes"
+        } else {
+            "No"
+        }
+    );
+    if config.identity_search_dirs.is_empty() {
+        println!("  Identity search dirs: (disabled)");
+    } else {
+        let dirs: Vec<_> = config
+            .identity_search_dirs
+            .iter()
+            .map(|d| d.display().to_string())
+            .collect();
+        println!("  Identity search dirs: {}", dirs.join(", "));
+    }
+
+    Ok(())
+}
+
+/// `bssh config repair`: rewrite a config file that no longer deserializes
+pub async fn repair(config: AppConfig) -> Result<()> {
+    let config_file = AppConfig::config_file_for_env(&config.environment);
+    if !config_file.exists() {
+        println!(
+            "ℹ️  No config file at {}; defaults are used",
+            config_file.display()
+        );
+        return Ok(());
+    }
+
+    let content = std::fs::read_to_string(&config_file)?;
+    if serde_json::from_str::<AppConfig>(&content).is_ok() {
+        println!("✅ {} is valid; nothing to repair", config_file.display());
+        return Ok(());
+    }
+
+    let (mut recovered, problems) = AppConfig::recover(&content, &config.environment);
+    println!("⚠️  Problems in {}:", config_file.display());
+    for problem in &problems {
+        println!("  - {}", problem);
+    }
+
+    let backup = config_file.with_extension("json.bak");
+    std::fs::write(&backup, &content)?;
+
+    // Same rule as AppConfig::load: the database lives in the environment dir.
+    if let Some(env_dir) = config_file.parent() {
+        if !recovered.database_path.starts_with(env_dir) {
+            recovered.database_path = env_dir.join("history.db");
+        }
+    }
+    recovered.save()?;
+    info!("Repaired config for environment {}", config.environment);
+
+    println!(
+        "\n🔧 Rewrote {} with the recovered settings",
+        config_file.display()
+    );
+    println!("💾 Original saved as {}", backup.display());
 
     Ok(())
 }
@@ -1,8 +1,11 @@
-use crate::cli::utils::resolve_connection;
+use crate::cli::utils::{assumed_yes, confirm, select_target};
 use crate::config::AppConfig;
+use crate::errors::AppError;
 use crate::services::SshService;
 use anyhow::Result;
-use tracing::info;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use tracing::{info, warn};
 
 #[allow(clippy::too_many_arguments)]
 pub async fn execute(
@@ -14,33 +17,83 @@ pub async fn execute(
     no_bastion: bool,
     bastion_user: Option<String>,
     key: Option<String>,
+    tunnels: Vec<String>,
+    label: Option<String>,
+    attach: Option<String>,
+    no_attach: bool,
+    check_port: bool,
+    dry_run: bool,
+    save: Option<bool>,
+    ephemeral: bool,
+    no_record: bool,
+    log: Option<PathBuf>,
+    env_file: Option<PathBuf>,
     config: AppConfig,
 ) -> Result<()> {
     info!("Connecting to target: {}", target);
 
-    let ssh_service = SshService::new(config.clone())?;
+    let mut ssh_service = SshService::new(config.clone())?;
+    if no_record {
+        ssh_service = ssh_service.without_recording();
+    }
+    if let Some(path) = log {
+        // Kept in history, so it must still make sense from another directory.
+        let path = if path.is_relative() {
+            std::env::current_dir()?.join(path)
+        } else {
+            path
+        };
+        ssh_service = ssh_service.with_transcript(path);
+    }
+    if let Some(path) = env_file {
+        ssh_service = ssh_service.with_env_file(path);
+    }
 
-    let connection = match resolve_connection(&ssh_service, &target, "connect to", true).await {
-        Ok(c) => c,
-        Err(_) => {
+    // Exact names and aliases win over fuzzy matching; remember the alias so
+    // the session can be attributed to it. --ephemeral takes the target as a
+    // hostname even if it names a saved connection.
+    let found = if ephemeral {
+        None
+    } else {
+        select_target(&ssh_service, &target, "connect to", true).await?
+    };
+    let mut adhoc = false;
+    let (mut connection, alias_used) = match found {
+        Some(found) => found,
+        None => {
             info!(
                 "Connection not resolved, attempting direct connection to {}",
                 target
             );
-            crate::models::Connection::new(
-                target.clone(),
-                target.clone(),
-                user.clone().unwrap_or_else(|| config.default_user.clone()),
-                port.unwrap_or(config.default_port),
-                bastion.clone(),
-                bastion_user.clone(),
-                kerberos.unwrap_or(config.use_kerberos_by_default),
-                key.clone(),
+            adhoc = true;
+            (
+                crate::models::Connection::new(
+                    target.clone(),
+                    target.clone(),
+                    user.clone().unwrap_or_else(|| config.default_user.clone()),
+                    port.unwrap_or(config.default_port),
+                    bastion.clone(),
+                    bastion_user.clone(),
+                    kerberos.unwrap_or(config.use_kerberos_by_default),
+                    key.clone(),
+                ),
+                None,
             )
         }
     };
 
-    ssh_service
+    // An unsaved host is offered for saving as entered, before --attach
+    let unsaved = adhoc.then(|| connection.clone());
+
+    // Like the other overrides, --attach/--no-attach apply to this session only
+    if let Some(session) = attach {
+        connection.auto_attach = Some(session);
+    }
+    if no_attach {
+        connection.auto_attach = None;
+    }
+
+    let established = ssh_service
         .connect_to_connection(
             &connection,
             user,
@@ -50,6 +103,40 @@ pub async fn execute(
             no_bastion,
             bastion_user,
             key,
+            alias_used,
+            tunnels,
+            label,
+            check_port,
+            dry_run,
         )
-        .await
+        .await?;
+    if !established && !dry_run {
+        return Err(AppError::Unreachable(connection.name).into());
+    }
+
+    if let Some(connection) = unsaved.filter(|_| established) {
+        if should_save(save, &config) {
+            ssh_service.save_adhoc_connection(&connection)?;
+            println!(
+                "✅ Saved connection '{}' (rename it with: bssh edit {} --name NEW)",
+                connection.name, connection.name
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Whether to save a host reached ad hoc: the flag wins, otherwise ask when
+/// `prompt_save_adhoc` is on and someone can answer
+fn should_save(save: Option<bool>, config: &AppConfig) -> bool {
+    if let Some(save) = save {
+        return save;
+    }
+    if !config.prompt_save_adhoc || !(std::io::stdin().is_terminal() || assumed_yes()) {
+        return false;
+    }
+    confirm("Save this connection?", false).unwrap_or_else(|e| {
+        warn!("Not saving the connection: {}", e);
+        false
+    })
 }
@@ -0,0 +1,31 @@
+use crate::cli::utils::resolve_connection;
+use crate::config::AppConfig;
+use crate::services::SshService;
+use anyhow::Result;
+use std::path::PathBuf;
+use tracing::info;
+
+pub async fn execute(
+    target: String,
+    public_key: Option<PathBuf>,
+    dry_run: bool,
+    config: AppConfig,
+) -> Result<()> {
+    let ssh_service = SshService::new(config)?;
+    let connection = resolve_connection(&ssh_service, &target, "copy a key to", true).await?;
+    info!("Copying a public key to {}", connection.name);
+
+    let public_key = ssh_service
+        .copy_id(&connection, public_key, dry_run)
+        .await?;
+    if !dry_run {
+        println!(
+            "✅ Installed {} on '{}' ({}@{})",
+            public_key.display(),
+            connection.name,
+            connection.user,
+            connection.host
+        );
+    }
+    Ok(())
+}
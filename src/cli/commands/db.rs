@@ -0,0 +1,106 @@
+//! Database maintenance commands
+
+use crate::cli::utils::format_bytes;
+use crate::cli::DbCommands;
+use crate::config::AppConfig;
+use crate::database::Database;
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+pub async fn execute(command: DbCommands, config: AppConfig) -> Result<()> {
+    match command {
+        DbCommands::Check { fix } => check(fix, &config),
+        DbCommands::Optimize => optimize(&config),
+    }
+}
+
+/// `bssh db optimize`: checkpoint the WAL and VACUUM, reporting the size change
+fn optimize(config: &AppConfig) -> Result<()> {
+    let db = Database::new(config)?;
+    let before = database_size(&config.database_path);
+
+    db.optimize()?;
+
+    let after = database_size(&config.database_path);
+    info!("Optimized database: {} -> {} bytes", before, after);
+    println!("✅ Database optimized: {}", config.database_path.display());
+    println!("   Before: {}", format_bytes(before));
+    println!("   After:  {}", format_bytes(after));
+    if before > after {
+        println!("   Reclaimed {}", format_bytes(before - after));
+    }
+    Ok(())
+}
+
+/// Bytes used by the database file plus its `-wal` and `-shm` companions
+fn database_size(path: &Path) -> u64 {
+    let mut companions = vec![path.to_path_buf()];
+    for suffix in ["-wal", "-shm"] {
+        let mut name = path.as_os_str().to_os_string();
+        name.push(suffix);
+        companions.push(PathBuf::from(name));
+    }
+    companions
+        .iter()
+        .filter_map(|p| std::fs::metadata(p).ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// `bssh db check`: report (and with `fix`, delete) rows that reference
+/// connections which no longer exist
+fn check(fix: bool, config: &AppConfig) -> Result<()> {
+    let db = Database::new(config)?;
+    let sessions = db.find_orphaned_sessions()?;
+    let aliases = db.find_orphaned_aliases()?;
+
+    if sessions.is_empty() && aliases.is_empty() {
+        println!("✅ No orphaned sessions or aliases.");
+        return Ok(());
+    }
+
+    if !sessions.is_empty() {
+        println!(
+            "⚠️  {} session(s) reference a missing connection:",
+            sessions.len()
+        );
+        for (id, connection_id, started_at) in &sessions {
+            println!(
+                "   • {} (connection {}, started {})",
+                short_id(id),
+                short_id(connection_id),
+                started_at
+            );
+        }
+    }
+    if !aliases.is_empty() {
+        println!(
+            "⚠️  {} alias(es) point at a missing connection:",
+            aliases.len()
+        );
+        for (alias, connection_id) in &aliases {
+            println!("   • {} → {}", alias, short_id(connection_id));
+        }
+    }
+
+    if !fix {
+        println!("\n💡 Run 'bssh db check --fix' to delete them");
+        return Ok(());
+    }
+
+    let (deleted_sessions, deleted_aliases) = db.delete_orphans()?;
+    info!(
+        "Deleted {} orphaned session(s) and {} alias(es)",
+        deleted_sessions, deleted_aliases
+    );
+    println!(
+        "\n🧹 Deleted {} orphaned session(s) and {} alias(es)",
+        deleted_sessions, deleted_aliases
+    );
+    Ok(())
+}
+
+fn short_id(id: &str) -> &str {
+    &id[..8.min(id.len())]
+}
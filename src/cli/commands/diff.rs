@@ -0,0 +1,106 @@
+//! Diff command implementation - field-by-field comparison of two connections
+
+use super::close::truncate;
+use crate::cli::utils::{format_env, resolve_connection};
+use crate::config::AppConfig;
+use crate::models::Connection;
+use crate::services::SshService;
+use anyhow::Result;
+use tracing::info;
+
+/// Execute the diff command
+pub async fn execute(a: String, b: String, config: AppConfig) -> Result<()> {
+    info!("Comparing connections: {} and {}", a, b);
+
+    let ssh_service = SshService::new(config)?;
+    let left = resolve_connection(&ssh_service, &a, "compare", true).await?;
+    let right = resolve_connection(&ssh_service, &b, "compare", true).await?;
+
+    let rows: Vec<(&str, String, String)> = fields(&left)
+        .into_iter()
+        .zip(fields(&right))
+        .map(|((name, l), (_, r))| (name, l, r))
+        .collect();
+    let differences = rows.iter().filter(|(_, l, r)| l != r).count();
+
+    println!("🔍 Comparing '{}' and '{}'\n", left.name, right.name);
+    println!(
+        "   {:<14} {:<28} {:<28}",
+        "FIELD",
+        truncate(&left.name, 27),
+        truncate(&right.name, 27)
+    );
+    println!("{}", "─".repeat(75));
+    for (name, l, r) in &rows {
+        let marker = if l != r { "≠" } else { " " };
+        println!(
+            " {} {:<14} {:<28} {:<28}",
+            marker,
+            name,
+            truncate(l, 27),
+            truncate(r, 27)
+        );
+    }
+    println!();
+
+    if differences == 0 {
+        println!("✅ No differences in saved settings");
+    } else {
+        println!("⚠️  {} field(s) differ (marked ≠)", differences);
+    }
+
+    Ok(())
+}
+
+/// The settings worth comparing, rendered for display
+pub(crate) fn fields(conn: &Connection) -> Vec<(&'static str, String)> {
+    let or_none = |value: &Option<String>| value.clone().unwrap_or_else(|| "-".to_string());
+    let mut tags = conn.tags.clone();
+    tags.sort();
+
+    vec![
+        ("host", conn.host.clone()),
+        ("user", conn.user.clone()),
+        ("port", conn.port.to_string()),
+        ("bastion", or_none(&conn.bastion)),
+        ("bastion user", or_none(&conn.bastion_user)),
+        ("proxy command", or_none(&conn.proxy_command)),
+        ("tmux attach", or_none(&conn.auto_attach)),
+        (
+            "kerberos",
+            if conn.use_kerberos { "yes" } else { "no" }.to_string(),
+        ),
+        ("key", or_none(&conn.key_path)),
+        (
+            "ssh config",
+            conn.ssh_config_file
+                .as_ref()
+                .map(|p| p.display().to_string())
+                .unwrap_or_else(|| "-".to_string()),
+        ),
+        (
+            "set env",
+            if conn.env.is_empty() {
+                "-".to_string()
+            } else {
+                format_env(&conn.env)
+            },
+        ),
+        (
+            "send env",
+            if conn.send_env.is_empty() {
+                "-".to_string()
+            } else {
+                conn.send_env.join(", ")
+            },
+        ),
+        (
+            "tags",
+            if tags.is_empty() {
+                "-".to_string()
+            } else {
+                tags.join(", ")
+            },
+        ),
+    ]
+}
@@ -0,0 +1,121 @@
+//! Discover command implementation - bootstrap connections from known_hosts
+
+use crate::cli::utils::confirm;
+use crate::config::AppConfig;
+use crate::services::{known_hosts, SshService};
+use anyhow::Result;
+use std::path::PathBuf;
+use tracing::info;
+
+/// Tag put on every connection added by `bssh discover`
+const DISCOVERED_TAG: &str = "discovered";
+
+pub async fn execute(
+    file: Option<PathBuf>,
+    no_bastion: bool,
+    dry_run: bool,
+    config: AppConfig,
+) -> Result<()> {
+    let path = file.unwrap_or_else(known_hosts::default_path);
+    info!("Discovering hosts from {:?}", path);
+
+    if !path.exists() {
+        println!("❌ known_hosts file not found: {:?}", path);
+        return Ok(());
+    }
+
+    let default_user = config.default_user.clone();
+    let ssh_service = SshService::new(config)?;
+    let discovery = known_hosts::discover_hosts(&path)?;
+
+    // Name each candidate after its host; a non-standard port is appended
+    // so `[db]:2222` and `db` can both be saved.
+    let mut candidates = Vec::new();
+    let mut skipped = 0;
+    for (host, port) in discovery.hosts {
+        let name = if port == 22 {
+            host.clone()
+        } else {
+            format!("{}-{}", host, port)
+        };
+        let exists = ssh_service.get_connection(&name).await?.is_some()
+            || ssh_service
+                .find_by_endpoint(&host, &default_user, port)
+                .await?
+                .is_some();
+        if exists {
+            skipped += 1;
+        } else {
+            candidates.push((name, host, port));
+        }
+    }
+
+    if candidates.is_empty() {
+        println!("✨ No new hosts found in {:?}", path);
+    } else {
+        println!("🔍 Found {} new host(s) in {:?}:", candidates.len(), path);
+        for (name, host, port) in &candidates {
+            println!("  +  {:<20} {}@{}:{}", name, default_user, host, port);
+        }
+        println!();
+    }
+    if skipped > 0 {
+        println!("⏭️  Skipped {} host(s) already saved", skipped);
+    }
+    if discovery.hashed > 0 {
+        println!(
+            "🔒 {} hashed entr{} cannot be read back into hostnames (HashKnownHosts)",
+            discovery.hashed,
+            if discovery.hashed == 1 { "y" } else { "ies" }
+        );
+    }
+    if candidates.is_empty() {
+        return Ok(());
+    }
+
+    if dry_run {
+        println!("💡 Nothing was written; run again without --dry-run to add them");
+        return Ok(());
+    }
+    if !confirm(
+        &format!(
+            "Add {} connection(s) tagged '{}'?",
+            candidates.len(),
+            DISCOVERED_TAG
+        ),
+        false,
+    )? {
+        println!("❌ Discovery cancelled.");
+        return Ok(());
+    }
+
+    for (name, host, port) in &candidates {
+        ssh_service
+            .add_connection(
+                name.clone(),
+                host.clone(),
+                None,
+                Some(*port),
+                None,
+                None,
+                no_bastion,
+                None,
+                None,
+                vec![DISCOVERED_TAG.to_string()],
+                None,
+                None,
+                Vec::new(),
+                Vec::new(),
+                None,
+                None,
+            )
+            .await?;
+    }
+
+    println!(
+        "✅ Added {} connection(s) tagged '{}'",
+        candidates.len(),
+        DISCOVERED_TAG
+    );
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use crate::cli::utils::fuzzy_select_connection;
+use crate::cli::utils::select_target;
 use crate::config::AppConfig;
 use crate::services::SshService;
 use anyhow::Result;
@@ -7,20 +7,8 @@ use tracing::info;
 pub async fn execute(source: String, new_name: String, config: AppConfig) -> Result<()> {
     let ssh_service = SshService::new(config)?;
 
-    // Try to find the exact connection by name or alias first
-    let mut target_conn = ssh_service
-        .get_connection(&source)
-        .await
-        .unwrap_or_default();
-
-    // Fall back to fuzzy search if not found
-    if target_conn.is_none() {
-        println!("🔍 Connection '{}' not found, searching...", source);
-        target_conn = fuzzy_select_connection(&ssh_service, &source, "duplicate", true).await?;
-    }
-
-    let original = match target_conn {
-        Some(c) => c,
+    let original = match select_target(&ssh_service, &source, "duplicate", true).await? {
+        Some((c, _)) => c,
         None => {
             println!("❌ No connection selected for duplication.");
             return Ok(());
@@ -51,6 +39,12 @@ pub async fn execute(source: String, new_name: String, config: AppConfig) -> Res
             original.bastion_user.clone(),
             original.key_path.clone(),
             original.tags.clone(),
+            original.expires_at,
+            original.ssh_config_file.clone(),
+            original.env.clone(),
+            original.send_env.clone(),
+            original.proxy_command.clone(),
+            original.auto_attach.clone(),
         )
         .await?;
 
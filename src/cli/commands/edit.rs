@@ -1,12 +1,17 @@
-use crate::cli::utils::resolve_connection;
+use super::alias::ensure_alias_free;
+use super::diff;
+use crate::cli::utils::{confirm, format_env, progress_bar, prompt_field, resolve_connection};
 use crate::config::AppConfig;
+use crate::database::Database;
+use crate::models::Connection;
+use crate::services::auth::{check_key_file, KeyFileProblem};
 use crate::services::SshService;
-use anyhow::Result;
+use anyhow::{bail, Result};
+use std::path::PathBuf;
 use tracing::info;
 
-#[allow(clippy::too_many_arguments)]
-pub async fn execute(
-    target: String,
+/// Field changes requested on the command line
+struct Changes {
     name: Option<String>,
     host: Option<String>,
     user: Option<String>,
@@ -15,37 +20,151 @@ pub async fn execute(
     bastion: Option<String>,
     no_bastion: bool,
     bastion_user: Option<String>,
+    clear_bastion_user: bool,
     key: Option<String>,
+    clear_key: bool,
+    ssh_config: Option<PathBuf>,
+    clear_ssh_config: bool,
+    set_env: Vec<(String, String)>,
+    unset_env: Vec<String>,
+    send_env: Vec<String>,
+    clear_send_env: bool,
+    proxy_command: Option<String>,
+    clear_proxy_command: bool,
+    auto_attach: Option<String>,
+    clear_auto_attach: bool,
+    notes: Option<String>,
+    clear_notes: bool,
     add_tags: Vec<String>,
     remove_tags: Vec<String>,
-    config: AppConfig,
-) -> Result<()> {
-    info!("Editing connection: {}", target);
+}
 
-    let ssh_service = SshService::new(config)?;
-    let connection = resolve_connection(&ssh_service, &target, "edit", false).await?;
-    update_connection(
-        ssh_service,
-        connection,
-        name,
-        host,
-        user,
-        port,
-        kerberos,
-        bastion,
-        no_bastion,
-        bastion_user,
-        key,
-        add_tags,
-        remove_tags,
-    )
-    .await
+impl Changes {
+    fn is_empty(&self) -> bool {
+        self.name.is_none()
+            && self.host.is_none()
+            && self.user.is_none()
+            && self.port.is_none()
+            && self.kerberos.is_none()
+            && self.bastion.is_none()
+            && !self.no_bastion
+            && self.bastion_user.is_none()
+            && !self.clear_bastion_user
+            && self.key.is_none()
+            && !self.clear_key
+            && self.ssh_config.is_none()
+            && !self.clear_ssh_config
+            && self.set_env.is_empty()
+            && self.unset_env.is_empty()
+            && self.send_env.is_empty()
+            && !self.clear_send_env
+            && self.proxy_command.is_none()
+            && !self.clear_proxy_command
+            && self.auto_attach.is_none()
+            && !self.clear_auto_attach
+            && self.notes.is_none()
+            && !self.clear_notes
+            && self.add_tags.is_empty()
+            && self.remove_tags.is_empty()
+    }
+
+    fn apply(&self, connection: &mut Connection) {
+        if let Some(name) = &self.name {
+            connection.name = name.clone();
+        }
+        if let Some(host) = &self.host {
+            connection.host = host.clone();
+        }
+        if let Some(user) = &self.user {
+            connection.user = user.clone();
+        }
+        if let Some(port) = self.port {
+            connection.port = port;
+        }
+        if let Some(kerberos) = self.kerberos {
+            connection.use_kerberos = kerberos;
+        }
+        if let Some(bastion) = &self.bastion {
+            connection.bastion = Some(bastion.clone());
+            connection.proxy_command = None;
+        }
+        if self.no_bastion {
+            connection.bastion = None;
+            connection.bastion_user = None;
+        }
+        if let Some(bastion_user) = &self.bastion_user {
+            connection.bastion_user = Some(bastion_user.clone());
+        }
+        if self.clear_bastion_user {
+            connection.bastion_user = None;
+        }
+        if let Some(key) = &self.key {
+            connection.key_path = Some(key.clone());
+        }
+        if self.clear_key {
+            connection.key_path = None;
+        }
+        if let Some(ssh_config) = &self.ssh_config {
+            connection.ssh_config_file = Some(ssh_config.clone());
+        }
+        if self.clear_ssh_config {
+            connection.ssh_config_file = None;
+        }
+        connection
+            .env
+            .retain(|(key, _)| !self.unset_env.contains(key));
+        for (key, value) in &self.set_env {
+            match connection.env.iter_mut().find(|(k, _)| k == key) {
+                Some(existing) => existing.1 = value.clone(),
+                None => connection.env.push((key.clone(), value.clone())),
+            }
+        }
+        if self.clear_send_env {
+            connection.send_env.clear();
+        }
+        for pattern in &self.send_env {
+            if !connection.send_env.contains(pattern) {
+                connection.send_env.push(pattern.clone());
+            }
+        }
+        // A ProxyCommand and a bastion are mutually exclusive
+        if let Some(proxy_command) = &self.proxy_command {
+            connection.proxy_command = Some(proxy_command.clone());
+            connection.bastion = None;
+            connection.bastion_user = None;
+        }
+        if self.clear_proxy_command {
+            connection.proxy_command = None;
+        }
+        if let Some(session) = &self.auto_attach {
+            connection.auto_attach = Some(session.clone());
+        }
+        if self.clear_auto_attach {
+            connection.auto_attach = None;
+        }
+        if let Some(notes) = &self.notes {
+            connection.notes = Some(notes.clone());
+        }
+        if self.clear_notes {
+            connection.notes = None;
+        }
+
+        // Handle tags
+        for tag in &self.add_tags {
+            connection.add_tag(tag.clone());
+        }
+        for tag in &self.remove_tags {
+            connection.remove_tag(tag);
+        }
+    }
 }
 
 #[allow(clippy::too_many_arguments)]
-async fn update_connection(
-    ssh_service: SshService,
-    mut connection: crate::models::Connection,
+pub async fn execute(
+    target: Option<String>,
+    filter: Option<String>,
+    all: bool,
+    interactive: bool,
     name: Option<String>,
     host: Option<String>,
     user: Option<String>,
@@ -54,50 +173,86 @@ async fn update_connection(
     bastion: Option<String>,
     no_bastion: bool,
     bastion_user: Option<String>,
+    clear_bastion_user: bool,
     key: Option<String>,
+    clear_key: bool,
+    ssh_config: Option<PathBuf>,
+    clear_ssh_config: bool,
+    set_env: Vec<(String, String)>,
+    unset_env: Vec<String>,
+    send_env: Vec<String>,
+    clear_send_env: bool,
+    proxy_command: Option<String>,
+    clear_proxy_command: bool,
+    auto_attach: Option<String>,
+    clear_auto_attach: bool,
+    notes: Option<String>,
+    clear_notes: bool,
     add_tags: Vec<String>,
     remove_tags: Vec<String>,
+    add_aliases: Vec<String>,
+    remove_aliases: Vec<String>,
+    config: AppConfig,
 ) -> Result<()> {
-    // Update fields if provided
-    if let Some(name) = name {
-        connection.name = name;
-    }
-    if let Some(host) = host {
-        connection.host = host;
-    }
-    if let Some(user) = user {
-        connection.user = user;
-    }
-    if let Some(port) = port {
-        connection.port = port;
-    }
-    if let Some(kerberos) = kerberos {
-        connection.use_kerberos = kerberos;
-    }
-    if let Some(bastion) = bastion {
-        connection.bastion = Some(bastion);
-    }
-    if no_bastion {
-        connection.bastion = None;
-        connection.bastion_user = None;
-    }
-    if let Some(bastion_user) = bastion_user {
-        connection.bastion_user = Some(bastion_user);
-    }
-    if let Some(key) = key {
-        connection.key_path = Some(key);
+    let changes = Changes {
+        name,
+        host,
+        user,
+        port,
+        kerberos,
+        bastion,
+        no_bastion,
+        bastion_user,
+        clear_bastion_user,
+        key,
+        clear_key,
+        ssh_config,
+        clear_ssh_config,
+        set_env,
+        unset_env,
+        send_env,
+        clear_send_env,
+        proxy_command,
+        clear_proxy_command,
+        auto_attach,
+        clear_auto_attach,
+        notes,
+        clear_notes,
+        add_tags,
+        remove_tags,
+    };
+
+    let db = Database::new(&config)?;
+    let ssh_service = SshService::new(config)?;
+
+    let Some(target) = target else {
+        return bulk_update(&ssh_service, filter.as_deref(), all, &changes).await;
+    };
+
+    info!("Editing connection: {}", target);
+
+    let mut connection = resolve_connection(&ssh_service, &target, "edit", false).await?;
+    let connection_id = connection.id.to_string();
+    check_alias_changes(&db, &connection, &changes, &add_aliases, &remove_aliases)?;
+
+    changes.apply(&mut connection);
+    if interactive && !edit_interactively(&ssh_service, &mut connection).await? {
+        return Ok(());
     }
 
-    // Handle tags
-    for tag in add_tags {
-        connection.add_tag(tag);
+    // Update in database; alias-only edits leave the connection row alone
+    let aliases_only = changes.is_empty() && !(add_aliases.is_empty() && remove_aliases.is_empty());
+    if interactive || !aliases_only {
+        ssh_service.update_connection(connection.clone()).await?;
     }
-    for tag in remove_tags {
-        connection.remove_tag(&tag);
+    for alias in &remove_aliases {
+        db.remove_alias(alias)?;
+        println!("✅ Removed alias '{}'", alias);
+    }
+    for alias in &add_aliases {
+        db.add_alias(alias, &connection_id)?;
+        println!("✅ Added alias '{}' → '{}'", alias, connection.name);
     }
-
-    // Update in database
-    ssh_service.update_connection(connection.clone()).await?;
 
     println!("✅ Connection '{}' updated successfully!", connection.name);
     println!("\nUpdated connection details:");
@@ -122,9 +277,335 @@ async fn update_connection(
             "Disabled"
         }
     );
+    if let Some(key) = &connection.key_path {
+        println!("  SSH Key: {}", key);
+    }
+    if let Some(proxy_command) = &connection.proxy_command {
+        println!("  Proxy command: {}", proxy_command);
+    }
+    if let Some(ssh_config) = &connection.ssh_config_file {
+        println!("  SSH config: {}", ssh_config.display());
+    }
+    if let Some(session) = &connection.auto_attach {
+        println!("  Auto-attach: tmux session '{}'", session);
+    }
+    if let Some(notes) = &connection.notes {
+        println!("  Notes: {} line(s)", notes.lines().count());
+    }
+    if !connection.env.is_empty() {
+        println!("  SetEnv: {}", format_env(&connection.env));
+    }
+    if !connection.send_env.is_empty() {
+        println!("  SendEnv: {}", connection.send_env.join(", "));
+    }
     if !connection.tags.is_empty() {
         println!("  Tags: {}", connection.tags.join(", "));
     }
+    let aliases = db.get_aliases_for_connection(&connection_id)?;
+    if !aliases.is_empty() {
+        println!("  Aliases: {}", aliases.join(", "));
+    }
+
+    Ok(())
+}
+
+/// Validate `--add-alias`/`--remove-alias` before anything is written, with
+/// the same uniqueness rules as `bssh alias add`
+fn check_alias_changes(
+    db: &Database,
+    connection: &Connection,
+    changes: &Changes,
+    add_aliases: &[String],
+    remove_aliases: &[String],
+) -> Result<()> {
+    for alias in remove_aliases {
+        match db.get_connection_by_alias(alias)? {
+            Some(owner) if owner.id == connection.id => {}
+            Some(owner) => bail!(
+                "Alias '{}' points to '{}', not '{}'",
+                alias,
+                owner.name,
+                connection.name
+            ),
+            None => bail!("Alias '{}' not found", alias),
+        }
+    }
+    for alias in add_aliases {
+        ensure_alias_free(db, alias)?;
+        if changes.name.as_deref() == Some(alias.as_str()) {
+            bail!(
+                "Cannot create alias '{}' - it is the connection's new name",
+                alias
+            );
+        }
+    }
+    Ok(())
+}
+
+/// One answer in the interactive form
+enum Answer<T> {
+    Keep,
+    Clear,
+    Set(T),
+}
+
+/// Prompt for `label` until the input parses. `-` clears optional fields.
+fn ask<T>(
+    label: &str,
+    current: &str,
+    optional: bool,
+    parse: impl Fn(&str) -> Result<T, String>,
+) -> Result<Answer<T>> {
+    loop {
+        let input = prompt_field(label, current)?;
+        if input.is_empty() {
+            return Ok(Answer::Keep);
+        }
+        if optional && input == "-" {
+            return Ok(Answer::Clear);
+        }
+        match parse(&input) {
+            Ok(value) => return Ok(Answer::Set(value)),
+            Err(e) => println!("    ❌ {}", e),
+        }
+    }
+}
+
+/// Update an optional field from an answer
+fn answer_into<T>(answer: Answer<T>, field: &mut Option<T>) {
+    match answer {
+        Answer::Keep => {}
+        Answer::Clear => *field = None,
+        Answer::Set(value) => *field = Some(value),
+    }
+}
+
+/// Walk through the main fields of `connection`, then show what changed and
+/// ask to save. Returns whether the caller should save.
+async fn edit_interactively(ssh_service: &SshService, connection: &mut Connection) -> Result<bool> {
+    let original = connection.clone();
+    println!(
+        "✏️  Editing '{}'. Press Enter to keep a value, type a new one to change it,\n   or '-' to clear an optional field.\n",
+        connection.name
+    );
 
+    loop {
+        match ask("Name", &connection.name, false, parse_required)? {
+            Answer::Set(name) if name != original.name => {
+                if ssh_service.get_connection(&name).await?.is_some() {
+                    println!("    ❌ A connection named '{}' already exists", name);
+                    continue;
+                }
+                connection.name = name;
+            }
+            _ => {}
+        }
+        break;
+    }
+    if let Answer::Set(host) = ask("Host", &connection.host, false, parse_host)? {
+        connection.host = host;
+    }
+    if let Answer::Set(user) = ask("User", &connection.user, false, parse_user)? {
+        connection.user = user;
+    }
+    if let Answer::Set(port) = ask("Port", &connection.port.to_string(), false, parse_port)? {
+        connection.port = port;
+    }
+    let kerberos = if connection.use_kerberos { "yes" } else { "no" };
+    if let Answer::Set(kerberos) = ask("Kerberos (yes/no)", kerberos, false, parse_yes_no)? {
+        connection.use_kerberos = kerberos;
+    }
+    let bastion = connection.bastion.clone().unwrap_or_default();
+    answer_into(
+        ask("Bastion", &bastion, true, parse_host)?,
+        &mut connection.bastion,
+    );
+    if connection.bastion.is_some() {
+        let bastion_user = connection.bastion_user.clone().unwrap_or_default();
+        answer_into(
+            ask("Bastion user", &bastion_user, true, parse_user)?,
+            &mut connection.bastion_user,
+        );
+    } else {
+        connection.bastion_user = None;
+    }
+    let key = connection.key_path.clone().unwrap_or_default();
+    answer_into(
+        ask("SSH key", &key, true, parse_key)?,
+        &mut connection.key_path,
+    );
+    let proxy_command = connection.proxy_command.clone().unwrap_or_default();
+    answer_into(
+        ask("Proxy command", &proxy_command, true, parse_required)?,
+        &mut connection.proxy_command,
+    );
+    // A ProxyCommand and a bastion are mutually exclusive
+    if connection.proxy_command.is_some() && connection.bastion.is_some() {
+        println!("    ℹ️  The proxy command replaces the bastion; bastion removed");
+        connection.bastion = None;
+        connection.bastion_user = None;
+    }
+    let tags = connection.tags.join(", ");
+    match ask("Tags (comma-separated)", &tags, true, parse_tags)? {
+        Answer::Keep => {}
+        Answer::Clear => connection.tags.clear(),
+        Answer::Set(tags) => connection.tags = tags,
+    }
+
+    let mut changed: Vec<(&str, String, String)> = diff::fields(&original)
+        .into_iter()
+        .zip(diff::fields(connection))
+        .filter(|((_, before), (_, after))| before != after)
+        .map(|((name, before), (_, after))| (name, before, after))
+        .collect();
+    if original.name != connection.name {
+        changed.insert(0, ("name", original.name.clone(), connection.name.clone()));
+    }
+    if changed.is_empty() {
+        println!("\nNo changes.");
+        return Ok(false);
+    }
+
+    println!("\nChanges:");
+    for (field, before, after) in &changed {
+        println!("  {}: {} → {}", field, before, after);
+    }
+    if !confirm("Save these changes?", true)? {
+        println!("❌ Edit cancelled.");
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+fn parse_required(input: &str) -> Result<String, String> {
+    if input.is_empty() {
+        return Err("A value is required".to_string());
+    }
+    Ok(input.to_string())
+}
+
+fn parse_host(input: &str) -> Result<String, String> {
+    if input.contains(char::is_whitespace) || input.contains('@') {
+        return Err("Enter a hostname or IP address, without spaces or a user@ prefix".to_string());
+    }
+    Ok(input.to_string())
+}
+
+fn parse_user(input: &str) -> Result<String, String> {
+    if input.contains(char::is_whitespace) || input.contains(['@', ':']) {
+        return Err("Usernames cannot contain spaces, '@' or ':'".to_string());
+    }
+    Ok(input.to_string())
+}
+
+fn parse_port(input: &str) -> Result<u16, String> {
+    match input.parse::<u16>() {
+        Ok(port) if port > 0 => Ok(port),
+        _ => Err("Enter a port number from 1 to 65535".to_string()),
+    }
+}
+
+fn parse_yes_no(input: &str) -> Result<bool, String> {
+    match input.to_lowercase().as_str() {
+        "y" | "yes" | "true" | "on" => Ok(true),
+        "n" | "no" | "false" | "off" => Ok(false),
+        _ => Err("Answer yes or no".to_string()),
+    }
+}
+
+fn parse_key(input: &str) -> Result<String, String> {
+    match check_key_file(std::path::Path::new(input)) {
+        Some(KeyFileProblem::Missing(path)) => Err(format!("{} does not exist", path.display())),
+        _ => Ok(input.to_string()),
+    }
+}
+
+fn parse_tags(input: &str) -> Result<Vec<String>, String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in input.split(',').map(str::trim) {
+        if tag.is_empty() {
+            return Err("Tags cannot be empty; separate them with commas".to_string());
+        }
+        if !tags.iter().any(|t| t == tag) {
+            tags.push(tag.to_string());
+        }
+    }
+    Ok(tags)
+}
+
+/// Apply `changes` to every connection tagged `filter` (or all of them)
+async fn bulk_update(
+    ssh_service: &SshService,
+    filter: Option<&str>,
+    all: bool,
+    changes: &Changes,
+) -> Result<()> {
+    if changes.name.is_some() {
+        bail!("--name cannot be used with --filter/--all: connection names must be unique");
+    }
+    if changes.is_empty() {
+        bail!("Nothing to change: pass at least one field flag (e.g. --bastion)");
+    }
+
+    let tag = if all { None } else { filter };
+    info!("Bulk editing connections (tag: {:?})", tag);
+
+    let connections = ssh_service.list_connections(tag, false, true).await?;
+    if connections.is_empty() {
+        match tag {
+            Some(tag) => println!("📭 No connections tagged '{}'.", tag),
+            None => println!("📭 No connections found."),
+        }
+        return Ok(());
+    }
+
+    println!("📝 This will update {} connection(s):", connections.len());
+    for conn in &connections {
+        println!(
+            "   • {} ({}@{}:{})",
+            conn.name, conn.user, conn.host, conn.port
+        );
+    }
+    println!();
+    if !confirm(
+        &format!("Apply changes to {} connection(s)?", connections.len()),
+        false,
+    )? {
+        println!("❌ Edit cancelled.");
+        return Ok(());
+    }
+
+    let bar = progress_bar(connections.len(), "Updating");
+    for mut connection in connections.iter().cloned() {
+        bar.set_message(connection.name.clone());
+        changes.apply(&mut connection);
+        ssh_service.update_connection(connection).await?;
+        bar.inc(1);
+    }
+    bar.finish_and_clear();
+
+    println!("✅ Updated {} connection(s)", connections.len());
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn form_fields_are_validated() {
+        assert_eq!(parse_port("2222"), Ok(2222));
+        assert!(parse_port("0").is_err());
+        assert!(parse_port("ssh").is_err());
+        assert_eq!(parse_yes_no("Y"), Ok(true));
+        assert_eq!(parse_yes_no("off"), Ok(false));
+        assert!(parse_yes_no("maybe").is_err());
+        assert!(parse_host("deploy@web").is_err());
+        assert!(parse_user("de ploy").is_err());
+        assert_eq!(
+            parse_tags("prod, web,prod"),
+            Ok(vec!["prod".to_string(), "web".to_string()])
+        );
+        assert!(parse_tags("prod,,web").is_err());
+    }
+}
@@ -1,12 +1,21 @@
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
 use tracing::info;
 
-use crate::cli::utils::resolve_connection;
+use crate::cli::utils::{progress_bar, resolve_connection};
 use crate::config::AppConfig;
-use crate::services::transport::execute_with_fallback;
+use crate::models::Connection;
+use crate::services::transport::{execute_with_fallback, ExecOutput};
 use crate::services::SshService;
 
-pub async fn execute(target: String, command: Vec<String>, config: AppConfig) -> Result<()> {
+pub async fn execute(
+    target: String,
+    command: Vec<String>,
+    log: Option<PathBuf>,
+    config: AppConfig,
+) -> Result<()> {
     if command.is_empty() {
         bail!("no command supplied — use: bssh exec <target> -- <command...>");
     }
@@ -27,6 +36,13 @@ pub async fn execute(target: String, command: Vec<String>, config: AppConfig) ->
 
     // Write stdout to stdout, stderr to stderr.
     use std::io::Write;
+    if let Some(path) = &log {
+        // The streams arrive separately, so the log keeps them apart too.
+        let mut file = std::fs::File::create(path)
+            .with_context(|| format!("Failed to create {}", path.display()))?;
+        file.write_all(&output.stdout)?;
+        file.write_all(&output.stderr)?;
+    }
     if !output.stdout.is_empty() {
         std::io::stdout().write_all(&output.stdout)?;
         if !output.stdout.ends_with(b"\n") {
@@ -42,3 +58,158 @@ pub async fn execute(target: String, command: Vec<String>, config: AppConfig) ->
     }
     Ok(())
 }
+
+/// How one host of a `--tag` run went
+struct HostResult {
+    name: String,
+    outcome: Result<ExecOutput>,
+}
+
+impl HostResult {
+    fn passed(&self) -> bool {
+        matches!(&self.outcome, Ok(output) if output.exit_code == 0)
+    }
+}
+
+/// Saved connections tagged `tag`; an error when there are none
+pub(super) async fn tagged_connections(
+    ssh_service: &SshService,
+    tag: &str,
+) -> Result<Vec<Connection>> {
+    let connections = ssh_service
+        .list_connections(Some(tag), false, false)
+        .await?;
+    if connections.is_empty() {
+        bail!("No connections tagged '{}'", tag);
+    }
+    Ok(connections)
+}
+
+/// Run `task` for every connection, at most `parallel` at a time, and
+/// return each connection's name with its result, in the original order.
+/// A progress bar labelled `label` counts finished hosts.
+pub(super) async fn run_on_each<T, F, Fut>(
+    connections: Vec<Connection>,
+    parallel: usize,
+    label: &str,
+    task: F,
+) -> Result<Vec<(String, T)>>
+where
+    F: Fn(Connection) -> Fut,
+    Fut: std::future::Future<Output = T> + Send + 'static,
+    T: Send + 'static,
+{
+    let bar = progress_bar(connections.len(), label);
+    let limiter = Arc::new(Semaphore::new(parallel));
+    let handles: Vec<_> = connections
+        .into_iter()
+        .map(|connection| {
+            let limiter = Arc::clone(&limiter);
+            let bar = bar.clone();
+            let name = connection.name.clone();
+            let run = task(connection);
+            tokio::spawn(async move {
+                let _permit = limiter.acquire_owned().await;
+                let result = run.await;
+                bar.set_message(name.clone());
+                bar.inc(1);
+                (name, result)
+            })
+        })
+        .collect();
+
+    let mut results = Vec::with_capacity(handles.len());
+    for handle in handles {
+        results.push(handle.await?);
+    }
+    bar.finish_and_clear();
+    Ok(results)
+}
+
+/// Run `command` on every connection tagged `tag`, at most `parallel` at a
+/// time. Output is printed per host in list order, then a pass/fail table;
+/// it fails (exit 1) if any host failed or could not be reached.
+pub async fn execute_on_tag(
+    tag: String,
+    command: Vec<String>,
+    parallel: usize,
+    config: AppConfig,
+) -> Result<()> {
+    if command.is_empty() {
+        bail!("no command supplied — use: bssh exec --tag <tag> -- <command...>");
+    }
+
+    let ssh_service = SshService::new(config.clone())?;
+    let connections = tagged_connections(&ssh_service, &tag).await?;
+
+    let cmd_str = command.join(" ");
+    info!(
+        "exec '{}' on {} host(s) tagged {} ({} at a time)",
+        cmd_str,
+        connections.len(),
+        tag,
+        parallel
+    );
+
+    let results: Vec<HostResult> = run_on_each(connections, parallel, "Running", |connection| {
+        let config = config.clone();
+        let cmd = cmd_str.clone();
+        async move {
+            execute_with_fallback(&connection, &config, |transport| {
+                let conn = connection.clone();
+                let cmd = cmd.clone();
+                Box::pin(async move { transport.exec(&conn, &cmd).await })
+            })
+            .await
+            .map_err(|e| anyhow::anyhow!("{e}"))
+        }
+    })
+    .await?
+    .into_iter()
+    .map(|(name, outcome)| HostResult { name, outcome })
+    .collect();
+
+    use std::io::Write;
+    for result in &results {
+        println!("── {} ──", result.name);
+        match &result.outcome {
+            Ok(output) => {
+                std::io::stdout().write_all(&output.stdout)?;
+                if !output.stdout.is_empty() && !output.stdout.ends_with(b"\n") {
+                    println!();
+                }
+                std::io::stdout().flush()?;
+                std::io::stderr().write_all(&output.stderr)?;
+            }
+            Err(e) => eprintln!("❌ {e}"),
+        }
+    }
+
+    let width = results
+        .iter()
+        .map(|r| r.name.len())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+    println!("\n{:<width$}  {:<6}  EXIT", "HOST", "RESULT");
+    for result in &results {
+        let (status, exit) = match &result.outcome {
+            Ok(output) if output.exit_code == 0 => ("✅ ok", output.exit_code.to_string()),
+            Ok(output) => ("❌ fail", output.exit_code.to_string()),
+            Err(_) => ("❌ fail", "-".to_string()),
+        };
+        println!("{:<width$}  {:<6}  {}", result.name, status, exit);
+    }
+
+    let failed = results.iter().filter(|r| !r.passed()).count();
+    println!("\n📊 {} passed, {} failed", results.len() - failed, failed);
+    if failed > 0 {
+        bail!(
+            "'{}' failed on {} of {} host(s)",
+            cmd_str,
+            failed,
+            results.len()
+        );
+    }
+    Ok(())
+}
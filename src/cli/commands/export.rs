@@ -36,7 +36,9 @@ pub async fn execute(
         .parse::<ExportFormat>()?;
 
     let ssh_service = SshService::new(config)?;
-    let connections = ssh_service.list_connections(tag.as_deref(), false).await?;
+    let connections = ssh_service
+        .list_connections(tag.as_deref(), false, true)
+        .await?;
 
     if connections.is_empty() {
         println!("No connections found to export.");
@@ -118,7 +120,7 @@ fn generate_ssh_config(connections: &[Connection]) -> String {
     config
 }
 
-fn expand_tilde(path: &str) -> String {
+pub(crate) fn expand_tilde(path: &str) -> String {
     if let Some(stripped) = path.strip_prefix("~/") {
         if let Some(mut home) = dirs::home_dir() {
             home.push(stripped);
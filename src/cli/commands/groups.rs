@@ -5,7 +5,7 @@ use std::collections::HashMap;
 
 pub async fn execute(group_name: Option<String>, config: AppConfig) -> Result<()> {
     let ssh_service = SshService::new(config)?;
-    let connections = ssh_service.list_connections(None, false).await?;
+    let connections = ssh_service.list_connections(None, false, false).await?;
 
     if let Some(tag) = group_name {
         // List connections in the specific group
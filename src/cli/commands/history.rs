@@ -1,28 +1,144 @@
 //! History command implementation - display session history with stats
 
+use super::close::truncate;
+use crate::cli::commands::export::expand_tilde;
+use crate::cli::output::{csv_table, OutputFormat};
+use crate::cli::utils::{absolute_time_format, format_absolute_time};
 use crate::config::AppConfig;
 use crate::database::Database;
 use crate::models::{SessionHistoryEntry, SessionStatus};
-use anyhow::Result;
-use chrono::Duration;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::io::IsTerminal;
+use std::path::Path;
+
+/// Entries shown when no `--limit` is given; exports default to everything
+const DEFAULT_DISPLAY_LIMIT: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HistoryExportFormat {
+    Csv,
+    Json,
+}
+
+impl std::str::FromStr for HistoryExportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(HistoryExportFormat::Csv),
+            "json" => Ok(HistoryExportFormat::Json),
+            _ => bail!("Invalid history export format. Must be 'csv' or 'json'"),
+        }
+    }
+}
+
+/// One exported session, flattened for audit tooling
+#[derive(Debug, Serialize)]
+struct ExportedSession {
+    connection: String,
+    started_at: String,
+    ended_at: Option<String>,
+    duration_secs: Option<i64>,
+    status: String,
+    error: Option<String>,
+    exit_code: Option<i32>,
+    label: Option<String>,
+    transcript: Option<String>,
+}
+
+impl From<&SessionHistoryEntry> for ExportedSession {
+    fn from(session: &SessionHistoryEntry) -> Self {
+        let (status, error) = match &session.status {
+            SessionStatus::Starting => ("starting", None),
+            SessionStatus::Active => ("active", None),
+            SessionStatus::Disconnected => ("disconnected", None),
+            SessionStatus::Terminated => ("terminated", None),
+            SessionStatus::Error(e) => ("error", Some(e.clone())),
+        };
+        Self {
+            connection: session.connection_name.clone(),
+            started_at: session.started_at.to_rfc3339(),
+            ended_at: session.ended_at.map(|t| t.to_rfc3339()),
+            duration_secs: session.duration.map(|d| d.num_seconds()),
+            status: status.to_string(),
+            error,
+            exit_code: session.exit_code,
+            label: session.label.clone(),
+            transcript: session.transcript.clone(),
+        }
+    }
+}
 
 /// Execute the history command
+#[allow(clippy::too_many_arguments)]
 pub async fn execute(
     connection: Option<String>,
-    limit: usize,
+    limit: Option<usize>,
     days: Option<u32>,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
     show_failed: bool,
+    absolute_time: bool,
+    label: Option<String>,
+    export: Option<String>,
+    format: Option<String>,
+    output: OutputFormat,
     config: AppConfig,
 ) -> Result<()> {
+    let format = match format {
+        Some(format) => format.parse::<HistoryExportFormat>()?,
+        None if output == OutputFormat::Csv => HistoryExportFormat::Csv,
+        None => HistoryExportFormat::Json,
+    };
+    if let (Some(since), Some(until)) = (since, until) {
+        if since > until {
+            bail!("--since must not be later than --until");
+        }
+    }
+    // `--days` is another lower bound; the later of the two wins
+    let days_cutoff = days.map(|d| Utc::now() - Duration::days(d as i64));
+    let since = since.max(days_cutoff);
+
+    let limit = match (limit, &export) {
+        (Some(limit), _) => limit,
+        (None, Some(_)) => i64::MAX as usize,
+        (None, None) => DEFAULT_DISPLAY_LIMIT,
+    };
+
+    let time_format = absolute_time_format(absolute_time, &config);
     let db = Database::new(&config)?;
 
     // Get session history with filters
-    let sessions = db.get_session_history(connection.as_deref(), limit, days, show_failed)?;
+    let sessions = db.get_session_history(
+        connection.as_deref(),
+        limit,
+        since,
+        until,
+        show_failed,
+        label.as_deref(),
+    )?;
+
+    if let Some(path) = export {
+        let path = expand_tilde(&path);
+        write_export(Path::new(&path), &sessions, format)?;
+        println!("✅ Exported {} session(s) to {}", sessions.len(), path);
+        return Ok(());
+    }
+
+    if output != OutputFormat::Human {
+        // Same rows as an export, on stdout
+        print!("{}", render(&sessions, format)?);
+        return Ok(());
+    }
 
     if sessions.is_empty() {
         println!("📋 No session history found.");
-        if connection.is_some() {
-            println!("   Try without the connection filter to see all history.");
+        if connection.is_some() || label.is_some() {
+            println!("   Try without the connection or label filter to see all history.");
+        } else if since.is_some() || until.is_some() {
+            println!("   Try a wider --since/--until/--days window.");
         }
         return Ok(());
     }
@@ -46,6 +162,8 @@ pub async fn execute(
     println!("{}", "─".repeat(80));
     println!();
 
+    let color = std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+
     // Print session entries
     println!(
         "{:<20} {:<25} {:<12} {:<15} EXIT CODE",
@@ -54,23 +172,52 @@ pub async fn execute(
     println!("{}", "─".repeat(80));
 
     for session in &sessions {
-        let status_str = format_status(&session.status);
+        let status_str = paint(
+            &format!("{:<15}", format_status(&session.status)),
+            status_color(session),
+            color,
+        );
         let duration_str = session
             .duration
             .map(format_duration_short)
             .unwrap_or_else(|| "ongoing".to_string());
-        let exit_str = session
-            .exit_code
-            .map(|c| c.to_string())
-            .unwrap_or_else(|| "-".to_string());
+        let exit_str = match session.exit_code {
+            Some(code) => match exit_code_meaning(code) {
+                Some(meaning) => format!("{} ({})", code, meaning),
+                None => code.to_string(),
+            },
+            None => "-".to_string(),
+        };
+
+        let label_str = session
+            .label
+            .as_deref()
+            .map(|l| format!("  🏷  {}", l))
+            .unwrap_or_default();
+        let transcript_str = session
+            .transcript
+            .as_deref()
+            .map(|t| format!("  📝 {}", t))
+            .unwrap_or_default();
+
+        let name = if session.ad_hoc {
+            format!("{} (ad-hoc)", truncate(&session.connection_name, 10))
+        } else {
+            truncate(&session.connection_name, 19)
+        };
 
         println!(
-            "{:<20} {:<25} {:<12} {:<15} {}",
-            truncate(&session.connection_name, 19),
-            session.started_at.format("%Y-%m-%d %H:%M:%S"),
+            "{:<20} {:<25} {:<12} {} {}{}{}",
+            name,
+            match &time_format {
+                Some(format) => format_absolute_time(session.started_at, format),
+                None => session.started_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            },
             duration_str,
             status_str,
-            exit_str
+            exit_str,
+            label_str,
+            transcript_str
         );
     }
 
@@ -78,6 +225,61 @@ pub async fn execute(
     Ok(())
 }
 
+/// Write `sessions` to `path`, creating parent directories as needed
+fn write_export(
+    path: &Path,
+    sessions: &[SessionHistoryEntry],
+    format: HistoryExportFormat,
+) -> Result<()> {
+    let content = render(sessions, format)?;
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() && !parent.exists() {
+            std::fs::create_dir_all(parent).context("Failed to create parent directories")?;
+        }
+    }
+    std::fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(())
+}
+
+/// `sessions` as exported, also what `--output json|csv` prints
+fn render(sessions: &[SessionHistoryEntry], format: HistoryExportFormat) -> Result<String> {
+    let rows: Vec<ExportedSession> = sessions.iter().map(ExportedSession::from).collect();
+    Ok(match format {
+        HistoryExportFormat::Json => serde_json::to_string_pretty(&rows)? + "\n",
+        HistoryExportFormat::Csv => to_csv(&rows),
+    })
+}
+
+fn to_csv(rows: &[ExportedSession]) -> String {
+    let header = [
+        "connection",
+        "started_at",
+        "ended_at",
+        "duration_secs",
+        "status",
+        "error",
+        "exit_code",
+        "label",
+        "transcript",
+    ];
+    csv_table(
+        &header,
+        rows.iter().map(|row| {
+            vec![
+                row.connection.clone(),
+                row.started_at.clone(),
+                row.ended_at.clone().unwrap_or_default(),
+                row.duration_secs.map(|d| d.to_string()).unwrap_or_default(),
+                row.status.clone(),
+                row.error.clone().unwrap_or_default(),
+                row.exit_code.map(|c| c.to_string()).unwrap_or_default(),
+                row.label.clone().unwrap_or_default(),
+                row.transcript.clone().unwrap_or_default(),
+            ]
+        }),
+    )
+}
+
 /// Statistics for session history
 struct SessionStats {
     total: usize,
@@ -139,6 +341,49 @@ fn format_status(status: &SessionStatus) -> String {
     }
 }
 
+/// What an ssh exit code usually means. An established session exits with
+/// the remote shell's status, so most of these are shell conventions; ssh
+/// itself only ever reports 255.
+fn exit_code_meaning(code: i32) -> Option<String> {
+    let meaning = match code {
+        0 => "success",
+        1 => "error",
+        2 => "misuse of shell builtin",
+        126 => "not executable",
+        127 => "command not found",
+        129 => "hangup (SIGHUP)",
+        130 => "interrupted (SIGINT)",
+        137 => "killed (SIGKILL)",
+        143 => "terminated (SIGTERM)",
+        255 => "connection failed",
+        // Recorded by `bssh sessions --kill` and `bssh close`
+        -15 => "killed by bssh",
+        -1 => "no exit status",
+        _ if (129..=192).contains(&code) => return Some(format!("signal {}", code - 128)),
+        _ => return None,
+    };
+    Some(meaning.to_string())
+}
+
+/// ANSI color for the status column: green for a clean exit, red when the
+/// session never came up, yellow for other nonzero exits
+fn status_color(session: &SessionHistoryEntry) -> Option<&'static str> {
+    match (&session.status, session.exit_code) {
+        (SessionStatus::Error(_), _) | (_, Some(255)) => Some("31"),
+        (SessionStatus::Terminated, Some(0)) => Some("32"),
+        (SessionStatus::Terminated, Some(_)) => Some("33"),
+        (SessionStatus::Starting | SessionStatus::Active, _) => Some("36"),
+        _ => None,
+    }
+}
+
+fn paint(text: &str, color: Option<&str>, enabled: bool) -> String {
+    match color {
+        Some(code) if enabled => format!("\x1b[{}m{}\x1b[0m", code, text),
+        _ => text.to_string(),
+    }
+}
+
 fn format_duration_short(duration: Duration) -> String {
     let secs = duration.num_seconds();
     if secs < 60 {
@@ -150,10 +395,27 @@ fn format_duration_short(duration: Duration) -> String {
     }
 }
 
-fn truncate(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
-    } else {
-        format!("{}…", &s[..max_len - 1])
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn common_exit_codes_are_explained() {
+        assert_eq!(exit_code_meaning(0).as_deref(), Some("success"));
+        assert_eq!(exit_code_meaning(255).as_deref(), Some("connection failed"));
+        assert_eq!(
+            exit_code_meaning(130).as_deref(),
+            Some("interrupted (SIGINT)")
+        );
+        assert_eq!(exit_code_meaning(137).as_deref(), Some("killed (SIGKILL)"));
+        assert_eq!(exit_code_meaning(134).as_deref(), Some("signal 6"));
+        assert_eq!(exit_code_meaning(42), None);
+    }
+
+    #[test]
+    fn paint_only_colors_when_enabled() {
+        assert_eq!(paint("ok", Some("32"), false), "ok");
+        assert_eq!(paint("ok", None, true), "ok");
+        assert_eq!(paint("ok", Some("32"), true), "\x1b[32mok\x1b[0m");
     }
 }
@@ -1,9 +1,227 @@
+use crate::cli::utils::progress_bar;
 use crate::config::AppConfig;
 use crate::services::SshService;
 use anyhow::Result;
+use indicatif::ProgressBar;
 use tracing::info;
 
-pub async fn execute(file: Option<String>, no_bastion: bool, config: AppConfig) -> Result<()> {
+/// Settings shared by every `Host` block of one import run
+struct ImportOptions {
+    no_bastion: bool,
+    update_existing: bool,
+    /// Report what would happen without writing to the database
+    dry_run: bool,
+    default_user: String,
+    default_port: u16,
+    default_bastion: Option<String>,
+    default_bastion_user: Option<String>,
+}
+
+/// One `Host` block and the directives bssh understands
+#[derive(Default, Clone)]
+struct HostBlock {
+    host: String,
+    hostname: Option<String>,
+    user: Option<String>,
+    port: Option<u16>,
+    identity_file: Option<String>,
+    proxy_jump: Option<ProxyJump>,
+}
+
+impl HostBlock {
+    /// `Host *`, `Host *.internal` and the like match other hosts instead of
+    /// naming one
+    fn is_pattern(&self) -> bool {
+        self.host.contains(['*', '?'])
+    }
+
+    /// Whether this block's patterns select `name`, as ssh matches `Host`
+    /// lines: any pattern may match, but a matching `!pattern` vetoes it
+    fn applies_to(&self, name: &str) -> bool {
+        let name = name.to_lowercase();
+        let mut matched = false;
+        for pattern in self.host.split_whitespace() {
+            let pattern = pattern.to_lowercase();
+            match pattern.strip_prefix('!') {
+                Some(negated) if host_pattern_matches(negated, &name) => return false,
+                Some(_) => {}
+                None => matched |= host_pattern_matches(&pattern, &name),
+            }
+        }
+        matched
+    }
+
+    /// Take every directive this block leaves unset from `defaults`
+    fn inherit(&mut self, defaults: &HostBlock) {
+        self.user = self.user.take().or_else(|| defaults.user.clone());
+        self.port = self.port.or(defaults.port);
+        self.identity_file = self
+            .identity_file
+            .take()
+            .or_else(|| defaults.identity_file.clone());
+        // A jump host matched by its own pattern would jump through itself
+        let own_jump =
+            matches!(&defaults.proxy_jump, Some(ProxyJump::Via { host, .. }) if *host == self.host);
+        if self.proxy_jump.is_none() && !own_jump {
+            self.proxy_jump = defaults.proxy_jump.clone();
+        }
+    }
+}
+
+/// ssh's `Host` pattern syntax: `*` matches any run of characters and `?`
+/// exactly one
+fn host_pattern_matches(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    // Last `*` seen, and where in `name` it currently stops
+    let (mut p, mut n) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    p = star_p + 1;
+                    n = star_n + 1;
+                    star = Some((star_p, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Every `Host` block of an ssh config, in file order
+fn parse_host_blocks(content: &str) -> Vec<HostBlock> {
+    let mut blocks: Vec<HostBlock> = Vec::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+
+        // Skip comments and empty lines
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        // Parse SSH config directives
+        if let Some(host) = line.strip_prefix("Host ") {
+            blocks.push(HostBlock {
+                host: host.trim().to_string(),
+                ..HostBlock::default()
+            });
+            continue;
+        }
+        let Some(block) = blocks.last_mut() else {
+            continue;
+        };
+        if let Some(user) = line.strip_prefix("User ") {
+            block.user = Some(user.trim().to_string());
+        } else if let Some(port) = line.strip_prefix("Port ") {
+            if let Ok(port) = port.trim().parse::<u16>() {
+                block.port = Some(port);
+            }
+        } else if let Some(hostname) = line.strip_prefix("HostName ") {
+            block.hostname = Some(hostname.trim().to_string());
+        } else if let Some(identity_file) = line.strip_prefix("IdentityFile ") {
+            block.identity_file = Some(identity_file.trim().to_string());
+        } else if let Some(proxy_jump) = line.strip_prefix("ProxyJump ") {
+            block.proxy_jump = parse_proxy_jump(proxy_jump);
+        }
+    }
+
+    // Wildcard blocks are defaults for the hosts they match, wherever they
+    // appear; the host's own settings win, then the first matching pattern.
+    let patterns: Vec<HostBlock> = blocks.iter().filter(|b| b.is_pattern()).cloned().collect();
+    for block in blocks.iter_mut().filter(|b| !b.is_pattern()) {
+        let name = block.host.clone();
+        for pattern in patterns.iter().filter(|p| p.applies_to(&name)) {
+            block.inherit(pattern);
+        }
+    }
+    blocks
+}
+
+/// A `ProxyJump` directive bssh can represent as a bastion
+#[derive(Debug, Clone, PartialEq)]
+enum ProxyJump {
+    /// `ProxyJump none`
+    Direct,
+    /// A single `[user@]host[:port]` hop
+    Via { host: String, user: Option<String> },
+}
+
+/// Parse a `ProxyJump` value. Multi-hop chains have no bastion equivalent
+/// and are ignored.
+fn parse_proxy_jump(value: &str) -> Option<ProxyJump> {
+    let value = value.trim();
+    if value.eq_ignore_ascii_case("none") {
+        return Some(ProxyJump::Direct);
+    }
+    if value.is_empty() || value.contains(',') {
+        return None;
+    }
+    Some(match value.split_once('@') {
+        Some((user, host)) => ProxyJump::Via {
+            host: host.to_string(),
+            user: Some(user.to_string()),
+        },
+        None => ProxyJump::Via {
+            host: value.to_string(),
+            user: None,
+        },
+    })
+}
+
+/// What happened to one `Host` block
+enum ImportOutcome {
+    Added,
+    Updated,
+    /// Already saved with the same settings (`--update-existing`)
+    Unchanged,
+    /// Already present under this name or endpoint and left untouched
+    Skipped,
+    /// Wildcard patterns are not real hosts, only defaults for them
+    Ignored,
+}
+
+#[derive(Default)]
+struct ImportSummary {
+    added: usize,
+    updated: usize,
+    unchanged: usize,
+    skipped: usize,
+    ignored: usize,
+}
+
+impl ImportSummary {
+    fn record(&mut self, bar: &ProgressBar, host: &str, result: Result<ImportOutcome>) {
+        bar.inc(1);
+        match result {
+            Ok(ImportOutcome::Added) => self.added += 1,
+            Ok(ImportOutcome::Updated) => self.updated += 1,
+            Ok(ImportOutcome::Unchanged) => self.unchanged += 1,
+            Ok(ImportOutcome::Skipped) => self.skipped += 1,
+            Ok(ImportOutcome::Ignored) => self.ignored += 1,
+            Err(e) => bar.suspend(|| eprintln!("Warning: Failed to import host '{}': {}", host, e)),
+        }
+    }
+}
+
+pub async fn execute(
+    file: Option<String>,
+    no_bastion: bool,
+    update_existing: bool,
+    dry_run: bool,
+    config: AppConfig,
+) -> Result<()> {
     let ssh_config_path = if let Some(file) = file {
         std::path::PathBuf::from(file)
     } else {
@@ -24,112 +242,209 @@ pub async fn execute(file: Option<String>, no_bastion: bool, config: AppConfig)
         return Ok(());
     }
 
+    let options = ImportOptions {
+        no_bastion,
+        update_existing,
+        dry_run,
+        default_user: config.default_user.clone(),
+        default_port: config.default_port,
+        default_bastion: config.default_bastion.clone(),
+        default_bastion_user: config.default_bastion_user.clone(),
+    };
     let ssh_service = SshService::new(config)?;
     let content = std::fs::read_to_string(&ssh_config_path)?;
 
-    let mut imported_count = 0;
-    let mut current_host: Option<String> = None;
-    let mut current_hostname: Option<String> = None;
-    let mut current_user: Option<String> = None;
-    let mut current_port: Option<u16> = None;
-    let mut current_identity_file: Option<String> = None;
+    if dry_run {
+        println!("🔍 Dry run: previewing import from {:?}\n", ssh_config_path);
+    }
 
-    for line in content.lines() {
-        let line = line.trim();
+    let blocks = parse_host_blocks(&content);
 
-        // Skip comments and empty lines
-        if line.is_empty() || line.starts_with('#') {
-            continue;
-        }
+    // The dry-run preview prints a line per host instead
+    let bar = if dry_run {
+        ProgressBar::hidden()
+    } else {
+        progress_bar(blocks.len(), "Importing")
+    };
 
-        // Parse SSH config directives
-        if let Some(host) = line.strip_prefix("Host ") {
-            // Save previous host if exists
-            if let Some(host) = current_host.take() {
-                if let Err(e) = self::import_host(
-                    &ssh_service,
-                    &host,
-                    current_hostname.take(),
-                    current_user.take(),
-                    current_port.take(),
-                    current_identity_file.take(),
-                    no_bastion,
-                )
-                .await
-                {
-                    eprintln!("Warning: Failed to import host '{}': {}", host, e);
-                } else {
-                    imported_count += 1;
-                }
-            }
+    let mut summary = ImportSummary::default();
+    for block in blocks {
+        bar.set_message(block.host.clone());
+        let host = block.host.clone();
+        let result = self::import_host(&ssh_service, block, &options).await;
+        summary.record(&bar, &host, result);
+    }
+    bar.finish_and_clear();
 
-            // Start new host
-            current_host = Some(host.trim().to_string());
-            current_hostname = None;
-            current_user = None;
-            current_port = None;
-            current_identity_file = None;
-        } else if let Some(user) = line.strip_prefix("User ") {
-            current_user = Some(user.trim().to_string());
-        } else if let Some(port) = line.strip_prefix("Port ") {
-            if let Ok(port) = port.trim().parse::<u16>() {
-                current_port = Some(port);
-            }
-        } else if let Some(hostname) = line.strip_prefix("HostName ") {
-            current_hostname = Some(hostname.trim().to_string());
-        } else if let Some(identity_file) = line.strip_prefix("IdentityFile ") {
-            current_identity_file = Some(identity_file.trim().to_string());
-        }
+    if dry_run {
+        println!(
+            "\n🔍 {} host(s) would be imported, {} updated, {} unchanged, {} skipped as duplicates, {} wildcard pattern(s) ignored",
+            summary.added, summary.updated, summary.unchanged, summary.skipped, summary.ignored
+        );
+        println!("💡 Nothing was written; run again without --dry-run to import");
+        return Ok(());
     }
 
-    // Don't forget the last host
-    if let Some(host) = current_host {
-        if let Err(e) = self::import_host(
-            &ssh_service,
-            &host,
-            current_hostname,
-            current_user,
-            current_port,
-            current_identity_file,
-            no_bastion,
-        )
-        .await
-        {
-            eprintln!("Warning: Failed to import host '{}': {}", host, e);
-        } else {
-            imported_count += 1;
-        }
+    if update_existing {
+        println!(
+            "✅ {} added, {} updated, {} unchanged",
+            summary.added, summary.updated, summary.unchanged
+        );
+        return Ok(());
     }
 
     println!(
         "✅ Successfully imported {} connection(s) from SSH config",
-        imported_count
+        summary.added
     );
+    if summary.skipped > 0 {
+        println!(
+            "⏭️  Skipped {} host(s) already saved (use --update-existing to refresh them)",
+            summary.skipped
+        );
+    }
 
     Ok(())
 }
 
 async fn import_host(
     ssh_service: &SshService,
-    host: &str,
-    hostname: Option<String>,
-    user: Option<String>,
-    port: Option<u16>,
-    identity_file: Option<String>,
-    no_bastion: bool,
-) -> Result<()> {
-    // Skip wildcard hosts
-    if host.contains('*') || host.contains('?') {
-        return Ok(());
-    }
+    block: HostBlock,
+    options: &ImportOptions,
+) -> Result<ImportOutcome> {
+    let block_is_pattern = block.is_pattern();
+    let HostBlock {
+        host,
+        hostname,
+        user,
+        port,
+        identity_file,
+        proxy_jump,
+    } = block;
+    let host = host.as_str();
+    // --no-bastion imports everything as direct connections
+    let proxy_jump = if options.no_bastion { None } else { proxy_jump };
 
-    // Skip if host already exists
-    if ssh_service.get_connection(host).await?.is_some() {
-        return Ok(());
+    // Wildcard blocks only lend their settings to the hosts they match
+    if block_is_pattern {
+        if options.dry_run {
+            println!(
+                "  ·  {:<20} not imported (wildcard pattern, used as defaults)",
+                host
+            );
+        }
+        return Ok(ImportOutcome::Ignored);
     }
 
     // Use HostName if available, otherwise fall back to Host
     let actual_host = hostname.unwrap_or_else(|| host.to_string());
+    let endpoint_user = user.clone().unwrap_or_else(|| options.default_user.clone());
+    let endpoint_port = port.unwrap_or(options.default_port);
+
+    // Match by name first, then by endpoint so renamed connections are not duplicated
+    let existing = match ssh_service.get_connection(host).await? {
+        Some(conn) => Some(conn),
+        None => {
+            ssh_service
+                .find_by_endpoint(&actual_host, &endpoint_user, endpoint_port)
+                .await?
+        }
+    };
+
+    if let Some(mut conn) = existing {
+        if !options.update_existing {
+            if options.dry_run {
+                println!(
+                    "  =  {:<20} skipped (already saved as '{}')",
+                    host, conn.name
+                );
+            }
+            return Ok(ImportOutcome::Skipped);
+        }
+        // Tags, aliases and usage history stay as they are
+        let before = endpoint_fields(&conn);
+        conn.host = actual_host;
+        conn.user = endpoint_user;
+        conn.port = endpoint_port;
+        if identity_file.is_some() {
+            conn.key_path = identity_file;
+        }
+        match proxy_jump {
+            Some(ProxyJump::Direct) => {
+                conn.bastion = None;
+                conn.bastion_user = None;
+            }
+            Some(ProxyJump::Via { host, user }) => {
+                conn.bastion = Some(host);
+                conn.bastion_user = user;
+                conn.proxy_command = None;
+            }
+            None => {}
+        }
+        if endpoint_fields(&conn) == before {
+            if options.dry_run {
+                println!("  =  {:<20} unchanged ('{}')", host, conn.name);
+            }
+            return Ok(ImportOutcome::Unchanged);
+        }
+        if options.dry_run {
+            println!(
+                "  ~  {:<20} would update '{}' → {}",
+                host,
+                conn.name,
+                describe(
+                    &conn.user,
+                    &conn.host,
+                    conn.port,
+                    &conn.key_path,
+                    &conn.bastion
+                )
+            );
+            return Ok(ImportOutcome::Updated);
+        }
+        ssh_service.save_connection(&conn).await?;
+        return Ok(ImportOutcome::Updated);
+    }
+
+    // A ProxyJump hop wins over the configured default bastion
+    let (bastion, bastion_user, direct) = match proxy_jump {
+        Some(ProxyJump::Via { host, user }) => (Some(host), user, false),
+        Some(ProxyJump::Direct) => (None, None, true),
+        None => (None, None, options.no_bastion),
+    };
+
+    if options.dry_run {
+        // Same bastion resolution as add_connection
+        let (bastion, bastion_user) = match (&bastion, direct) {
+            (Some(_), _) => (
+                bastion.clone(),
+                bastion_user
+                    .clone()
+                    .or_else(|| options.default_bastion_user.clone()),
+            ),
+            (None, true) => (None, None),
+            (None, false) => (
+                options.default_bastion.clone(),
+                options.default_bastion_user.clone(),
+            ),
+        };
+        let bastion = bastion.map(|b| match bastion_user {
+            Some(bu) => format!("{}@{}", bu, b),
+            None => b,
+        });
+        println!(
+            "  +  {:<20} {}",
+            host,
+            describe(
+                &endpoint_user,
+                &actual_host,
+                endpoint_port,
+                &identity_file,
+                &bastion
+            )
+        );
+        return Ok(ImportOutcome::Added);
+    }
 
     ssh_service
         .add_connection(
@@ -137,14 +452,82 @@ async fn import_host(
             actual_host,      // Use HostName as the actual host
             user,
             port,
-            None,       // kerberos
-            None,       // bastion
-            no_bastion, // use the parameter passed from command line
-            None,       // bastion_user
+            None, // kerberos
+            bastion,
+            direct,
+            bastion_user,
             identity_file,
             vec!["imported".to_string()],
+            None,
+            None,
+            Vec::new(),
+            Vec::new(),
+            None,
+            None,
         )
         .await?;
 
-    Ok(())
+    Ok(ImportOutcome::Added)
+}
+
+/// The fields an import refreshes, for spotting no-op updates
+fn endpoint_fields(
+    conn: &crate::models::Connection,
+) -> (
+    String,
+    String,
+    u16,
+    Option<String>,
+    Option<String>,
+    Option<String>,
+) {
+    (
+        conn.host.clone(),
+        conn.user.clone(),
+        conn.port,
+        conn.key_path.clone(),
+        conn.bastion.clone(),
+        conn.bastion_user.clone(),
+    )
+}
+
+/// One-line summary of the resolved fields shown by `--dry-run`
+fn describe(
+    user: &str,
+    host: &str,
+    port: u16,
+    key: &Option<String>,
+    bastion: &Option<String>,
+) -> String {
+    format!(
+        "{}@{}:{}  key: {}  bastion: {}",
+        user,
+        host,
+        port,
+        key.as_deref().unwrap_or("(default)"),
+        bastion.as_deref().unwrap_or("none")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn host_patterns_follow_ssh_syntax() {
+        assert!(host_pattern_matches("*", "web"));
+        assert!(host_pattern_matches("*.internal", "db.internal"));
+        assert!(!host_pattern_matches("*.internal", "internal"));
+        assert!(host_pattern_matches("web-??", "web-01"));
+        assert!(!host_pattern_matches("web-??", "web-1"));
+        assert!(host_pattern_matches("a*b*c", "axxbyyc"));
+
+        let block = HostBlock {
+            host: "*.corp !bastion.corp".into(),
+            ..HostBlock::default()
+        };
+        assert!(block.applies_to("DB.corp"));
+        assert!(!block.applies_to("bastion.corp"));
+        assert!(!block.applies_to("web.example"));
+    }
 }
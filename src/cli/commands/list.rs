@@ -1,13 +1,23 @@
-use crate::cli::utils::format_duration;
+use super::close::truncate;
+use crate::cli::output::{connections_csv, OutputFormat};
+use crate::cli::utils::{
+    absolute_time_format, format_absolute_time, format_duration, format_expiry,
+};
 use crate::config::AppConfig;
 use crate::services::SshService;
 use anyhow::Result;
 use tracing::info;
 
+#[allow(clippy::too_many_arguments)]
 pub async fn execute(
     tag: Option<String>,
     recent: bool,
     detailed: bool,
+    output: OutputFormat,
+    oneline: bool,
+    absolute_time: bool,
+    all: bool,
+    archived: bool,
     config: AppConfig,
 ) -> Result<()> {
     info!(
@@ -15,8 +25,35 @@ pub async fn execute(
         tag, recent, detailed
     );
 
+    let time_format = absolute_time_format(absolute_time, &config);
+    let format_time = |dt| match &time_format {
+        Some(format) => format_absolute_time(dt, format),
+        None => format_duration(dt),
+    };
+
     let ssh_service = SshService::new(config)?;
-    let connections = ssh_service.list_connections(tag.as_deref(), recent).await?;
+    let mut connections = ssh_service
+        .list_connections(tag.as_deref(), recent, all || archived)
+        .await?;
+    if archived {
+        connections.retain(|conn| conn.archived);
+    }
+
+    if output != OutputFormat::Human {
+        ssh_service.load_all_aliases(&mut connections).await?;
+        match output {
+            OutputFormat::Csv => print!("{}", connections_csv(&connections)),
+            _ => println!("{}", serde_json::to_string_pretty(&connections)?),
+        }
+        return Ok(());
+    }
+
+    if oneline {
+        for conn in &connections {
+            println!("{}\t{}\t{}\t{}", conn.name, conn.host, conn.user, conn.port);
+        }
+        return Ok(());
+    }
 
     if connections.is_empty() {
         println!("📭 No connections found.");
@@ -27,11 +64,20 @@ pub async fn execute(
     }
 
     // Header
-    let filter_info = match (&tag, recent) {
-        (Some(t), true) => format!(" (tag: {}, recent only)", t),
-        (Some(t), false) => format!(" (tag: {})", t),
-        (None, true) => " (recent only)".to_string(),
-        (None, false) => String::new(),
+    let mut filters = Vec::new();
+    if let Some(t) = &tag {
+        filters.push(format!("tag: {}", t));
+    }
+    if recent {
+        filters.push("recent only".to_string());
+    }
+    if archived {
+        filters.push("archived".to_string());
+    }
+    let filter_info = if filters.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", filters.join(", "))
     };
 
     println!("\n🔗 Connections{}", filter_info);
@@ -41,9 +87,10 @@ pub async fn execute(
         for conn in &connections {
             // Connection header
             let status_icons = format!(
-                "{}{}",
+                "{}{}{}",
                 if conn.use_kerberos { "🔐" } else { "" },
-                if conn.bastion.is_some() { "🔗" } else { "" }
+                if conn.bastion.is_some() { "🔗" } else { "" },
+                if conn.archived { "📦" } else { "" }
             );
 
             println!("\n┌─ {} {}", conn.name, status_icons);
@@ -66,7 +113,11 @@ pub async fn execute(
             }
 
             if let Some(last_used) = conn.last_used {
-                println!("│  last: {}", format_duration(last_used));
+                println!("│  last: {}", format_time(last_used));
+            }
+
+            if let Some(expires_at) = conn.expires_at {
+                println!("│  ⏳ {}", format_expiry(expires_at));
             }
 
             println!("└─ id: {}", conn.id);
@@ -86,7 +137,7 @@ pub async fn execute(
                 if conn.bastion.is_some() { "🔗" } else { "  " }
             );
 
-            let last_used = conn.last_used.map(format_duration).unwrap_or_default();
+            let last_used = conn.last_used.map(format_time).unwrap_or_default();
 
             let tags = if !conn.tags.is_empty() {
                 format!("[{}]", conn.tags.join(","))
@@ -94,7 +145,14 @@ pub async fn execute(
                 String::new()
             };
 
-            let info = format!("{} {} {}", icons, last_used, tags)
+            let archived = if conn.archived { "(archived)" } else { "" };
+
+            let expiry = conn
+                .expires_at
+                .map(|at| format!("⏳ {}", format_expiry(at)))
+                .unwrap_or_default();
+
+            let info = format!("{} {} {} {} {}", icons, last_used, tags, archived, expiry)
                 .trim()
                 .to_string();
 
@@ -115,11 +173,3 @@ pub async fn execute(
 
     Ok(())
 }
-
-fn truncate(s: &str, max: usize) -> String {
-    if s.len() <= max {
-        s.to_string()
-    } else {
-        format!("{}…", &s[..max - 1])
-    }
-}
@@ -1,11 +1,16 @@
 pub mod add;
 pub mod alias;
+pub mod archive;
 pub mod backup;
 pub mod close;
 pub mod completions;
 pub mod config;
 pub mod connect;
+pub mod copy_id;
+pub mod db;
 pub mod desktop;
+pub mod diff;
+pub mod discover;
 pub mod doctor;
 pub mod duplicate;
 pub mod edit;
@@ -17,10 +22,18 @@ pub mod groups;
 pub mod history;
 pub mod import;
 pub mod list;
+pub mod move_tag;
+pub mod note;
+#[cfg(feature = "password_auth")]
+pub mod password;
 pub mod ping;
+pub mod provision;
 pub mod proxy;
+pub mod prune;
+pub mod recent;
 pub mod remove;
 pub mod restore;
+pub mod sessions;
 pub mod show;
 pub mod stats;
 pub mod transfer;
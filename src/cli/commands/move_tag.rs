@@ -0,0 +1,33 @@
+//! Move command implementation - rename a tag across connections
+
+use crate::config::AppConfig;
+use crate::services::SshService;
+use anyhow::{bail, Result};
+use tracing::info;
+
+/// `bssh move --from-tag <old> --to-tag <new>`
+pub async fn execute(from_tag: String, to_tag: String, config: AppConfig) -> Result<()> {
+    if from_tag == to_tag {
+        bail!("--from-tag and --to-tag are both '{}'", from_tag);
+    }
+    if to_tag.trim().is_empty() {
+        bail!("--to-tag cannot be empty");
+    }
+
+    let ssh_service = SshService::new(config)?;
+    let moved = ssh_service.move_tag(&from_tag, &to_tag).await?;
+    info!(
+        "Moved tag '{}' to '{}' on {} connection(s)",
+        from_tag, to_tag, moved
+    );
+
+    if moved == 0 {
+        println!("📭 No connections tagged '{}'.", from_tag);
+    } else {
+        println!(
+            "✅ Moved {} connection(s) from tag '{}' to '{}'",
+            moved, from_tag, to_tag
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use crate::cli::utils::resolve_connection;
+use crate::config::AppConfig;
+use crate::services::SshService;
+use anyhow::{bail, Context, Result};
+use std::io::{IsTerminal, Write};
+use std::process::{Command, Stdio};
+use tracing::info;
+
+/// Edit a connection's notes in `$VISUAL`/`$EDITOR`, or page them with `--show`
+pub async fn execute(target: String, show: bool, config: AppConfig) -> Result<()> {
+    let ssh_service = SshService::new(config)?;
+    let action = if show {
+        "show notes of"
+    } else {
+        "edit notes of"
+    };
+    let connection = resolve_connection(&ssh_service, &target, action, show).await?;
+
+    if show {
+        let Some(notes) = &connection.notes else {
+            println!("📝 No notes for '{}'", connection.name);
+            println!("💡 Add some with: bssh note {}", connection.name);
+            return Ok(());
+        };
+        return page(notes);
+    }
+
+    info!("Editing notes of connection: {}", connection.name);
+    // Random name, mode 0600, removed on drop: notes are often private
+    let mut file = tempfile::Builder::new()
+        .prefix("bssh-note-")
+        .suffix(".md")
+        .tempfile()
+        .context("Failed to create a temporary notes file")?;
+    file.write_all(connection.notes.as_deref().unwrap_or_default().as_bytes())
+        .and_then(|_| file.flush())
+        .with_context(|| format!("Failed to write {}", file.path().display()))?;
+
+    let notes = edit(file.path())?.trim_end().to_string();
+    let notes = (!notes.is_empty()).then_some(notes);
+    if notes == connection.notes {
+        println!("ℹ️  Notes for '{}' unchanged", connection.name);
+        return Ok(());
+    }
+
+    let removed = notes.is_none();
+    ssh_service.set_notes(&connection, notes.as_deref()).await?;
+    if removed {
+        println!("🗑️  Notes for '{}' removed", connection.name);
+    } else {
+        println!("✅ Notes for '{}' saved", connection.name);
+    }
+    Ok(())
+}
+
+/// Run the user's editor on `path` and read it back. An editor that exits
+/// nonzero (e.g. `:cq` in vim) aborts the edit.
+fn edit(path: &std::path::Path) -> Result<String> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    // Through the shell, so EDITOR="code --wait" works
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(format!("{editor} \"$@\""))
+        .arg(&editor)
+        .arg(path)
+        .status()
+        .with_context(|| format!("Failed to run editor '{editor}'"))?;
+    if !status.success() {
+        bail!("Editor '{editor}' exited with {status}; notes not saved");
+    }
+    std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))
+}
+
+/// Show `notes` through `$PAGER` (default `less`), or print them when stdout
+/// is not a terminal
+fn page(notes: &str) -> Result<()> {
+    if !std::io::stdout().is_terminal() {
+        println!("{notes}");
+        return Ok(());
+    }
+    let pager = std::env::var("PAGER").unwrap_or_else(|_| "less".to_string());
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(&pager)
+        .stdin(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run pager '{pager}'"))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // The pager may quit before reading everything
+        let _ = writeln!(stdin, "{notes}");
+    }
+    child.wait()?;
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use crate::cli::utils::resolve_connection;
+use crate::cli::PasswordCommands;
+use crate::config::AppConfig;
+use crate::services::secrets::{self, PASSWORD_WARNING};
+use crate::services::SshService;
+use anyhow::{bail, Result};
+use tracing::info;
+
+pub async fn execute(command: PasswordCommands, config: AppConfig) -> Result<()> {
+    let ssh_service = SshService::new(config)?;
+
+    match command {
+        PasswordCommands::Set { target, reference } => {
+            let mut connection =
+                resolve_connection(&ssh_service, &target, "password", false).await?;
+            let reference = reference
+                .or_else(|| connection.password_ref.clone())
+                .unwrap_or_else(|| format!("{}@{}", connection.user, connection.host));
+            info!("Storing password for {} as {}", connection.name, reference);
+
+            println!("{}", PASSWORD_WARNING);
+            let password = rpassword::prompt_password(format!(
+                "Password for {}@{}: ",
+                connection.user, connection.host
+            ))?;
+            if password.is_empty() {
+                bail!("No password entered; nothing stored");
+            }
+            secrets::set_password(&reference, &password)?;
+
+            connection.password_ref = Some(reference.clone());
+            ssh_service.update_connection(connection.clone()).await?;
+            println!(
+                "✅ Password for '{}' stored in the keyring as '{}'",
+                connection.name, reference
+            );
+            println!("   bssh now logs in through sshpass, which must be installed.");
+        }
+        PasswordCommands::Clear { target } => {
+            let mut connection =
+                resolve_connection(&ssh_service, &target, "password", false).await?;
+            let Some(reference) = connection.password_ref.take() else {
+                println!("ℹ️  '{}' has no stored password", connection.name);
+                return Ok(());
+            };
+            info!("Clearing password for {} ({})", connection.name, reference);
+
+            // Entries can be shared with --ref; keep them while still in use
+            let shared = ssh_service
+                .list_connections(None, false, true)
+                .await?
+                .iter()
+                .any(|c| c.id != connection.id && c.password_ref.as_ref() == Some(&reference));
+            if shared {
+                println!(
+                    "ℹ️  Keeping keyring entry '{}': other connections still use it",
+                    reference
+                );
+            } else if !secrets::delete_password(&reference)? {
+                println!("ℹ️  The keyring had no entry '{}'", reference);
+            }
+            ssh_service.update_connection(connection.clone()).await?;
+            println!(
+                "✅ Removed the password for '{}'; back to key authentication",
+                connection.name
+            );
+        }
+    }
+
+    Ok(())
+}
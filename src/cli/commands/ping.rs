@@ -1,30 +1,66 @@
+use super::exec::{run_on_each, tagged_connections};
 use crate::cli::utils::resolve_connection;
 use crate::config::AppConfig;
+use crate::errors::AppError;
+use crate::models::Connection;
+use crate::services::ping::tcp_ping;
 use crate::services::SshService;
 use anyhow::{Context, Result};
-use std::time::Instant;
+use std::process::Output;
+use std::time::{Duration, Instant};
 use tokio::process::Command;
 use tracing::info;
 
 pub async fn execute(target: String, timeout: Option<u64>, config: AppConfig) -> Result<()> {
     let ssh_service = SshService::new(config)?;
 
-    let connection = match resolve_connection(&ssh_service, &target, "ping", true).await {
-        Ok(c) => c,
-        Err(_) => {
-            println!("❌ No connection selected.");
-            return Ok(());
-        }
-    };
+    let connection = resolve_connection(&ssh_service, &target, "ping", true).await?;
 
     println!(
         "Testing connectivity to '{}' ({})...",
         connection.name, connection.host
     );
 
-    let start_time = Instant::now();
     let timeout_secs = timeout.unwrap_or(5); // Default 5s timeout
 
+    // A closed port explains most hangs; report it without waiting on ssh
+    if connection.proxy_command.is_none() {
+        let (host, port) = connection.probe_endpoint();
+        let probe = ssh_service.probe_port(&connection, timeout_secs).await;
+        println!("🔌 TCP {}:{}: {}", host, port, probe);
+        if !probe.is_reachable() {
+            println!("❌ SSH ping to '{}' skipped.", connection.name);
+            return Err(AppError::Unreachable(format!("{}:{}", host, port)).into());
+        }
+    }
+
+    let (result, duration) = ssh_ping(&connection, timeout_secs).await?;
+
+    if result.status.success() {
+        println!(
+            "✅ SSH ping to '{}' successful! (took {:.2?})",
+            connection.name, duration
+        );
+    } else {
+        println!(
+            "❌ SSH ping to '{}' failed. (took {:.2?})",
+            connection.name, duration
+        );
+        let stderr = String::from_utf8_lossy(&result.stderr);
+        if !stderr.is_empty() {
+            println!("Error output:\n{}", stderr.trim());
+        }
+        return Err(AppError::Unreachable(connection.name).into());
+    }
+
+    Ok(())
+}
+
+/// Run `ssh … exit 0` against `connection`; returns its output and how long
+/// it took
+async fn ssh_ping(connection: &Connection, timeout_secs: u64) -> Result<(Output, Duration)> {
+    let start_time = Instant::now();
+
     let mut cmd = Command::new("ssh");
 
     if let Some(bastion) = &connection.bastion {
@@ -63,23 +99,82 @@ pub async fn execute(target: String, timeout: Option<u64>, config: AppConfig) ->
         .output()
         .await
         .context("Failed to execute ssh command")?;
-    let duration = start_time.elapsed();
+    Ok((result, start_time.elapsed()))
+}
 
+/// Ping one host of a `--tag` run: how long ssh took, or why it failed
+async fn ping_quietly(connection: Connection, timeout_secs: u64) -> Result<Duration, String> {
+    if connection.proxy_command.is_none() {
+        let (host, port) = connection.probe_endpoint();
+        let probe = tcp_ping(&host, port, timeout_secs).await;
+        if !probe.is_reachable() {
+            return Err(format!("TCP {}:{}: {}", host, port, probe));
+        }
+    }
+    let (result, duration) = ssh_ping(&connection, timeout_secs)
+        .await
+        .map_err(|e| e.to_string())?;
     if result.status.success() {
-        println!(
-            "✅ SSH ping to '{}' successful! (took {:.2?})",
-            connection.name, duration
-        );
-    } else {
-        println!(
-            "❌ SSH ping to '{}' failed. (took {:.2?})",
-            connection.name, duration
-        );
-        let stderr = String::from_utf8_lossy(&result.stderr);
-        if !stderr.is_empty() {
-            println!("Error output:\n{}", stderr.trim());
+        return Ok(duration);
+    }
+    let stderr = String::from_utf8_lossy(&result.stderr);
+    Err(stderr
+        .lines()
+        .rev()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or("ssh failed")
+        .to_string())
+}
+
+/// Ping every connection tagged `tag`, at most `parallel` at a time, then
+/// print one line per host. Fails as unreachable (exit 5) if any host is.
+pub async fn execute_on_tag(
+    tag: String,
+    timeout: Option<u64>,
+    parallel: usize,
+    config: AppConfig,
+) -> Result<()> {
+    let ssh_service = SshService::new(config)?;
+    let connections = tagged_connections(&ssh_service, &tag).await?;
+    let timeout_secs = timeout.unwrap_or(5);
+
+    println!(
+        "Testing connectivity to {} host(s) tagged '{}'...",
+        connections.len(),
+        tag
+    );
+    let results = run_on_each(connections, parallel, "Testing", |connection| {
+        ping_quietly(connection, timeout_secs)
+    })
+    .await?;
+
+    let width = results
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+    println!("\n{:<width$}  {:<6}  DETAILS", "HOST", "RESULT");
+    for (name, outcome) in &results {
+        match outcome {
+            Ok(took) => println!("{:<width$}  {:<6}  took {:.2?}", name, "✅ ok", took),
+            Err(reason) => println!("{:<width$}  {:<6}  {}", name, "❌ fail", reason),
         }
     }
 
+    let unreachable: Vec<&str> = results
+        .iter()
+        .filter(|(_, outcome)| outcome.is_err())
+        .map(|(name, _)| name.as_str())
+        .collect();
+    println!(
+        "\n📊 {} reachable, {} unreachable",
+        results.len() - unreachable.len(),
+        unreachable.len()
+    );
+    if !unreachable.is_empty() {
+        return Err(AppError::Unreachable(unreachable.join(", ")).into());
+    }
     Ok(())
 }
@@ -0,0 +1,177 @@
+use crate::cli::utils::resolve_connection;
+use crate::config::AppConfig;
+use crate::models::Connection;
+use crate::services::SshService;
+use anyhow::{bail, Result};
+use tracing::info;
+
+/// Guard against typos like `{1..10000}`
+const MAX_HOSTS: usize = 1000;
+
+pub async fn execute(
+    template: String,
+    hosts: Vec<String>,
+    dry_run: bool,
+    config: AppConfig,
+) -> Result<()> {
+    let mut expanded: Vec<String> = Vec::new();
+    for pattern in &hosts {
+        for host in expand_braces(pattern)? {
+            if !expanded.contains(&host) {
+                expanded.push(host);
+            }
+        }
+        if expanded.len() > MAX_HOSTS {
+            bail!(
+                "The host patterns expand to more than {} hosts; split them up",
+                MAX_HOSTS
+            );
+        }
+    }
+
+    let ssh_service = SshService::new(config)?;
+    let template = resolve_connection(&ssh_service, &template, "provision from", false).await?;
+    info!(
+        "Provisioning {} host(s) from template {}",
+        expanded.len(),
+        template.name
+    );
+
+    let mut created = 0;
+    let mut skipped = 0;
+    let mut names: Vec<String> = Vec::new();
+    for host in expanded {
+        let name = Connection::derive_name(&host);
+        if names.contains(&name) || ssh_service.get_connection(&name).await?.is_some() {
+            println!("⏭️  Skipped {}: the name '{}' is taken", host, name);
+            skipped += 1;
+            continue;
+        }
+
+        if !dry_run {
+            ssh_service
+                .add_connection(
+                    name.clone(),
+                    host.clone(),
+                    Some(template.user.clone()),
+                    Some(template.port),
+                    Some(template.use_kerberos),
+                    template.bastion.clone(),
+                    template.bastion.is_none(),
+                    template.bastion_user.clone(),
+                    template.key_path.clone(),
+                    template.tags.clone(),
+                    template.expires_at,
+                    template.ssh_config_file.clone(),
+                    template.env.clone(),
+                    template.send_env.clone(),
+                    template.proxy_command.clone(),
+                    template.auto_attach.clone(),
+                )
+                .await?;
+        }
+        println!(
+            "✅ {} {} ({})",
+            if dry_run { "Would create" } else { "Created" },
+            name,
+            host
+        );
+        names.push(name);
+        created += 1;
+    }
+
+    println!(
+        "\n📦 {} {} connection(s) from '{}', skipped {}",
+        if dry_run { "Would create" } else { "Created" },
+        created,
+        template.name,
+        skipped
+    );
+    Ok(())
+}
+
+/// Expand shell-style brace patterns: `{01..10}` is a numeric range that
+/// keeps zero padding, `{a,b}` a list of words. Several braces multiply.
+fn expand_braces(pattern: &str) -> Result<Vec<String>> {
+    let Some(open) = pattern.find('{') else {
+        return Ok(vec![pattern.to_string()]);
+    };
+    let Some(len) = pattern[open..].find('}') else {
+        bail!("Unclosed '{{' in host pattern '{}'", pattern);
+    };
+    let close = open + len;
+    let (prefix, body, rest) = (
+        &pattern[..open],
+        &pattern[open + 1..close],
+        &pattern[close + 1..],
+    );
+
+    let choices: Vec<String> = if let Some((start, end)) = body.split_once("..") {
+        let (Ok(from), Ok(to)) = (start.parse::<u64>(), end.parse::<u64>()) else {
+            bail!(
+                "'{{{}}}' in host pattern '{}' is not a numeric range",
+                body,
+                pattern
+            );
+        };
+        // `{01..10}` pads to the width of the widest bound
+        let width = if start.starts_with('0') || end.starts_with('0') {
+            start.len().max(end.len())
+        } else {
+            0
+        };
+        let range: Vec<u64> = if from <= to {
+            (from..=to).collect()
+        } else {
+            (to..=from).rev().collect()
+        };
+        if range.len() > MAX_HOSTS {
+            bail!("'{{{}}}' expands to more than {} hosts", body, MAX_HOSTS);
+        }
+        range
+            .into_iter()
+            .map(|n| format!("{:0width$}", n, width = width))
+            .collect()
+    } else if body.contains(',') {
+        body.split(',').map(str::to_string).collect()
+    } else {
+        bail!(
+            "'{{{}}}' in host pattern '{}' is neither a range (1..5) nor a list (a,b)",
+            body,
+            pattern
+        );
+    };
+
+    // Same order as the shell: the leftmost brace varies slowest
+    let tails = expand_braces(rest)?;
+    let mut hosts = Vec::new();
+    for choice in &choices {
+        for tail in &tails {
+            hosts.push(format!("{}{}{}", prefix, choice, tail));
+        }
+    }
+    Ok(hosts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn braces_expand_ranges_and_lists() {
+        assert_eq!(expand_braces("web01.prod").unwrap(), ["web01.prod"]);
+        assert_eq!(
+            expand_braces("web{08..10}.prod").unwrap(),
+            ["web08.prod", "web09.prod", "web10.prod"]
+        );
+        assert_eq!(expand_braces("h{3..1}").unwrap(), ["h3", "h2", "h1"]);
+        assert_eq!(
+            expand_braces("db{1..2}.{eu,us}").unwrap(),
+            ["db1.eu", "db1.us", "db2.eu", "db2.us"]
+        );
+        assert!(expand_braces("web{01..10").is_err());
+        assert!(expand_braces("web{a..c}").is_err());
+        assert!(expand_braces("web{x}").is_err());
+        assert!(expand_braces("web{1..5000}").is_err());
+    }
+}
@@ -0,0 +1,54 @@
+//! Prune command implementation - clean up expired ephemeral connections
+
+use crate::cli::utils::{confirm, progress_bar};
+use crate::config::AppConfig;
+use crate::services::SshService;
+use anyhow::Result;
+use tracing::info;
+
+/// `bssh prune --expired`: remove connections past their `expires_at`
+pub async fn expired(config: AppConfig) -> Result<()> {
+    let ssh_service = SshService::new(config)?;
+    let expired = ssh_service.list_expired_connections().await?;
+
+    if expired.is_empty() {
+        println!("✨ No expired connections.");
+        return Ok(());
+    }
+
+    println!("⏳ {} connection(s) have expired:", expired.len());
+    for conn in &expired {
+        if let Some(at) = conn.expires_at {
+            println!(
+                "   • {} ({}) — expired {}",
+                conn.name,
+                conn.host,
+                at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")
+            );
+        }
+    }
+    println!();
+
+    if !confirm(
+        &format!("Remove {} expired connection(s)?", expired.len()),
+        false,
+    )? {
+        println!("❌ Prune cancelled.");
+        return Ok(());
+    }
+
+    let bar = progress_bar(expired.len(), "Pruning");
+    let mut removed = 0;
+    for conn in &expired {
+        bar.set_message(conn.name.clone());
+        if ssh_service.remove_connection(&conn.id.to_string()).await? {
+            info!("Pruned expired connection '{}'", conn.name);
+            removed += 1;
+        }
+        bar.inc(1);
+    }
+    bar.finish_and_clear();
+
+    println!("✅ Removed {} expired connection(s)", removed);
+    Ok(())
+}
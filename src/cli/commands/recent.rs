@@ -0,0 +1,41 @@
+//! Recent command implementation - most-recently-used connect targets
+
+use crate::cli::utils::{absolute_time_format, format_absolute_time, format_duration};
+use crate::config::AppConfig;
+use crate::services::SshService;
+use anyhow::Result;
+
+/// Execute the recent command
+pub async fn execute(limit: usize, order: bool, config: AppConfig) -> Result<()> {
+    let time_format = absolute_time_format(false, &config);
+    let ssh_service = SshService::new(config)?;
+    let mut targets = ssh_service.get_recent_targets(limit).await?;
+
+    if targets.is_empty() {
+        println!("📭 No recent targets yet.");
+        println!("   Connections you open with 'bssh connect' will appear here.");
+        return Ok(());
+    }
+
+    if order {
+        // Chronological: the order the hosts were touched in
+        targets.reverse();
+    }
+
+    println!("🕘 Recent Targets\n");
+    for (i, (conn, used_at)) in targets.iter().enumerate() {
+        let when = match &time_format {
+            Some(format) => format_absolute_time(*used_at, format),
+            None => format_duration(*used_at),
+        };
+        println!(
+            "  {:>2}. {:<20} {:<30} {}",
+            i + 1,
+            conn.name,
+            format!("{}@{}", conn.user, conn.host),
+            when
+        );
+    }
+
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use crate::cli::utils::{confirm, resolve_connection};
 use crate::config::AppConfig;
+use crate::errors::AppError;
 use crate::services::SshService;
 use anyhow::Result;
 use tracing::info;
@@ -26,30 +27,16 @@ async fn remove_connection_with_confirmation(
         println!("   Tags: {}", connection.tags.join(", "));
     }
 
-    // If force flag is set, skip confirmation
-    if force {
-        if ssh_service.remove_connection(&connection.name).await? {
-            println!(
-                "\n✅ Connection '{}' removed successfully!",
-                connection.name
-            );
-        } else {
-            println!("\n❌ Failed to remove connection '{}'", connection.name);
-        }
-        return Ok(());
-    }
-
-    // Ask for confirmation
+    // --force skips the confirmation
     println!();
-    if confirm(&format!("Remove connection '{}'?", connection.name), false)? {
-        if ssh_service.remove_connection(&connection.name).await? {
-            println!("✅ Connection '{}' removed successfully!", connection.name);
-        } else {
-            println!("❌ Failed to remove connection '{}'", connection.name);
-        }
-    } else {
-        println!("❌ Removal cancelled.");
+    if !force && !confirm(&format!("Remove connection '{}'?", connection.name), false)? {
+        return Err(AppError::Cancelled.into());
     }
 
+    // Gone in the meantime, e.g. removed by another bssh
+    if !ssh_service.remove_connection(&connection.name).await? {
+        return Err(AppError::ConnectionNotFound(connection.name.clone()).into());
+    }
+    println!("✅ Connection '{}' removed successfully!", connection.name);
     Ok(())
 }
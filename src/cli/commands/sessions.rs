@@ -0,0 +1,155 @@
+//! Sessions command implementation - scriptable view of active sessions
+
+use super::close::{format_duration, is_process_running, truncate};
+use crate::cli::output::{csv_table, OutputFormat};
+use crate::config::AppConfig;
+use crate::database::Database;
+use anyhow::{bail, Result};
+use nix::sys::signal::{kill, Signal};
+use nix::unistd::Pid;
+use serde::Serialize;
+
+/// One active session as emitted by `bssh sessions --json`
+#[derive(Debug, Serialize)]
+struct SessionRow {
+    id: String,
+    connection: String,
+    pid: Option<u32>,
+    started: chrono::DateTime<chrono::Utc>,
+    duration_secs: i64,
+    /// Whether the recorded PID is still running (`null` when no PID was recorded)
+    alive: Option<bool>,
+}
+
+/// Execute the sessions command
+pub async fn execute(
+    active: bool,
+    output: OutputFormat,
+    kill_id: Option<String>,
+    config: AppConfig,
+) -> Result<()> {
+    let db = Database::new(&config)?;
+
+    if let Some(id) = kill_id {
+        return kill_session(&db, &id);
+    }
+
+    let now = chrono::Utc::now();
+    let rows: Vec<SessionRow> = db
+        .get_active_sessions()?
+        .into_iter()
+        .map(|(id, connection, pid, started)| SessionRow {
+            id,
+            connection,
+            pid,
+            started,
+            duration_secs: now.signed_duration_since(started).num_seconds(),
+            alive: pid.map(is_process_running),
+        })
+        .filter(|row| !active || row.alive == Some(true))
+        .collect();
+
+    match output {
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&rows)?);
+            return Ok(());
+        }
+        OutputFormat::Csv => {
+            print!("{}", sessions_csv(&rows));
+            return Ok(());
+        }
+        OutputFormat::Human => {}
+    }
+
+    if rows.is_empty() {
+        println!("📋 No active sessions.");
+        return Ok(());
+    }
+
+    println!(
+        "{:<10} {:<20} {:<10} {:<20} DURATION",
+        "ID", "CONNECTION", "PID", "STARTED"
+    );
+    println!("{}", "─".repeat(75));
+
+    for row in &rows {
+        let status = match row.alive {
+            Some(true) => "🟢",
+            Some(false) => "⚠️ stale",
+            None => "❓",
+        };
+        println!(
+            "{:<10} {:<20} {:<10} {:<20} {} {}",
+            &row.id[..8.min(row.id.len())],
+            truncate(&row.connection, 19),
+            row.pid
+                .map(|p| p.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            row.started.format("%Y-%m-%d %H:%M:%S"),
+            format_duration(chrono::Duration::seconds(row.duration_secs)),
+            status
+        );
+    }
+
+    println!("\n💡 Use 'bssh sessions --kill <id>' to terminate a session");
+
+    Ok(())
+}
+
+/// The JSON fields as CSV columns; unknown `pid` and `alive` are empty
+fn sessions_csv(rows: &[SessionRow]) -> String {
+    csv_table(
+        &[
+            "id",
+            "connection",
+            "pid",
+            "started",
+            "duration_secs",
+            "alive",
+        ],
+        rows.iter().map(|row| {
+            vec![
+                row.id.clone(),
+                row.connection.clone(),
+                row.pid.map(|p| p.to_string()).unwrap_or_default(),
+                row.started.to_rfc3339(),
+                row.duration_secs.to_string(),
+                row.alive.map(|a| a.to_string()).unwrap_or_default(),
+            ]
+        }),
+    )
+}
+
+/// Terminate one session by id (or unique id prefix)
+fn kill_session(db: &Database, id: &str) -> Result<()> {
+    let matches: Vec<_> = db
+        .get_active_sessions()?
+        .into_iter()
+        .filter(|(session_id, ..)| session_id.starts_with(id))
+        .collect();
+
+    let (session_id, conn_name, pid, _) = match matches.as_slice() {
+        [] => bail!("No active session with id '{}'", id),
+        [single] => single.clone(),
+        _ => bail!(
+            "Session id '{}' is ambiguous ({} matches); use more characters",
+            id,
+            matches.len()
+        ),
+    };
+
+    match pid {
+        Some(p) if is_process_running(p) => {
+            kill(Pid::from_raw(p as i32), Signal::SIGTERM)
+                .map_err(|e| anyhow::anyhow!("Failed to kill PID {}: {}", p, e))?;
+            db.mark_session_terminated(&session_id, -15)?; // SIGTERM = 15
+            println!("✅ Sent SIGTERM to session '{}' (PID {})", conn_name, p);
+        }
+        _ => {
+            db.mark_session_terminated(&session_id, -1)?;
+            println!("🧹 Session '{}' was not running; record closed", conn_name);
+        }
+    }
+
+    Ok(())
+}
@@ -1,13 +1,62 @@
-use crate::cli::utils::{resolve_connection, show_connection_details};
+use crate::cli::output::{connections_csv, OutputFormat};
+use crate::cli::utils::{absolute_time_format, resolve_connection, show_connection_details};
 use crate::config::AppConfig;
-use crate::services::SshService;
+use crate::services::{known_hosts, SshService};
 use anyhow::Result;
-use tracing::info;
+use tracing::{info, warn};
 
-pub async fn execute(target: String, config: AppConfig) -> Result<()> {
+pub async fn execute(
+    target: String,
+    output: OutputFormat,
+    absolute_time: bool,
+    host_key: bool,
+    config: AppConfig,
+) -> Result<()> {
     info!("Showing details for connection: {}", target);
 
-    let ssh_service = SshService::new(config)?;
-    let connection = resolve_connection(&ssh_service, &target, "show", true).await?;
-    show_connection_details(&connection)
+    let time_format = absolute_time_format(absolute_time, &config);
+
+    let ssh_service = SshService::new(config.clone())?;
+    let mut connection = resolve_connection(&ssh_service, &target, "show", true).await?;
+    ssh_service.load_aliases(&mut connection).await?;
+
+    let mut changed_from = None;
+    if host_key {
+        let live = known_hosts::keyscan_fingerprints(&connection.host, connection.port)
+            .await?
+            .join("\n");
+        ssh_service
+            .set_host_key_fingerprint(&connection, &live)
+            .await?;
+        changed_from = connection
+            .host_key_fingerprint
+            .replace(live.clone())
+            .filter(|cached| *cached != live);
+    }
+
+    if let Some(cached) = &changed_from {
+        warn!(
+            "Host key fingerprint of {} changed (was: {})",
+            connection.host,
+            cached.replace('\n', ", ")
+        );
+        eprintln!(
+            "⚠️  The host key of {} changed since it was last checked!",
+            connection.host
+        );
+        eprintln!("   Previously cached:");
+        for fingerprint in cached.lines() {
+            eprintln!("     {}", fingerprint);
+        }
+        eprintln!("   Verify the change with the server's owner before connecting.");
+    }
+
+    match output {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&connection)?),
+        OutputFormat::Csv => print!("{}", connections_csv(std::slice::from_ref(&connection))),
+        OutputFormat::Human => {
+            show_connection_details(&connection, time_format.as_deref(), &config)?
+        }
+    }
+    Ok(())
 }
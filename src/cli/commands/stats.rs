@@ -1,14 +1,29 @@
+use super::close::{format_duration, truncate};
+use crate::cli::output::{csv_table, OutputFormat};
 use crate::config::AppConfig;
+use crate::models::ConnectionStats;
 use crate::services::SshService;
 use anyhow::Result;
 use tracing::info;
 
-pub async fn execute(config: AppConfig) -> Result<()> {
+pub async fn execute(output: OutputFormat, config: AppConfig) -> Result<()> {
     info!("Showing application statistics");
 
     let ssh_service = SshService::new(config)?;
     let stats = ssh_service.get_stats().await?;
 
+    match output {
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string_pretty(&stats)?);
+            return Ok(());
+        }
+        OutputFormat::Csv => {
+            print!("{}", stats_csv(&stats));
+            return Ok(());
+        }
+        OutputFormat::Human => {}
+    }
+
     println!("📊 Bayesian SSH Statistics\n");
 
     println!("Total Connections: {}", stats.total_connections);
@@ -38,6 +53,45 @@ pub async fn execute(config: AppConfig) -> Result<()> {
         }
     }
 
+    if stats.total_sessions > 0 {
+        println!("\nSessions:");
+        println!("  Total: {}", stats.total_sessions);
+        println!("  Success rate: {:.1}%", stats.success_rate * 100.0);
+        if let Some(avg) = stats.average_session_secs {
+            println!(
+                "  Average duration: {}",
+                format_duration(chrono::Duration::seconds(avg.round() as i64))
+            );
+        }
+        if let Some(day) = &stats.busiest_weekday {
+            println!("  Busiest day: {}", day);
+        }
+        println!(
+            "  Kerberos / direct: {} / {}",
+            stats.kerberos_sessions, stats.direct_sessions
+        );
+        if stats.adhoc_sessions > 0 {
+            println!("  Ad hoc: {}", stats.adhoc_sessions);
+        }
+    }
+
+    if stats.usage.iter().any(|u| u.all_time > 0) {
+        println!("\nUsage by Connection:");
+        println!(
+            "  {:<24} {:>9} {:>10} {:>9}",
+            "NAME", "THIS WEEK", "THIS MONTH", "ALL TIME"
+        );
+        for usage in &stats.usage {
+            println!(
+                "  {:<24} {:>9} {:>10} {:>9}",
+                truncate(&usage.name, 23),
+                usage.week,
+                usage.month,
+                usage.all_time
+            );
+        }
+    }
+
     if !stats.by_tag.is_empty() {
         println!("\nConnections by Tag:");
         let mut tag_vec: Vec<_> = stats.by_tag.iter().collect();
@@ -50,3 +104,40 @@ pub async fn execute(config: AppConfig) -> Result<()> {
 
     Ok(())
 }
+
+/// The headline figures as `metric,value` rows; per-connection usage and
+/// tag counts are only in the JSON output
+fn stats_csv(stats: &ConnectionStats) -> String {
+    let rows = [
+        ("total_connections", stats.total_connections.to_string()),
+        (
+            "most_used",
+            stats
+                .most_used
+                .as_ref()
+                .map(|c| c.name.clone())
+                .unwrap_or_default(),
+        ),
+        ("total_sessions", stats.total_sessions.to_string()),
+        ("success_rate", stats.success_rate.to_string()),
+        (
+            "average_session_secs",
+            stats
+                .average_session_secs
+                .map(|s| s.to_string())
+                .unwrap_or_default(),
+        ),
+        (
+            "busiest_weekday",
+            stats.busiest_weekday.clone().unwrap_or_default(),
+        ),
+        ("kerberos_sessions", stats.kerberos_sessions.to_string()),
+        ("direct_sessions", stats.direct_sessions.to_string()),
+        ("adhoc_sessions", stats.adhoc_sessions.to_string()),
+    ];
+    csv_table(
+        &["metric", "value"],
+        rows.into_iter()
+            .map(|(metric, value)| vec![metric.to_string(), value]),
+    )
+}
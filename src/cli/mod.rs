@@ -2,15 +2,20 @@ use crate::config::AppConfig;
 use anyhow::Result;
 
 mod commands;
+pub mod output;
 pub mod parser;
 pub mod utils;
 
 #[allow(unused_imports)]
 use commands::*;
-pub use parser::{AliasSubcommand, Cli, Commands, EnvCommands};
+#[cfg(feature = "password_auth")]
+pub use parser::PasswordCommands;
+pub use parser::{AliasSubcommand, Cli, Commands, ConfigCommands, DbCommands, EnvCommands};
 
 impl Cli {
     pub async fn execute(self, config: AppConfig) -> Result<()> {
+        utils::set_assume_yes(self.yes || utils::assume_yes_from_env());
+
         match self.command {
             Commands::Connect {
                 target,
@@ -18,10 +23,45 @@ impl Cli {
                 port,
                 kerberos,
                 bastion,
+                jump,
                 no_bastion,
                 bastion_user,
                 key,
+                local_forwards,
+                remote_forwards,
+                dynamic_forwards,
+                label,
+                retries,
+                verbose,
+                attach,
+                no_attach,
+                check_port,
+                dry_run,
+                save,
+                no_save,
+                ephemeral,
+                no_record,
+                log,
+                env_file,
             } => {
+                let mut config = config;
+                if let Some(retries) = retries {
+                    config.connect_retries = retries;
+                }
+                let mut tunnels =
+                    utils::tunnel_args(&local_forwards, &remote_forwards, &dynamic_forwards)?;
+                // ssh's own -v rides along with the forwards: both need the ssh binary.
+                if verbose > 0 {
+                    tunnels.insert(0, format!("-{}", "v".repeat(verbose.min(3) as usize)));
+                }
+                // `--jump [user@]host` is `--bastion host [--bastion-user user]`.
+                let (bastion, bastion_user) = match jump.as_deref().map(|j| j.split_once('@')) {
+                    Some(Some((jump_user, jump_host))) => {
+                        (Some(jump_host.to_string()), Some(jump_user.to_string()))
+                    }
+                    Some(None) => (jump, bastion_user),
+                    None => (bastion, bastion_user),
+                };
                 commands::connect::execute(
                     target,
                     user,
@@ -31,6 +71,18 @@ impl Cli {
                     no_bastion,
                     bastion_user,
                     key,
+                    tunnels,
+                    label,
+                    attach,
+                    no_attach,
+                    check_port,
+                    dry_run,
+                    // --save / --no-save, or None to follow prompt_save_adhoc
+                    (save || no_save || ephemeral).then_some(save),
+                    ephemeral,
+                    no_record,
+                    log,
+                    env_file,
                     config,
                 )
                 .await
@@ -46,7 +98,18 @@ impl Cli {
                 bastion_user,
                 key,
                 tags,
+                expires,
+                ssh_config,
+                set_env,
+                send_env,
+                proxy_command,
+                auto_attach,
             } => {
+                // A lone positional is the host
+                let (name, host) = match host {
+                    Some(host) => (Some(name), host),
+                    None => (None, name),
+                };
                 commands::add::execute(
                     name,
                     host,
@@ -58,6 +121,12 @@ impl Cli {
                     bastion_user,
                     key,
                     tags,
+                    expires,
+                    ssh_config,
+                    set_env,
+                    send_env,
+                    proxy_command,
+                    auto_attach,
                     config,
                 )
                 .await
@@ -66,13 +135,52 @@ impl Cli {
                 tag,
                 recent,
                 detailed,
-            } => commands::list::execute(tag, recent, detailed, config).await,
+                output,
+                oneline,
+                absolute_time,
+                all,
+                archived,
+            } => {
+                commands::list::execute(
+                    tag,
+                    recent,
+                    detailed,
+                    output.format(),
+                    oneline,
+                    absolute_time,
+                    all,
+                    archived,
+                    config,
+                )
+                .await
+            }
+            Commands::Recent { limit, order } => {
+                commands::recent::execute(limit, order, config).await
+            }
+            Commands::Prune { .. } => commands::prune::expired(config).await,
             Commands::Remove { target, force } => {
                 commands::remove::execute(target, force, config).await
             }
-            Commands::Show { target } => commands::show::execute(target, config).await,
+            Commands::Archive { target } => commands::archive::execute(target, true, config).await,
+            Commands::Unarchive { target } => {
+                commands::archive::execute(target, false, config).await
+            }
+            Commands::Show {
+                target,
+                output,
+                absolute_time,
+                host_key,
+            } => {
+                commands::show::execute(target, output.format(), absolute_time, host_key, config)
+                    .await
+            }
+            Commands::Note { target, show } => commands::note::execute(target, show, config).await,
+            Commands::Diff { a, b } => commands::diff::execute(a, b, config).await,
             Commands::Edit {
                 target,
+                filter,
+                all,
+                interactive,
                 name,
                 host,
                 user,
@@ -81,12 +189,31 @@ impl Cli {
                 bastion,
                 no_bastion,
                 bastion_user,
+                clear_bastion_user,
                 key,
+                clear_key,
+                ssh_config,
+                clear_ssh_config,
+                set_env,
+                unset_env,
+                send_env,
+                clear_send_env,
+                proxy_command,
+                clear_proxy_command,
+                auto_attach,
+                clear_auto_attach,
+                notes,
+                clear_notes,
                 add_tags,
                 remove_tags,
+                add_alias,
+                remove_alias,
             } => {
                 commands::edit::execute(
                     target,
+                    filter,
+                    all,
+                    interactive,
                     name,
                     host,
                     user,
@@ -95,14 +222,35 @@ impl Cli {
                     bastion,
                     no_bastion,
                     bastion_user,
+                    clear_bastion_user,
                     key,
+                    clear_key,
+                    ssh_config,
+                    clear_ssh_config,
+                    set_env,
+                    unset_env,
+                    send_env,
+                    clear_send_env,
+                    proxy_command,
+                    clear_proxy_command,
+                    auto_attach,
+                    clear_auto_attach,
+                    notes,
+                    clear_notes,
                     add_tags,
                     remove_tags,
+                    add_alias,
+                    remove_alias,
                     config,
                 )
                 .await
             }
             Commands::Config {
+                action: Some(ConfigCommands::Repair),
+                ..
+            } => commands::config::repair(config).await,
+            Commands::Config {
+                action: None,
                 default_user,
                 default_bastion,
                 default_bastion_user,
@@ -111,6 +259,15 @@ impl Cli {
                 log_level,
                 clear_bastion,
                 search_mode,
+                time_format,
+                connect_retries,
+                theme,
+                default_ssh_config,
+                clear_default_ssh_config,
+                env_file,
+                clear_env_file,
+                retain_sessions_on_delete,
+                prompt_save_adhoc,
             } => {
                 commands::config::execute(
                     default_user,
@@ -121,11 +278,20 @@ impl Cli {
                     log_level,
                     clear_bastion,
                     search_mode,
+                    time_format,
+                    connect_retries,
+                    theme,
+                    default_ssh_config,
+                    clear_default_ssh_config,
+                    env_file,
+                    clear_env_file,
+                    retain_sessions_on_delete,
+                    prompt_save_adhoc,
                     config,
                 )
                 .await
             }
-            Commands::Stats => commands::stats::execute(config).await,
+            Commands::Stats { output } => commands::stats::execute(output.format(), config).await,
             Commands::Export {
                 format,
                 output,
@@ -136,29 +302,101 @@ impl Cli {
             Commands::Restore { file, force } => {
                 commands::restore::execute(file, force, config).await
             }
+            Commands::Db { command } => commands::db::execute(command, config).await,
+            #[cfg(feature = "password_auth")]
+            Commands::Password { command } => commands::password::execute(command, config).await,
             Commands::Duplicate { source, new_name } => {
                 commands::duplicate::execute(source, new_name, config).await
             }
-            Commands::Ping { target, timeout } => {
-                commands::ping::execute(target, timeout, config).await
-            }
+            Commands::Provision {
+                template,
+                hosts,
+                dry_run,
+            } => commands::provision::execute(template, hosts, dry_run, config).await,
+            Commands::CopyId {
+                target,
+                public_key,
+                dry_run,
+            } => commands::copy_id::execute(target, public_key, dry_run, config).await,
+            Commands::Ping {
+                target,
+                timeout,
+                tag,
+                parallel,
+            } => match (target, tag) {
+                (_, Some(tag)) => {
+                    commands::ping::execute_on_tag(tag, timeout, parallel as usize, config).await
+                }
+                (Some(target), None) => commands::ping::execute(target, timeout, config).await,
+                (None, None) => anyhow::bail!("ping needs a target or --tag"),
+            },
             Commands::Groups { group_name } => commands::groups::execute(group_name, config).await,
+            Commands::Move { from_tag, to_tag } => {
+                commands::move_tag::execute(from_tag, to_tag, config).await
+            }
             Commands::Env { command } => commands::env::execute(command).await,
-            Commands::Import { file, no_bastion } => {
-                commands::import::execute(file, no_bastion, config).await
+            Commands::Import {
+                file,
+                no_bastion,
+                update_existing,
+                dry_run,
+            } => {
+                commands::import::execute(file, no_bastion, update_existing, dry_run, config).await
             }
-            Commands::Completions { shell } => commands::completions::execute(shell, config).await,
+            Commands::Discover {
+                file,
+                no_bastion,
+                dry_run,
+            } => commands::discover::execute(file, no_bastion, dry_run, config).await,
+            Commands::Completions {
+                shell,
+                install,
+                force,
+            } => commands::completions::execute(shell, install, force, config).await,
             Commands::History {
                 connection,
                 limit,
                 days,
                 failed,
-            } => commands::history::execute(connection, limit, days, failed, config).await,
+                absolute_time,
+                label,
+                since,
+                until,
+                export,
+                format,
+                output,
+            } => {
+                commands::history::execute(
+                    connection,
+                    limit,
+                    days,
+                    since,
+                    until,
+                    failed,
+                    absolute_time,
+                    label,
+                    export,
+                    format,
+                    output.format(),
+                    config,
+                )
+                .await
+            }
             Commands::Tui => commands::tui::execute(config).await,
             Commands::Desktop => commands::desktop::execute(config).await,
-            Commands::Exec { target, command } => {
-                commands::exec::execute(target, command, config).await
-            }
+            Commands::Exec {
+                target,
+                tag,
+                parallel,
+                log,
+                command,
+            } => match (target, tag) {
+                (_, Some(tag)) => {
+                    commands::exec::execute_on_tag(tag, command, parallel as usize, config).await
+                }
+                (Some(target), None) => commands::exec::execute(target, command, log, config).await,
+                (None, None) => anyhow::bail!("exec needs a target or --tag"),
+            },
             Commands::Upload {
                 target,
                 local,
@@ -196,18 +434,27 @@ impl Cli {
                     AliasSubcommand::Remove { alias } => {
                         commands::alias::AliasAction::Remove { alias }
                     }
-                    AliasSubcommand::List { target } => {
-                        commands::alias::AliasAction::List { target }
+                    AliasSubcommand::List { pattern, output } => {
+                        commands::alias::AliasAction::List {
+                            pattern,
+                            output: output.format(),
+                        }
                     }
                 };
                 commands::alias::execute(alias_action, config).await
             }
             Commands::Close {
                 target,
+                number,
                 all,
                 cleanup,
                 force,
-            } => commands::close::execute(target, all, cleanup, force, config).await,
+            } => commands::close::execute(target, number, all, cleanup, force, config).await,
+            Commands::Sessions {
+                active,
+                output,
+                kill,
+            } => commands::sessions::execute(active, output.format(), kill, config).await,
         }
     }
 }
@@ -0,0 +1,133 @@
+//! `--output human|json|csv`, shared by the commands that print records
+//! (`list`, `show`, `stats`, `history`, `sessions`, `alias list`) so
+//! scripts get one flag everywhere.
+
+use crate::models::Connection;
+use clap::{Args, ValueEnum};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Tables and emoji for the terminal
+    #[default]
+    Human,
+    /// Pretty-printed JSON
+    Json,
+    /// Comma-separated values with a header row
+    Csv,
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct OutputArgs {
+    /// Output format: human (default), json, or csv
+    #[arg(long, value_enum, value_name = "FORMAT")]
+    output: Option<OutputFormat>,
+    /// Shorthand for --output json
+    #[arg(long, conflicts_with = "output")]
+    json: bool,
+}
+
+impl OutputArgs {
+    /// The format asked for, with `--json` as `--output json`
+    pub fn format(&self) -> OutputFormat {
+        if self.json {
+            OutputFormat::Json
+        } else {
+            self.output.unwrap_or_default()
+        }
+    }
+}
+
+/// `header` and `rows` as CSV, fields quoted where needed
+pub fn csv_table(header: &[&str], rows: impl IntoIterator<Item = Vec<String>>) -> String {
+    let mut out = header.join(",");
+    out.push('\n');
+    for row in rows {
+        let line: Vec<String> = row.iter().map(|f| csv_field(f)).collect();
+        out.push_str(&line.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+/// Quote a CSV field when it contains a delimiter, quote or line break
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// One row per connection; tags and aliases are `;`-separated
+pub fn connections_csv(connections: &[Connection]) -> String {
+    csv_table(
+        &[
+            "name",
+            "host",
+            "user",
+            "port",
+            "bastion",
+            "bastion_user",
+            "kerberos",
+            "key_path",
+            "tags",
+            "aliases",
+            "last_used",
+            "archived",
+        ],
+        connections.iter().map(|conn| {
+            vec![
+                conn.name.clone(),
+                conn.host.clone(),
+                conn.user.clone(),
+                conn.port.to_string(),
+                conn.bastion.clone().unwrap_or_default(),
+                conn.bastion_user.clone().unwrap_or_default(),
+                conn.use_kerberos.to_string(),
+                conn.key_path.clone().unwrap_or_default(),
+                conn.tags.join(";"),
+                conn.aliases.join(";"),
+                conn.last_used.map(|t| t.to_rfc3339()).unwrap_or_default(),
+                conn.archived.to_string(),
+            ]
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_fields_are_quoted_only_when_needed() {
+        assert_eq!(csv_field("prod-web"), "prod-web");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn connections_are_one_row_each() {
+        let mut conn = Connection::new(
+            "web-prod".into(),
+            "web.corp".into(),
+            "deploy".into(),
+            22,
+            Some("jump.corp".into()),
+            None,
+            false,
+            None,
+        );
+        conn.tags = vec!["prod".into(), "web".into()];
+        let csv = connections_csv(&[conn]);
+        let mut lines = csv.lines();
+        assert!(lines
+            .next()
+            .unwrap()
+            .starts_with("name,host,user,port,bastion,"));
+        assert_eq!(
+            lines.next().unwrap(),
+            "web-prod,web.corp,deploy,22,jump.corp,,false,,prod;web,,,false"
+        );
+        assert_eq!(lines.next(), None);
+    }
+}
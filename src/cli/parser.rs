@@ -1,4 +1,6 @@
+use super::output::OutputArgs;
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(name = "bayesian-ssh")]
@@ -15,10 +17,23 @@ use clap::{Parser, Subcommand};
 )]
 #[command(version)]
 pub struct Cli {
-    /// Override the active environment for this invocation (does not persist)
-    #[arg(long, global = true, value_name = "ENV_NAME")]
+    /// Override the active environment for this invocation (does not persist; alias: --profile)
+    #[arg(
+        long,
+        global = true,
+        value_name = "ENV_NAME",
+        visible_alias = "profile"
+    )]
     pub env: Option<String>,
 
+    /// Use an alternate connection database file for this invocation (does not persist)
+    #[arg(long, global = true, value_name = "PATH")]
+    pub database: Option<PathBuf>,
+
+    /// Answer yes to every confirmation prompt (also: BSSH_ASSUME_YES=1)
+    #[arg(short = 'y', long, global = true)]
+    pub yes: bool,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -38,31 +53,92 @@ pub enum EnvCommands {
         name: String,
     },
     /// Remove an environment and all its stored connections
+    #[command(visible_alias = "delete")]
     Remove {
         /// Name of the environment to delete
         name: String,
     },
 }
 
+#[derive(Subcommand)]
+pub enum DbCommands {
+    /// Find sessions and aliases that reference deleted connections
+    Check {
+        /// Delete the orphaned rows instead of only reporting them
+        #[arg(long)]
+        fix: bool,
+    },
+    /// Checkpoint the write-ahead log and VACUUM to reclaim disk space
+    Optimize,
+}
+
+#[cfg(feature = "password_auth")]
+#[derive(Subcommand)]
+pub enum PasswordCommands {
+    /// Prompt for the password and store it in the OS keyring
+    Set {
+        /// Connection name, alias, or ID
+        target: String,
+        /// Keyring entry to use (default: the current one, or user@host)
+        #[arg(long = "ref", value_name = "NAME")]
+        reference: Option<String>,
+    },
+    /// Delete the keyring entry and go back to key authentication
+    Clear {
+        /// Connection name, alias, or ID
+        target: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommands {
+    /// Report settings that no longer parse, back up the file, and rewrite it
+    Repair,
+}
+
 #[derive(Subcommand)]
 pub enum Commands {
     /// Connect to a saved server (supports fuzzy name matching)
     #[command(long_about = "Open an SSH session to a saved connection.\n\n\
             The target is matched by name, alias, or hostname using fuzzy search.\n\
             Bayesian scoring ranks the best match from your usage history.\n\
-            Override any stored setting with the optional flags below.\n\n\
+            Override any stored setting with the optional flags below; overrides apply\n\
+            to this session only and are never saved to the connection.\n\n\
+            A target that matches no saved connection is tried as a hostname. Once\n\
+            that session ends, bssh offers to save it (see prompt_save_adhoc);\n\
+            --save and --no-save answer for you. --ephemeral skips saved connections\n\
+            altogether: the session is kept in history, marked (ad-hoc), and the host\n\
+            is never saved. --no-record writes nothing: no history entry, no\n\
+            last-used or use-count bump (auto_save_history = false only drops the\n\
+            history entry, for every session). --log records a transcript of the\n\
+            session and keeps its path in history. --env-file sets KEY=VALUE lines\n\
+            in the environment of the local ssh process (e.g. KRB5CCNAME to pick a\n\
+            Kerberos cache), overriding the env_file setting.\n\n\
             Examples:\n\
               bssh connect web-prod\n\
+              bssh connect 10.0.0.9 -u admin --save\n\
+              bssh connect --ephemeral scratch-box.corp\n\
+              bssh connect web-prod --no-record\n\
+              bssh connect web-prod --log ~/incident-4821.log\n\
+              bssh connect backend --env-file ~/.config/bssh/eu-realm.env\n\
               bssh connect db01 -u admin -p 2222\n\
-              bssh connect backend -k true -b bastion.corp")]
+              bssh connect backend -k true -b bastion.corp\n\
+              bssh connect db01 --jump ops@jump2.corp\n\
+              bssh connect db01 -L 5432:localhost:5432 -D 1080\n\
+              bssh connect web-prod --label incident-4821\n\
+              bssh connect web-prod --retries 3\n\
+              bssh connect web-prod -vv --dry-run\n\
+              bssh connect web-prod --attach\n\
+              bssh connect web-prod --attach deploy\n\
+              bssh connect web-prod --check-port")]
     Connect {
         /// Connection name, alias, or hostname (fuzzy-matched)
         target: String,
         /// Override the SSH username for this session
         #[arg(short = 'u', long, value_name = "USER")]
         user: Option<String>,
-        /// Override the SSH port (default: 22)
-        #[arg(short = 'p', long, default_value = "22", value_name = "PORT")]
+        /// Override the SSH port for this session
+        #[arg(short = 'p', long, value_name = "PORT")]
         port: Option<u16>,
         /// Force Kerberos auth on or off for this session
         #[arg(short = 'k', long, value_name = "BOOL")]
@@ -70,6 +146,9 @@ pub enum Commands {
         /// Route through this bastion/jump host
         #[arg(short = 'b', long, value_name = "HOST")]
         bastion: Option<String>,
+        /// Jump through this host for this session only, like `ssh -J`
+        #[arg(short = 'J', long, value_name = "[USER@]HOST", conflicts_with_all = ["bastion", "no_bastion"])]
+        jump: Option<String>,
         /// Bypass any configured bastion and connect directly
         #[arg(long)]
         no_bastion: bool,
@@ -79,21 +158,85 @@ pub enum Commands {
         /// Path to an SSH private key to use
         #[arg(short = 'i', long, value_name = "FILE")]
         key: Option<String>,
+        /// Open a local forward for this session: [bind:]port:host:hostport (repeatable)
+        #[arg(short = 'L', long = "local-forward", value_name = "SPEC")]
+        local_forwards: Vec<String>,
+        /// Open a remote forward for this session: [bind:]port:host:hostport (repeatable)
+        #[arg(short = 'R', long = "remote-forward", value_name = "SPEC")]
+        remote_forwards: Vec<String>,
+        /// Open a SOCKS proxy for this session: [bind:]port (repeatable)
+        #[arg(short = 'D', long = "dynamic-forward", value_name = "SPEC")]
+        dynamic_forwards: Vec<String>,
+        /// Label this session for grouping in `bssh history --label`
+        #[arg(long, value_name = "LABEL")]
+        label: Option<String>,
+        /// Retry this many times if the connection fails (overrides connect_retries)
+        #[arg(long, value_name = "COUNT")]
+        retries: Option<u32>,
+        /// Pass -v to ssh for handshake diagnostics (repeat for -vv/-vvv)
+        #[arg(short = 'v', long, action = clap::ArgAction::Count)]
+        verbose: u8,
+        /// Attach to a tmux session on the host, creating it if needed (default name: bssh)
+        #[arg(
+            long,
+            value_name = "SESSION",
+            num_args = 0..=1,
+            default_missing_value = "bssh",
+            value_parser = parse_tmux_session
+        )]
+        attach: Option<String>,
+        /// Open a plain shell even if the connection auto-attaches to tmux
+        #[arg(long, conflicts_with = "attach")]
+        no_attach: bool,
+        /// Fail fast if the SSH port (or the bastion's) does not accept TCP connections
+        #[arg(long)]
+        check_port: bool,
+        /// Print the ssh command instead of running it
+        #[arg(long)]
+        dry_run: bool,
+        /// Save an unsaved host after the session without asking
+        #[arg(long)]
+        save: bool,
+        /// Never offer to save an unsaved host
+        #[arg(long, conflicts_with = "save")]
+        no_save: bool,
+        /// Connect to TARGET as a hostname, skipping saved connections; the
+        /// session is kept in history as ad-hoc and the host is never saved
+        #[arg(long, conflicts_with = "save")]
+        ephemeral: bool,
+        /// Leave no trace: skip the session record and don't bump last used
+        #[arg(long, conflicts_with = "label")]
+        no_record: bool,
+        /// Record the whole session, output included, to FILE (uses script(1))
+        #[arg(long, value_name = "FILE")]
+        log: Option<PathBuf>,
+        /// Set the KEY=VALUE lines of FILE in the local ssh process's environment
+        #[arg(long, value_name = "FILE")]
+        env_file: Option<PathBuf>,
     },
 
     /// Save a new SSH connection
     #[command(long_about = "Add a new named connection to the database.\n\n\
             The name is used as a friendly identifier for connect, upload, exec, etc.\n\
+            Given only a host, the name is derived from it: the first label of a\n\
+            hostname (web01.prod.example.com -> web01), or the address with '-'\n\
+            separators for an IP. A numeric suffix (web01-2) avoids taken names.\n\
             Tags let you group related connections (e.g. --tags prod --tags eu-west).\n\n\
             Examples:\n\
               bssh add web-prod web.example.com -u deploy\n\
+              bssh add web01.prod.example.com -u deploy\n\
               bssh add db01 10.0.1.5 -p 2222 -k true -b bastion.corp\n\
-              bssh add staging app.staging.internal -t staging -t backend")]
+              bssh add staging app.staging.internal -t staging -t backend\n\
+              bssh add gpu01 gpu01.lab -i ~/.ssh/lab --ssh-config ~/.ssh/lab_config\n\
+              bssh add app01 app01.corp --set-env APP_ENV=staging --send-env 'LC_*'\n\
+              bssh add vault vault.corp --proxy-command 'sso-ssh-proxy --target %h:%p'\n\
+              bssh add build01 build01.corp --auto-attach")]
     Add {
-        /// Friendly name for this connection (must be unique)
+        /// Friendly name for this connection (must be unique); when it is the
+        /// only argument, it is taken as the host and the name is derived
         name: String,
         /// Server hostname or IP address
-        host: String,
+        host: Option<String>,
         /// SSH username (falls back to config default or $USER)
         #[arg(short = 'u', long, value_name = "USER")]
         user: Option<String>,
@@ -118,17 +261,46 @@ pub enum Commands {
         /// Tags for grouping and filtering (repeatable)
         #[arg(short = 't', long, value_name = "TAG")]
         tags: Vec<String>,
+        /// Mark as ephemeral: expire after this long (e.g. 30m, 2h, 3d, 1w)
+        #[arg(long, value_parser = parse_ttl, value_name = "DURATION")]
+        expires: Option<chrono::Duration>,
+        /// OpenSSH config file passed to ssh as `-F` for this connection
+        #[arg(long, value_name = "FILE")]
+        ssh_config: Option<PathBuf>,
+        /// Set a variable on the remote side via SetEnv (repeatable)
+        #[arg(long, value_parser = parse_env_var, value_name = "KEY=VALUE")]
+        set_env: Vec<(String, String)>,
+        /// Forward matching local variables via SendEnv (repeatable, e.g. 'LC_*')
+        #[arg(long, value_parser = parse_env_pattern, value_name = "PATTERN")]
+        send_env: Vec<String>,
+        /// Reach the host through this ProxyCommand instead of a bastion (%h host, %p port, %r user)
+        #[arg(long, value_name = "COMMAND", conflicts_with_all = ["bastion", "bastion_user"])]
+        proxy_command: Option<String>,
+        /// Attach to this tmux session on every connect (default name: bssh)
+        #[arg(
+            long,
+            value_name = "SESSION",
+            num_args = 0..=1,
+            default_missing_value = "bssh",
+            value_parser = parse_tmux_session
+        )]
+        auto_attach: Option<String>,
     },
 
     /// List saved connections (filterable by tag or recency)
     #[command(
         long_about = "Display all saved connections in the active environment.\n\n\
             Connections are ranked by Bayesian score (most-used first).\n\
-            Use --tag to filter by group, --recent for recently used, --detailed for full info.\n\n\
+            Use --tag to filter by group, --recent for recently used, --detailed for full info.\n\
+            Archived connections are hidden unless --all or --archived is given.\n\n\
             Examples:\n\
               bssh list\n\
               bssh list -t prod -d\n\
-              bssh list --recent"
+              bssh list --recent\n\
+              bssh list --archived\n\
+              bssh list --json\n\
+              bssh list --output csv > hosts.csv\n\
+              bssh list --oneline | cut -f1"
     )]
     List {
         /// Show only connections with this tag
@@ -138,8 +310,55 @@ pub enum Commands {
         #[arg(short = 'r', long)]
         recent: bool,
         /// Show full connection details (host, port, bastion, auth)
-        #[arg(short = 'd', long)]
+        #[arg(short = 'd', long, conflicts_with_all = ["json", "output"])]
         detailed: bool,
+        #[command(flatten)]
+        output: OutputArgs,
+        /// Print `name host user port` per line, tab-separated, for scripts
+        #[arg(long, conflicts_with_all = ["detailed", "json", "output"])]
+        oneline: bool,
+        /// Show absolute timestamps instead of "2 hours ago"
+        #[arg(long)]
+        absolute_time: bool,
+        /// Include archived connections
+        #[arg(short = 'a', long)]
+        all: bool,
+        /// Show only archived connections
+        #[arg(long, conflicts_with = "all")]
+        archived: bool,
+    },
+
+    /// Show the most recently used distinct connect targets
+    #[command(
+        long_about = "List the connections you connected to most recently, one entry per\n\
+            connection, newest first. Unlike `list --recent`, this keeps the order in\n\
+            which hosts were touched rather than a single timestamp per connection.\n\n\
+            Examples:\n\
+              bssh recent\n\
+              bssh recent -n 5 --order"
+    )]
+    Recent {
+        /// Maximum number of targets to show
+        #[arg(short = 'n', long, default_value = "10", value_name = "COUNT")]
+        limit: usize,
+        /// List in the order the targets were used (oldest first)
+        #[arg(long)]
+        order: bool,
+    },
+
+    /// Delete connections that have passed their expiry time
+    #[command(
+        long_about = "Remove connections added with `bssh add --expires` whose expiry time has\n\
+            passed, together with their session history and aliases. The affected\n\
+            connections are listed and confirmation is asked first (skip with --yes).\n\n\
+            Examples:\n\
+              bssh prune --expired\n\
+              bssh --yes prune --expired"
+    )]
+    Prune {
+        /// Remove connections past their `--expires` time
+        #[arg(long, required = true)]
+        expired: bool,
     },
 
     /// Remove a saved connection and its session history
@@ -151,22 +370,120 @@ pub enum Commands {
         force: bool,
     },
 
+    /// Hide a connection from list and search without deleting it
+    #[command(
+        long_about = "Park a connection that is temporarily unreachable (maintenance,\n\
+            decommission pending, ...). Archived connections are left out of `list`,\n\
+            search and the TUI, but keep their history and aliases and can still be\n\
+            reached by exact name or alias. Use `bssh unarchive` to bring them back.\n\n\
+            Examples:\n\
+              bssh archive old-db\n\
+              bssh list --archived\n\
+              bssh unarchive old-db"
+    )]
+    Archive {
+        /// Connection name, alias, or ID to archive
+        target: String,
+    },
+
+    /// Restore an archived connection to list and search
+    Unarchive {
+        /// Connection name, alias, or ID to restore
+        target: String,
+    },
+
     /// Show full details of a saved connection
+    #[command(
+        long_about = "Print every stored field of a connection, including its aliases.\n\n\
+            With --host-key, the host's ed25519 and RSA keys are fetched with\n\
+            ssh-keyscan (a direct network call, not through the bastion) and their\n\
+            SHA256 fingerprints are shown and cached. A warning is printed when they\n\
+            differ from the cached ones.\n\n\
+            Examples:\n\
+              bssh show web-prod\n\
+              bssh show web-prod --host-key\n\
+              bssh show web-prod --json\n\
+              bssh show web-prod --output csv"
+    )]
     Show {
         /// Connection name, alias, or ID
         target: String,
+        #[command(flatten)]
+        output: OutputArgs,
+        /// Show timestamps in RFC3339 or the configured time_format
+        #[arg(long)]
+        absolute_time: bool,
+        /// Fetch the host-key fingerprints with ssh-keyscan and compare with the cached ones
+        #[arg(long)]
+        host_key: bool,
+    },
+
+    /// Edit a connection's runbook notes in $EDITOR, or read them with --show
+    #[command(
+        long_about = "Keep free-form, multi-line notes with a connection: runbooks, contacts,\n\
+            maintenance windows. The notes open in $VISUAL or $EDITOR (default: vi)\n\
+            and are saved when the editor exits; an editor that exits with an error\n\
+            (e.g. :cq in vim) leaves them unchanged. Emptying the file removes them.\n\
+            --show pipes them to $PAGER (default: less), or prints them when the\n\
+            output is not a terminal.\n\n\
+            Examples:\n\
+              bssh note db-prod\n\
+              bssh note db-prod --show\n\
+              EDITOR=nano bssh note web-prod"
+    )]
+    Note {
+        /// Connection name, alias, or ID
+        target: String,
+        /// Show the notes instead of editing them
+        #[arg(long)]
+        show: bool,
+    },
+
+    /// Compare the saved settings of two connections side by side
+    #[command(
+        long_about = "Print a field-by-field comparison of two saved connections
+            (host, user, port, bastion, kerberos, key, tags). Differing fields are marked.
+
+            Examples:
+              bssh diff web-staging web-prod
+              bssh diff db01 db02"
+    )]
+    Diff {
+        /// First connection name, alias, or ID
+        a: String,
+        /// Second connection name, alias, or ID
+        b: String,
     },
 
     /// Edit one or more settings of an existing connection
     #[command(long_about = "Modify fields on a saved connection.\n\n\
-            Only the fields you pass are updated; everything else stays unchanged.\n\n\
+            Only the fields you pass are updated; everything else stays unchanged.\n\
+            With --filter or --all, the changes are applied to every matching\n\
+            connection after listing them and asking for confirmation.\n\n\
             Examples:\n\
               bssh edit web-prod --user deploy --port 2222\n\
               bssh edit db01 --bastion new-bastion.corp\n\
-              bssh edit staging --add-tags canary --remove-tags legacy")]
+              bssh edit db01 --clear-key --clear-bastion-user\n\
+              bssh edit staging --add-tags canary --remove-tags legacy\n\
+              bssh edit web-prod --add-alias web --remove-alias wp\n\
+              bssh edit --filter staging --bastion jump.staging.corp\n\
+              bssh edit --filter lab --ssh-config ~/.ssh/lab_config\n\
+              bssh edit app01 --set-env APP_ENV=prod --unset-env DEBUG\n\
+              bssh edit vault --proxy-command 'sso-ssh-proxy --target %h:%p'\n\
+              bssh edit web-prod -i")]
     Edit {
         /// Connection name, alias, or ID to edit
-        target: String,
+        #[arg(required_unless_present_any = ["filter", "all"], conflicts_with_all = ["filter", "all"])]
+        target: Option<String>,
+        /// Edit every connection with this tag instead of a single target
+        #[arg(long, value_name = "TAG", conflicts_with = "all")]
+        filter: Option<String>,
+        /// Edit every connection instead of a single target
+        #[arg(long)]
+        all: bool,
+        /// Walk through the main fields one prompt at a time, pre-filled with current values
+        #[arg(short = 'i', long, conflicts_with_all = ["filter", "all"])]
+        interactive: bool,
         /// Rename the connection
         #[arg(long, value_name = "NAME")]
         name: Option<String>,
@@ -191,15 +508,69 @@ pub enum Commands {
         /// Change the bastion username
         #[arg(long, value_name = "USER")]
         bastion_user: Option<String>,
+        /// Remove the bastion username (fall back to the connection user)
+        #[arg(long, conflicts_with = "bastion_user")]
+        clear_bastion_user: bool,
         /// Set or change the SSH private key path
         #[arg(long, value_name = "FILE")]
         key: Option<String>,
+        /// Remove the SSH private key path (use agent/default identities)
+        #[arg(long, conflicts_with = "key")]
+        clear_key: bool,
+        /// Set or change the OpenSSH config file passed to ssh as `-F`
+        #[arg(long, value_name = "FILE")]
+        ssh_config: Option<PathBuf>,
+        /// Stop passing a per-connection `-F` file (the global default still applies)
+        #[arg(long, conflicts_with = "ssh_config")]
+        clear_ssh_config: bool,
+        /// Set or replace a remote variable sent via SetEnv (repeatable)
+        #[arg(long, value_parser = parse_env_var, value_name = "KEY=VALUE")]
+        set_env: Vec<(String, String)>,
+        /// Stop setting a remote variable (repeatable)
+        #[arg(long, value_name = "KEY")]
+        unset_env: Vec<String>,
+        /// Add a SendEnv pattern for local variables (repeatable)
+        #[arg(long, value_parser = parse_env_pattern, value_name = "PATTERN")]
+        send_env: Vec<String>,
+        /// Remove all SendEnv patterns
+        #[arg(long)]
+        clear_send_env: bool,
+        /// Set a ProxyCommand template (%h host, %p port, %r user); removes the bastion
+        #[arg(long, value_name = "COMMAND", conflicts_with_all = ["bastion", "bastion_user"])]
+        proxy_command: Option<String>,
+        /// Remove the ProxyCommand
+        #[arg(long, conflicts_with = "proxy_command")]
+        clear_proxy_command: bool,
+        /// Attach to this tmux session on every connect (default name: bssh)
+        #[arg(
+            long,
+            value_name = "SESSION",
+            num_args = 0..=1,
+            default_missing_value = "bssh",
+            value_parser = parse_tmux_session
+        )]
+        auto_attach: Option<String>,
+        /// Stop attaching to tmux on connect
+        #[arg(long, conflicts_with = "auto_attach")]
+        clear_auto_attach: bool,
+        /// Replace the connection's notes (use `bssh note` to edit them in $EDITOR)
+        #[arg(long, value_name = "TEXT")]
+        notes: Option<String>,
+        /// Remove the connection's notes
+        #[arg(long, conflicts_with = "notes")]
+        clear_notes: bool,
         /// Add tags (repeatable)
         #[arg(long, value_name = "TAG")]
         add_tags: Vec<String>,
         /// Remove tags (repeatable)
         #[arg(long, value_name = "TAG")]
         remove_tags: Vec<String>,
+        /// Add an alias for the connection (repeatable)
+        #[arg(long, value_name = "ALIAS", conflicts_with_all = ["filter", "all"])]
+        add_alias: Vec<String>,
+        /// Remove one of the connection's aliases (repeatable)
+        #[arg(long, value_name = "ALIAS", conflicts_with_all = ["filter", "all"])]
+        remove_alias: Vec<String>,
     },
 
     /// View or update global application settings
@@ -210,8 +581,16 @@ pub enum Commands {
               bssh config\n\
               bssh config --default-user deploy --use-kerberos true\n\
               bssh config --search-mode bayesian\n\
-              bssh config --clear-bastion")]
+              bssh config --time-format \"%Y-%m-%d %H:%M %Z\"\n\
+              bssh config --clear-bastion\n\
+              bssh config --default-ssh-config ~/.ssh/bssh_config\n\
+              bssh config --env-file ~/.config/bssh/krb5.env\n\
+              bssh config --retain-sessions-on-delete true\n\
+              bssh config repair")]
+    #[command(args_conflicts_with_subcommands = true)]
     Config {
+        #[command(subcommand)]
+        action: Option<ConfigCommands>,
         /// Default SSH username for new connections
         #[arg(long, value_name = "USER")]
         default_user: Option<String>,
@@ -236,10 +615,48 @@ pub enum Commands {
         /// Connection search algorithm: "bayesian" (usage-ranked) or "fuzzy" (substring)
         #[arg(long, value_parser = ["bayesian", "fuzzy"], value_name = "MODE")]
         search_mode: Option<String>,
+        /// Timestamp display: "relative", "rfc3339", or a strftime pattern such as "%Y-%m-%d %H:%M"
+        #[arg(long, value_name = "FORMAT")]
+        time_format: Option<String>,
+        /// Times `connect` retries after a connection failure (0 disables retries)
+        #[arg(long, value_name = "COUNT")]
+        connect_retries: Option<u32>,
+        /// TUI color theme
+        #[arg(long, value_parser = ["default", "solarized", "mono"], value_name = "THEME")]
+        theme: Option<String>,
+        /// OpenSSH config file passed as `-F` to connections without their own
+        #[arg(long, value_name = "FILE")]
+        default_ssh_config: Option<PathBuf>,
+        /// Stop passing a default `-F` file
+        #[arg(long, conflicts_with = "default_ssh_config")]
+        clear_default_ssh_config: bool,
+        /// `KEY=VALUE` file sourced into the local ssh environment on connect
+        #[arg(long, value_name = "FILE")]
+        env_file: Option<PathBuf>,
+        /// Stop loading a default env file
+        #[arg(long, conflicts_with = "env_file")]
+        clear_env_file: bool,
+        /// Keep session history when a connection is removed
+        #[arg(long, value_name = "BOOL")]
+        retain_sessions_on_delete: Option<bool>,
+        /// Offer to save hosts reached with `connect` that are not saved
+        #[arg(long, value_name = "BOOL")]
+        prompt_save_adhoc: Option<bool>,
     },
 
     /// Show usage statistics (total connections, sessions, top hosts)
-    Stats,
+    #[command(
+        long_about = "Summarize connections and session history: totals, success rate,\n\
+            average session length, busiest weekday, and Kerberos vs direct usage.\n\n\
+            Examples:\n\
+              bssh stats\n\
+              bssh stats --json\n\
+              bssh stats --output csv"
+    )]
+    Stats {
+        #[command(flatten)]
+        output: OutputArgs,
+    },
 
     /// Export connections to a file or stdout
     #[command(
@@ -287,6 +704,20 @@ pub enum Commands {
         force: bool,
     },
 
+    /// Database maintenance (integrity checks)
+    #[command(long_about = "Maintenance commands for the connection database.\n\n\
+            `check` lists sessions and aliases whose connection no longer exists;\n\
+            with --fix it deletes them. `optimize` checkpoints the write-ahead log\n\
+            and runs VACUUM, which shrinks the file after lots of session churn.\n\n\
+            Examples:\n\
+              bssh db check\n\
+              bssh db check --fix\n\
+              bssh db optimize")]
+    Db {
+        #[command(subcommand)]
+        command: DbCommands,
+    },
+
     /// Clone an existing connection under a new name
     #[command(
         long_about = "Create a copy of a saved connection with a different name.\n\
@@ -301,20 +732,82 @@ pub enum Commands {
         new_name: String,
     },
 
+    /// Create a connection per host from a template connection
+    #[command(
+        long_about = "Create one connection per host, copying every setting but the host\n\
+            from a saved template connection (user, port, bastion, key, tags, ...).\n\
+            Hosts may use brace patterns, expanded by bssh when quoted: numeric\n\
+            ranges keep zero padding ({01..10}) and lists pick words ({a,b}).\n\
+            Each connection is named after its host the way 'bssh add HOST' does;\n\
+            hosts whose name is already taken are skipped.\n\n\
+            Examples:\n\
+              bssh provision --template web --hosts 'web{01..10}.prod'\n\
+              bssh provision --template db --hosts 'db{1..3}.{eu,us}.corp' --dry-run\n\
+              bssh provision --template web --hosts web11.prod web12.prod"
+    )]
+    Provision {
+        /// Saved connection whose settings are copied
+        #[arg(long, value_name = "CONNECTION")]
+        template: String,
+        /// Hosts or brace patterns to create connections for
+        #[arg(long, required = true, num_args = 1.., value_name = "HOST")]
+        hosts: Vec<String>,
+        /// List the connections that would be created without saving them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Install a public key on a saved connection's host with ssh-copy-id
+    #[command(
+        name = "copy-id",
+        long_about = "Run ssh-copy-id against a saved connection, with its user, port, bastion\n\
+            or proxy command, so key authentication works from then on.\n\
+            The key defaults to the connection's key with .pub appended, or else the\n\
+            first of ~/.ssh/id_ed25519.pub, id_ecdsa.pub, id_rsa.pub and id_dsa.pub.\n\
+            The run is recorded in history with the label copy-id.\n\n\
+            Examples:\n\
+              bssh copy-id web-prod\n\
+              bssh copy-id db01 ~/.ssh/deploy.pub\n\
+              bssh copy-id web-prod --dry-run"
+    )]
+    CopyId {
+        /// Connection name, alias, or hostname
+        target: String,
+        /// Public key to install (default: the connection's key + .pub, or ~/.ssh/id_*.pub)
+        #[arg(value_name = "PUBKEY")]
+        public_key: Option<PathBuf>,
+        /// Print the ssh-copy-id command instead of running it
+        #[arg(long)]
+        dry_run: bool,
+    },
+
     /// Test SSH reachability of a saved connection
     #[command(
-        long_about = "Attempt a TCP connect (and optional SSH handshake) to verify the host is reachable.\n\
+        long_about = "Attempt a TCP connect, then an SSH handshake, to verify the host is reachable.\n\
+            The TCP check reports a refused or timed-out port before ssh is tried.\n\
             Useful for verifying firewall rules or bastion routing before a full session.\n\n\
+            With --tag instead of a target, every connection with that tag is tested,\n\
+            --parallel at a time, and one line per host is printed; the exit code is 5\n\
+            if any host is unreachable.\n\n\
             Examples:\n\
               bssh ping web-prod\n\
-              bssh ping db01 -t 10"
+              bssh ping db01 -t 10\n\
+              bssh ping --tag prod --parallel 16"
     )]
     Ping {
         /// Connection name, alias, or hostname
-        target: String,
+        #[arg(required_unless_present = "tag")]
+        target: Option<String>,
         /// Connection timeout in seconds (default: 5)
         #[arg(short = 't', long, value_name = "SECS")]
         timeout: Option<u64>,
+        /// Test every connection with this tag instead of one target
+        #[arg(long, value_name = "TAG", conflicts_with = "target")]
+        tag: Option<String>,
+        /// Hosts to test at once with --tag
+        #[arg(long, value_name = "N", default_value_t = 8, requires = "tag",
+              value_parser = clap::value_parser!(u16).range(1..))]
+        parallel: u16,
     },
 
     /// List tag groups, or show connections in a specific group
@@ -323,14 +816,38 @@ pub enum Commands {
         group_name: Option<String>,
     },
 
+    /// Move every connection from one tag (group) to another
+    #[command(
+        long_about = "Replace one tag with another on every connection that carries it, in a\n\
+            single transaction. Connections that already have the new tag simply lose\n\
+            the old one. Archived connections are included.\n\n\
+            Examples:\n\
+              bssh move --from-tag staging --to-tag preprod\n\
+              bssh move --from-tag old-dc --to-tag dc-paris"
+    )]
+    Move {
+        /// Tag to remove
+        #[arg(long, value_name = "TAG")]
+        from_tag: String,
+        /// Tag to add in its place
+        #[arg(long, value_name = "TAG")]
+        to_tag: String,
+    },
+
     /// Import SSH hosts from an OpenSSH config file
     #[command(
         long_about = "Parse an OpenSSH config file and import each Host block as a connection.\n\
-            Defaults to ~/.ssh/config when --file is omitted.\n\n\
+            Defaults to ~/.ssh/config when --file is omitted. Hosts already saved under the\n\
+            same name or the same user@host:port endpoint are skipped unless --update-existing\n\
+            (alias --update), which refreshes their host, user, port, key and ProxyJump bastion\n\
+            while keeping tags and usage history.\n\
+            Use --dry-run to preview the result without writing anything.\n\n\
             Examples:\n\
               bssh import\n\
+              bssh import --dry-run\n\
               bssh import -f /etc/ssh/ssh_config\n\
-              bssh import --no-bastion"
+              bssh import --no-bastion\n\
+              bssh import --update"
     )]
     Import {
         /// Path to the SSH config file (default: ~/.ssh/config)
@@ -339,9 +856,40 @@ pub enum Commands {
         /// Import all hosts as direct connections (ignore ProxyJump)
         #[arg(long)]
         no_bastion: bool,
+        /// Refresh hosts already saved under the same name or endpoint (host, user, port, key, bastion)
+        #[arg(long, visible_alias = "update")]
+        update_existing: bool,
+        /// Show which hosts would be imported, updated or skipped without saving anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Add hosts found in ~/.ssh/known_hosts as connections
+    #[command(
+        long_about = "Read an OpenSSH known_hosts file and offer to save every host in it as a\n\
+            connection tagged 'discovered', using the default user. Hosts already saved\n\
+            under the same name or user@host:port endpoint are skipped. Hashed entries\n\
+            (HashKnownHosts yes) cannot be turned back into hostnames and are only counted.\n\
+            Use --dry-run to list the hosts without saving anything.\n\n\
+            Examples:\n\
+              bssh discover --dry-run\n\
+              bssh discover\n\
+              bssh --yes discover -f ~/.ssh/known_hosts.old"
+    )]
+    Discover {
+        /// known_hosts file to read (default: ~/.ssh/known_hosts)
+        #[arg(short = 'f', long, value_name = "FILE")]
+        file: Option<PathBuf>,
+        /// Save hosts as direct connections (ignore the default bastion)
+        #[arg(long)]
+        no_bastion: bool,
+        /// List the hosts that would be added without saving anything
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Manage multi-environment profiles (separate connection databases)
+    #[command(visible_alias = "profile")]
     Env {
         #[command(subcommand)]
         command: EnvCommands,
@@ -349,15 +897,27 @@ pub enum Commands {
 
     /// Generate shell completion script for bash, zsh, fish, or powershell
     #[command(long_about = "Print a completion script to stdout.\n\
-            Source or install it for your shell to enable tab-completion.\n\n\
+            Source or install it for your shell to enable tab-completion.\n\
+            With --install, the script is written to your shell's conventional\n\
+            completion directory (the shell is detected from $SHELL when omitted).\n\n\
             Examples:\n\
+              bssh completions --install\n\
+              bssh completions --install zsh --force\n\
               bssh completions bash > ~/.local/share/bash-completion/completions/bssh\n\
               bssh completions zsh > ~/.zfunc/_bssh\n\
               bssh completions fish > ~/.config/fish/completions/bssh.fish")]
     Completions {
-        /// Target shell
-        #[arg(value_enum)]
-        shell: clap_complete::Shell,
+        /// Target shell (detected from $SHELL with --install)
+        #[arg(value_enum, required_unless_present = "install")]
+        shell: Option<clap_complete::Shell>,
+
+        /// Write the script to the shell's completion directory instead of stdout
+        #[arg(long)]
+        install: bool,
+
+        /// Overwrite an existing completion file when installing
+        #[arg(long, requires = "install")]
+        force: bool,
     },
 
     /// Show past SSH session history (timestamps, durations, exit codes)
@@ -366,20 +926,45 @@ pub enum Commands {
             Examples:\n\
               bssh history\n\
               bssh history -c web-prod -n 50\n\
-              bssh history --days 7 --failed")]
+              bssh history --days 7 --failed\n\
+              bssh history --label incident-4821\n\
+              bssh history -c db-prod --since 2026-03-02 --until 2026-03-03\n\
+              bssh history --since 2026-03-02T14:00:00Z --until 2026-03-02T16:30:00Z\n\
+              bssh history --days 1 --output json | jq '.[].connection'\n\
+              bssh history -c prod --since 2026-01-01 --until 2026-03-31 --export q1.csv --format csv")]
     History {
         /// Show only sessions for this connection name
         #[arg(short = 'c', long, value_name = "NAME")]
         connection: Option<String>,
-        /// Maximum number of entries to display
-        #[arg(short = 'n', long, default_value = "20", value_name = "COUNT")]
-        limit: usize,
+        /// Maximum number of entries to display (default: 20, unlimited with --export)
+        #[arg(short = 'n', long, value_name = "COUNT")]
+        limit: Option<usize>,
         /// Limit to sessions from the last N days
         #[arg(short = 'd', long, value_name = "DAYS")]
         days: Option<u32>,
         /// Show only sessions that exited with an error
         #[arg(short = 'f', long)]
         failed: bool,
+        /// Show timestamps in RFC3339 or the configured time_format
+        #[arg(long)]
+        absolute_time: bool,
+        /// Show only sessions opened with this `connect --label`
+        #[arg(short = 'l', long, value_name = "LABEL")]
+        label: Option<String>,
+        /// Show only sessions started at or after this time (RFC3339, or YYYY-MM-DD from local midnight)
+        #[arg(long, value_parser = parse_since, value_name = "DATE")]
+        since: Option<chrono::DateTime<chrono::Utc>>,
+        /// Show only sessions started at or before this time (RFC3339, or YYYY-MM-DD through the end of that day)
+        #[arg(long, value_parser = parse_until, value_name = "DATE")]
+        until: Option<chrono::DateTime<chrono::Utc>>,
+        /// Write the matching sessions to this file instead of printing them
+        #[arg(long, value_name = "FILE")]
+        export: Option<String>,
+        /// Export format: csv or json (default: --output if set, else json)
+        #[arg(long, value_name = "FMT", requires = "export")]
+        format: Option<String>,
+        #[command(flatten)]
+        output: OutputArgs,
     },
 
     /// Launch the interactive terminal dashboard
@@ -402,15 +987,32 @@ pub enum Commands {
         alias = "run",
         long_about = "Execute a one-off command over SSH without opening an interactive shell.\n\
             The remote stdout and stderr are printed locally.\n\n\
+            With --tag instead of a target, the command runs on every connection with\n\
+            that tag, --parallel at a time. Each host's output is printed once it is\n\
+            done, followed by a pass/fail table; the exit code is 1 if any host failed.\n\n\
             IMPORTANT: Use -- to separate the remote command from bssh flags.\n\n\
             Examples:\n\
               bssh exec web-prod -- uname -a\n\
               bssh exec db01 -- ls -l /tmp\n\
+              bssh exec db01 --log uptime.log -- uptime\n\
+              bssh exec --tag web -- systemctl is-active nginx\n\
+              bssh exec --tag db --parallel 2 -- df -h /var\n\
               bssh run staging -- systemctl status nginx"
     )]
     Exec {
         /// Connection name, alias, or hostname
-        target: String,
+        #[arg(required_unless_present = "tag")]
+        target: Option<String>,
+        /// Run on every connection with this tag instead of one target
+        #[arg(short = 't', long, value_name = "TAG", conflicts_with_all = ["target", "log"])]
+        tag: Option<String>,
+        /// Hosts to run on at once with --tag
+        #[arg(long, value_name = "N", default_value_t = 8, requires = "tag",
+              value_parser = clap::value_parser!(u16).range(1..))]
+        parallel: u16,
+        /// Also write the command's stdout and stderr to FILE
+        #[arg(long, value_name = "FILE")]
+        log: Option<PathBuf>,
         /// Remote command and arguments (put -- before the command)
         #[arg(last = true, required = true)]
         command: Vec<String>,
@@ -508,12 +1110,31 @@ pub enum Commands {
         bind: String,
     },
 
+    /// Log in with a password kept in the OS keyring (via sshpass)
+    #[cfg(feature = "password_auth")]
+    #[command(
+        long_about = "For hosts that forbid key authentication. The password is stored in\n\
+            the OS keyring and only the entry name is saved on the connection;\n\
+            connect and exec then run ssh through `sshpass -e`, which must be\n\
+            installed. Passwords are less secure than keys: use this only where\n\
+            keys are not allowed.\n\n\
+            Examples:\n\
+              bssh password set legacy01\n\
+              bssh password set legacy02 --ref legacy-shared\n\
+              bssh password clear legacy01"
+    )]
+    Password {
+        #[command(subcommand)]
+        command: PasswordCommands,
+    },
+
     /// Create, remove, or list short aliases for connections
     #[command(
         long_about = "Aliases let you refer to connections by shorter names.\n\n\
             Examples:\n\
               bssh alias add wp web-prod\n\
               bssh alias list\n\
+              bssh alias list prod --json\n\
               bssh alias remove wp"
     )]
     Alias {
@@ -528,12 +1149,16 @@ pub enum Commands {
             With no arguments, lists currently active sessions.\n\n\
             Examples:\n\
               bssh close             # list active sessions\n\
-              bssh close web-prod    # close one session\n\
+              bssh close web-prod    # close its session (asks which if several)\n\
+              bssh close web-prod -n 2 -f  # close session #2 of web-prod\n\
               bssh kill --all -f     # force-close everything"
     )]
     Close {
         /// Connection name to close (omit to list active sessions)
         target: Option<String>,
+        /// Close only this session, numbered as in the `bssh close` listing
+        #[arg(short = 'n', long, value_name = "N", requires = "target")]
+        number: Option<usize>,
         /// Close all active sessions at once
         #[arg(short = 'a', long)]
         all: bool,
@@ -544,6 +1169,31 @@ pub enum Commands {
         #[arg(short = 'f', long)]
         force: bool,
     },
+
+    /// List active sessions by id, or terminate one by id
+    #[command(
+        long_about = "Show sessions started by bssh that have not ended yet.\n\
+            Unlike 'close', sessions are addressed by their id, which makes\n\
+            this command suitable for scripts.\n\n\
+            Examples:\n\
+              bssh sessions                  # table of active sessions\n\
+              bssh sessions --active --json  # running sessions as JSON\n\
+              bssh sessions --kill 3f2a9c1e  # terminate by id (or unique prefix)"
+    )]
+    Sessions {
+        /// Only include sessions whose process is still running
+        #[arg(long)]
+        active: bool,
+        #[command(flatten)]
+        output: OutputArgs,
+        /// Terminate the session with this id (or unique id prefix)
+        #[arg(
+            long,
+            value_name = "SESSION_ID",
+            conflicts_with_all = ["active", "json", "output"]
+        )]
+        kill: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -560,13 +1210,128 @@ pub enum AliasSubcommand {
         /// Alias name to delete
         alias: String,
     },
-    /// List aliases (optionally filtered to one connection)
+    /// List aliases (optionally filtered by alias or connection name)
     List {
-        /// Show only aliases for this connection (omit for all)
-        target: Option<String>,
+        /// Show only aliases whose name or connection name contains this text
+        pattern: Option<String>,
+        #[command(flatten)]
+        output: OutputArgs,
     },
 }
 
+/// Parse a relative duration such as `90s`, `30m`, `2h`, `3d` or `1w`.
+fn parse_ttl(s: &str) -> Result<chrono::Duration, String> {
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (amount, unit) = s.split_at(split);
+    let amount: i64 = amount
+        .parse()
+        .map_err(|_| format!("invalid duration '{s}': expected e.g. 30m, 2h, 3d"))?;
+    let duration = match unit {
+        "s" => chrono::Duration::try_seconds(amount),
+        "m" => chrono::Duration::try_minutes(amount),
+        "h" => chrono::Duration::try_hours(amount),
+        "d" => chrono::Duration::try_days(amount),
+        "w" => chrono::Duration::try_weeks(amount),
+        _ => {
+            return Err(format!(
+                "invalid duration '{s}': unit must be s, m, h, d or w"
+            ))
+        }
+    }
+    .ok_or_else(|| format!("invalid duration '{s}': too large"))?;
+    if amount == 0 {
+        return Err(format!("invalid duration '{s}': must be greater than zero"));
+    }
+    Ok(duration)
+}
+
+/// Parse `history --since`: RFC3339, or the start of a local `YYYY-MM-DD`.
+fn parse_since(s: &str) -> Result<chrono::DateTime<chrono::Utc>, String> {
+    parse_date_bound(s, false)
+}
+
+/// Parse `history --until`: RFC3339, or the end of a local `YYYY-MM-DD`.
+fn parse_until(s: &str) -> Result<chrono::DateTime<chrono::Utc>, String> {
+    parse_date_bound(s, true)
+}
+
+fn parse_date_bound(s: &str, end_of_day: bool) -> Result<chrono::DateTime<chrono::Utc>, String> {
+    if let Ok(instant) = chrono::DateTime::parse_from_rfc3339(s) {
+        return Ok(instant.with_timezone(&chrono::Utc));
+    }
+    let date = chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .map_err(|_| format!("invalid date '{s}': expected YYYY-MM-DD or RFC3339"))?;
+    let day = if end_of_day {
+        date.succ_opt()
+    } else {
+        Some(date)
+    }
+    .ok_or_else(|| format!("invalid date '{s}': out of range"))?;
+    let midnight = day
+        .and_hms_opt(0, 0, 0)
+        .and_then(|t| t.and_local_timezone(chrono::Local).earliest())
+        .ok_or_else(|| format!("invalid date '{s}': no local midnight"))?
+        .with_timezone(&chrono::Utc);
+    Ok(if end_of_day {
+        midnight - chrono::Duration::nanoseconds(1)
+    } else {
+        midnight
+    })
+}
+
+/// Parse a `KEY=VALUE` pair for `--set-env`.
+fn parse_env_var(s: &str) -> Result<(String, String), String> {
+    let (key, value) = s
+        .split_once('=')
+        .ok_or_else(|| format!("invalid variable '{s}': expected KEY=VALUE"))?;
+    let mut chars = key.chars();
+    let valid_key = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid_key {
+        return Err(format!(
+            "invalid variable name '{key}': use letters, digits and '_', not starting with a digit"
+        ));
+    }
+    if value.contains(['"', '\\', '\n']) {
+        return Err(format!(
+            "invalid value for '{key}': quotes, backslashes and newlines are not supported"
+        ));
+    }
+    Ok((key.to_string(), value.to_string()))
+}
+
+/// Parse a `SendEnv` pattern such as `LC_*` for `--send-env`.
+fn parse_env_pattern(s: &str) -> Result<String, String> {
+    let name = s.strip_prefix('-').unwrap_or(s);
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '*' | '?'))
+    {
+        return Err(format!(
+            "invalid pattern '{s}': use variable names with optional * and ? wildcards"
+        ));
+    }
+    Ok(s.to_string())
+}
+
+/// Parse a tmux session name for `--attach`/`--auto-attach`. The name ends up
+/// in the remote command line, so only shell-safe characters are accepted.
+fn parse_tmux_session(s: &str) -> Result<String, String> {
+    if s.is_empty()
+        || !s
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-'))
+    {
+        return Err(format!(
+            "invalid tmux session '{s}': use letters, digits, '_' and '-'"
+        ));
+    }
+    Ok(s.to_string())
+}
+
 fn parse_octal(s: &str) -> Result<u32, String> {
     let s = s.trim_start_matches("0o").trim_start_matches("0O");
     u32::from_str_radix(s, 8).map_err(|e| format!("invalid octal mode '{s}': {e}"))
@@ -4,10 +4,36 @@
 //! to reduce code duplication and ensure consistent UX.
 
 use crate::config::AppConfig;
-use crate::models::Connection;
-use crate::services::SshService;
+use crate::errors::AppError;
+use crate::models::matching::name_match_positions;
+use crate::models::{Connection, SshCommandMode};
+use crate::services::transport::SubprocessTransport;
+use crate::services::{SshService, TargetResolution};
 use anyhow::Result;
-use std::io::{self, Write};
+use indicatif::{ProgressBar, ProgressStyle};
+use std::io::{self, IsTerminal, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set from `--yes` / `BSSH_ASSUME_YES` before any command runs
+static ASSUME_YES: AtomicBool = AtomicBool::new(false);
+
+/// Answer every `confirm` prompt with "yes" for the rest of the process.
+pub fn set_assume_yes(assume_yes: bool) {
+    ASSUME_YES.store(assume_yes, Ordering::Relaxed);
+}
+
+/// Whether `--yes` / `BSSH_ASSUME_YES` is in effect, for prompts that are
+/// not plain yes/no questions
+pub fn assumed_yes() -> bool {
+    ASSUME_YES.load(Ordering::Relaxed)
+}
+
+/// Whether `BSSH_ASSUME_YES` is set to a truthy value (`1`, `true`, `yes`).
+pub fn assume_yes_from_env() -> bool {
+    std::env::var("BSSH_ASSUME_YES")
+        .map(|v| matches!(v.trim().to_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false)
+}
 
 /// Format a datetime as a human-readable duration string (e.g., "2 hours ago")
 pub fn format_duration(dt: chrono::DateTime<chrono::Utc>) -> String {
@@ -37,8 +63,68 @@ pub fn format_duration(dt: chrono::DateTime<chrono::Utc>) -> String {
     }
 }
 
-/// Display connection info in a consistent format for selection lists
-pub fn print_connection_info(connection: &Connection, index: usize) {
+/// Size in binary units, e.g. "1.5 MB"
+pub fn format_bytes(bytes: u64) -> String {
+    const KB: u64 = 1_024;
+    const MB: u64 = 1_024 * KB;
+    const GB: u64 = 1_024 * MB;
+    if bytes >= GB {
+        format!("{:.1} GB", bytes as f64 / GB as f64)
+    } else if bytes >= MB {
+        format!("{:.1} MB", bytes as f64 / MB as f64)
+    } else if bytes >= KB {
+        format!("{:.1} KB", bytes as f64 / KB as f64)
+    } else {
+        format!("{bytes} B")
+    }
+}
+
+/// `KEY=VALUE` pairs joined for display
+pub fn format_env(env: &[(String, String)]) -> String {
+    env.iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Time left until `expires_at`, e.g. "3h left", or "expired"
+pub fn format_expiry(expires_at: chrono::DateTime<chrono::Utc>) -> String {
+    let left = expires_at.signed_duration_since(chrono::Utc::now());
+
+    if left <= chrono::Duration::zero() {
+        "expired".to_string()
+    } else if left.num_days() > 0 {
+        format!("{}d left", left.num_days())
+    } else if left.num_hours() > 0 {
+        format!("{}h left", left.num_hours())
+    } else {
+        format!("{}m left", left.num_minutes().max(1))
+    }
+}
+
+/// Resolve the absolute timestamp format requested by `--absolute-time` or
+/// `config.time_format`. `None` keeps the command's usual rendering.
+pub fn absolute_time_format(absolute: bool, config: &AppConfig) -> Option<String> {
+    match config.time_format.as_str() {
+        "relative" if absolute => Some("rfc3339".to_string()),
+        "relative" => None,
+        format => Some(format.to_string()),
+    }
+}
+
+/// Render a timestamp with a format from `absolute_time_format`.
+pub fn format_absolute_time(dt: chrono::DateTime<chrono::Utc>, format: &str) -> String {
+    if format == "rfc3339" || !crate::config::is_valid_time_format(format) {
+        dt.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+    } else {
+        dt.format(format).to_string()
+    }
+}
+
+/// Display connection info in a consistent format for selection lists.
+/// The characters of the name that `query` matched are highlighted when
+/// stdout is a color terminal.
+pub fn print_connection_info(connection: &Connection, index: usize, query: &str) {
     let tags_str = if connection.tags.is_empty() {
         "".to_string()
     } else {
@@ -50,7 +136,17 @@ pub fn print_connection_info(connection: &Connection, index: usize) {
         .map(|dt| format!(" (last used: {})", format_duration(dt)))
         .unwrap_or_default();
 
-    println!("  {}. {} ({})", index, connection.name, connection.host);
+    let color = io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none();
+    let name = if color {
+        highlight(
+            &connection.name,
+            &name_match_positions(&connection.name, query),
+        )
+    } else {
+        connection.name.clone()
+    };
+
+    println!("  {}. {} ({})", index, name, connection.host);
     println!(
         "     Tags: {}{}",
         if tags_str.is_empty() {
@@ -63,6 +159,24 @@ pub fn print_connection_info(connection: &Connection, index: usize) {
     println!();
 }
 
+/// `text` with the chars at `positions` in bold yellow
+fn highlight(text: &str, positions: &[usize]) -> String {
+    let mut out = String::new();
+    let mut lit = false;
+    for (i, c) in text.chars().enumerate() {
+        let hit = positions.contains(&i);
+        if hit != lit {
+            out.push_str(if hit { "\x1b[1;33m" } else { "\x1b[0m" });
+            lit = hit;
+        }
+        out.push(c);
+    }
+    if lit {
+        out.push_str("\x1b[0m");
+    }
+    out
+}
+
 /// Result type for interactive selection operations
 #[allow(clippy::large_enum_variant)]
 pub enum SelectionResult {
@@ -93,7 +207,10 @@ pub fn interactive_selection_with_search(
         io::stdout().flush()?;
 
         let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
+        if io::stdin().read_line(&mut input)? == 0 {
+            // End of input: nobody is left to answer
+            return Ok(SelectionResult::Cancelled);
+        }
         let input = input.trim().to_lowercase();
 
         match input.as_str() {
@@ -141,7 +258,8 @@ pub fn interactive_selection_with_search(
 /// - "Search again" functionality with recursive search
 /// - Auto-connect for single matches (improved UX)
 ///
-/// Returns the selected connection, or None if cancelled.
+/// Returns the selected connection, or None if nothing matched; quitting the
+/// picker is an [`AppError::Cancelled`] error.
 pub async fn fuzzy_select_connection(
     ssh_service: &SshService,
     initial_query: &str,
@@ -166,12 +284,35 @@ pub async fn fuzzy_select_connection_with_config(
     auto_select_single: bool,
     config: &AppConfig,
 ) -> Result<Option<Connection>> {
-    let mut current_query = initial_query.to_string();
     let search_mode = config.search_mode.as_str();
+    let matches = ssh_service.search(initial_query, 10, search_mode).await?;
+    select_from_matches(
+        ssh_service,
+        initial_query,
+        matches,
+        action_name,
+        auto_select_single,
+        search_mode,
+    )
+    .await
+}
 
-    loop {
-        let matches = ssh_service.search(&current_query, 10, search_mode).await?;
+/// Let the user pick one of `matches`, searching again with `search_mode`
+/// whenever they type a new query. `None` when nothing matched; quitting
+/// is an [`AppError::Cancelled`] error.
+async fn select_from_matches(
+    ssh_service: &SshService,
+    initial_query: &str,
+    mut matches: Vec<Connection>,
+    action_name: &str,
+    auto_select_single: bool,
+    search_mode: &str,
+) -> Result<Option<Connection>> {
+    let mut current_query = initial_query.to_string();
+    // Loaded on the first "search again" and reused for later ones
+    let mut candidates: Option<Vec<Connection>> = None;
 
+    loop {
         match matches.len() {
             0 => {
                 println!("❌ No connections found matching '{}'", current_query);
@@ -196,12 +337,16 @@ pub async fn fuzzy_select_connection_with_config(
                         SelectionResult::Selected(conn) => return Ok(Some(conn)),
                         SelectionResult::SearchAgain(new_query) => {
                             current_query = new_query;
+                            matches = search_again(
+                                ssh_service,
+                                &mut candidates,
+                                &current_query,
+                                search_mode,
+                            )
+                            .await?;
                             continue;
                         }
-                        SelectionResult::Cancelled => {
-                            println!("Operation cancelled.");
-                            return Ok(None);
-                        }
+                        SelectionResult::Cancelled => return Err(AppError::Cancelled.into()),
                     }
                 } else {
                     println!("No recent connections found.");
@@ -218,21 +363,13 @@ pub async fn fuzzy_select_connection_with_config(
                 // Single match without auto-select - ask for confirmation
                 let conn = &matches[0];
                 println!("🔍 Found one similar connection:");
-                print_connection_info(conn, 1);
-
-                print!("{} this connection? [Y/n]: ", capitalize_first(action_name));
-                io::stdout().flush()?;
+                print_connection_info(conn, 1, &current_query);
 
-                let mut input = String::new();
-                io::stdin().read_line(&mut input)?;
-                let input = input.trim().to_lowercase();
-
-                // Default to yes (Y is uppercase in prompt)
-                if input.is_empty() || matches!(input.as_str(), "y" | "yes") {
+                let prompt = format!("{} this connection?", capitalize_first(action_name));
+                if confirm(&prompt, true)? {
                     return Ok(Some(conn.clone()));
                 } else {
-                    println!("Operation cancelled.");
-                    return Ok(None);
+                    return Err(AppError::Cancelled.into());
                 }
             }
             _ => {
@@ -245,7 +382,7 @@ pub async fn fuzzy_select_connection_with_config(
                 println!();
 
                 for (i, conn) in matches.iter().enumerate() {
-                    print_connection_info(conn, i + 1);
+                    print_connection_info(conn, i + 1, &current_query);
                 }
 
                 match interactive_selection_with_search(
@@ -255,19 +392,34 @@ pub async fn fuzzy_select_connection_with_config(
                     SelectionResult::Selected(conn) => return Ok(Some(conn)),
                     SelectionResult::SearchAgain(new_query) => {
                         current_query = new_query;
+                        matches =
+                            search_again(ssh_service, &mut candidates, &current_query, search_mode)
+                                .await?;
                         println!(); // Add spacing before new results
                         continue;
                     }
-                    SelectionResult::Cancelled => {
-                        println!("Operation cancelled.");
-                        return Ok(None);
-                    }
+                    SelectionResult::Cancelled => return Err(AppError::Cancelled.into()),
                 }
             }
         }
     }
 }
 
+/// Search again within one picker session. The connections are read from
+/// the database the first time only, so each new query is filtered in memory.
+async fn search_again(
+    ssh_service: &SshService,
+    candidates: &mut Option<Vec<Connection>>,
+    query: &str,
+    search_mode: &str,
+) -> Result<Vec<Connection>> {
+    if candidates.is_none() {
+        *candidates = Some(ssh_service.search_candidates().await?);
+    }
+    let loaded = candidates.as_deref().unwrap_or_default();
+    Ok(ssh_service.search_in(loaded, query, 10, search_mode))
+}
+
 #[allow(dead_code)]
 pub fn show_no_matches_message(query: &str) {
     println!("❌ No connections found matching '{}'", query);
@@ -286,9 +438,49 @@ fn capitalize_first(s: &str) -> String {
     }
 }
 
+/// Read one field of an interactive form, showing the current value in
+/// brackets. Returns the trimmed input; empty means "keep".
+///
+/// Like `confirm`, this fails without a terminal on stdin.
+pub fn prompt_field(label: &str, current: &str) -> Result<String> {
+    if !io::stdin().is_terminal() {
+        anyhow::bail!("Input required but stdin is not a terminal: {}", label);
+    }
+
+    if current.is_empty() {
+        print!("  {}: ", label);
+    } else {
+        print!("  {} [{}]: ", label, current);
+    }
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    if io::stdin().read_line(&mut input)? == 0 {
+        anyhow::bail!("Input ended before the form was complete");
+    }
+    Ok(input.trim().to_string())
+}
+
 /// Ask for simple yes/no confirmation
+///
+/// With `--yes` / `BSSH_ASSUME_YES` the answer is always yes. Without a
+/// terminal on stdin there is nobody to ask, so this fails instead of
+/// silently taking the default.
 pub fn confirm(prompt: &str, default_yes: bool) -> Result<bool> {
     let hint = if default_yes { "[Y/n]" } else { "[y/N]" };
+
+    if ASSUME_YES.load(Ordering::Relaxed) {
+        println!("{} {}: y (assumed)", prompt, hint);
+        return Ok(true);
+    }
+
+    if !io::stdin().is_terminal() {
+        anyhow::bail!(
+            "Confirmation required but stdin is not a terminal: {}",
+            prompt
+        );
+    }
+
     print!("{} {}: ", prompt, hint);
     io::stdout().flush()?;
 
@@ -303,8 +495,39 @@ pub fn confirm(prompt: &str, default_yes: bool) -> Result<bool> {
     Ok(matches!(input.as_str(), "y" | "yes"))
 }
 
+/// Progress bar for a batch of `len` items, drawn on stderr. `label` names
+/// the operation; set the bar's message to the item being processed.
+///
+/// Hidden when stdout is not a terminal so piped or scripted runs stay
+/// clean. Print through `ProgressBar::suspend` while it is visible.
+pub fn progress_bar(len: usize, label: &str) -> ProgressBar {
+    if !io::stdout().is_terminal() {
+        return ProgressBar::hidden();
+    }
+    let bar = ProgressBar::new(len as u64);
+    bar.set_style(
+        ProgressStyle::with_template("{spinner} {prefix} [{bar:30}] {pos}/{len} {wide_msg}")
+            .unwrap_or_else(|_| ProgressStyle::default_bar())
+            .progress_chars("=> "),
+    );
+    bar.set_prefix(label.to_string());
+    bar
+}
+
 /// Display detailed connection information
-pub fn show_connection_details(connection: &Connection) -> Result<()> {
+///
+/// `time_format` comes from `absolute_time_format`; `None` keeps the
+/// default UTC layout.
+pub fn show_connection_details(
+    connection: &Connection,
+    time_format: Option<&str>,
+    config: &AppConfig,
+) -> Result<()> {
+    let format_time = |dt: chrono::DateTime<chrono::Utc>| match time_format {
+        Some(format) => format_absolute_time(dt, format),
+        None => dt.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+    };
+
     println!("🔗 Connection Details: {}\n", connection.name);
     println!("  ID:       {}", connection.id);
     println!("  Host:     {}:{}", connection.host, connection.port);
@@ -334,46 +557,199 @@ pub fn show_connection_details(connection: &Connection) -> Result<()> {
         println!("  SSH Key:  {}", key);
     }
 
-    println!(
-        "  Created:  {}",
-        connection.created_at.format("%Y-%m-%d %H:%M:%S UTC")
-    );
+    if let Some(proxy_command) = &connection.proxy_command {
+        println!("  Proxy:    {}", proxy_command);
+    }
+
+    if let Some(ssh_config) = &connection.ssh_config_file {
+        println!("  SSH config: {}", ssh_config.display());
+    }
+
+    if let Some(session) = &connection.auto_attach {
+        println!("  Attach:   tmux session '{}'", session);
+    }
+
+    if let Some(reference) = &connection.password_ref {
+        println!("  Password: keyring entry '{}' (sshpass)", reference);
+    }
+
+    if !connection.env.is_empty() {
+        println!("  SetEnv:   {}", format_env(&connection.env));
+    }
+
+    if !connection.send_env.is_empty() {
+        println!("  SendEnv:  {}", connection.send_env.join(", "));
+    }
+
+    println!("  Created:  {}", format_time(connection.created_at));
 
     if let Some(last_used) = connection.last_used {
-        println!("  Last used: {}", last_used.format("%Y-%m-%d %H:%M:%S UTC"));
+        println!("  Last used: {}", format_time(last_used));
+    }
+
+    if connection.use_count > 0 {
+        println!("  Sessions: {}", connection.use_count);
     }
 
     if !connection.tags.is_empty() {
         println!("  Tags:     {}", connection.tags.join(", "));
     }
 
+    if connection.archived {
+        println!("  Archived: yes (hidden from list and search)");
+    }
+
+    if let Some(expires_at) = connection.expires_at {
+        println!(
+            "  Expires:  {} (⏳ {})",
+            format_time(expires_at),
+            format_expiry(expires_at)
+        );
+    }
+
+    if let Some(error) = &connection.last_error {
+        match connection.last_error_at {
+            Some(at) => println!("  ❌ Last error ({}):", format_time(at)),
+            None => println!("  ❌ Last error:"),
+        }
+        for line in error.lines() {
+            println!("     {}", line);
+        }
+    }
+
+    if let Some(fingerprints) = &connection.host_key_fingerprint {
+        for (i, fingerprint) in fingerprints.lines().enumerate() {
+            let label = if i == 0 { "Host keys:" } else { "" };
+            println!("  {:<11}{}", label, fingerprint);
+        }
+    }
+
     if !connection.aliases.is_empty() {
         println!("  Aliases:  {}", connection.aliases.join(", "));
     }
 
-    println!("\n  SSH Command: {}", connection.to_ssh_command());
+    if let Some(notes) = &connection.notes {
+        println!(
+            "  📝 Notes:  {} line(s), read with 'bssh note {} --show'",
+            notes.lines().count(),
+            connection.name
+        );
+    }
+
+    println!(
+        "\n  SSH Command: {}",
+        SubprocessTransport::display_command(config, connection, SshCommandMode::Interactive)
+    );
+    println!(
+        "  Exec:        {}",
+        SubprocessTransport::display_command(config, connection, SshCommandMode::Exec)
+    );
 
     Ok(())
 }
 
-/// Resolve a connection by exact lookup or interactive fuzzy search.
+/// Resolve a connection by exact name, id or alias, then interactive fuzzy search.
 ///
-/// Bails with "No connection selected" if the search is cancelled or no matches are found.
+/// Fails with [`AppError::ConnectionNotFound`] when nothing matches and with
+/// [`AppError::Cancelled`] when the picker is quit.
 pub async fn resolve_connection(
     ssh_service: &SshService,
     target: &str,
     action: &str,
     auto_select_single: bool,
 ) -> Result<Connection> {
-    if let Some(conn) = ssh_service.get_connection(target).await.unwrap_or_default() {
-        return Ok(conn);
+    match select_target(ssh_service, target, action, auto_select_single).await? {
+        Some((conn, _)) => Ok(conn),
+        None => Err(AppError::ConnectionNotFound(target.to_string()).into()),
     }
+}
 
-    if let Some(conn) =
-        fuzzy_select_connection(ssh_service, target, action, auto_select_single).await?
-    {
-        Ok(conn)
-    } else {
-        anyhow::bail!("No connection selected")
+/// Like [`resolve_connection`], but also returns the alias the target was
+/// resolved through, and `None` instead of an error when nothing matched.
+pub async fn select_target(
+    ssh_service: &SshService,
+    target: &str,
+    action: &str,
+    auto_select_single: bool,
+) -> Result<Option<(Connection, Option<String>)>> {
+    let matches = match ssh_service.resolve_target(target).await? {
+        TargetResolution::Name(conn) => return Ok(Some((conn, None))),
+        TargetResolution::Alias(conn, alias) => return Ok(Some((conn, Some(alias)))),
+        TargetResolution::Fuzzy(matches) => matches,
+    };
+
+    let selected = select_from_matches(
+        ssh_service,
+        target,
+        matches,
+        action,
+        auto_select_single,
+        ssh_service.search_mode(),
+    )
+    .await?;
+    Ok(selected.map(|conn| (conn, None)))
+}
+
+/// Validate ad-hoc `-L`/`-R`/`-D` specs and turn them into ssh arguments.
+///
+/// `-L`/`-R` take `[bind_address:]port:host:hostport`; `-D` takes
+/// `[bind_address:]port`. Errors name the offending flag and spec.
+pub fn tunnel_args(local: &[String], remote: &[String], dynamic: &[String]) -> Result<Vec<String>> {
+    fn port(value: &str, flag: &str, spec: &str) -> Result<()> {
+        value.parse::<u16>().map(|_| ()).map_err(|_| {
+            anyhow::anyhow!(
+                "Invalid {} spec '{}': '{}' is not a port",
+                flag,
+                spec,
+                value
+            )
+        })
+    }
+
+    let mut args = Vec::new();
+    for (flag, specs) in [("-L", local), ("-R", remote)] {
+        for spec in specs {
+            let parts: Vec<&str> = spec.split(':').collect();
+            let (bind_port, host, host_port) = match parts.as_slice() {
+                [bp, h, hp] | [_, bp, h, hp] => (*bp, *h, *hp),
+                _ => anyhow::bail!(
+                    "Invalid {} spec '{}': expected [bind_address:]port:host:hostport",
+                    flag,
+                    spec
+                ),
+            };
+            port(bind_port, flag, spec)?;
+            port(host_port, flag, spec)?;
+            if host.is_empty() {
+                anyhow::bail!("Invalid {} spec '{}': missing host", flag, spec);
+            }
+            args.push(flag.to_string());
+            args.push(spec.clone());
+        }
+    }
+    for spec in dynamic {
+        let bind_port = spec.rsplit(':').next().unwrap_or_default();
+        if spec.matches(':').count() > 1 {
+            anyhow::bail!("Invalid -D spec '{}': expected [bind_address:]port", spec);
+        }
+        port(bind_port, "-D", spec)?;
+        args.push("-D".to_string());
+        args.push(spec.clone());
+    }
+
+    Ok(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn highlight_wraps_runs_of_matched_chars() {
+        assert_eq!(
+            highlight("web-prod", &[0, 1, 4]),
+            "\x1b[1;33mwe\x1b[0mb-\x1b[1;33mp\x1b[0mrod"
+        );
+        assert_eq!(highlight("web", &[]), "web");
     }
 }
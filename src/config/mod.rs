@@ -28,6 +28,10 @@ impl Default for AuthConfig {
 pub struct TransportConfig {
     pub force_subprocess: bool,
     pub strict_host_key_checking: String, // "strict" | "accept-new" | "off"
+    /// Multiplex jump-host hops: one persistent control socket per bastion endpoint.
+    pub share_bastion_connections: bool,
+    /// Seconds an idle shared bastion connection stays open (`ControlPersist`).
+    pub bastion_control_persist: u64,
 }
 
 impl Default for TransportConfig {
@@ -35,15 +39,35 @@ impl Default for TransportConfig {
         Self {
             force_subprocess: false,
             strict_host_key_checking: "accept-new".into(),
+            share_bastion_connections: false,
+            bastion_control_persist: 600,
         }
     }
 }
 
+/// How the TUI looked when it was last closed, restored on the next start.
+/// Written by the TUI itself; there is no `bssh config` flag for it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TuiState {
+    /// Connection list sort: "name", "host", "last_used" or "created".
+    /// `None` keeps the database order (most recently used first).
+    pub sort_field: Option<String>,
+    pub sort_descending: bool,
+    /// Reopen with the detail pane showing
+    pub show_details: bool,
+    pub compact_view: bool,
+}
+
+/// Missing keys take their `Default` value so configs written by older
+/// versions keep loading; `load` then writes the new fields back.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AppConfig {
     #[serde(skip, default = "default_environment")]
     pub environment: String,
     pub database_path: PathBuf,
+    #[serde(default = "system_user")]
     pub default_user: String,
     pub default_bastion: Option<String>,
     pub default_bastion_user: Option<String>,
@@ -55,20 +79,87 @@ pub struct AppConfig {
     pub max_history_size: usize,
     #[serde(default = "default_search_mode")]
     pub search_mode: String, // "bayesian" or "fuzzy"
+    /// Timestamp display: "relative", "rfc3339", or a strftime pattern
+    #[serde(default = "default_time_format")]
+    pub time_format: String,
+    /// Times `connect` re-runs ssh after a connection failure (exit 255), with
+    /// exponential backoff. Authentication failures are never retried.
+    #[serde(default)]
+    pub connect_retries: u32,
+    /// TUI color preset: "default", "solarized" or "mono"
+    #[serde(default = "default_theme")]
+    pub theme: String,
+    /// Directories holding per-project key folders; a connection tag matching
+    /// a folder name selects its key when no `key_path` is set. Empty = disabled.
+    #[serde(default)]
+    pub identity_search_dirs: Vec<PathBuf>,
+    /// OpenSSH config file passed as `-F` to connections that do not set
+    /// their own `ssh_config_file`
+    #[serde(default)]
+    pub default_ssh_config_file: Option<PathBuf>,
+    /// `KEY=VALUE` file whose variables are set for the local ssh process
+    /// (e.g. `KRB5CCNAME`), unless `connect --env-file` names another
+    #[serde(default)]
+    pub env_file: Option<PathBuf>,
+    /// Keep a removed connection's sessions in history (under its old name)
+    /// instead of deleting them with it
+    #[serde(default)]
+    pub retain_sessions_on_delete: bool,
+    /// Offer to save a host reached with `connect` that is not a saved
+    /// connection
+    #[serde(default = "default_true")]
+    pub prompt_save_adhoc: bool,
     #[serde(default)]
     pub transport: TransportConfig,
     #[serde(default)]
     pub auth: AuthConfig,
+    #[serde(default)]
+    pub tui_state: TuiState,
 }
 
 fn default_search_mode() -> String {
     "bayesian".to_string()
 }
 
+fn default_time_format() -> String {
+    "relative".to_string()
+}
+
+fn default_theme() -> String {
+    "default".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Whether `format` is usable as `time_format`: one of the keywords or a
+/// strftime pattern chrono can render.
+pub fn is_valid_time_format(format: &str) -> bool {
+    use chrono::format::{Item, StrftimeItems};
+
+    matches!(format, "relative" | "rfc3339")
+        || (!format.is_empty() && StrftimeItems::new(format).all(|i| !matches!(i, Item::Error)))
+}
+
 fn default_environment() -> String {
     "default".to_string()
 }
 
+/// Resolve the local login name: `$USER` first, then the OS account name.
+pub fn system_user() -> String {
+    system_user_from(|key| std::env::var(key))
+}
+
+/// [`system_user`] with the environment lookup passed in
+fn system_user_from(var: impl Fn(&str) -> Result<String, std::env::VarError>) -> String {
+    var("USER")
+        .ok()
+        .map(|u| u.trim().to_string())
+        .filter(|u| !u.is_empty())
+        .unwrap_or_else(whoami::username)
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         Self::default_for_env("default")
@@ -131,11 +222,37 @@ impl AppConfig {
         std::fs::create_dir_all(&env_dir)?;
 
         let config_file = env_dir.join("config.json");
+        let mut needs_save = false;
 
         let mut config = if config_file.exists() {
             let content = std::fs::read_to_string(&config_file)?;
-            let mut cfg: AppConfig = serde_json::from_str(&content)?;
+            let mut cfg: AppConfig = match serde_json::from_str(&content) {
+                Ok(cfg) => {
+                    needs_save = Self::has_missing_fields(&content);
+                    cfg
+                }
+                Err(e) => {
+                    // Keep the tool usable after a botched manual edit: back up the
+                    // file once and carry on with whatever fields still parse.
+                    let backup = config_file.with_extension("json.bak");
+                    if std::fs::read_to_string(&backup).ok().as_deref() != Some(content.as_str()) {
+                        std::fs::write(&backup, &content)?;
+                    }
+                    eprintln!(
+                        "⚠️  Could not parse {} ({}); continuing with recovered settings. \
+                         A copy was saved to {}. Run 'bssh config repair' to fix it.",
+                        config_file.display(),
+                        e,
+                        backup.display()
+                    );
+                    Self::recover(&content, &environment).0
+                }
+            };
             cfg.environment = environment.clone();
+            // A blank user in the file means "unset"; an explicit value always wins.
+            if cfg.default_user.trim().is_empty() {
+                cfg.default_user = system_user();
+            }
             cfg
         } else {
             let cfg = Self::default_for_env(&environment);
@@ -146,12 +263,82 @@ impl AppConfig {
         // Ensure database path is absolute and uses the environment dir
         if config.database_path.is_relative() || !config.database_path.starts_with(&env_dir) {
             config.database_path = env_dir.join("history.db");
+            needs_save = true;
+        }
+        if needs_save {
             config.save()?;
         }
 
         Ok(config)
     }
 
+    /// Whether `content` lacks a field this version writes (e.g. after an upgrade)
+    fn has_missing_fields(content: &str) -> bool {
+        match (
+            serde_json::from_str::<serde_json::Value>(content),
+            serde_json::to_value(Self::default()),
+        ) {
+            (Ok(serde_json::Value::Object(file)), Ok(serde_json::Value::Object(current))) => {
+                current.keys().any(|key| !file.contains_key(key))
+            }
+            _ => false,
+        }
+    }
+
+    /// Path of the `config.json` for `env`
+    pub fn config_file_for_env(env: &str) -> PathBuf {
+        dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("~/.config"))
+            .join("bayesian-ssh")
+            .join("environments")
+            .join(env)
+            .join("config.json")
+    }
+
+    /// Rebuild a config from file contents that failed to deserialize.
+    ///
+    /// Starts from the defaults for `env` and keeps every top-level field of
+    /// `content` that still deserializes on its own. Returns the recovered
+    /// config and one message per field (or the whole file) that was dropped.
+    pub fn recover(content: &str, env: &str) -> (Self, Vec<String>) {
+        let defaults = Self::default_for_env(env);
+        let mut problems = Vec::new();
+
+        let fields = match serde_json::from_str::<serde_json::Value>(content) {
+            Ok(serde_json::Value::Object(fields)) => fields,
+            Ok(_) => {
+                problems.push("file is not a JSON object; all settings reset".to_string());
+                return (defaults, problems);
+            }
+            Err(e) => {
+                problems.push(format!("invalid JSON ({}); all settings reset", e));
+                return (defaults, problems);
+            }
+        };
+
+        let mut merged = match serde_json::to_value(&defaults) {
+            Ok(serde_json::Value::Object(map)) => map,
+            _ => return (defaults, problems),
+        };
+        for (key, value) in fields {
+            if !merged.contains_key(&key) {
+                continue;
+            }
+            let mut candidate = merged.clone();
+            candidate.insert(key.clone(), value);
+            match serde_json::from_value::<AppConfig>(serde_json::Value::Object(candidate.clone()))
+            {
+                Ok(_) => merged = candidate,
+                Err(e) => problems.push(format!("'{}': {}; using the default", key, e)),
+            }
+        }
+
+        let mut config = serde_json::from_value::<AppConfig>(serde_json::Value::Object(merged))
+            .unwrap_or(defaults);
+        config.environment = env.to_string();
+        (config, problems)
+    }
+
     pub fn default_for_env(env: &str) -> Self {
         let config_dir = dirs::config_dir()
             .unwrap_or_else(|| PathBuf::from("~/.config"))
@@ -161,7 +348,7 @@ impl AppConfig {
         Self {
             environment: env.to_string(),
             database_path: env_dir.join("history.db"),
-            default_user: whoami::username(),
+            default_user: system_user(),
             default_bastion: None,
             default_bastion_user: None,
             default_port: 22,
@@ -171,8 +358,17 @@ impl AppConfig {
             auto_save_history: true,
             max_history_size: 1000,
             search_mode: "bayesian".to_string(),
+            time_format: default_time_format(),
+            connect_retries: 0,
+            theme: default_theme(),
+            identity_search_dirs: Vec::new(),
+            default_ssh_config_file: None,
+            env_file: None,
+            retain_sessions_on_delete: false,
+            prompt_save_adhoc: true,
             transport: TransportConfig::default(),
             auth: AuthConfig::default(),
+            tui_state: TuiState::default(),
         }
     }
 
@@ -193,7 +389,11 @@ impl AppConfig {
 
     pub fn update(&mut self, updates: AppConfigUpdates) -> Result<()> {
         if let Some(user) = updates.default_user {
-            self.default_user = user;
+            self.default_user = if user.trim().is_empty() {
+                system_user()
+            } else {
+                user
+            };
         }
         if let Some(bastion) = updates.default_bastion {
             self.default_bastion = bastion;
@@ -221,6 +421,29 @@ impl AppConfig {
                 self.search_mode = search_mode;
             }
         }
+        if let Some(time_format) = updates.time_format {
+            if is_valid_time_format(&time_format) {
+                self.time_format = time_format;
+            }
+        }
+        if let Some(retries) = updates.connect_retries {
+            self.connect_retries = retries;
+        }
+        if let Some(theme) = updates.theme {
+            self.theme = theme;
+        }
+        if let Some(ssh_config) = updates.default_ssh_config_file {
+            self.default_ssh_config_file = ssh_config;
+        }
+        if let Some(env_file) = updates.env_file {
+            self.env_file = env_file;
+        }
+        if let Some(retain) = updates.retain_sessions_on_delete {
+            self.retain_sessions_on_delete = retain;
+        }
+        if let Some(prompt) = updates.prompt_save_adhoc {
+            self.prompt_save_adhoc = prompt;
+        }
 
         self.save()
     }
@@ -237,4 +460,100 @@ pub struct AppConfigUpdates {
     pub auto_save_history: Option<bool>,
     pub max_history_size: Option<usize>,
     pub search_mode: Option<String>,
+    pub time_format: Option<String>,
+    pub connect_retries: Option<u32>,
+    pub theme: Option<String>,
+    pub default_ssh_config_file: Option<Option<PathBuf>>,
+    pub env_file: Option<Option<PathBuf>>,
+    pub retain_sessions_on_delete: Option<bool>,
+    pub prompt_save_adhoc: Option<bool>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_default_user_falls_back_to_system_user() {
+        let json = r#"{
+            "database_path": "/tmp/bssh-test/history.db",
+            "default_bastion": null,
+            "default_bastion_user": null,
+            "default_port": 22,
+            "use_kerberos_by_default": false,
+            "ssh_config_path": null,
+            "log_level": "info",
+            "auto_save_history": true,
+            "max_history_size": 1000
+        }"#;
+        let cfg: AppConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(cfg.default_user, system_user());
+    }
+
+    #[test]
+    fn system_user_prefers_a_non_blank_user_variable() {
+        let user = |value: &'static str| move |_: &str| Ok(value.to_string());
+        assert_eq!(system_user_from(user("bssh-test-user")), "bssh-test-user");
+        assert_eq!(system_user_from(user("  ")), whoami::username());
+        assert_eq!(
+            system_user_from(|_| Err(std::env::VarError::NotPresent)),
+            whoami::username()
+        );
+    }
+
+    #[test]
+    fn older_config_without_new_fields_still_loads() {
+        let json = r#"{"database_path": "/tmp/bssh-test/history.db", "default_user": "deploy"}"#;
+        let cfg: AppConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(cfg.default_user, "deploy");
+        assert_eq!(cfg.default_port, 22);
+        assert_eq!(cfg.search_mode, "bayesian");
+        assert!(cfg.auto_save_history);
+        assert!(AppConfig::has_missing_fields(json));
+
+        let full = serde_json::to_string(&cfg).unwrap();
+        assert!(!AppConfig::has_missing_fields(&full));
+    }
+
+    #[test]
+    fn explicit_default_user_is_kept() {
+        let mut cfg = AppConfig::default_for_env("default");
+        cfg.default_user = "deploy".into();
+        let json = serde_json::to_string(&cfg).unwrap();
+        let back: AppConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.default_user, "deploy");
+    }
+
+    #[test]
+    fn recover_keeps_valid_fields_and_reports_bad_ones() {
+        let json = r#"{
+            "database_path": "/tmp/bssh-test/history.db",
+            "default_user": "deploy",
+            "default_port": "twenty-two",
+            "search_mode": "fuzzy"
+        }"#;
+        let (cfg, problems) = AppConfig::recover(json, "default");
+        assert_eq!(cfg.default_user, "deploy");
+        assert_eq!(cfg.search_mode, "fuzzy");
+        assert_eq!(cfg.default_port, 22);
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("default_port"));
+    }
+
+    #[test]
+    fn recover_truncated_file_falls_back_to_defaults() {
+        let (cfg, problems) = AppConfig::recover(r#"{"default_user": "dep"#, "work");
+        assert_eq!(cfg.environment, "work");
+        assert_eq!(cfg.default_port, 22);
+        assert!(problems[0].contains("invalid JSON"));
+    }
+
+    #[test]
+    fn time_format_accepts_keywords_and_strftime_only() {
+        assert!(is_valid_time_format("relative"));
+        assert!(is_valid_time_format("rfc3339"));
+        assert!(is_valid_time_format("%Y-%m-%d %H:%M %Z"));
+        assert!(!is_valid_time_format("%Q"));
+        assert!(!is_valid_time_format(""));
+    }
 }
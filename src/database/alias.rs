@@ -1,7 +1,8 @@
-use crate::database::Database;
+use crate::database::{Database, CONNECTION_COLUMNS};
 use crate::models::Connection;
 use anyhow::Result;
 use rusqlite::params;
+use std::collections::HashMap;
 use tracing::info;
 
 impl Database {
@@ -36,14 +37,42 @@ impl Database {
         Ok(aliases)
     }
 
-    pub fn get_connection_by_alias(&self, alias: &str) -> Result<Option<Connection>> {
+    /// Fill in `aliases` of every connection with one query, instead of one
+    /// `get_aliases_for_connection` call each
+    pub fn attach_aliases(&self, connections: &mut [Connection]) -> Result<()> {
+        let mut aliases: HashMap<String, Vec<String>> = HashMap::new();
+        let mut stmt = self
+            .conn
+            .prepare("SELECT connection_id, alias FROM aliases ORDER BY alias")?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            aliases.entry(row.get(0)?).or_default().push(row.get(1)?);
+        }
+        for conn in connections {
+            conn.aliases = aliases.remove(&conn.id.to_string()).unwrap_or_default();
+        }
+        Ok(())
+    }
+
+    /// Every alias as `(alias, connection name, connection id)`, ordered by
+    /// connection name then alias.
+    pub fn list_all_aliases(&self) -> Result<Vec<(String, String, String)>> {
         let mut stmt = self.conn.prepare(
-            "SELECT c.id, c.name, c.host, c.user, c.port, c.bastion, c.bastion_user, 
-                    c.use_kerberos, c.key_path, c.created_at, c.last_used, c.tags
-             FROM connections c
-             JOIN aliases a ON c.id = a.connection_id
-             WHERE a.alias = ?",
+            "SELECT a.alias, c.name, c.id
+             FROM aliases a
+             JOIN connections c ON c.id = a.connection_id
+             ORDER BY c.name ASC, a.alias ASC",
         )?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    pub fn get_connection_by_alias(&self, alias: &str) -> Result<Option<Connection>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {CONNECTION_COLUMNS}
+             FROM connections
+             WHERE id = (SELECT connection_id FROM aliases WHERE alias = ?)"
+        ))?;
         let mut rows = stmt.query(params![alias])?;
 
         if let Some(row) = rows.next()? {
@@ -53,13 +82,27 @@ impl Database {
         }
     }
 
-    /// Enhanced get_connection that also checks aliases
-    pub fn get_connection_or_alias(&self, name_or_alias: &str) -> Result<Option<Connection>> {
-        // First try direct lookup
-        if let Some(conn) = self.get_connection(name_or_alias)? {
-            return Ok(Some(conn));
+    /// Number of sessions started through each alias, keyed by
+    /// `(connection id, alias)`.
+    ///
+    /// Every defined alias is present in the map, including unused ones.
+    /// Sessions only count for the connection the alias pointed to at the
+    /// time, so an alias moved to another connection starts from zero.
+    pub fn get_alias_usage_counts(&self) -> Result<HashMap<(String, String), usize>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT a.connection_id, a.alias, COUNT(s.id)
+             FROM aliases a
+             LEFT JOIN sessions s
+                    ON s.alias_used = a.alias AND s.connection_id = a.connection_id
+             GROUP BY a.connection_id, a.alias",
+        )?;
+        let mut rows = stmt.query([])?;
+
+        let mut counts = HashMap::new();
+        while let Some(row) = rows.next()? {
+            let count: i64 = row.get(2)?;
+            counts.insert((row.get(0)?, row.get(1)?), count as usize);
         }
-        // Then try alias lookup
-        self.get_connection_by_alias(name_or_alias)
+        Ok(counts)
     }
 }
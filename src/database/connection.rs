@@ -1,18 +1,30 @@
-use crate::database::Database;
-use crate::models::Connection;
+use crate::database::{Database, CONNECTION_COLUMNS};
+use crate::models::{Connection, SessionStatus};
 use anyhow::Result;
+use chrono::Datelike;
 use rusqlite::params;
 use tracing::info;
 
+const WEEKDAYS: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
 impl Database {
     // Connection management
     pub fn add_connection(&self, connection: &Connection) -> Result<()> {
         let tags_json = serde_json::to_string(&connection.tags)?;
 
         self.conn.execute(
-            "INSERT OR REPLACE INTO connections 
-             (id, name, host, user, port, bastion, bastion_user, use_kerberos, key_path, created_at, last_used, tags)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            &format!(
+                "INSERT OR REPLACE INTO connections ({CONNECTION_COLUMNS})
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+            ),
             params![
                 connection.id.to_string(),
                 connection.name,
@@ -26,6 +38,22 @@ impl Database {
                 connection.created_at.to_rfc3339(),
                 connection.last_used.map(|d| d.to_rfc3339()),
                 tags_json,
+                connection.archived,
+                connection.host_key_fingerprint,
+                connection.expires_at.map(|d| d.to_rfc3339()),
+                connection
+                    .ssh_config_file
+                    .as_ref()
+                    .map(|p| p.to_string_lossy().into_owned()),
+                serde_json::to_string(&connection.env)?,
+                serde_json::to_string(&connection.send_env)?,
+                connection.last_error,
+                connection.last_error_at.map(|d| d.to_rfc3339()),
+                connection.proxy_command,
+                connection.auto_attach,
+                connection.use_count,
+                connection.password_ref,
+                connection.notes,
             ],
         )?;
 
@@ -34,11 +62,11 @@ impl Database {
     }
 
     pub fn get_connection(&self, name_or_id: &str) -> Result<Option<Connection>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, name, host, user, port, bastion, bastion_user, use_kerberos, key_path, created_at, last_used, tags
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {CONNECTION_COLUMNS}
              FROM connections 
              WHERE id = ? OR name = ?"
-        )?;
+        ))?;
 
         let mut rows = stmt.query(params![name_or_id, name_or_id])?;
 
@@ -50,13 +78,38 @@ impl Database {
         }
     }
 
+    /// Find a connection pointing at the same `user@host:port`, whatever its name.
+    pub fn find_by_endpoint(
+        &self,
+        host: &str,
+        user: &str,
+        port: u16,
+    ) -> Result<Option<Connection>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {CONNECTION_COLUMNS}
+             FROM connections
+             WHERE host = ? COLLATE NOCASE AND user = ? AND port = ?
+             ORDER BY created_at ASC
+             LIMIT 1"
+        ))?;
+
+        let mut rows = stmt.query(params![host, user, port])?;
+
+        if let Some(row) = rows.next()? {
+            Ok(Some(self.row_to_connection(row)?))
+        } else {
+            Ok(None)
+        }
+    }
+
     pub fn list_connections(
         &self,
         tag_filter: Option<&str>,
         recent_only: bool,
+        include_archived: bool,
     ) -> Result<Vec<Connection>> {
-        let mut query = String::from(
-            "SELECT id, name, host, user, port, bastion, bastion_user, use_kerberos, key_path, created_at, last_used, tags
+        let mut query = format!(
+            "SELECT {CONNECTION_COLUMNS}
              FROM connections"
         );
 
@@ -72,12 +125,17 @@ impl Database {
             conditions.push("last_used IS NOT NULL");
         }
 
+        if !include_archived {
+            conditions.push("archived = 0");
+        }
+
         if !conditions.is_empty() {
             query.push_str(" WHERE ");
             query.push_str(&conditions.join(" AND "));
         }
 
-        query.push_str(" ORDER BY last_used DESC NULLS LAST, name ASC");
+        // Never-used connections last; `NULLS LAST` would need SQLite 3.30+
+        query.push_str(" ORDER BY (last_used IS NULL), last_used DESC, name ASC");
 
         let mut stmt = self.conn.prepare(&query)?;
         let mut rows = stmt.query(rusqlite::params_from_iter(
@@ -92,13 +150,38 @@ impl Database {
         Ok(connections)
     }
 
+    /// Count one use and set `last_used` to now, without writing any other
+    /// field; returns whether a saved connection with this id exists.
+    ///
+    /// The increment happens in SQL so parallel sessions of the same
+    /// connection cannot lose each other's update.
+    pub fn touch_last_used(&self, connection_id: &str) -> Result<bool> {
+        let rows = self.conn.execute(
+            "UPDATE connections SET use_count = use_count + 1, last_used = ? WHERE id = ?",
+            params![chrono::Utc::now().to_rfc3339(), connection_id],
+        )?;
+        Ok(rows > 0)
+    }
+
+    /// Record why the last connect attempt failed, or clear it with `None`
+    pub fn set_last_error(&self, connection_id: &str, error: Option<&str>) -> Result<()> {
+        let at = error.map(|_| chrono::Utc::now().to_rfc3339());
+        self.conn.execute(
+            "UPDATE connections SET last_error = ?, last_error_at = ? WHERE id = ?",
+            params![error, at, connection_id],
+        )?;
+        Ok(())
+    }
+
     pub fn update_connection(&self, connection: &Connection) -> Result<()> {
         let tags_json = serde_json::to_string(&connection.tags)?;
 
         self.conn.execute(
             "UPDATE connections SET 
              name = ?, host = ?, user = ?, port = ?, bastion = ?, bastion_user = ?, 
-             use_kerberos = ?, key_path = ?, last_used = ?, tags = ?
+             use_kerberos = ?, key_path = ?, last_used = ?, tags = ?, expires_at = ?,
+             ssh_config_file = ?, env = ?, send_env = ?, proxy_command = ?,
+             auto_attach = ?, password_ref = ?, notes = ?
              WHERE id = ?",
             params![
                 connection.name,
@@ -111,6 +194,17 @@ impl Database {
                 connection.key_path,
                 connection.last_used.map(|d| d.to_rfc3339()),
                 tags_json,
+                connection.expires_at.map(|d| d.to_rfc3339()),
+                connection
+                    .ssh_config_file
+                    .as_ref()
+                    .map(|p| p.to_string_lossy().into_owned()),
+                serde_json::to_string(&connection.env)?,
+                serde_json::to_string(&connection.send_env)?,
+                connection.proxy_command,
+                connection.auto_attach,
+                connection.password_ref,
+                connection.notes,
                 connection.id.to_string(),
             ],
         )?;
@@ -126,23 +220,124 @@ impl Database {
         Ok(())
     }
 
-    pub fn remove_connection(&self, name_or_id: &str) -> Result<bool> {
+    /// Replace only the notes, so edits made while they were open elsewhere survive
+    pub fn set_notes(&self, connection_id: &str, notes: Option<&str>) -> Result<()> {
+        self.conn.execute(
+            "UPDATE connections SET notes = ? WHERE id = ?",
+            params![notes, connection_id],
+        )?;
+        Ok(())
+    }
+
+    /// Park or restore a connection without touching its other fields
+    pub fn set_archived(&self, connection_id: &str, archived: bool) -> Result<()> {
+        self.conn.execute(
+            "UPDATE connections SET archived = ? WHERE id = ?",
+            params![archived, connection_id],
+        )?;
+        Ok(())
+    }
+
+    /// Replace tag `from` with `to` on every connection carrying it, in one
+    /// transaction. Returns the number of connections changed.
+    pub fn move_tag(&self, from: &str, to: &str) -> Result<usize> {
+        let tx = self.conn.unchecked_transaction()?;
+
+        let mut tagged = Vec::new();
+        {
+            // LIKE narrows the scan; the exact match is checked on the parsed JSON
+            let mut stmt = tx.prepare("SELECT id, tags FROM connections WHERE tags LIKE ?")?;
+            let mut rows = stmt.query(params![format!("%\"{}\"%", from)])?;
+            while let Some(row) = rows.next()? {
+                let id: String = row.get(0)?;
+                let tags: Vec<String> = serde_json::from_str(&row.get::<_, String>(1)?)?;
+                if tags.iter().any(|t| t == from) {
+                    tagged.push((id, tags));
+                }
+            }
+        }
+
+        for (id, tags) in &tagged {
+            let mut moved: Vec<String> = Vec::with_capacity(tags.len());
+            for tag in tags {
+                let tag = if tag == from { to } else { tag.as_str() };
+                if !moved.iter().any(|t| t == tag) {
+                    moved.push(tag.to_string());
+                }
+            }
+            tx.execute(
+                "UPDATE connections SET tags = ? WHERE id = ?",
+                params![serde_json::to_string(&moved)?, id],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(tagged.len())
+    }
+
+    /// Connections whose `expires_at` is in the past
+    pub fn list_expired_connections(&self) -> Result<Vec<Connection>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {CONNECTION_COLUMNS}
+             FROM connections
+             WHERE expires_at IS NOT NULL AND expires_at <= ?
+             ORDER BY expires_at ASC"
+        ))?;
+        let mut rows = stmt.query(params![chrono::Utc::now().to_rfc3339()])?;
+
+        let mut connections = Vec::new();
+        while let Some(row) = rows.next()? {
+            connections.push(self.row_to_connection(row)?);
+        }
+        Ok(connections)
+    }
+
+    /// Cache the host-key fingerprints last seen by `show --host-key`
+    pub fn set_host_key_fingerprint(&self, connection_id: &str, fingerprint: &str) -> Result<()> {
+        self.conn.execute(
+            "UPDATE connections SET host_key_fingerprint = ? WHERE id = ?",
+            params![fingerprint, connection_id],
+        )?;
+        Ok(())
+    }
+
+    /// Delete a connection. Its sessions are deleted too, unless
+    /// `retain_sessions` is set: then they are detached and keep the
+    /// connection's name, so history still shows them.
+    pub fn remove_connection(&self, name_or_id: &str, retain_sessions: bool) -> Result<bool> {
         // First, find the connection to get its ID
-        let connection_id = if let Some(conn) = self.get_connection(name_or_id)? {
-            conn.id.to_string()
+        let (connection_id, connection_name) =
+            if let Some(conn) = self.get_connection(name_or_id)? {
+                (conn.id.to_string(), conn.name)
+            } else {
+                return Ok(false); // Connection not found
+            };
+
+        if retain_sessions {
+            let sessions_kept = self.conn.execute(
+                "UPDATE sessions SET connection_name = ?, connection_id = NULL
+                 WHERE connection_id = ?",
+                params![connection_name, connection_id],
+            )?;
+            info!(
+                "Kept {} sessions for connection '{}'",
+                sessions_kept, name_or_id
+            );
         } else {
-            return Ok(false); // Connection not found
-        };
+            let sessions_deleted = self.conn.execute(
+                "DELETE FROM sessions WHERE connection_id = ?",
+                params![connection_id],
+            )?;
+            info!(
+                "Deleted {} sessions for connection '{}'",
+                sessions_deleted, name_or_id
+            );
+        }
 
-        // Delete all sessions for this connection first
-        let mut stmt = self
-            .conn
-            .prepare("DELETE FROM sessions WHERE connection_id = ?")?;
-        let sessions_deleted = stmt.execute(params![connection_id])?;
-        info!(
-            "Deleted {} sessions for connection '{}'",
-            sessions_deleted, name_or_id
-        );
+        self.conn.execute(
+            "DELETE FROM recent_targets WHERE connection_id = ?",
+            params![connection_id],
+        )?;
 
         // Now delete the connection
         let mut stmt = self
@@ -182,6 +377,29 @@ impl Database {
                     .map(|dt| dt.with_timezone(&chrono::Utc))
             }),
             tags,
+            archived: row.get(12)?,
+            host_key_fingerprint: row.get(13)?,
+            expires_at: row.get::<_, Option<String>>(14)?.and_then(|s| {
+                chrono::DateTime::parse_from_rfc3339(&s)
+                    .ok()
+                    .map(|dt| dt.with_timezone(&chrono::Utc))
+            }),
+            ssh_config_file: row
+                .get::<_, Option<String>>(15)?
+                .map(std::path::PathBuf::from),
+            env: serde_json::from_str(&row.get::<_, String>(16)?)?,
+            send_env: serde_json::from_str(&row.get::<_, String>(17)?)?,
+            last_error: row.get(18)?,
+            last_error_at: row.get::<_, Option<String>>(19)?.and_then(|s| {
+                chrono::DateTime::parse_from_rfc3339(&s)
+                    .ok()
+                    .map(|dt| dt.with_timezone(&chrono::Utc))
+            }),
+            proxy_command: row.get(20)?,
+            auto_attach: row.get(21)?,
+            use_count: row.get(22)?,
+            password_ref: row.get(23)?,
+            notes: row.get(24)?,
         })
     }
 
@@ -192,39 +410,110 @@ impl Database {
 
         let most_used = {
             let result = self.conn.query_row(
-                "SELECT id, name, host, user, port, bastion, bastion_user, use_kerberos, key_path, created_at, last_used, tags
-                 FROM connections
-                 WHERE last_used IS NOT NULL
-                 ORDER BY last_used DESC
-                 LIMIT 1",
+                &format!(
+                    "SELECT {CONNECTION_COLUMNS}
+                     FROM connections
+                     WHERE last_used IS NOT NULL
+                     ORDER BY use_count DESC, last_used DESC
+                     LIMIT 1"
+                ),
                 [],
                 |row| {
                     let conn_result = self.row_to_connection(row);
                     match conn_result {
                         Ok(conn) => Ok(conn),
-                        Err(_) => Err(rusqlite::Error::InvalidParameterName("Failed to parse connection".to_string())),
+                        Err(_) => Err(rusqlite::Error::InvalidParameterName(
+                            "Failed to parse connection".to_string(),
+                        )),
                     }
-                }
+                },
             );
             result.ok()
         };
 
-        let mut recent_connections = self.list_connections(None, true)?;
+        let mut recent_connections = self.list_connections(None, true, false)?;
         recent_connections.truncate(10);
 
         let mut tag_counts = std::collections::HashMap::new();
-        let connections = self.list_connections(None, false)?;
+        let connections = self.list_connections(None, false, false)?;
         for conn in connections {
             for tag in &conn.tags {
                 *tag_counts.entry(tag.clone()).or_insert(0) += 1;
             }
         }
 
+        // Session aggregates are computed here rather than in SQL because
+        // timestamps and statuses are stored as RFC3339 / JSON text.
+        let mut stmt = self.conn.prepare(
+            "SELECT s.started_at, s.ended_at, s.status, s.exit_code, c.use_kerberos
+             FROM sessions s
+             LEFT JOIN connections c ON s.connection_id = c.id
+             WHERE c.id IS NOT NULL OR s.connection_name IS NOT NULL",
+        )?;
+        let mut rows = stmt.query([])?;
+
+        let mut total_sessions = 0usize;
+        let mut successful = 0usize;
+        let mut kerberos_sessions = 0usize;
+        let mut adhoc_sessions = 0usize;
+        let mut finished = 0usize;
+        let mut total_secs = 0i64;
+        let mut by_weekday = [0usize; 7];
+        while let Some(row) = rows.next()? {
+            let started_str: String = row.get(0)?;
+            let ended_str: Option<String> = row.get(1)?;
+            let status_json: String = row.get(2)?;
+            let exit_code: Option<i32> = row.get(3)?;
+            // NULL for ad-hoc sessions, which have no saved connection
+            let use_kerberos: Option<bool> = row.get(4)?;
+
+            let started_at = chrono::DateTime::parse_from_rfc3339(&started_str)?;
+            total_sessions += 1;
+            match use_kerberos {
+                Some(true) => kerberos_sessions += 1,
+                Some(false) => {}
+                None => adhoc_sessions += 1,
+            }
+            let status: Option<SessionStatus> = serde_json::from_str(&status_json).ok();
+            if matches!(status, Some(SessionStatus::Terminated)) && exit_code == Some(0) {
+                successful += 1;
+            }
+            if let Some(ended_at) = ended_str
+                .as_deref()
+                .and_then(|s| chrono::DateTime::parse_from_rfc3339(s).ok())
+            {
+                finished += 1;
+                total_secs += (ended_at - started_at).num_seconds();
+            }
+            let weekday = started_at.with_timezone(&chrono::Local).weekday();
+            by_weekday[weekday.num_days_from_monday() as usize] += 1;
+        }
+
+        let busiest_weekday = by_weekday
+            .iter()
+            .enumerate()
+            .filter(|(_, count)| **count > 0)
+            // Earliest weekday wins ties
+            .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(&a.0)))
+            .map(|(day, _)| WEEKDAYS[day].to_string());
+
         Ok(crate::models::ConnectionStats {
             total_connections: total_connections as usize,
             most_used,
             recently_used: recent_connections,
             by_tag: tag_counts,
+            total_sessions,
+            success_rate: if total_sessions > 0 {
+                successful as f64 / total_sessions as f64
+            } else {
+                0.0
+            },
+            average_session_secs: (finished > 0).then(|| total_secs as f64 / finished as f64),
+            busiest_weekday,
+            kerberos_sessions,
+            direct_sessions: total_sessions - kerberos_sessions - adhoc_sessions,
+            adhoc_sessions,
+            usage: self.get_connection_usage()?,
         })
     }
 }
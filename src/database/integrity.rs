@@ -0,0 +1,52 @@
+use crate::database::Database;
+use anyhow::Result;
+
+impl Database {
+    /// Sessions whose `connection_id` has no matching connection, as
+    /// `(session_id, connection_id, started_at)`. Sessions retained after a
+    /// removal have no `connection_id` and are not orphans.
+    pub fn find_orphaned_sessions(&self) -> Result<Vec<(String, String, String)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT s.id, s.connection_id, s.started_at
+             FROM sessions s
+             LEFT JOIN connections c ON c.id = s.connection_id
+             WHERE c.id IS NULL AND s.connection_id IS NOT NULL
+             ORDER BY s.started_at ASC",
+        )?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Aliases pointing at a missing connection, as `(alias, connection_id)`
+    pub fn find_orphaned_aliases(&self) -> Result<Vec<(String, String)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT a.alias, a.connection_id
+             FROM aliases a
+             LEFT JOIN connections c ON c.id = a.connection_id
+             WHERE c.id IS NULL
+             ORDER BY a.alias ASC",
+        )?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Fold the WAL back into the main file and rebuild it to drop free pages
+    pub fn optimize(&self) -> Result<()> {
+        self.conn
+            .execute_batch("PRAGMA wal_checkpoint(TRUNCATE); VACUUM;")?;
+        Ok(())
+    }
+
+    /// Delete every orphaned session and alias; returns `(sessions, aliases)` removed
+    pub fn delete_orphans(&self) -> Result<(usize, usize)> {
+        let sessions = self.conn.execute(
+            "DELETE FROM sessions WHERE connection_id NOT IN (SELECT id FROM connections)",
+            [],
+        )?;
+        let aliases = self.conn.execute(
+            "DELETE FROM aliases WHERE connection_id NOT IN (SELECT id FROM connections)",
+            [],
+        )?;
+        Ok((sessions, aliases))
+    }
+}
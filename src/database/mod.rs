@@ -6,8 +6,18 @@ pub struct Database {
     pub(crate) conn: SqliteConnection,
 }
 
+/// Columns of the `connections` table in the order `row_to_connection`
+/// reads them. Queries select these first so the indices line up.
+const CONNECTION_COLUMNS: &str =
+    "id, name, host, user, port, bastion, bastion_user, use_kerberos, \
+     key_path, created_at, last_used, tags, archived, host_key_fingerprint, expires_at, \
+     ssh_config_file, env, send_env, last_error, last_error_at, proxy_command, auto_attach, \
+     use_count, password_ref, notes";
+
 mod alias;
 mod connection;
+mod integrity;
+mod recent;
 mod search;
 mod session;
 
@@ -19,6 +29,9 @@ impl Database {
         }
 
         let conn = SqliteConnection::open(&config.database_path)?;
+        // Parallel sessions write to the same file; wait for a lock rather
+        // than failing with "database is locked".
+        conn.busy_timeout(std::time::Duration::from_secs(5))?;
         let db = Database { conn };
         db.init()?;
 
@@ -41,7 +54,20 @@ impl Database {
                 key_path TEXT,
                 created_at TEXT NOT NULL,
                 last_used TEXT,
-                tags TEXT NOT NULL
+                tags TEXT NOT NULL,
+                archived BOOLEAN NOT NULL DEFAULT 0,
+                host_key_fingerprint TEXT,
+                expires_at TEXT,
+                ssh_config_file TEXT,
+                env TEXT NOT NULL DEFAULT '[]',
+                send_env TEXT NOT NULL DEFAULT '[]',
+                last_error TEXT,
+                last_error_at TEXT,
+                proxy_command TEXT,
+                auto_attach TEXT,
+                use_count INTEGER NOT NULL DEFAULT 0,
+                password_ref TEXT,
+                notes TEXT
             )",
             [],
         )?;
@@ -50,28 +76,150 @@ impl Database {
         self.conn.execute(
             "CREATE TABLE IF NOT EXISTS sessions (
                 id TEXT PRIMARY KEY,
-                connection_id TEXT NOT NULL,
+                connection_id TEXT,
                 started_at TEXT NOT NULL,
                 ended_at TEXT,
                 status TEXT NOT NULL,
                 pid INTEGER,
                 exit_code INTEGER,
                 transport TEXT,
+                alias_used TEXT,
+                label TEXT,
+                connection_name TEXT,
+                ad_hoc BOOLEAN NOT NULL DEFAULT 0,
+                transcript TEXT,
                 FOREIGN KEY (connection_id) REFERENCES connections (id)
             )",
             [],
         )?;
 
-        // Additive migration: sessions.transport was added in 1.5.0.
-        let has_col: bool = {
+        // Additive migrations: sessions.transport was added in 1.5.0,
+        // sessions.alias_used and sessions.label later on.
+        let session_cols: Vec<String> = {
             let mut stmt = self.conn.prepare("PRAGMA table_info(sessions)")?;
             let rows = stmt.query_map([], |r| r.get::<_, String>(1))?;
-            let names: Vec<String> = rows.filter_map(Result::ok).collect();
-            names.iter().any(|n| n == "transport")
+            rows.filter_map(Result::ok).collect()
         };
-        if !has_col {
+        for col in ["transport", "alias_used", "label"] {
+            if !session_cols.iter().any(|n| n == col) {
+                self.conn
+                    .execute(&format!("ALTER TABLE sessions ADD COLUMN {} TEXT", col), [])?;
+            }
+        }
+
+        // Sessions kept after their connection is removed (see
+        // `retain_sessions_on_delete`) have no connection_id and carry the
+        // old name in connection_name. SQLite cannot drop the NOT NULL on
+        // connection_id in place, so older tables are rebuilt.
+        if !session_cols.iter().any(|n| n == "connection_name") {
+            self.conn.execute_batch(
+                "BEGIN;
+                 CREATE TABLE sessions_new (
+                     id TEXT PRIMARY KEY,
+                     connection_id TEXT,
+                     started_at TEXT NOT NULL,
+                     ended_at TEXT,
+                     status TEXT NOT NULL,
+                     pid INTEGER,
+                     exit_code INTEGER,
+                     transport TEXT,
+                     alias_used TEXT,
+                     label TEXT,
+                     connection_name TEXT,
+                     FOREIGN KEY (connection_id) REFERENCES connections (id)
+                 );
+                 INSERT INTO sessions_new
+                     (id, connection_id, started_at, ended_at, status, pid, exit_code, transport, alias_used, label)
+                 SELECT id, connection_id, started_at, ended_at, status, pid, exit_code, transport, alias_used, label
+                 FROM sessions;
+                 DROP TABLE sessions;
+                 ALTER TABLE sessions_new RENAME TO sessions;
+                 COMMIT;",
+            )?;
+        }
+
+        // Sessions with a host that was never saved (see `connect --ephemeral`)
+        if !session_cols.iter().any(|n| n == "ad_hoc") {
+            self.conn.execute(
+                "ALTER TABLE sessions ADD COLUMN ad_hoc BOOLEAN NOT NULL DEFAULT 0",
+                [],
+            )?;
+        }
+
+        // Where `connect --log` recorded the session; added after the rebuild
+        // above so that older tables keep it.
+        if !session_cols.iter().any(|n| n == "transcript") {
             self.conn
-                .execute("ALTER TABLE sessions ADD COLUMN transport TEXT", [])?;
+                .execute("ALTER TABLE sessions ADD COLUMN transcript TEXT", [])?;
+        }
+
+        // connections.archived marks parked hosts; host_key_fingerprint
+        // caches `show --host-key` results; expires_at drives `prune --expired`;
+        // ssh_config_file is passed to ssh as `-F`; env and send_env are JSON
+        // lists turned into SetEnv/SendEnv options; last_error keeps the
+        // stderr of the last failed connect; proxy_command replaces the
+        // bastion with a ProxyCommand template; auto_attach names the tmux
+        // session `connect` attaches to; use_count counts established
+        // sessions; password_ref names the keyring entry holding a password;
+        // notes holds the runbook edited with `bssh note`.
+        let connection_cols: Vec<String> = {
+            let mut stmt = self.conn.prepare("PRAGMA table_info(connections)")?;
+            let rows = stmt.query_map([], |r| r.get::<_, String>(1))?;
+            rows.filter_map(Result::ok).collect()
+        };
+        if !connection_cols.iter().any(|n| n == "archived") {
+            self.conn.execute(
+                "ALTER TABLE connections ADD COLUMN archived BOOLEAN NOT NULL DEFAULT 0",
+                [],
+            )?;
+        }
+        if !connection_cols.iter().any(|n| n == "host_key_fingerprint") {
+            self.conn.execute(
+                "ALTER TABLE connections ADD COLUMN host_key_fingerprint TEXT",
+                [],
+            )?;
+        }
+        if !connection_cols.iter().any(|n| n == "expires_at") {
+            self.conn
+                .execute("ALTER TABLE connections ADD COLUMN expires_at TEXT", [])?;
+        }
+        if !connection_cols.iter().any(|n| n == "ssh_config_file") {
+            self.conn.execute(
+                "ALTER TABLE connections ADD COLUMN ssh_config_file TEXT",
+                [],
+            )?;
+        }
+        for col in [
+            "last_error",
+            "last_error_at",
+            "proxy_command",
+            "auto_attach",
+            "password_ref",
+            "notes",
+        ] {
+            if !connection_cols.iter().any(|n| n == col) {
+                self.conn.execute(
+                    &format!("ALTER TABLE connections ADD COLUMN {} TEXT", col),
+                    [],
+                )?;
+            }
+        }
+        if !connection_cols.iter().any(|n| n == "use_count") {
+            self.conn.execute(
+                "ALTER TABLE connections ADD COLUMN use_count INTEGER NOT NULL DEFAULT 0",
+                [],
+            )?;
+        }
+        for col in ["env", "send_env"] {
+            if !connection_cols.iter().any(|n| n == col) {
+                self.conn.execute(
+                    &format!(
+                        "ALTER TABLE connections ADD COLUMN {} TEXT NOT NULL DEFAULT '[]'",
+                        col
+                    ),
+                    [],
+                )?;
+            }
         }
 
         // Create indexes
@@ -116,6 +264,16 @@ impl Database {
             [],
         )?;
 
+        // Most-recently-used ring of distinct connect targets
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS recent_targets (
+                connection_id TEXT PRIMARY KEY,
+                used_at TEXT NOT NULL,
+                FOREIGN KEY (connection_id) REFERENCES connections (id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
         Ok(())
     }
 }
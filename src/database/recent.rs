@@ -0,0 +1,51 @@
+use crate::database::{Database, CONNECTION_COLUMNS};
+use crate::models::Connection;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::params;
+
+/// How many distinct targets the recent ring keeps
+const RECENT_TARGETS_CAP: usize = 50;
+
+impl Database {
+    /// Move a saved connection to the front of the recent-targets ring.
+    ///
+    /// Unsaved ad-hoc targets are ignored, and the ring is trimmed to
+    /// `RECENT_TARGETS_CAP` entries.
+    pub fn record_recent_target(&self, connection_id: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO recent_targets (connection_id, used_at)
+             SELECT ?1, ?2 WHERE EXISTS (SELECT 1 FROM connections WHERE id = ?1)",
+            params![connection_id, Utc::now().to_rfc3339()],
+        )?;
+        self.conn.execute(
+            "DELETE FROM recent_targets WHERE connection_id NOT IN (
+                SELECT connection_id FROM recent_targets ORDER BY used_at DESC LIMIT ?
+             )",
+            params![RECENT_TARGETS_CAP as i64],
+        )?;
+        Ok(())
+    }
+
+    /// Distinct recently used connections, most recent first.
+    pub fn get_recent_targets(&self, limit: usize) -> Result<Vec<(Connection, DateTime<Utc>)>> {
+        // `recent_targets` shares no column names with `connections`, so the
+        // unqualified column list is unambiguous here.
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {CONNECTION_COLUMNS}, r.used_at
+             FROM recent_targets r
+             JOIN connections c ON c.id = r.connection_id
+             ORDER BY r.used_at DESC
+             LIMIT ?"
+        ))?;
+        let mut rows = stmt.query(params![limit as i64])?;
+
+        let mut targets = Vec::new();
+        while let Some(row) = rows.next()? {
+            let used_at: String = row.get(25)?;
+            let used_at = DateTime::parse_from_rfc3339(&used_at)?.with_timezone(&Utc);
+            targets.push((self.row_to_connection(row)?, used_at));
+        }
+        Ok(targets)
+    }
+}
@@ -1,4 +1,5 @@
-use crate::database::Database;
+use crate::database::{Database, CONNECTION_COLUMNS};
+use crate::models::matching::matches_name_pattern;
 use crate::models::Connection;
 use anyhow::Result;
 use rusqlite::params;
@@ -27,190 +28,79 @@ impl Database {
         limit: usize,
         mode: &str,
     ) -> Result<Vec<Connection>> {
-        let mut all_matches = Vec::new();
-        let normalized_query = query.to_lowercase();
-
-        // Search in names with multiple strategies
-        if let Ok(mut name_matches) = self.search_by_field(&normalized_query, "name", limit) {
-            all_matches.append(&mut name_matches);
-        }
-
-        // Enhanced fuzzy matching for names
-        if let Ok(mut fuzzy_matches) = self.enhanced_fuzzy_search(&normalized_query, limit) {
-            all_matches.append(&mut fuzzy_matches);
-        }
-
-        // Search in hosts
-        if let Ok(mut host_matches) = self.search_by_field(&normalized_query, "host", limit) {
-            all_matches.append(&mut host_matches);
-        }
-
-        // Search in tags (JSON array search)
-        if let Ok(mut tag_matches) = self.search_in_tags(&normalized_query, limit) {
-            all_matches.append(&mut tag_matches);
-        }
-
-        // Remove duplicates and sort by relevance
-        self.deduplicate_and_rank(&mut all_matches, &normalized_query, mode);
-
-        // Limit results
-        all_matches.truncate(limit);
-
-        Ok(all_matches)
+        let candidates = self.load_search_candidates()?;
+        Ok(self.search_candidates(&candidates, query, limit, mode))
     }
 
-    fn search_by_field(&self, query: &str, field: &str, limit: usize) -> Result<Vec<Connection>> {
+    /// Every connection a search can return (archived ones excluded), most
+    /// recently used first, with aliases attached. Load once and pass to
+    /// `search_candidates` to search repeatedly without re-reading the table.
+    pub fn load_search_candidates(&self) -> Result<Vec<Connection>> {
         let sql = format!(
-            "SELECT id, name, host, user, port, bastion, bastion_user, use_kerberos, key_path, created_at, last_used, tags
+            "SELECT {CONNECTION_COLUMNS}
              FROM connections
-             WHERE {} LIKE ? COLLATE NOCASE
-             ORDER BY last_used DESC NULLS LAST, name ASC
-             LIMIT ?",
-            field
+             WHERE archived = 0
+             ORDER BY (last_used IS NULL), last_used DESC, name ASC"
         );
 
         let mut stmt = self.conn.prepare(&sql)?;
-        let like_pattern = format!("%{}%", query);
-        let mut rows = stmt.query(params![like_pattern, limit])?;
+        let mut rows = stmt.query([])?;
 
         let mut connections = Vec::new();
         while let Some(row) = rows.next()? {
             connections.push(self.row_to_connection(row)?);
         }
 
+        self.attach_aliases(&mut connections)?;
         Ok(connections)
     }
 
-    fn enhanced_fuzzy_search(&self, query: &str, limit: usize) -> Result<Vec<Connection>> {
-        let sql = "SELECT id, name, host, user, port, bastion, bastion_user, use_kerberos, key_path, created_at, last_used, tags
-                   FROM connections
-                   ORDER BY last_used DESC NULLS LAST, name ASC";
-
-        let mut stmt = self.conn.prepare(sql)?;
-        let mut rows = stmt.query([])?;
+    /// Match `query` against names, hosts, tags and aliases of `candidates`
+    /// and return the best `limit` hits ranked by `mode`
+    pub fn search_candidates(
+        &self,
+        candidates: &[Connection],
+        query: &str,
+        limit: usize,
+        mode: &str,
+    ) -> Vec<Connection> {
+        let normalized_query = query.to_lowercase();
 
-        let mut connections = Vec::new();
-        while let Some(row) = rows.next()? {
-            let connection = self.row_to_connection(row)?;
-            let name_lower = connection.name.to_lowercase();
-
-            // Enhanced matching patterns
-            if self.matches_enhanced_patterns(query, &name_lower) {
-                connections.push(connection);
-                if connections.len() >= limit {
-                    break;
-                }
-            }
-        }
+        let matches: Vec<(f64, Connection)> = candidates
+            .iter()
+            .filter_map(|conn| Some((conn.matches(&normalized_query)?, conn.clone())))
+            .collect();
 
-        Ok(connections)
+        let mut ranked = self.rank(matches, &normalized_query, mode);
+        ranked.truncate(limit);
+        ranked
     }
 
-    fn matches_enhanced_patterns(&self, query: &str, name: &str) -> bool {
-        let query = query.to_lowercase();
-
-        // 1. Standard substring match (already covered by search_by_field)
-
-        // 2. Word-based matching - split query into words and find them
-        let query_words: Vec<&str> = query.split_whitespace().collect();
-        if query_words.len() > 1 {
-            let all_words_found = query_words.iter().all(|word| name.contains(word));
-            if all_words_found {
-                return true;
-            }
-        }
-
-        // 3. Handle common separators (hyphens, underscores, dots)
-        let normalized_name = name.replace("-", "").replace("_", "").replace(".", "");
-
-        let normalized_query = query.replace("-", "").replace("_", "").replace(".", "");
-
-        // Check if normalized versions match
-        if normalized_name.contains(&normalized_query) {
-            return true;
-        }
-
-        // 4. Acronym matching (first letters of words)
-        if query.len() >= 2 {
-            let words: Vec<&str> = name.split(&['-', '_', ' '][..]).collect();
-            if words.len() > 1 {
-                let acronym: String = words
-                    .iter()
-                    .filter_map(|word| word.chars().next())
-                    .collect();
-                if acronym.to_lowercase().contains(&query) {
-                    return true;
-                }
-            }
-        }
-
-        // 5. Partial acronym matching
-        if query.len() >= 2 {
-            let name_chars: String = name.chars().filter(|c| c.is_alphanumeric()).collect();
-            if name_chars.to_lowercase().starts_with(&query) {
-                return true;
-            }
-        }
-
-        false
-    }
-
-    fn search_in_tags(&self, query: &str, limit: usize) -> Result<Vec<Connection>> {
-        let sql =
-            "SELECT id, name, host, user, port, bastion, bastion_user, use_kerberos, key_path, created_at, last_used, tags
-             FROM connections
-             WHERE tags LIKE ? COLLATE NOCASE
-             ORDER BY last_used DESC NULLS LAST, name ASC
-             LIMIT ?";
-
-        let mut stmt = self.conn.prepare(sql)?;
-        let like_pattern = format!("%\"{}\"%", query);
-        let mut rows = stmt.query(params![like_pattern, limit])?;
-
-        let mut connections = Vec::new();
-        while let Some(row) = rows.next()? {
-            connections.push(self.row_to_connection(row)?);
-        }
-
-        Ok(connections)
-    }
-
-    fn deduplicate_and_rank(&self, connections: &mut Vec<Connection>, query: &str, mode: &str) {
-        use std::collections::HashSet;
-
-        let mut seen = HashSet::new();
-        connections.retain(|conn| {
-            if seen.contains(&conn.id) {
-                false
-            } else {
-                seen.insert(conn.id);
-                true
-            }
-        });
-
-        // Sort by relevance score based on mode
-        connections.sort_by(|a, b| {
-            let score_a = if mode == "bayesian" {
-                self.calculate_bayesian_score(a, query)
-            } else {
-                self.calculate_relevance_score(a, query)
-            };
-            let score_b = if mode == "bayesian" {
-                self.calculate_bayesian_score(b, query)
-            } else {
-                self.calculate_relevance_score(b, query)
-            };
-            score_b
-                .partial_cmp(&score_a)
-                .unwrap_or(std::cmp::Ordering::Equal)
-        });
+    /// Sort by relevance score based on mode. Each connection is scored
+    /// once: the bayesian score reads session statistics from the database.
+    /// `matches` pairs each connection with its [`Connection::matches`] score.
+    fn rank(&self, matches: Vec<(f64, Connection)>, query: &str, mode: &str) -> Vec<Connection> {
+        let mut scored: Vec<(f64, Connection)> = matches
+            .into_iter()
+            .map(|(likelihood, conn)| {
+                let score = if mode == "bayesian" {
+                    self.calculate_bayesian_score(&conn, likelihood)
+                } else {
+                    self.calculate_relevance_score(&conn, query)
+                };
+                (score, conn)
+            })
+            .collect();
+
+        scored.sort_by(|(a, _), (b, _)| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+        scored.into_iter().map(|(_, conn)| conn).collect()
     }
 
     /// Bayesian-inspired scoring that combines:
     /// - Prior probability (frequency of use)
     /// - Likelihood (match quality)
     /// - Recency (temporal decay)
-    fn calculate_bayesian_score(&self, connection: &Connection, query: &str) -> f64 {
+    fn calculate_bayesian_score(&self, connection: &Connection, likelihood: f64) -> f64 {
         // Get connection usage statistics
         let (frequency, total_connections) = self.get_connection_frequency(&connection.id);
 
@@ -222,7 +112,7 @@ impl Database {
         };
 
         // 2. Likelihood: P(query | connection) - how well does query match?
-        let likelihood = self.calculate_match_likelihood(connection, query);
+        //    Passed in from `Connection::matches`.
 
         // 3. Recency factor: exponential decay based on last use
         let recency = self.calculate_recency_factor(connection);
@@ -272,71 +162,6 @@ impl Database {
         }
     }
 
-    fn calculate_match_likelihood(&self, connection: &Connection, query: &str) -> f64 {
-        let query_lower = query.to_lowercase();
-        let name_lower = connection.name.to_lowercase();
-
-        // Exact match - highest likelihood
-        if name_lower == query_lower {
-            return 1.0;
-        }
-
-        // Prefix match - very high
-        if name_lower.starts_with(&query_lower) {
-            return 0.9;
-        }
-
-        // Word boundary match (e.g., "prod" matches "web-prod-server")
-        let words: Vec<&str> = name_lower.split(&['-', '_', '.', ' '][..]).collect();
-        for word in &words {
-            if *word == query_lower {
-                return 0.85;
-            }
-            if word.starts_with(&query_lower) {
-                return 0.75;
-            }
-        }
-
-        // Contains match
-        if name_lower.contains(&query_lower) {
-            return 0.6;
-        }
-
-        // Normalized match (ignoring separators)
-        let normalized_name = name_lower.replace(&['-', '_', '.'][..], "");
-        let normalized_query = query_lower.replace(&['-', '_', '.'][..], "");
-        if normalized_name.contains(&normalized_query) {
-            return 0.5;
-        }
-
-        // Host match
-        if connection.host.to_lowercase().contains(&query_lower) {
-            return 0.4;
-        }
-
-        // Tag match
-        for tag in &connection.tags {
-            if tag.to_lowercase().contains(&query_lower) {
-                return 0.45;
-            }
-        }
-
-        // Acronym match
-        if query_lower.len() >= 2 && words.len() > 1 {
-            let acronym: String = words.iter().filter_map(|w| w.chars().next()).collect();
-            if acronym.contains(&query_lower) {
-                return 0.35;
-            }
-        }
-
-        // Fuzzy/pattern match (lowest but still valid)
-        if self.matches_enhanced_patterns(&query_lower, &name_lower) {
-            return 0.2;
-        }
-
-        0.1 // Minimal likelihood for any match
-    }
-
     fn calculate_recency_factor(&self, connection: &Connection) -> f64 {
         if let Some(last_used) = connection.last_used {
             let hours_since_used = chrono::Utc::now()
@@ -376,10 +201,25 @@ impl Database {
         }
 
         // Enhanced pattern matching scores
-        if self.matches_enhanced_patterns(query, &name_lower) {
+        if matches_name_pattern(query, &name_lower) {
             score += 15.0; // Bonus for pattern matching
         }
 
+        // Query in aliases: exact and prefix hits count almost like the name
+        for alias in &connection.aliases {
+            let alias = alias.to_lowercase();
+            if alias == query_lower {
+                score += 90.0;
+            } else if alias.starts_with(&query_lower) {
+                score += 40.0;
+            } else if alias.contains(&query_lower) {
+                score += 20.0;
+            } else {
+                continue;
+            }
+            break;
+        }
+
         // Query in host
         if connection.host.to_lowercase().contains(&query_lower) {
             score += 15.0;
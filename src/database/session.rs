@@ -1,29 +1,53 @@
 use crate::database::Database;
-use crate::models::Session;
+use crate::models::{ConnectionUsage, Session};
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 use rusqlite::params;
 
 impl Database {
     // Session management
     pub fn add_session(&self, session: &Session) -> Result<()> {
+        // A host reached ad hoc is not in `connections`; its sessions are
+        // kept by name, like those of a removed connection, and flagged.
+        let connection_id = session.connection.id.to_string();
+        let saved: bool = self.conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM connections WHERE id = ?)",
+            params![connection_id],
+            |row| row.get(0),
+        )?;
         self.conn.execute(
-            "INSERT INTO sessions (id, connection_id, started_at, ended_at, status, pid, exit_code, transport)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            "INSERT INTO sessions (id, connection_id, started_at, ended_at, status, pid, exit_code, transport, alias_used, label, connection_name, ad_hoc, transcript)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
             params![
                 session.id.to_string(),
-                session.connection.id.to_string(),
+                saved.then_some(&connection_id),
                 session.started_at.to_rfc3339(),
                 session.ended_at.map(|d| d.to_rfc3339()),
                 serde_json::to_string(&session.status)?,
                 session.pid,
                 session.exit_code,
                 session.transport.as_deref(),
+                session.alias_used.as_deref(),
+                session.label.as_deref(),
+                (!saved).then_some(&session.connection.name),
+                !saved,
+                session.transcript.as_deref(),
             ],
         )?;
 
         Ok(())
     }
 
+    /// Link the ad-hoc sessions kept under `connection`'s name to it, once
+    /// that host is saved. Returns how many were linked.
+    pub fn adopt_sessions(&self, connection: &crate::models::Connection) -> Result<usize> {
+        Ok(self.conn.execute(
+            "UPDATE sessions SET connection_id = ?, connection_name = NULL, ad_hoc = 0
+             WHERE connection_id IS NULL AND ad_hoc = 1 AND connection_name = ?",
+            params![connection.id.to_string(), connection.name],
+        )?)
+    }
+
     pub fn update_session(&self, session: &Session) -> Result<()> {
         self.conn.execute(
             "UPDATE sessions SET 
@@ -47,36 +71,46 @@ impl Database {
         &self,
         connection_filter: Option<&str>,
         limit: usize,
-        days: Option<u32>,
+        since: Option<DateTime<Utc>>,
+        until: Option<DateTime<Utc>>,
         show_failed_only: bool,
+        label: Option<&str>,
     ) -> Result<Vec<crate::models::SessionHistoryEntry>> {
         use crate::models::{SessionHistoryEntry, SessionStatus};
-        use chrono::{Duration, Utc};
 
         let mut query = String::from(
-            "SELECT s.id, c.name, s.started_at, s.ended_at, s.status, s.exit_code
+            "SELECT s.id, COALESCE(c.name, s.connection_name), s.started_at, s.ended_at, s.status, s.exit_code, s.label, s.ad_hoc, s.transcript
              FROM sessions s
-             JOIN connections c ON s.connection_id = c.id
-             WHERE 1=1",
+             LEFT JOIN connections c ON s.connection_id = c.id
+             WHERE (c.id IS NOT NULL OR s.connection_name IS NOT NULL)",
         );
         let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
 
         if let Some(conn_name) = connection_filter {
-            query.push_str(" AND (c.name LIKE ? OR c.id = ?)");
+            query.push_str(" AND (COALESCE(c.name, s.connection_name) LIKE ? OR c.id = ?)");
             params.push(Box::new(format!("%{}%", conn_name)));
             params.push(Box::new(conn_name.to_string()));
         }
 
-        if let Some(d) = days {
-            let cutoff = Utc::now() - Duration::days(d as i64);
+        // Timestamps are stored as UTC RFC3339, which sorts as text
+        if let Some(since) = since {
             query.push_str(" AND s.started_at >= ?");
-            params.push(Box::new(cutoff.to_rfc3339()));
+            params.push(Box::new(since.to_rfc3339()));
+        }
+        if let Some(until) = until {
+            query.push_str(" AND s.started_at <= ?");
+            params.push(Box::new(until.to_rfc3339()));
         }
 
         if show_failed_only {
             query.push_str(" AND (s.status LIKE '%Error%' OR s.exit_code != 0)");
         }
 
+        if let Some(label) = label {
+            query.push_str(" AND s.label = ?");
+            params.push(Box::new(label.to_string()));
+        }
+
         query.push_str(" ORDER BY s.started_at DESC LIMIT ?");
         params.push(Box::new(limit as i64));
 
@@ -111,32 +145,67 @@ impl Database {
                 status,
                 exit_code: row.get(5)?,
                 duration,
+                label: row.get(6)?,
+                ad_hoc: row.get(7)?,
+                transcript: row.get(8)?,
             });
         }
 
         Ok(entries)
     }
 
+    /// Sessions per connection in the last 7 and 30 days and overall.
+    ///
+    /// Connections that were never opened are included with zero counts,
+    /// since they are the likeliest candidates for removal.
+    pub fn get_connection_usage(&self) -> Result<Vec<ConnectionUsage>> {
+        let now = chrono::Utc::now();
+        let week_cutoff = (now - chrono::Duration::days(7)).to_rfc3339();
+        let month_cutoff = (now - chrono::Duration::days(30)).to_rfc3339();
+
+        let mut stmt = self.conn.prepare(
+            "SELECT c.name,
+                    COALESCE(SUM(s.started_at >= ?1), 0),
+                    COALESCE(SUM(s.started_at >= ?2), 0),
+                    COUNT(s.id)
+             FROM connections c
+             LEFT JOIN sessions s ON s.connection_id = c.id
+             GROUP BY c.id
+             ORDER BY 2 DESC, 3 DESC, 4 DESC, c.name ASC",
+        )?;
+        let rows = stmt.query_map(params![week_cutoff, month_cutoff], |row| {
+            Ok(ConnectionUsage {
+                name: row.get(0)?,
+                week: row.get::<_, i64>(1)? as usize,
+                month: row.get::<_, i64>(2)? as usize,
+                all_time: row.get::<_, i64>(3)? as usize,
+            })
+        })?;
+
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
     // Active session management
     #[allow(clippy::type_complexity)]
     pub fn get_active_sessions(
         &self,
-    ) -> Result<Vec<(String, Option<u32>, chrono::DateTime<chrono::Utc>)>> {
+    ) -> Result<Vec<(String, String, Option<u32>, chrono::DateTime<chrono::Utc>)>> {
         let mut stmt = self.conn.prepare(
-            "SELECT c.name, s.pid, s.started_at
+            "SELECT s.id, COALESCE(c.name, s.connection_name), s.pid, s.started_at
              FROM sessions s
-             JOIN connections c ON s.connection_id = c.id
-             WHERE s.ended_at IS NULL AND s.status LIKE '%Active%'
+             LEFT JOIN connections c ON s.connection_id = c.id
+             WHERE (c.id IS NOT NULL OR s.connection_name IS NOT NULL)
+               AND s.ended_at IS NULL AND s.status LIKE '%Active%'
              ORDER BY s.started_at DESC",
         )?;
         let mut rows = stmt.query([])?;
 
         let mut sessions = Vec::new();
         while let Some(row) = rows.next()? {
-            let started_str: String = row.get(2)?;
+            let started_str: String = row.get(3)?;
             let started_at =
                 chrono::DateTime::parse_from_rfc3339(&started_str)?.with_timezone(&chrono::Utc);
-            sessions.push((row.get(0)?, row.get(1)?, started_at));
+            sessions.push((row.get(0)?, row.get(1)?, row.get(2)?, started_at));
         }
         Ok(sessions)
     }
@@ -147,11 +216,12 @@ impl Database {
         target: &str,
     ) -> Result<Vec<(String, String, Option<u32>, chrono::DateTime<chrono::Utc>)>> {
         let mut stmt = self.conn.prepare(
-            "SELECT s.id, c.name, s.pid, s.started_at
+            "SELECT s.id, COALESCE(c.name, s.connection_name), s.pid, s.started_at
              FROM sessions s
-             JOIN connections c ON s.connection_id = c.id
-             WHERE s.ended_at IS NULL AND s.status LIKE '%Active%'
-               AND (c.name LIKE ? OR c.id = ?)
+             LEFT JOIN connections c ON s.connection_id = c.id
+             WHERE (c.id IS NOT NULL OR s.connection_name IS NOT NULL)
+               AND s.ended_at IS NULL AND s.status LIKE '%Active%'
+               AND (COALESCE(c.name, s.connection_name) LIKE ? OR c.id = ?)
              ORDER BY s.started_at DESC",
         )?;
         let like_pattern = format!("%{}%", target);
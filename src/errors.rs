@@ -22,6 +22,45 @@ pub enum AppError {
 
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
+
+    #[error("Operation cancelled")]
+    Cancelled,
+
+    #[error("Could not connect to {0}")]
+    Unreachable(String),
+}
+
+/// Exit statuses scripts can branch on. clap exits 2 for usage errors, and
+/// `exec` passes the remote command's status through.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCode {
+    /// Any other failure
+    Failure = 1,
+    /// The target matched no saved connection
+    NotFound = 3,
+    /// A prompt was declined or the picker was quit
+    Cancelled = 4,
+    /// The host, its SSH port or the login could not be reached
+    Unreachable = 5,
+}
+
+impl AppError {
+    pub fn exit_code(&self) -> ExitCode {
+        match self {
+            AppError::ConnectionNotFound(_) => ExitCode::NotFound,
+            AppError::Cancelled => ExitCode::Cancelled,
+            AppError::Unreachable(_) => ExitCode::Unreachable,
+            _ => ExitCode::Failure,
+        }
+    }
+}
+
+/// Process exit status for an error returned by a command
+pub fn exit_code_for(error: &anyhow::Error) -> i32 {
+    error
+        .chain()
+        .find_map(|e| e.downcast_ref::<AppError>())
+        .map_or(ExitCode::Failure, AppError::exit_code) as i32
 }
 
 pub fn report_cli_error(error: &anyhow::Error) {
@@ -47,6 +86,12 @@ fn suggestion_for(error: &anyhow::Error) -> Option<&'static str> {
         );
     }
 
+    if error_contains(error, "confirmation required") {
+        return Some(
+            "re-run with `--yes` (or set BSSH_ASSUME_YES=1) to approve prompts in automation",
+        );
+    }
+
     if error_contains(error, "permission denied") {
         return Some("check file permissions and ownership for the path shown above");
     }
@@ -33,7 +33,11 @@ async fn main() -> anyhow::Result<()> {
     let is_tui = matches!(&cli.command, Commands::Tui);
 
     // Load configuration first (before initializing logging)
-    let config = AppConfig::load(cli.env.clone())?;
+    let mut config = AppConfig::load(cli.env.clone())?;
+    if let Some(database) = cli.database.clone() {
+        // Database::new creates the parent directory on first use.
+        config.database_path = database;
+    }
 
     let env_prefix = format!("[{}] ", config.environment);
 
@@ -69,9 +73,11 @@ async fn main() -> anyhow::Result<()> {
                 .with_writer(std::sync::Mutex::new(log_file))
                 .init();
         } else {
+            // Logs go to stderr so stdout stays clean for piped/JSON output.
             tracing_subscriber::fmt()
                 .event_format(format)
                 .with_max_level(log_level)
+                .with_writer(std::io::stderr)
                 .init();
 
             if log_level >= LevelFilter::INFO {
@@ -86,7 +92,7 @@ async fn main() -> anyhow::Result<()> {
         if !is_completions {
             error!("Error executing command: {}", e);
         }
-        std::process::exit(1);
+        std::process::exit(errors::exit_code_for(&e));
     }
 
     if !is_completions {
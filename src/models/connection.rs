@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,6 +17,48 @@ pub struct Connection {
     pub created_at: DateTime<Utc>,
     pub last_used: Option<DateTime<Utc>>,
     pub tags: Vec<String>,
+    /// Parked: hidden from `list` and search until unarchived
+    #[serde(default)]
+    pub archived: bool,
+    /// Host-key fingerprints (`key_type SHA256:…`, one per line) from the
+    /// last `show --host-key`
+    #[serde(default)]
+    pub host_key_fingerprint: Option<String>,
+    /// When set, `bssh prune --expired` removes the connection after this time
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Passed to ssh as `-F`, so host-specific `Match` blocks from an
+    /// externally maintained config apply
+    #[serde(default)]
+    pub ssh_config_file: Option<PathBuf>,
+    /// Variables set on the remote side with `-o SetEnv=KEY=VALUE`
+    #[serde(default)]
+    pub env: Vec<(String, String)>,
+    /// Local variable patterns forwarded with `-o SendEnv=PATTERN`
+    #[serde(default)]
+    pub send_env: Vec<String>,
+    /// Tail of ssh's stderr from the last failed connect, cleared on success
+    #[serde(default)]
+    pub last_error: Option<String>,
+    #[serde(default)]
+    pub last_error_at: Option<DateTime<Utc>>,
+    /// `ProxyCommand` template used instead of a bastion; `%h`, `%p` and
+    /// `%r` expand to the host, port and user
+    #[serde(default)]
+    pub proxy_command: Option<String>,
+    /// tmux session that `connect` attaches to (creating it if needed)
+    #[serde(default)]
+    pub auto_attach: Option<String>,
+    /// Number of established sessions, bumped alongside `last_used`
+    #[serde(default)]
+    pub use_count: u32,
+    /// Keyring entry holding the password fed to `sshpass` (the secret itself
+    /// is never stored here)
+    #[serde(default)]
+    pub password_ref: Option<String>,
+    /// Free-form, possibly multi-line runbook notes, edited with `bssh note`
+    #[serde(default)]
+    pub notes: Option<String>,
     /// Aliases for this connection (not stored in main table, loaded separately)
     #[serde(default)]
     pub aliases: Vec<String>,
@@ -46,10 +89,41 @@ impl Connection {
             created_at: Utc::now(),
             last_used: None,
             tags: Vec::new(),
+            archived: false,
+            host_key_fingerprint: None,
+            expires_at: None,
+            ssh_config_file: None,
+            env: Vec::new(),
+            send_env: Vec::new(),
+            last_error: None,
+            last_error_at: None,
+            proxy_command: None,
+            auto_attach: None,
+            use_count: 0,
+            password_ref: None,
+            notes: None,
             aliases: Vec::new(),
         }
     }
 
+    /// Default name for a connection to `host`: the first label of a
+    /// hostname (`web01.prod.example.com` → `web01`), or an IP address with
+    /// its separators turned into `-` (`10.0.1.5` → `10-0-1-5`)
+    pub fn derive_name(host: &str) -> String {
+        let host = host.trim().trim_start_matches('[').trim_end_matches(']');
+        if host.parse::<std::net::IpAddr>().is_ok() {
+            return host
+                .split(['.', ':'])
+                .filter(|part| !part.is_empty())
+                .collect::<Vec<_>>()
+                .join("-");
+        }
+        match host.split('.').next() {
+            Some(label) if !label.is_empty() => label.to_string(),
+            _ => host.to_string(),
+        }
+    }
+
     pub fn update_last_used(&mut self) {
         self.last_used = Some(Utc::now());
     }
@@ -64,35 +138,295 @@ impl Connection {
         self.tags.retain(|t| t != tag);
     }
 
-    pub fn to_ssh_command(&self) -> String {
-        let mut cmd = String::new();
+    /// The `host:port` a TCP reachability probe should try: the bastion
+    /// (port 22 unless given as `host:port`) when there is one, since the
+    /// target usually only answers from behind it, otherwise the target
+    pub fn probe_endpoint(&self) -> (String, u16) {
+        match &self.bastion {
+            Some(bastion) => match bastion.rsplit_once(':') {
+                Some((host, port)) if !host.contains(':') => match port.parse() {
+                    Ok(port) => (host.to_string(), port),
+                    Err(_) => (bastion.clone(), 22),
+                },
+                _ => (bastion.clone(), 22),
+            },
+            None => (self.host.clone(), self.port),
+        }
+    }
+
+    /// Remote command that attaches to the `auto_attach` tmux session,
+    /// creating it on first use
+    pub fn attach_command(&self) -> Option<String> {
+        self.auto_attach
+            .as_deref()
+            .map(|session| format!("tmux new -A -s {session}"))
+    }
+
+    /// `-o SetEnv=…` / `-o SendEnv=…` arguments for `env` and `send_env`.
+    /// Values are double-quoted so ssh keeps embedded spaces.
+    pub fn env_options(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        for (key, value) in &self.env {
+            args.push("-o".to_string());
+            args.push(format!("SetEnv={key}=\"{value}\""));
+        }
+        for pattern in &self.send_env {
+            args.push("-o".to_string());
+            args.push(format!("SendEnv={pattern}"));
+        }
+        args
+    }
+
+    /// `proxy_command` with its `%h`/`%p`/`%r` tokens filled in. ssh expands
+    /// the result again, so `%%` and other tokens are kept as they are and a
+    /// `%` in a filled-in value is doubled.
+    pub fn expanded_proxy_command(&self) -> Option<String> {
+        let template = self.proxy_command.as_deref()?;
+        let mut expanded = String::with_capacity(template.len());
+        let mut chars = template.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                expanded.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('h') => expanded.push_str(&self.host.replace('%', "%%")),
+                Some('p') => expanded.push_str(&self.port.to_string()),
+                Some('r') => expanded.push_str(&self.user.replace('%', "%%")),
+                Some(other) => {
+                    expanded.push('%');
+                    expanded.push(other);
+                }
+                None => expanded.push_str("%%"),
+            }
+        }
+        Some(expanded)
+    }
 
+    /// `-o ProxyCommand=…` arguments when `proxy_command` is set
+    pub fn proxy_options(&self) -> Vec<String> {
+        match self.expanded_proxy_command() {
+            Some(command) => vec!["-o".to_string(), format!("ProxyCommand={command}")],
+            None => Vec::new(),
+        }
+    }
+
+    /// The ssh command line `mode` would run, for display. Mirrors the
+    /// subprocess transport's argv builders.
+    pub fn to_ssh_command(&self, mode: SshCommandMode) -> String {
+        self.to_ssh_command_via(mode, None)
+    }
+
+    /// [`Self::to_ssh_command`] with `bastion_hop`, an ssh option such as a
+    /// shared-bastion `ProxyCommand=…`, in place of `-J`
+    pub fn to_ssh_command_via(&self, mode: SshCommandMode, bastion_hop: Option<&str>) -> String {
+        // Exec through an interactive bastion drives a shell session
+        let interactive_bastion = self.use_kerberos && self.bastion.is_some();
+        let interactive = mode == SshCommandMode::Interactive || interactive_bastion;
+
+        let mut cmd = String::from("ssh ");
+        if interactive {
+            cmd.push_str("-tt ");
+            if self.use_kerberos {
+                cmd.push_str("-A ");
+            }
+        }
         if self.use_kerberos {
-            cmd.push_str("ssh -t -A -K ");
-        } else {
-            cmd.push_str("ssh ");
+            cmd.push_str("-K ");
+        }
+
+        if let Some(config_file) = &self.ssh_config_file {
+            cmd.push_str(&format!("-F {} ", config_file.display()));
         }
 
         if let Some(key) = &self.key_path {
             cmd.push_str(&format!("-i {} ", key));
         }
 
-        if let Some(bastion) = &self.bastion {
-            let bastion_user = self.bastion_user.as_deref().unwrap_or(&self.user);
-            cmd.push_str(&format!("-p 22 {}@{}", bastion_user, bastion));
-            cmd.push_str(&format!(" {}@{}", self.user, self.host));
-        } else {
-            cmd.push_str(&format!("-p {} {}@{}", self.port, self.user, self.host));
+        for (key, value) in &self.env {
+            cmd.push_str(&format!("-o 'SetEnv={}=\"{}\"' ", key, value));
+        }
+        for pattern in &self.send_env {
+            cmd.push_str(&format!("-o 'SendEnv={}' ", pattern));
+        }
+        if let Some(command) = self.expanded_proxy_command() {
+            cmd.push_str(&format!("-o 'ProxyCommand={}' ", command));
+        }
+        if !interactive {
+            cmd.push_str("-o BatchMode=yes -o StrictHostKeyChecking=accept-new ");
+        }
+
+        let bastion_user = self.bastion_user.as_deref().unwrap_or(&self.user);
+        match &self.bastion {
+            Some(bastion) if interactive_bastion => {
+                cmd.push_str(&format!("-p 22 {}@{}", bastion_user, bastion));
+                cmd.push_str(&format!(" {}@{}", self.user, self.host));
+            }
+            Some(bastion) => {
+                match bastion_hop {
+                    Some(hop) => cmd.push_str(&format!("-o '{}' ", hop.replace('\'', "'\\''"))),
+                    None => cmd.push_str(&format!("-J {}@{} ", bastion_user, bastion)),
+                }
+                cmd.push_str(&format!("-p {} {}@{}", self.port, self.user, self.host));
+            }
+            None => cmd.push_str(&format!("-p {} {}@{}", self.port, self.user, self.host)),
+        }
+
+        match mode {
+            SshCommandMode::Exec => cmd.push_str(" <command>"),
+            SshCommandMode::Interactive => {
+                if let Some(attach) = self.attach_command() {
+                    cmd.push_str(&format!(" {attach}"));
+                }
+            }
         }
 
         cmd
     }
 }
 
+/// Which kind of session `Connection::to_ssh_command` renders
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SshCommandMode {
+    /// `bssh connect`: a login shell with a forced TTY
+    Interactive,
+    /// `bssh exec`: one non-interactive remote command
+    Exec,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConnectionStats {
     pub total_connections: usize,
     pub most_used: Option<Connection>,
     pub recently_used: Vec<Connection>,
     pub by_tag: std::collections::HashMap<String, usize>,
+    #[serde(default)]
+    pub total_sessions: usize,
+    /// Share of sessions that ended cleanly (exit code 0), from 0.0 to 1.0
+    #[serde(default)]
+    pub success_rate: f64,
+    /// Mean length of finished sessions, in seconds
+    #[serde(default)]
+    pub average_session_secs: Option<f64>,
+    /// Weekday (local time) with the most session starts
+    #[serde(default)]
+    pub busiest_weekday: Option<String>,
+    #[serde(default)]
+    pub kerberos_sessions: usize,
+    #[serde(default)]
+    pub direct_sessions: usize,
+    /// Sessions to hosts that were never saved, counted in neither of the above
+    #[serde(default)]
+    pub adhoc_sessions: usize,
+    /// Per-connection session counts over rolling windows, busiest first
+    #[serde(default)]
+    pub usage: Vec<ConnectionUsage>,
+}
+
+/// Sessions opened to one connection in the last 7 days, 30 days, and ever
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionUsage {
+    pub name: String,
+    pub week: usize,
+    pub month: usize,
+    pub all_time: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conn(bastion: Option<&str>, kerberos: bool) -> Connection {
+        Connection::new(
+            "web".into(),
+            "web.corp".into(),
+            "deploy".into(),
+            2222,
+            bastion.map(String::from),
+            None,
+            kerberos,
+            None,
+        )
+    }
+
+    #[test]
+    fn interactive_forces_a_tty_and_forwards_the_agent_only_for_kerberos() {
+        assert_eq!(
+            conn(None, false).to_ssh_command(SshCommandMode::Interactive),
+            "ssh -tt -p 2222 deploy@web.corp"
+        );
+        assert_eq!(
+            conn(None, true).to_ssh_command(SshCommandMode::Interactive),
+            "ssh -tt -A -K -p 2222 deploy@web.corp"
+        );
+        assert_eq!(
+            conn(Some("jump.corp"), false).to_ssh_command(SshCommandMode::Interactive),
+            "ssh -tt -J deploy@jump.corp -p 2222 deploy@web.corp"
+        );
+    }
+
+    #[test]
+    fn proxy_commands_stay_escaped_for_ssh() {
+        let mut c = conn(None, false);
+        c.proxy_command = Some("gw --to %h:%p --as %r --rate 100%% --id %n 5%".into());
+        assert_eq!(
+            c.expanded_proxy_command().unwrap(),
+            "gw --to web.corp:2222 --as deploy --rate 100%% --id %n 5%%"
+        );
+    }
+
+    #[test]
+    fn names_are_derived_from_the_host() {
+        assert_eq!(Connection::derive_name("web01.prod.example.com"), "web01");
+        assert_eq!(Connection::derive_name("gpu01"), "gpu01");
+        assert_eq!(Connection::derive_name("10.0.1.5"), "10-0-1-5");
+        assert_eq!(Connection::derive_name("[fe80::1]"), "fe80-1");
+    }
+
+    #[test]
+    fn probes_target_the_bastion_when_there_is_one() {
+        assert_eq!(
+            conn(None, false).probe_endpoint(),
+            ("web.corp".into(), 2222)
+        );
+        assert_eq!(
+            conn(Some("jump.corp"), false).probe_endpoint(),
+            ("jump.corp".into(), 22)
+        );
+        assert_eq!(
+            conn(Some("jump.corp:2200"), false).probe_endpoint(),
+            ("jump.corp".into(), 2200)
+        );
+    }
+
+    #[test]
+    fn auto_attach_runs_tmux_only_for_interactive_sessions() {
+        let mut c = conn(None, false);
+        c.auto_attach = Some("bssh".into());
+        assert_eq!(
+            c.to_ssh_command(SshCommandMode::Interactive),
+            "ssh -tt -p 2222 deploy@web.corp tmux new -A -s bssh"
+        );
+        assert!(!c.to_ssh_command(SshCommandMode::Exec).contains("tmux"));
+    }
+
+    #[test]
+    fn exec_is_batch_mode_without_tty_or_agent() {
+        assert_eq!(
+            conn(None, true).to_ssh_command(SshCommandMode::Exec),
+            "ssh -K -o BatchMode=yes -o StrictHostKeyChecking=accept-new -p 2222 deploy@web.corp <command>"
+        );
+        assert_eq!(
+            conn(Some("jump.corp"), false).to_ssh_command(SshCommandMode::Exec),
+            "ssh -o BatchMode=yes -o StrictHostKeyChecking=accept-new -J deploy@jump.corp -p 2222 deploy@web.corp <command>"
+        );
+    }
+
+    #[test]
+    fn exec_through_an_interactive_bastion_uses_the_shell_session() {
+        assert_eq!(
+            conn(Some("jump.corp"), true).to_ssh_command(SshCommandMode::Exec),
+            "ssh -tt -A -K -p 22 deploy@jump.corp deploy@web.corp <command>"
+        );
+    }
 }
@@ -0,0 +1,325 @@
+//! Search matching shared by the CLI and the TUI.
+//!
+//! Whether a query matches a connection, and how well, depends only on the
+//! connection itself. Usage statistics are layered on top by the database's
+//! ranking (see `Database::search_candidates`).
+
+use crate::models::Connection;
+
+impl Connection {
+    /// How well `query` matches this connection, case-insensitively: 1.0
+    /// for the exact name, down to 0.1 for the weakest kind of hit. `None`
+    /// when it does not match at all.
+    ///
+    /// Names and aliases match by substring or a fuzzy pattern (see
+    /// [`matches_name_pattern`]), hosts by substring, tags only whole.
+    pub fn matches(&self, query: &str) -> Option<f64> {
+        let query = query.to_lowercase();
+        let name = self.name.to_lowercase();
+        let aliases: Vec<String> = self.aliases.iter().map(|a| a.to_lowercase()).collect();
+        let host = self.host.to_lowercase();
+
+        let matched = name.contains(&query)
+            || matches_name_pattern(&query, &name)
+            || host.contains(&query)
+            || self.tags.iter().any(|t| t.to_lowercase() == query)
+            || aliases.iter().any(|a| a.contains(&query));
+        if !matched {
+            return None;
+        }
+
+        // Exact match - highest likelihood
+        if name == query {
+            return Some(1.0);
+        }
+
+        // Aliases are user-chosen shortcuts, so they rank right after the name
+        if aliases.contains(&query) {
+            return Some(0.95);
+        }
+
+        // Prefix match - very high
+        if name.starts_with(&query) {
+            return Some(0.9);
+        }
+
+        if aliases.iter().any(|a| a.starts_with(&query)) {
+            return Some(0.8);
+        }
+
+        // Word boundary match (e.g., "prod" matches "web-prod-server")
+        let words: Vec<&str> = name.split(&['-', '_', '.', ' '][..]).collect();
+        for word in &words {
+            if *word == query {
+                return Some(0.85);
+            }
+            if word.starts_with(&query) {
+                return Some(0.75);
+            }
+        }
+
+        // Contains match
+        if name.contains(&query) {
+            return Some(0.6);
+        }
+
+        if aliases.iter().any(|a| a.contains(&query)) {
+            return Some(0.55);
+        }
+
+        // Normalized match (ignoring separators)
+        let normalized_name = name.replace(&['-', '_', '.'][..], "");
+        let normalized_query = query.replace(&['-', '_', '.'][..], "");
+        if normalized_name.contains(&normalized_query) {
+            return Some(0.5);
+        }
+
+        // Host match
+        if host.contains(&query) {
+            return Some(0.4);
+        }
+
+        // Tag match
+        if self.tags.iter().any(|t| t.to_lowercase().contains(&query)) {
+            return Some(0.45);
+        }
+
+        // Acronym match
+        if query.len() >= 2 && words.len() > 1 {
+            let acronym: String = words.iter().filter_map(|w| w.chars().next()).collect();
+            if acronym.contains(&query) {
+                return Some(0.35);
+            }
+        }
+
+        // Fuzzy/pattern match (lowest but still valid)
+        if matches_name_pattern(&query, &name) {
+            return Some(0.2);
+        }
+
+        Some(0.1) // Minimal likelihood for any match
+    }
+}
+
+/// Char positions in `name` that `query` matched, case-insensitively, for
+/// highlighting: the substring [`Connection::matches`] found, or else the
+/// letters one of the name patterns used. Empty when the name itself did
+/// not match, e.g. because the host or a tag did.
+pub fn name_match_positions(name: &str, query: &str) -> Vec<usize> {
+    let chars = lowercase_chars(name);
+    let query = lowercase_chars(query);
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let all: Vec<(usize, char)> = chars.iter().copied().enumerate().collect();
+    find_run(&all, &query)
+        .or_else(|| name_pattern_positions(&chars, &query))
+        .unwrap_or_default()
+}
+
+/// Looser ways a lowercase `query` can match a lowercase `name` than a
+/// plain substring: all of its words, separators ignored, acronyms, and
+/// scattered letters (see [`subsequence_positions`]).
+pub(crate) fn matches_name_pattern(query: &str, name: &str) -> bool {
+    name_pattern_positions(&lowercase_chars(name), &lowercase_chars(query)).is_some()
+}
+
+/// The chars of `name` that [`matches_name_pattern`] matched `query` on,
+/// by position. Both are lowercase, one char per char of the original.
+fn name_pattern_positions(name: &[char], query: &[char]) -> Option<Vec<usize>> {
+    let all: Vec<(usize, char)> = name.iter().copied().enumerate().collect();
+
+    // 1. Word-based matching - split query into words and find them
+    let query_words: Vec<&[char]> = query
+        .split(|c| c.is_whitespace())
+        .filter(|w| !w.is_empty())
+        .collect();
+    if query_words.len() > 1 {
+        let found: Option<Vec<Vec<usize>>> =
+            query_words.iter().map(|w| find_run(&all, w)).collect();
+        if let Some(found) = found {
+            let mut positions: Vec<usize> = found.into_iter().flatten().collect();
+            positions.sort_unstable();
+            positions.dedup();
+            return Some(positions);
+        }
+    }
+
+    // 2. Handle common separators (hyphens, underscores, dots)
+    let is_separator = |c: &char| matches!(c, '-' | '_' | '.');
+    let kept: Vec<(usize, char)> = all
+        .iter()
+        .copied()
+        .filter(|(_, c)| !is_separator(c))
+        .collect();
+    let bare: Vec<char> = query.iter().copied().filter(|c| !is_separator(c)).collect();
+    if let Some(found) = find_run(&kept, &bare) {
+        return Some(found);
+    }
+
+    // 3. Acronym matching (first letters of words)
+    let word_separator = |c: char| matches!(c, '-' | '_' | ' ');
+    if query.len() >= 2 {
+        let initials: Vec<(usize, char)> = all
+            .iter()
+            .copied()
+            .filter(|&(i, c)| !word_separator(c) && (i == 0 || word_separator(name[i - 1])))
+            .collect();
+        if name.split(|c| word_separator(*c)).count() > 1 {
+            if let Some(found) = find_run(&initials, query) {
+                return Some(found);
+            }
+        }
+    }
+
+    // 4. Partial acronym matching
+    if query.len() >= 2 {
+        let alphanumeric: Vec<(usize, char)> = all
+            .iter()
+            .copied()
+            .filter(|(_, c)| c.is_alphanumeric())
+            .collect();
+        if alphanumeric.len() >= query.len()
+            && alphanumeric.iter().zip(query).all(|((_, c), q)| c == q)
+        {
+            return Some(
+                alphanumeric[..query.len()]
+                    .iter()
+                    .map(|(i, _)| *i)
+                    .collect(),
+            );
+        }
+    }
+
+    // 5. Scattered letters from the start of a word ("prdweb")
+    let name: String = name.iter().collect();
+    let query: String = query.iter().collect();
+    subsequence_positions(&name, &query)
+}
+
+/// `text` lowercased one char per char, so positions line up with it
+fn lowercase_chars(text: &str) -> Vec<char> {
+    text.chars()
+        .map(|c| c.to_lowercase().next().unwrap_or(c))
+        .collect()
+}
+
+/// Positions of the first run of `chars` that spells `query`
+fn find_run(chars: &[(usize, char)], query: &[char]) -> Option<Vec<usize>> {
+    if query.is_empty() {
+        return Some(Vec::new());
+    }
+    chars
+        .windows(query.len())
+        .find(|w| w.iter().map(|(_, c)| *c).eq(query.iter().copied()))
+        .map(|w| w.iter().map(|(i, _)| *i).collect())
+}
+
+/// Char positions in `name` of the letters of `query` in order, the first
+/// one at the start of a word, so "prdweb" finds "production-web". Separators
+/// in `query` are ignored. Queries under three letters never match: they
+/// would hit nearly every name.
+fn subsequence_positions(name: &str, query: &str) -> Option<Vec<usize>> {
+    let is_separator = |c: char| matches!(c, '-' | '_' | '.' | ' ');
+    // One char per char of `name`, so positions line up with it
+    let name: Vec<char> = name
+        .chars()
+        .map(|c| c.to_lowercase().next().unwrap_or(c))
+        .collect();
+    let query: Vec<char> = query
+        .chars()
+        .flat_map(char::to_lowercase)
+        .filter(|c| !is_separator(*c))
+        .collect();
+    if query.len() < 3 {
+        return None;
+    }
+
+    (0..name.len())
+        .filter(|&start| name[start] == query[0] && (start == 0 || is_separator(name[start - 1])))
+        .find_map(|start| {
+            let mut positions = vec![start];
+            let mut wanted = query[1..].iter().peekable();
+            for (i, c) in name.iter().enumerate().skip(start + 1) {
+                match wanted.peek() {
+                    Some(&&q) if q == *c => {
+                        positions.push(i);
+                        wanted.next();
+                    }
+                    Some(_) => {}
+                    None => break,
+                }
+            }
+            (positions.len() == query.len()).then_some(positions)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conn(name: &str, host: &str) -> Connection {
+        Connection::new(
+            name.into(),
+            host.into(),
+            "deploy".into(),
+            22,
+            None,
+            None,
+            false,
+            None,
+        )
+    }
+
+    #[test]
+    fn better_matches_score_higher() {
+        let mut web = conn("web-prod", "10.0.0.5");
+        web.aliases = vec!["shop".into()];
+        web.tags = vec!["frontend".into()];
+
+        let exact = web.matches("WEB-PROD").unwrap();
+        let alias = web.matches("shop").unwrap();
+        let prefix = web.matches("web").unwrap();
+        let word = web.matches("prod").unwrap();
+        let host = web.matches("10.0.0").unwrap();
+        assert!(exact > alias && alias > prefix && prefix > word && word > host);
+
+        assert!(web.matches("frontend").is_some(), "whole tags match");
+        assert!(web.matches("front").is_none(), "partial tags do not");
+        assert!(web.matches("db").is_none());
+    }
+
+    #[test]
+    fn name_match_positions_follow_the_matcher() {
+        // Plain substring, case-insensitive
+        assert_eq!(name_match_positions("Web-Prod", "prod"), vec![4, 5, 6, 7]);
+        // Ignoring separators
+        assert_eq!(name_match_positions("web-prod", "bpr"), vec![2, 4, 5]);
+        // Every word of the query
+        assert_eq!(
+            name_match_positions("db-prod-eu", "eu db"),
+            vec![0, 1, 8, 9]
+        );
+        // Initials
+        assert_eq!(
+            name_match_positions("web-prod-server", "wps"),
+            vec![0, 4, 9]
+        );
+        // Scattered letters
+        assert_eq!(
+            name_match_positions("production-web", "prdweb"),
+            vec![0, 1, 3, 11, 12, 13]
+        );
+        // Matched on something other than the name
+        assert!(name_match_positions("web-prod", "10.0.0.9").is_empty());
+    }
+
+    #[test]
+    fn fuzzy_patterns_match_the_name() {
+        let conn = conn("production-web", "10.0.0.5");
+        assert!(conn.matches("prdweb").is_some());
+        assert!(conn.matches("productionweb").is_some());
+        assert!(conn.matches("pw").is_some());
+        assert!(conn.matches("wbp").is_none());
+    }
+}
@@ -1,4 +1,5 @@
 pub mod connection;
+pub mod matching;
 pub mod session;
 
 pub use connection::*;
@@ -9,6 +9,15 @@ pub struct Session {
     pub connection: Connection,
     #[serde(default)]
     pub transport: Option<String>,
+    /// Alias the connection was resolved through, if any
+    #[serde(default)]
+    pub alias_used: Option<String>,
+    /// Free-form label for grouping sessions (e.g. an incident id)
+    #[serde(default)]
+    pub label: Option<String>,
+    /// File the terminal session was recorded to (`connect --log`)
+    #[serde(default)]
+    pub transcript: Option<String>,
     pub started_at: DateTime<Utc>,
     pub ended_at: Option<DateTime<Utc>>,
     pub status: SessionStatus,
@@ -31,6 +40,9 @@ impl Session {
             id: Uuid::new_v4(),
             connection,
             transport: None,
+            alias_used: None,
+            label: None,
+            transcript: None,
             started_at: Utc::now(),
             ended_at: None,
             status: SessionStatus::Starting,
@@ -93,4 +105,11 @@ pub struct SessionHistoryEntry {
     pub status: SessionStatus,
     pub exit_code: Option<i32>,
     pub duration: Option<chrono::Duration>,
+    #[serde(default)]
+    pub label: Option<String>,
+    /// The host was never a saved connection
+    #[serde(default)]
+    pub ad_hoc: bool,
+    #[serde(default)]
+    pub transcript: Option<String>,
 }
@@ -2,6 +2,7 @@
 //!
 //! Provides:
 //! - `candidate_key_paths()` — ordered list of key files to try
+//! - `check_key_file()` — catch missing or world-readable identity files
 //! - `AgentClient` wrapper for ssh-agent protocol (via `russh-keys`)
 
 #![allow(dead_code)]
@@ -66,10 +67,125 @@ pub fn candidate_key_paths(conn: &Connection, cfg: &AuthConfig) -> Vec<PathBuf>
     paths
 }
 
+/// Find a key for `conn` in `search_dirs` by matching its tags against
+/// sub-directory names (`<dir>/<tag>/`).
+///
+/// Within a matching directory, the standard key names win; otherwise the
+/// first private key file (by name, skipping `.pub` and ssh bookkeeping
+/// files) is used. Tags and directories are tried in order.
+pub fn discover_tagged_identity(conn: &Connection, search_dirs: &[PathBuf]) -> Option<PathBuf> {
+    for dir in search_dirs.iter().map(|d| expand_home(d)) {
+        for tag in &conn.tags {
+            if tag.is_empty() || tag.contains(['/', '\\']) || tag.starts_with('.') {
+                continue;
+            }
+            let project_dir = dir.join(tag);
+            if project_dir.is_dir() {
+                if let Some(key) = pick_key_in(&project_dir) {
+                    return Some(key);
+                }
+            }
+        }
+    }
+    None
+}
+
+fn pick_key_in(dir: &Path) -> Option<PathBuf> {
+    if let Some(key) = DEFAULT_KEY_NAMES
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|p| p.is_file())
+    {
+        return Some(key);
+    }
+
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| p.is_file())
+        .filter(|p| {
+            let name = p.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            !name.starts_with('.')
+                && !name.ends_with(".pub")
+                && !matches!(name, "known_hosts" | "config" | "authorized_keys")
+        })
+        .collect();
+    files.sort();
+    files.into_iter().next()
+}
+
+/// Public key for `ssh-copy-id` to install: `<key_path>.pub` when the
+/// connection has a key, otherwise the first standard `~/.ssh/id_*.pub`.
+///
+/// A connection with a key but no matching `.pub` gets `None` rather than
+/// some other key, which it would not log in with.
+pub fn default_public_key(key_path: Option<&Path>) -> Option<PathBuf> {
+    if let Some(key) = key_path {
+        let mut public = expand_home(key).into_os_string();
+        public.push(".pub");
+        let public = PathBuf::from(public);
+        return public.is_file().then_some(public);
+    }
+    let ssh_dir = ssh_dir()?;
+    DEFAULT_KEY_NAMES
+        .iter()
+        .map(|name| ssh_dir.join(format!("{name}.pub")))
+        .find(|p| p.is_file())
+}
+
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
 fn ssh_dir() -> Option<PathBuf> {
     dirs::home_dir().map(|h| h.join(".ssh"))
 }
 
+// ──────────────────────────────────────────────────────────────────────────────
+// Key file checks
+// ──────────────────────────────────────────────────────────────────────────────
+
+/// Why ssh would reject an identity file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyFileProblem {
+    Missing(PathBuf),
+    /// Group or others can access the key; ssh refuses to use it
+    TooOpen {
+        path: PathBuf,
+        mode: u32,
+    },
+}
+
+/// Check `path` (with `~` expanded) before handing it to ssh as `-i`
+pub fn check_key_file(path: &Path) -> Option<KeyFileProblem> {
+    let path = expand_home(path);
+    let metadata = match std::fs::metadata(&path) {
+        Ok(metadata) => metadata,
+        Err(_) => return Some(KeyFileProblem::Missing(path)),
+    };
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = metadata.permissions().mode() & 0o777;
+        if mode & 0o077 != 0 {
+            return Some(KeyFileProblem::TooOpen { path, mode });
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = metadata;
+    None
+}
+
+/// Make `path` readable and writable by its owner only (`chmod 600`)
+#[cfg(unix)]
+pub fn restrict_key_permissions(path: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+}
+
 // ──────────────────────────────────────────────────────────────────────────────
 // Key loading
 // ──────────────────────────────────────────────────────────────────────────────
@@ -168,6 +284,83 @@ mod tests {
         assert_eq!(count, 1);
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn key_file_check_flags_missing_and_open_keys() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempfile::tempdir().unwrap();
+        let key = dir.path().join("id_ed25519");
+
+        assert_eq!(
+            check_key_file(&key),
+            Some(KeyFileProblem::Missing(key.clone()))
+        );
+
+        std::fs::write(&key, "key").unwrap();
+        std::fs::set_permissions(&key, std::fs::Permissions::from_mode(0o644)).unwrap();
+        assert_eq!(
+            check_key_file(&key),
+            Some(KeyFileProblem::TooOpen {
+                path: key.clone(),
+                mode: 0o644
+            })
+        );
+
+        restrict_key_permissions(&key).unwrap();
+        assert_eq!(check_key_file(&key), None);
+    }
+
+    #[test]
+    fn public_key_follows_the_connection_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = dir.path().join("deploy");
+        std::fs::write(&key, "key").unwrap();
+        assert_eq!(default_public_key(Some(&key)), None);
+
+        std::fs::write(dir.path().join("deploy.pub"), "ssh-ed25519 AAAA").unwrap();
+        assert_eq!(
+            default_public_key(Some(&key)),
+            Some(dir.path().join("deploy.pub"))
+        );
+    }
+
+    #[test]
+    fn tagged_identity_prefers_standard_names() {
+        let root = tempfile::tempdir().unwrap();
+        let project = root.path().join("acme");
+        std::fs::create_dir(&project).unwrap();
+        for name in ["aaa_deploy", "aaa_deploy.pub", "id_ed25519"] {
+            std::fs::write(project.join(name), "key").unwrap();
+        }
+
+        let mut conn = minimal_conn();
+        conn.add_tag("other".into());
+        conn.add_tag("acme".into());
+        let found = discover_tagged_identity(&conn, &[root.path().to_path_buf()]);
+        assert_eq!(found, Some(project.join("id_ed25519")));
+    }
+
+    #[test]
+    fn tagged_identity_falls_back_to_first_private_key() {
+        let root = tempfile::tempdir().unwrap();
+        let project = root.path().join("acme");
+        std::fs::create_dir(&project).unwrap();
+        for name in ["b_key", "a_key.pub", "known_hosts"] {
+            std::fs::write(project.join(name), "key").unwrap();
+        }
+
+        let mut conn = minimal_conn();
+        conn.add_tag("acme".into());
+        let found = discover_tagged_identity(&conn, &[root.path().to_path_buf()]);
+        assert_eq!(found, Some(project.join("b_key")));
+
+        conn.tags.clear();
+        assert_eq!(
+            discover_tagged_identity(&conn, &[root.path().to_path_buf()]),
+            None
+        );
+    }
+
     #[test]
     fn agent_socket_from_env() {
         // Set env var and verify it is used when no config override
@@ -0,0 +1,79 @@
+//! Local environment files for the ssh process (`connect --env-file`).
+//!
+//! Unlike a connection's `env` (sent to the remote shell with `SetEnv`),
+//! these variables configure the local ssh invocation itself, e.g.
+//! `KRB5CCNAME` to pick a Kerberos credential cache.
+
+use anyhow::{anyhow, bail, Context, Result};
+use std::path::Path;
+
+/// Read `path` with [`parse_env_file`]
+pub fn load_env_file(path: &Path) -> Result<Vec<(String, String)>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read env file {}", path.display()))?;
+    parse_env_file(&content).map_err(|e| anyhow!("Invalid env file {}: {e}", path.display()))
+}
+
+/// Parse `KEY=VALUE` lines, in order. Blank lines and `#` comments are
+/// skipped, a leading `export ` is allowed, and one pair of matching quotes
+/// around the value is removed. Nothing is expanded.
+pub fn parse_env_file(content: &str) -> Result<Vec<(String, String)>> {
+    let mut vars = Vec::new();
+    for (number, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line).trim_start();
+        let Some((key, value)) = line.split_once('=') else {
+            bail!("line {}: expected KEY=VALUE", number + 1);
+        };
+        let key = key.trim();
+        let valid_key = key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid_key {
+            bail!("line {}: '{}' is not a variable name", number + 1, key);
+        }
+        let value = value.trim();
+        let value = ['"', '\'']
+            .iter()
+            .find_map(|&quote| value.strip_prefix(quote)?.strip_suffix(quote))
+            .unwrap_or(value);
+        vars.push((key.to_string(), value.to_string()));
+    }
+    Ok(vars)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn env_files_are_parsed_like_a_shell_would_source_them() {
+        let vars = parse_env_file(
+            "# Kerberos cache for the EU realm\n\
+             export KRB5CCNAME=FILE:/tmp/krb5cc_eu\n\
+             \n\
+             HTTPS_PROXY=\"http://proxy.corp:3128\"\n\
+             NO_PROXY='.corp, localhost'\n\
+             EMPTY=\n",
+        )
+        .unwrap();
+        assert_eq!(
+            vars,
+            [
+                ("KRB5CCNAME".into(), "FILE:/tmp/krb5cc_eu".into()),
+                ("HTTPS_PROXY".into(), "http://proxy.corp:3128".into()),
+                ("NO_PROXY".into(), ".corp, localhost".into()),
+                ("EMPTY".into(), String::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn malformed_lines_are_reported() {
+        let err = parse_env_file("A=1\nnot a pair\n").unwrap_err();
+        assert!(err.to_string().contains("line 2"), "{err}");
+        assert!(parse_env_file("1ABC=x").is_err());
+    }
+}
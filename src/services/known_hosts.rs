@@ -28,6 +28,8 @@ pub enum KnownHostsError {
     },
     #[error("base64 decode error: {0}")]
     Base64(#[from] base64::DecodeError),
+    #[error("ssh-keyscan found no host keys for {0}")]
+    NoKeys(String),
 }
 
 /// Result of checking a host key against known_hosts.
@@ -249,6 +251,74 @@ pub fn append(
     Ok(())
 }
 
+/// Host names recovered from a known_hosts file by `discover_hosts`.
+#[derive(Debug, Default, PartialEq)]
+pub struct Discovery {
+    /// `(hostname, port)` pairs in file order, without duplicates
+    pub hosts: Vec<(String, u16)>,
+    /// Entries whose only names are hashed (`HashKnownHosts yes`) and
+    /// therefore cannot be turned back into a hostname
+    pub hashed: usize,
+}
+
+/// Collect the plain-text host names stored in `path`.
+///
+/// Each entry contributes its first literal name (usually the hostname,
+/// ahead of the IP address OpenSSH records next to it). Wildcard patterns,
+/// `@revoked` lines and `@cert-authority` lines are skipped. A missing file
+/// yields an empty result.
+pub fn discover_hosts(path: &Path) -> Result<Discovery, KnownHostsError> {
+    let file = match fs::File::open(path) {
+        Ok(f) => f,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Discovery::default()),
+        Err(e) => return Err(KnownHostsError::Io(e)),
+    };
+
+    let mut discovery = Discovery::default();
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        let Some(entry) = parse_entry(&line) else {
+            continue;
+        };
+        if !entry.marker.is_empty() {
+            continue;
+        }
+
+        let first_plain = entry.patterns.iter().find_map(|p| match p {
+            HostPattern::Plain(name) if !name.contains(['*', '?']) => Some(name),
+            _ => None,
+        });
+        let Some(name) = first_plain else {
+            if entry
+                .patterns
+                .iter()
+                .any(|p| matches!(p, HostPattern::Hashed { .. }))
+            {
+                discovery.hashed += 1;
+            }
+            continue;
+        };
+
+        let host = split_hostport(name);
+        if !discovery.hosts.contains(&host) {
+            discovery.hosts.push(host);
+        }
+    }
+    Ok(discovery)
+}
+
+/// Inverse of `canonical_hostport`: `[host]:port` or a bare host on port 22
+fn split_hostport(name: &str) -> (String, u16) {
+    if let Some(rest) = name.strip_prefix('[') {
+        if let Some((host, port)) = rest.split_once("]:") {
+            if let Ok(port) = port.parse() {
+                return (host.to_string(), port);
+            }
+        }
+    }
+    (name.to_string(), 22)
+}
+
 /// SHA-256 fingerprint in the `SHA256:<base64>` format shown by OpenSSH.
 pub fn fingerprint_sha256(key_bytes: &[u8]) -> String {
     let digest = Sha256::digest(key_bytes);
@@ -259,6 +329,53 @@ pub fn fingerprint_sha256(key_bytes: &[u8]) -> String {
 
 // ──────────────────────────────────────────────────────────────────────────────
 // Tests
+/// Fetch the host's ed25519 and RSA keys with `ssh-keyscan` and return
+/// `key_type SHA256:…` lines, sorted by key type.
+///
+/// This contacts `hostname` directly, not through a bastion.
+pub async fn keyscan_fingerprints(
+    hostname: &str,
+    port: u16,
+) -> Result<Vec<String>, KnownHostsError> {
+    let output = tokio::process::Command::new("ssh-keyscan")
+        .args([
+            "-T",
+            "5",
+            "-t",
+            "ed25519,rsa",
+            "-p",
+            &port.to_string(),
+            hostname,
+        ])
+        .stdin(std::process::Stdio::null())
+        .output()
+        .await?;
+
+    let fingerprints = parse_keyscan(&String::from_utf8_lossy(&output.stdout));
+    if fingerprints.is_empty() {
+        return Err(KnownHostsError::NoKeys(canonical_hostport(hostname, port)));
+    }
+    Ok(fingerprints)
+}
+
+/// Turn `ssh-keyscan` output (`host key-type base64-key` lines) into
+/// sorted `key_type SHA256:…` fingerprints, skipping comments and junk.
+fn parse_keyscan(output: &str) -> Vec<String> {
+    let mut fingerprints: Vec<String> = output
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let (_host, key_type, key_b64) = (fields.next()?, fields.next()?, fields.next()?);
+            let key_bytes = B64.decode(key_b64).ok()?;
+            Some(format!("{} {}", key_type, fingerprint_sha256(&key_bytes)))
+        })
+        .collect();
+    fingerprints.sort();
+    fingerprints.dedup();
+    fingerprints
+}
+
 // ──────────────────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -270,6 +387,31 @@ mod tests {
         vec![seed; 32]
     }
 
+    #[test]
+    fn discover_reads_plain_names_and_counts_hashed_ones() {
+        let mut f = NamedTempFile::new().unwrap();
+        let key = B64.encode(dummy_key(0x07));
+        writeln!(f, "web.corp,10.0.0.5 ssh-ed25519 {key}").unwrap();
+        writeln!(f, "[db.corp]:2222 ssh-ed25519 {key}").unwrap();
+        writeln!(f, "web.corp ssh-rsa {key}").unwrap();
+        writeln!(f, "*.lab ssh-ed25519 {key}").unwrap();
+        writeln!(f, "@cert-authority *.corp ssh-ed25519 {key}").unwrap();
+        writeln!(
+            f,
+            "|1|{}|{} ssh-ed25519 {key}",
+            B64.encode([1u8; 20]),
+            B64.encode([2u8; 20])
+        )
+        .unwrap();
+
+        let discovery = discover_hosts(f.path()).unwrap();
+        assert_eq!(
+            discovery.hosts,
+            vec![("web.corp".to_string(), 22), ("db.corp".to_string(), 2222)]
+        );
+        assert_eq!(discovery.hashed, 1);
+    }
+
     #[test]
     fn append_and_find_plain_entry() {
         let f = NamedTempFile::new().unwrap();
@@ -316,6 +458,26 @@ mod tests {
         assert!(!glob_match("*.example.com", "example.com"));
     }
 
+    #[test]
+    fn keyscan_output_becomes_sorted_fingerprints() {
+        let ed = B64.encode(dummy_key(0x01));
+        let rsa = B64.encode(dummy_key(0x02));
+        let output = format!(
+            "# host.example:22 SSH-2.0-OpenSSH_9.6\n\
+             host.example ssh-rsa {rsa}\n\
+             host.example ssh-ed25519 {ed}\n\
+             garbage\n"
+        );
+        let fps = parse_keyscan(&output);
+        assert_eq!(
+            fps,
+            vec![
+                format!("ssh-ed25519 {}", fingerprint_sha256(&dummy_key(0x01))),
+                format!("ssh-rsa {}", fingerprint_sha256(&dummy_key(0x02))),
+            ]
+        );
+    }
+
     #[test]
     fn fingerprint_format() {
         let key = dummy_key(0xFF);
@@ -1,6 +1,8 @@
 pub mod auth;
+pub mod env_file;
 pub mod known_hosts;
 pub mod ping;
+pub mod secrets;
 pub mod ssh;
 pub mod transfer;
 pub mod transport;
@@ -4,30 +4,70 @@
 //! a host:port is reachable.  This avoids spawning an external `ssh`
 //! process and is suitable for background checks in the TUI.
 
+use std::fmt;
+use std::io::ErrorKind;
 use std::time::{Duration, Instant};
 use tokio::net::TcpStream;
 use tokio::time::timeout;
 
 /// Result of a TCP ping attempt.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum PingResult {
     /// Host is reachable; includes the round-trip time.
     Reachable(Duration),
-    /// Host did not respond within the timeout or the connection was refused.
-    Unreachable,
+    /// The host answered but nothing listens on the port.
+    Refused,
+    /// No answer within the timeout (filtered port, host down).
+    TimedOut,
+    /// The connection could not even be attempted (DNS failure, no route).
+    Unreachable(String),
+}
+
+impl PingResult {
+    pub fn is_reachable(&self) -> bool {
+        matches!(self, PingResult::Reachable(_))
+    }
+}
+
+impl fmt::Display for PingResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PingResult::Reachable(rtt) => write!(f, "reachable ({:.0?})", rtt),
+            PingResult::Refused => write!(f, "connection refused"),
+            PingResult::TimedOut => write!(f, "timed out"),
+            PingResult::Unreachable(reason) => write!(f, "unreachable: {}", reason),
+        }
+    }
 }
 
 /// Attempt a TCP connection to `host:port` with the given `timeout_secs`.
 ///
 /// For connections that go through a bastion host, callers should ping the
-/// bastion (`bastion_host:22`) rather than the final target since the
-/// target is typically not directly reachable.
+/// bastion rather than the final target since the target is typically not
+/// directly reachable; `Connection::probe_endpoint` picks the right one.
 pub async fn tcp_ping(host: &str, port: u16, timeout_secs: u64) -> PingResult {
     let addr = format!("{}:{}", host, port);
     let start = Instant::now();
 
     match timeout(Duration::from_secs(timeout_secs), TcpStream::connect(&addr)).await {
         Ok(Ok(_stream)) => PingResult::Reachable(start.elapsed()),
-        _ => PingResult::Unreachable,
+        Ok(Err(e)) if e.kind() == ErrorKind::ConnectionRefused => PingResult::Refused,
+        Ok(Err(e)) => PingResult::Unreachable(e.to_string()),
+        Err(_) => PingResult::TimedOut,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn listening_ports_are_reachable_and_closed_ones_refused() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        assert!(tcp_ping("127.0.0.1", port, 2).await.is_reachable());
+
+        drop(listener);
+        assert_eq!(tcp_ping("127.0.0.1", port, 2).await, PingResult::Refused);
     }
 }
@@ -0,0 +1,52 @@
+//! Passwords for hosts that only accept password logins.
+//!
+//! The secret lives in the OS keyring under the `bayesian-ssh` service; a
+//! connection only stores the entry name (`password_ref`). At connect time
+//! the password is handed to `sshpass -e` through its environment.
+
+use anyhow::Result;
+
+/// Keyring service every entry is stored under
+#[cfg(feature = "password_auth")]
+const SERVICE: &str = "bayesian-ssh";
+
+/// Printed whenever a password is stored
+#[cfg(feature = "password_auth")]
+pub const PASSWORD_WARNING: &str =
+    "⚠️  Password logins are less secure than SSH keys; prefer 'bssh copy-id' where the host allows it.";
+
+#[cfg(feature = "password_auth")]
+pub fn get_password(reference: &str) -> Result<String> {
+    match keyring::Entry::new(SERVICE, reference)?.get_password() {
+        Ok(password) => Ok(password),
+        Err(keyring::Error::NoEntry) => anyhow::bail!(
+            "No password in the keyring for '{}'; store one with 'bssh password set'",
+            reference
+        ),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(feature = "password_auth")]
+pub fn set_password(reference: &str, password: &str) -> Result<()> {
+    keyring::Entry::new(SERVICE, reference)?.set_password(password)?;
+    Ok(())
+}
+
+/// Remove the entry; false when there was none
+#[cfg(feature = "password_auth")]
+pub fn delete_password(reference: &str) -> Result<bool> {
+    match keyring::Entry::new(SERVICE, reference)?.delete_credential() {
+        Ok(()) => Ok(true),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(not(feature = "password_auth"))]
+pub fn get_password(reference: &str) -> Result<String> {
+    anyhow::bail!(
+        "The password for '{}' is kept in the keyring, but this bssh was built without the password_auth feature",
+        reference
+    )
+}
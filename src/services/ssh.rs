@@ -1,138 +1,379 @@
 use crate::config::AppConfig;
 use crate::database::Database;
+use crate::errors::AppError;
 use crate::models::{Connection, Session};
 use anyhow::Result;
 use std::process::Stdio;
 use tokio::process::Command as TokioCommand;
 use tracing::{error, info, warn};
 
+/// How long `connect --check-port` waits for the TCP handshake
+const PORT_CHECK_TIMEOUT_SECS: u64 = 5;
+
+/// How [`SshService::resolve_target`] matched a user-typed target
+#[derive(Debug, Clone)]
+pub enum TargetResolution {
+    /// Exact connection name or id
+    Name(Connection),
+    /// One of the connection's aliases (the alias is kept for session attribution)
+    Alias(Connection, String),
+    /// No exact hit: fuzzy search candidates, best first (empty when nothing matched)
+    Fuzzy(Vec<Connection>),
+}
+
 pub struct SshService {
     config: AppConfig,
     database: Database,
+    /// Cleared by `connect --no-record`: sessions leave no trace at all.
+    record: bool,
+    /// Set by `connect --log`: where interactive sessions are recorded.
+    transcript: Option<std::path::PathBuf>,
+    /// `KEY=VALUE` file whose variables are set for the local ssh process
+    env_file: Option<std::path::PathBuf>,
 }
 
 impl SshService {
     pub fn new(config: AppConfig) -> Result<Self> {
         let database = Database::new(&config)?;
-        Ok(SshService { config, database })
+        Ok(SshService {
+            env_file: config.env_file.clone(),
+            config,
+            database,
+            record: true,
+            transcript: None,
+        })
     }
 
-    #[allow(clippy::too_many_arguments)]
-    pub async fn connect(
+    /// Stop sessions run through this service from writing anything: no
+    /// session rows, no usage bump, no remembered error.
+    pub fn without_recording(mut self) -> Self {
+        self.record = false;
+        self
+    }
+
+    /// Record interactive sessions to `path` with `script(1)`; this needs
+    /// the ssh binary, so such sessions use the subprocess transport.
+    pub fn with_transcript(mut self, path: std::path::PathBuf) -> Self {
+        self.transcript = Some(path);
+        self
+    }
+
+    /// Load the local environment of ssh from `path` instead of the
+    /// configured `env_file`. The variables reach the ssh binary (and
+    /// `kinit`), so such sessions use the subprocess transport.
+    pub fn with_env_file(mut self, path: std::path::PathBuf) -> Self {
+        self.env_file = Some(path);
+        self
+    }
+
+    /// Variables from the env file, if one is set
+    fn local_env(&self) -> Result<Vec<(String, String)>> {
+        match &self.env_file {
+            Some(path) => crate::services::env_file::load_env_file(path),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// The subprocess transport, recording to the transcript if one is set
+    /// and running ssh with `env` added to its environment
+    fn subprocess_transport(
         &self,
-        target: &str,
-        user: Option<String>,
-        port: Option<u16>,
-        kerberos: Option<bool>,
-        bastion: Option<String>,
-        no_bastion: bool,
-        bastion_user: Option<String>,
-        key: Option<String>,
-    ) -> Result<()> {
-        // Try to find connection in database first (also checks aliases)
-        if let Some(mut connection) = self.database.get_connection_or_alias(target)? {
-            info!("Found existing connection: {}", connection.name);
+        env: Vec<(String, String)>,
+    ) -> crate::services::transport::SubprocessTransport {
+        let transport =
+            crate::services::transport::SubprocessTransport::new(self.config.clone()).with_env(env);
+        match &self.transcript {
+            Some(path) => transport.with_transcript(path.clone()),
+            None => transport,
+        }
+    }
 
-            // Override with command line arguments if provided
-            if let Some(user) = user {
-                connection.user = user;
-            }
-            if let Some(port) = port {
-                connection.port = port;
-            }
-            if let Some(kerberos) = kerberos {
-                connection.use_kerberos = kerberos;
-            }
-            if let Some(bastion) = bastion {
-                connection.bastion = Some(bastion);
-            }
-            if no_bastion {
-                connection.bastion = None;
-                connection.bastion_user = None;
-            }
-            if let Some(bastion_user) = bastion_user {
-                connection.bastion_user = Some(bastion_user);
-            }
-            if let Some(key) = key {
-                connection.key_path = Some(key);
-            }
+    /// Whether sessions get a history row; `auto_save_history` only turns
+    /// these off, usage is still counted.
+    fn records_sessions(&self) -> bool {
+        self.record && self.config.auto_save_history
+    }
 
-            // Update last used timestamp
-            connection.update_last_used();
-            self.database.update_connection(&connection)?;
+    /// Run the session and, only once it was actually established, bump
+    /// `last_used` and the recent-targets ring so failed attempts do not
+    /// count as usage.
+    ///
+    /// `connection` may carry one-off command-line overrides, so only
+    /// `last_used` is written back; the saved record is never rewritten.
+    async fn execute_and_record(
+        &self,
+        connection: Connection,
+        alias_used: Option<String>,
+        tunnels: Vec<String>,
+        label: Option<String>,
+    ) -> Result<bool> {
+        let established = self
+            .execute_ssh(&connection, alias_used, &tunnels, label)
+            .await?;
+        if established && self.record {
+            let id = connection.id.to_string();
+            // Ad-hoc targets that were never saved have nothing to update.
+            if self.database.touch_last_used(&id)? {
+                self.database.record_recent_target(&id)?;
+            }
+        }
+        Ok(established)
+    }
 
-            return self.execute_ssh(&connection).await;
+    /// Save a connection that was just used ad hoc, taking over the
+    /// sessions recorded under its name.
+    pub fn save_adhoc_connection(&self, connection: &Connection) -> Result<()> {
+        self.database.add_connection(connection)?;
+        self.database.adopt_sessions(connection)?;
+        if self.record {
+            let id = connection.id.to_string();
+            self.database.touch_last_used(&id)?;
+            self.database.record_recent_target(&id)?;
         }
+        Ok(())
+    }
 
-        // If not found, try to connect directly
-        info!(
-            "Connection '{}' not found, attempting direct connection",
-            target
-        );
+    /// Insert a session row and mark it active, unless history is off.
+    fn start_session(&self, session: &mut Session) -> Result<()> {
+        if !self.records_sessions() {
+            return Ok(());
+        }
+        self.database.add_session(session)?;
+        session.mark_active(std::process::id());
+        self.database.update_session(session)?;
+        Ok(())
+    }
 
-        let connection = Connection::new(
-            target.to_string(),
-            target.to_string(),
-            user.unwrap_or_else(|| self.config.default_user.clone()),
-            port.unwrap_or(self.config.default_port),
-            bastion,      // Only use bastion if explicitly specified
-            bastion_user, // Only use bastion_user if explicitly specified
-            kerberos.unwrap_or(self.config.use_kerberos_by_default),
-            key,
-        );
+    /// Write back a session started with [`Self::start_session`].
+    fn store_session(&self, session: &Session) -> Result<()> {
+        if self.records_sessions() {
+            self.database.update_session(session)?;
+        }
+        Ok(())
+    }
 
-        self.execute_ssh(&connection).await
+    /// Remember (or clear) why the last attempt failed, unless not recording.
+    fn store_last_error(&self, id: &str, error: Option<&str>) -> Result<()> {
+        if self.record {
+            self.database.set_last_error(id, error)?;
+        }
+        Ok(())
     }
 
-    async fn execute_ssh(&self, connection: &Connection) -> Result<()> {
+    /// Returns whether the session was established. The exit code of an
+    /// established session is the remote shell's; ssh itself reports
+    /// connection and authentication failures as 255.
+    ///
+    /// `tunnels` are extra `-L`/`-R`/`-D` arguments for this session only;
+    /// they need the ssh binary, so they always use the subprocess transport.
+    async fn execute_ssh(
+        &self,
+        connection: &Connection,
+        alias_used: Option<String>,
+        tunnels: &[String],
+        label: Option<String>,
+    ) -> Result<bool> {
         info!("Executing SSH connection to {}", connection.host);
 
+        // A key discovered from the connection's tags applies to this session only.
+        let discovered;
+        let connection = match self.tagged_identity(connection) {
+            Some(key) => {
+                info!("Using identity {} (matched by tag)", key.display());
+                let mut with_key = connection.clone();
+                with_key.key_path = Some(key.to_string_lossy().into_owned());
+                discovered = with_key;
+                &discovered
+            }
+            None => connection,
+        };
+
+        if let Some(key) = &connection.key_path {
+            self.check_identity(connection, key)?;
+        }
+
+        // Loaded before kinit so it sees the same credential cache as ssh
+        let local_env = self.local_env()?;
+
         // Check and create Kerberos ticket if needed
         if connection.use_kerberos {
-            self.ensure_kerberos_ticket().await?;
+            self.ensure_kerberos_ticket(&local_env).await?;
         }
 
         // Choose transport based on connection properties.
-        let kind = crate::services::transport::pick_kind(connection, &self.config);
+        let kind = if tunnels.is_empty() && self.transcript.is_none() && local_env.is_empty() {
+            crate::services::transport::pick_kind(connection, &self.config)
+        } else {
+            crate::services::transport::TransportKind::Subprocess
+        };
         info!("Using transport: {:?}", kind);
 
         // Create session record before running.
         let mut session = Session::new(connection.clone());
         session.transport = Some(format!("{kind:?}").to_lowercase());
-        self.database.add_session(&session)?;
-        session.mark_active(std::process::id());
-        self.database.update_session(&session)?;
+        session.alias_used = alias_used;
+        session.label = label;
+        session.transcript = self
+            .transcript
+            .as_ref()
+            .map(|path| path.to_string_lossy().into_owned());
+        self.start_session(&mut session)?;
 
+        // The subprocess transport tees ssh's stderr so a failure can be
+        // remembered; the native one only reports an exit code or error.
         let conn = connection.clone();
-        let result = crate::services::transport::execute_with_fallback(
-            connection,
-            &self.config,
-            |transport| {
-                let conn_clone = conn.clone();
-                Box::pin(async move { transport.run_interactive(&conn_clone).await })
-            },
-        )
-        .await;
+        let result = if kind == crate::services::transport::TransportKind::Subprocess {
+            self.run_with_retries(connection, tunnels, local_env)
+                .await
+                .map(|(code, stderr)| (code, Some(stderr)))
+        } else {
+            crate::services::transport::execute_with_fallback(
+                connection,
+                &self.config,
+                |transport| {
+                    let conn_clone = conn.clone();
+                    Box::pin(async move { transport.run_interactive(&conn_clone).await })
+                },
+            )
+            .await
+            .map(|code| (code, None))
+        };
 
-        match result {
-            Ok(0) => {
+        let id = connection.id.to_string();
+        let established = match result {
+            Ok((0, _)) => {
                 info!("SSH session completed successfully");
                 session.mark_terminated(0);
+                true
             }
-            Ok(code) => {
+            Ok((code, stderr)) => {
                 warn!("SSH session exited with code {code}");
                 session.mark_terminated(code);
+                if code == 255 {
+                    let error = stderr
+                        .as_deref()
+                        .and_then(error_snippet)
+                        .unwrap_or_else(|| format!("ssh exited with code {code}"));
+                    self.store_last_error(&id, Some(&error))?;
+                }
+                code != 255
             }
             Err(e) => {
                 error!("SSH transport error: {e}");
                 session.mark_error(format!("{e}"));
-                self.database.update_session(&session)?;
+                self.store_session(&session)?;
+                self.store_last_error(&id, Some(&e.to_string()))?;
                 return Err(anyhow::anyhow!("{}", e));
             }
+        };
+
+        if established {
+            self.store_last_error(&id, None)?;
         }
+        self.store_session(&session)?;
+        Ok(established)
+    }
 
-        self.database.update_session(&session)?;
-        Ok(())
+    /// Interactive subprocess session, re-run with exponential backoff while
+    /// ssh exits 255 for a reason other than rejected credentials. Returns
+    /// the last attempt's exit code and stderr tail.
+    async fn run_with_retries(
+        &self,
+        connection: &Connection,
+        tunnels: &[String],
+        env: Vec<(String, String)>,
+    ) -> Result<(i32, String), crate::services::transport::TransportError> {
+        use crate::services::transport::subprocess_impl::is_auth_failure;
+
+        let transport = self.subprocess_transport(env);
+        let retries = self.config.connect_retries;
+        let mut attempt = 0;
+        loop {
+            let (code, stderr) = transport
+                .run_interactive_capture(connection, tunnels)
+                .await?;
+            if code != 255 || attempt >= retries {
+                return Ok((code, stderr));
+            }
+            if is_auth_failure(&stderr) {
+                info!("Authentication was rejected; not retrying");
+                return Ok((code, stderr));
+            }
+
+            attempt += 1;
+            let delay = std::time::Duration::from_secs((1u64 << (attempt - 1).min(5)).min(30));
+            warn!(
+                "Connection to {} failed (exit 255), retry {}/{} in {}s",
+                connection.host,
+                attempt,
+                retries,
+                delay.as_secs()
+            );
+            eprintln!(
+                "🔁 Connection failed, retrying in {}s ({}/{})",
+                delay.as_secs(),
+                attempt,
+                retries
+            );
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Key from `identity_search_dirs` for a connection without its own `key_path`
+    fn tagged_identity(&self, connection: &Connection) -> Option<std::path::PathBuf> {
+        if connection.key_path.is_some() || self.config.identity_search_dirs.is_empty() {
+            return None;
+        }
+        crate::services::auth::discover_tagged_identity(
+            connection,
+            &self.config.identity_search_dirs,
+        )
+    }
+
+    /// Quick TCP check of the port a session would connect to first (the
+    /// bastion, if any). Always direct: ProxyCommand routes are not used.
+    pub async fn probe_port(
+        &self,
+        connection: &Connection,
+        timeout_secs: u64,
+    ) -> crate::services::ping::PingResult {
+        let (host, port) = connection.probe_endpoint();
+        crate::services::ping::tcp_ping(&host, port, timeout_secs).await
+    }
+
+    /// Stop before ssh fails opaquely on a missing key, and offer to fix a
+    /// key that ssh would refuse as unprotected
+    fn check_identity(&self, connection: &Connection, key: &str) -> Result<()> {
+        use crate::services::auth::{check_key_file, KeyFileProblem};
+
+        match check_key_file(std::path::Path::new(key)) {
+            None => Ok(()),
+            Some(KeyFileProblem::Missing(path)) => anyhow::bail!(
+                "SSH key {} for '{}' does not exist. Point the connection at another key with \
+                 `bssh edit {} --key FILE`, or drop it with `--clear-key`",
+                path.display(),
+                connection.name,
+                connection.name
+            ),
+            Some(KeyFileProblem::TooOpen { path, mode }) => {
+                warn!("SSH key {} has mode {:o}", path.display(), mode);
+                eprintln!(
+                    "⚠️  SSH key {} has permissions {:04o}; ssh refuses private keys other users can read.",
+                    path.display(),
+                    mode
+                );
+                #[cfg(unix)]
+                {
+                    // Without a terminal there is nobody to ask; ssh will report the problem.
+                    let prompt = format!("Restrict {} to 0600?", path.display());
+                    if crate::cli::utils::confirm(&prompt, true).unwrap_or(false) {
+                        crate::services::auth::restrict_key_permissions(&path)?;
+                        eprintln!("🔒 {} is now 0600", path.display());
+                    }
+                }
+                Ok(())
+            }
+        }
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -148,15 +389,22 @@ impl SshService {
         bastion_user: Option<String>,
         key: Option<String>,
         tags: Vec<String>,
+        expires_at: Option<chrono::DateTime<chrono::Utc>>,
+        ssh_config_file: Option<std::path::PathBuf>,
+        env: Vec<(String, String)>,
+        send_env: Vec<String>,
+        proxy_command: Option<String>,
+        auto_attach: Option<String>,
     ) -> Result<()> {
-        // Determine bastion configuration
-        let final_bastion = if no_bastion {
+        // Determine bastion configuration; a ProxyCommand replaces the bastion
+        let direct = no_bastion || proxy_command.is_some();
+        let final_bastion = if direct {
             None // Force no bastion
         } else {
             bastion.or_else(|| self.config.default_bastion.clone())
         };
 
-        let final_bastion_user = if no_bastion {
+        let final_bastion_user = if direct {
             None // Force no bastion user
         } else {
             bastion_user.or_else(|| self.config.default_bastion_user.clone())
@@ -187,6 +435,12 @@ impl SshService {
         for tag in tags {
             conn.add_tag(tag);
         }
+        conn.expires_at = expires_at;
+        conn.ssh_config_file = ssh_config_file;
+        conn.env = env;
+        conn.send_env = send_env;
+        conn.proxy_command = proxy_command;
+        conn.auto_attach = auto_attach;
 
         self.database.add_connection(&conn)?;
         info!("Connection '{}' added successfully", name);
@@ -198,18 +452,97 @@ impl SshService {
         &self,
         tag_filter: Option<&str>,
         recent_only: bool,
+        include_archived: bool,
     ) -> Result<Vec<Connection>> {
-        self.database.list_connections(tag_filter, recent_only)
+        self.database
+            .list_connections(tag_filter, recent_only, include_archived)
+    }
+
+    pub async fn list_expired_connections(&self) -> Result<Vec<Connection>> {
+        self.database.list_expired_connections()
+    }
+
+    pub async fn set_host_key_fingerprint(
+        &self,
+        connection: &Connection,
+        fingerprint: &str,
+    ) -> Result<()> {
+        self.database
+            .set_host_key_fingerprint(&connection.id.to_string(), fingerprint)
+    }
+
+    pub async fn set_notes(&self, connection: &Connection, notes: Option<&str>) -> Result<()> {
+        self.database.set_notes(&connection.id.to_string(), notes)
+    }
+
+    pub async fn set_archived(&self, connection: &Connection, archived: bool) -> Result<()> {
+        self.database
+            .set_archived(&connection.id.to_string(), archived)
+    }
+
+    pub async fn move_tag(&self, from: &str, to: &str) -> Result<usize> {
+        self.database.move_tag(from, to)
     }
 
     pub async fn remove_connection(&self, target: &str) -> Result<bool> {
-        self.database.remove_connection(target)
+        self.database
+            .remove_connection(target, self.config.retain_sessions_on_delete)
     }
 
+    /// Resolve what a user typed: exact name or id, then alias, then fuzzy
+    /// search with the configured `search_mode`.
+    pub async fn resolve_target(&self, target: &str) -> Result<TargetResolution> {
+        if let Some(conn) = self.database.get_connection(target)? {
+            return Ok(TargetResolution::Name(conn));
+        }
+        if let Some(conn) = self.database.get_connection_by_alias(target)? {
+            return Ok(TargetResolution::Alias(conn, target.to_string()));
+        }
+        let matches = self
+            .database
+            .search_connections(target, 10, &self.config.search_mode)?;
+        Ok(TargetResolution::Fuzzy(matches))
+    }
+
+    /// Exact name or id only. Use [`Self::resolve_target`] for anything a
+    /// user typed; this is for checks that must not follow aliases, such as
+    /// name collisions and import matching.
     pub async fn get_connection(&self, target: &str) -> Result<Option<Connection>> {
         self.database.get_connection(target)
     }
 
+    /// Mode fuzzy search runs in (`bayesian` or `fuzzy`)
+    pub fn search_mode(&self) -> &str {
+        &self.config.search_mode
+    }
+
+    /// Populate `connection.aliases` from the aliases table.
+    pub async fn load_aliases(&self, connection: &mut Connection) -> Result<()> {
+        connection.aliases = self
+            .database
+            .get_aliases_for_connection(&connection.id.to_string())?;
+        Ok(())
+    }
+
+    /// [`Self::load_aliases`] for many connections, in a single query
+    pub async fn load_all_aliases(&self, connections: &mut [Connection]) -> Result<()> {
+        self.database.attach_aliases(connections)
+    }
+
+    pub async fn find_by_endpoint(
+        &self,
+        host: &str,
+        user: &str,
+        port: u16,
+    ) -> Result<Option<Connection>> {
+        self.database.find_by_endpoint(host, user, port)
+    }
+
+    /// Persist changes to a connection without touching `last_used`.
+    pub async fn save_connection(&self, connection: &Connection) -> Result<()> {
+        self.database.update_connection(connection)
+    }
+
     pub async fn update_connection(&self, mut connection: Connection) -> Result<()> {
         connection.update_last_used();
         self.database.update_connection(&connection)
@@ -219,12 +552,18 @@ impl SshService {
         self.database.get_stats()
     }
 
-    /// Ensure a valid Kerberos ticket exists, creating one if necessary
-    async fn ensure_kerberos_ticket(&self) -> Result<()> {
+    /// Ensure a valid Kerberos ticket exists, creating one if necessary.
+    /// `env` is the session's local environment (e.g. `KRB5CCNAME`).
+    async fn ensure_kerberos_ticket(&self, env: &[(String, String)]) -> Result<()> {
         info!("Checking Kerberos ticket status...");
+        let env = env.iter().map(|(key, value)| (key, value));
 
         // Check if we have a valid ticket
-        let ticket_status = TokioCommand::new("klist").arg("-s").output().await?;
+        let ticket_status = TokioCommand::new("klist")
+            .arg("-s")
+            .envs(env.clone())
+            .output()
+            .await?;
 
         if ticket_status.status.success() {
             info!("Valid Kerberos ticket found");
@@ -236,6 +575,7 @@ impl SshService {
         // Create a new forwardable ticket
         let kinit_result = TokioCommand::new("kinit")
             .arg("-f") // Forwardable ticket
+            .envs(env)
             .stdin(Stdio::inherit())
             .stdout(Stdio::inherit())
             .stderr(Stdio::inherit())
@@ -267,15 +607,49 @@ impl SshService {
         self.database.search_connections(query, limit, mode)
     }
 
+    /// Connections for repeated in-memory searches; see [`Self::search_in`]
+    pub async fn search_candidates(&self) -> Result<Vec<Connection>> {
+        self.database.load_search_candidates()
+    }
+
+    /// Same ranking as [`Self::search`], over connections already loaded
+    /// with [`Self::search_candidates`]
+    pub fn search_in(
+        &self,
+        candidates: &[Connection],
+        query: &str,
+        limit: usize,
+        mode: &str,
+    ) -> Vec<Connection> {
+        self.database
+            .search_candidates(candidates, query, limit, mode)
+    }
+
+    /// Most recently used distinct connections, from the recent-targets ring.
+    ///
+    /// Databases created before the ring existed fall back to `last_used`.
     pub async fn get_recent_connections(&self, limit: usize) -> Result<Vec<Connection>> {
+        let recent = self.database.get_recent_targets(limit)?;
+        if !recent.is_empty() {
+            return Ok(recent.into_iter().map(|(conn, _)| conn).collect());
+        }
         self.database
-            .list_connections(None, true)
+            .list_connections(None, true, false)
             .map(|mut connections| {
                 connections.truncate(limit);
                 connections
             })
     }
 
+    /// Recent-targets ring with the time each target was last connected to.
+    pub async fn get_recent_targets(
+        &self,
+        limit: usize,
+    ) -> Result<Vec<(Connection, chrono::DateTime<chrono::Utc>)>> {
+        self.database.get_recent_targets(limit)
+    }
+
+    /// Returns whether a session was established; a dry run never is.
     #[allow(clippy::too_many_arguments)]
     pub async fn connect_to_connection(
         &self,
@@ -287,7 +661,12 @@ impl SshService {
         no_bastion: bool,
         bastion_user: Option<String>,
         key: Option<String>,
-    ) -> Result<()> {
+        alias_used: Option<String>,
+        tunnels: Vec<String>,
+        label: Option<String>,
+        check_port: bool,
+        dry_run: bool,
+    ) -> Result<bool> {
         info!("Connecting to connection: {}", connection.name);
 
         // Create a mutable copy to apply overrides
@@ -305,6 +684,7 @@ impl SshService {
         }
         if let Some(bastion) = bastion {
             conn.bastion = Some(bastion);
+            conn.proxy_command = None;
         }
         if no_bastion {
             conn.bastion = None;
@@ -317,11 +697,336 @@ impl SshService {
             conn.key_path = Some(key);
         }
 
-        // Update last used timestamp
-        conn.update_last_used();
-        self.database.update_connection(&conn)?;
+        if dry_run {
+            self.print_command(conn, &tunnels)?;
+            return Ok(false);
+        }
+
+        // A ProxyCommand may be the only route to the host, so there is
+        // nothing meaningful to probe directly
+        if check_port && conn.proxy_command.is_none() {
+            let (host, port) = conn.probe_endpoint();
+            let result = self.probe_port(&conn, PORT_CHECK_TIMEOUT_SECS).await;
+            if !result.is_reachable() {
+                return Err(
+                    anyhow::Error::from(AppError::Unreachable(format!("{host}:{port}"))).context(
+                        format!(
+                        "{host}:{port} is not accepting connections ({result}); not starting ssh"
+                    ),
+                    ),
+                );
+            }
+            info!("{}:{} {}", host, port, result);
+        }
+
+        self.execute_and_record(conn, alias_used, tunnels, label)
+            .await
+    }
+
+    /// Install `public_key` (default: see [`default_public_key`]) on the
+    /// connection's host with `ssh-copy-id`, through its bastion or proxy
+    /// command. The run is recorded as a session labelled `copy-id`.
+    ///
+    /// [`default_public_key`]: crate::services::auth::default_public_key
+    pub async fn copy_id(
+        &self,
+        connection: &Connection,
+        public_key: Option<std::path::PathBuf>,
+        dry_run: bool,
+    ) -> Result<std::path::PathBuf> {
+        if connection.use_kerberos && connection.bastion.is_some() {
+            anyhow::bail!(
+                "'{}' goes through an interactive bastion, which ssh-copy-id cannot use",
+                connection.name
+            );
+        }
+        let key_path = connection
+            .key_path
+            .clone()
+            .map(std::path::PathBuf::from)
+            .or_else(|| self.tagged_identity(connection));
+        let public_key = match public_key {
+            Some(path) => path,
+            None => crate::services::auth::default_public_key(key_path.as_deref())
+                .ok_or_else(|| match &key_path {
+                    Some(key) => anyhow::anyhow!(
+                        "No public key {}.pub next to the connection's key; pass one explicitly",
+                        key.display()
+                    ),
+                    None => anyhow::anyhow!(
+                        "No public key found in ~/.ssh (id_ed25519.pub, id_ecdsa.pub, id_rsa.pub, id_dsa.pub); pass one explicitly"
+                    ),
+                })?,
+        };
+        if !public_key.is_file() {
+            anyhow::bail!("Public key {} does not exist", public_key.display());
+        }
+
+        let argv = crate::services::transport::SubprocessTransport::new(self.config.clone())
+            .copy_id_argv(connection, &public_key)
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        if dry_run {
+            let argv: Vec<String> = argv.iter().map(|arg| shell_quote(arg)).collect();
+            println!("{}", argv.join(" "));
+            return Ok(public_key);
+        }
+
+        let mut session = Session::new(connection.clone());
+        session.transport = Some("subprocess".to_string());
+        session.label = Some("copy-id".to_string());
+        self.start_session(&mut session)?;
+
+        let status = TokioCommand::new(&argv[0]).args(&argv[1..]).status().await;
+        let code = match status {
+            Ok(status) => status.code().unwrap_or(-1),
+            Err(e) => {
+                session.mark_error(format!("{e}"));
+                self.store_session(&session)?;
+                return Err(anyhow::Error::from(e).context("Failed to run ssh-copy-id"));
+            }
+        };
+        session.mark_terminated(code);
+        self.store_session(&session)?;
+        if code != 0 {
+            anyhow::bail!("ssh-copy-id exited with code {}", code);
+        }
+        Ok(public_key)
+    }
+
+    /// Print the ssh command a session would run, without running it or
+    /// recording anything. Native-transport sessions are shown as the
+    /// equivalent ssh invocation, and env file variables as `KEY=value`
+    /// assignments in front of it.
+    fn print_command(&self, mut connection: Connection, extra_args: &[String]) -> Result<()> {
+        if let Some(key) = self.tagged_identity(&connection) {
+            connection.key_path = Some(key.to_string_lossy().into_owned());
+        }
+        let env = self.local_env()?;
+        let assignments: Vec<String> = env
+            .iter()
+            .map(|(key, value)| format!("{key}={}", shell_quote(value)))
+            .collect();
+        let argv = self
+            .subprocess_transport(env)
+            .interactive_argv(&connection, extra_args)
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        let argv: Vec<String> = assignments
+            .into_iter()
+            .chain(argv.iter().map(|arg| shell_quote(arg)))
+            .collect();
+        println!("{}", argv.join(" "));
+        Ok(())
+    }
+}
+
+/// Last few non-empty lines of ssh's stderr, which is where it puts the
+/// reason a connection failed
+fn error_snippet(stderr: &str) -> Option<String> {
+    const MAX_LINES: usize = 3;
+    let lines: Vec<&str> = stderr
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    if lines.is_empty() {
+        return None;
+    }
+    Some(lines[lines.len().saturating_sub(MAX_LINES)..].join("\n"))
+}
+
+/// Single-quote `arg` when a shell would split or expand it
+pub(crate) fn shell_quote(arg: &str) -> String {
+    let plain = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:@=,+%".contains(c));
+    if plain {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service(dir: &std::path::Path) -> SshService {
+        let config = AppConfig {
+            database_path: dir.join("history.db"),
+            ..AppConfig::default()
+        };
+        let service = SshService::new(config).expect("service should open");
+        for (name, host) in [
+            ("web-prod", "web.example.com"),
+            ("db-prod", "db.example.com"),
+        ] {
+            let conn = Connection::new(
+                name.into(),
+                host.into(),
+                "deploy".into(),
+                22,
+                None,
+                None,
+                false,
+                None,
+            );
+            service.database.add_connection(&conn).unwrap();
+        }
+        let web = service
+            .database
+            .get_connection("web-prod")
+            .unwrap()
+            .unwrap();
+        service
+            .database
+            .add_alias("storefront", &web.id.to_string())
+            .unwrap();
+        service
+    }
+
+    #[test]
+    fn parallel_uses_are_all_counted() {
+        let dir = tempfile::tempdir().unwrap();
+        let id = service(dir.path())
+            .database
+            .get_connection("web-prod")
+            .unwrap()
+            .unwrap()
+            .id
+            .to_string();
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let path = dir.path().to_path_buf();
+                let id = id.clone();
+                std::thread::spawn(move || {
+                    let config = AppConfig {
+                        database_path: path.join("history.db"),
+                        ..AppConfig::default()
+                    };
+                    let database = Database::new(&config).unwrap();
+                    for _ in 0..25 {
+                        assert!(database.touch_last_used(&id).unwrap());
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let config = AppConfig {
+            database_path: dir.path().join("history.db"),
+            ..AppConfig::default()
+        };
+        let web = Database::new(&config).unwrap().get_connection("web-prod");
+        assert_eq!(web.unwrap().unwrap().use_count, 100);
+    }
+
+    #[tokio::test]
+    async fn exact_names_resolve_by_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = service(dir.path());
+
+        match service.resolve_target("db-prod").await.unwrap() {
+            TargetResolution::Name(conn) => assert_eq!(conn.name, "db-prod"),
+            other => panic!("expected a name match, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn aliases_resolve_and_report_the_alias() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = service(dir.path());
+
+        match service.resolve_target("storefront").await.unwrap() {
+            TargetResolution::Alias(conn, alias) => {
+                assert_eq!(conn.name, "web-prod");
+                assert_eq!(alias, "storefront");
+            }
+            other => panic!("expected an alias match, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn anything_else_falls_back_to_fuzzy_candidates() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = service(dir.path());
+
+        match service.resolve_target("prod").await.unwrap() {
+            TargetResolution::Fuzzy(matches) => assert_eq!(matches.len(), 2),
+            other => panic!("expected fuzzy candidates, got {other:?}"),
+        }
+        match service.resolve_target("nothing-like-it").await.unwrap() {
+            TargetResolution::Fuzzy(matches) => assert!(matches.is_empty()),
+            other => panic!("expected no candidates, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn scattered_letters_match_from_a_word_start() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = service(dir.path());
+        let conn = Connection::new(
+            "production-web".into(),
+            "pw.example.com".into(),
+            "deploy".into(),
+            22,
+            None,
+            None,
+            false,
+            None,
+        );
+        service.database.add_connection(&conn).unwrap();
+        let candidates = service.search_candidates().await.unwrap();
+
+        let names =
+            |conns: Vec<Connection>| -> Vec<String> { conns.into_iter().map(|c| c.name).collect() };
+        for mode in ["fuzzy", "bayesian"] {
+            assert_eq!(
+                names(service.search_in(&candidates, "prdweb", 10, mode)),
+                ["production-web"]
+            );
+        }
+        // Not anchored at a word start
+        assert!(service
+            .search_in(&candidates, "rdwb", 10, "fuzzy")
+            .is_empty());
+    }
 
-        // Execute the connection
-        self.execute_ssh(&conn).await
+    #[tokio::test]
+    async fn search_in_reuses_loaded_candidates() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = service(dir.path());
+        let candidates = service.search_candidates().await.unwrap();
+
+        // Added after loading: an in-memory search must not see it
+        let late = Connection::new(
+            "web-late".into(),
+            "late.example.com".into(),
+            "deploy".into(),
+            22,
+            None,
+            None,
+            false,
+            None,
+        );
+        service.database.add_connection(&late).unwrap();
+
+        let names =
+            |conns: Vec<Connection>| -> Vec<String> { conns.into_iter().map(|c| c.name).collect() };
+        assert_eq!(
+            names(service.search_in(&candidates, "web", 10, "fuzzy")),
+            ["web-prod"]
+        );
+        assert_eq!(
+            names(service.search_in(&candidates, "store", 10, "bayesian")),
+            ["web-prod"]
+        );
+        assert_eq!(
+            names(service.search("web", 10, "fuzzy").await.unwrap()).len(),
+            2
+        );
     }
 }
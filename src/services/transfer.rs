@@ -15,8 +15,8 @@ use tracing::{debug, info, warn};
 use crate::config::AppConfig;
 use crate::database::Database;
 use crate::models::Connection;
-use crate::services::transport::scp_impl;
 use crate::services::transport::{pick_kind, TransportKind};
+use crate::services::transport::{scp_impl, subprocess_impl};
 
 const CHUNK_SIZE: usize = 256 * 1024; // 256 KiB
 const CHANNEL_CAP: usize = 16; // outstanding chunks in flight
@@ -427,7 +427,8 @@ impl TransferService {
             connection.host,
             remote_path,
         );
-        scp_impl::scp_upload(connection, local_path, remote_path, recursive).await
+        let ssh_config = subprocess_impl::ssh_config_file(connection, &self.config)?;
+        scp_impl::scp_upload(connection, local_path, remote_path, recursive, ssh_config).await
     }
 
     /// Download via SCP (bastion-aware).
@@ -444,7 +445,8 @@ impl TransferService {
             remote_path,
             local_path.display(),
         );
-        scp_impl::scp_download(connection, remote_path, local_path, recursive).await
+        let ssh_config = subprocess_impl::ssh_config_file(connection, &self.config)?;
+        scp_impl::scp_download(connection, remote_path, local_path, recursive, ssh_config).await
     }
 
     /// Returns `true` when SFTP is available for this connection, `false`
@@ -16,7 +16,25 @@ pub enum TransportKind {
 }
 
 pub fn pick_kind(conn: &Connection, cfg: &AppConfig) -> TransportKind {
-    if cfg.transport.force_subprocess || conn.use_kerberos || conn.bastion.is_some() {
+    // Only the ssh binary understands `-F` config files, SetEnv/SendEnv,
+    // ProxyCommand and a remote command for the login session; keyring
+    // passwords are fed to it by sshpass.
+    let ssh_options = conn.ssh_config_file.is_some()
+        || conn.password_ref.is_some()
+        || conn.proxy_command.is_some()
+        || conn.auto_attach.is_some()
+        || cfg.default_ssh_config_file.is_some()
+        || !conn.env.is_empty()
+        || !conn.send_env.is_empty();
+    // Retries classify ssh's exit code and stderr, which only the subprocess
+    // transport reports.
+    let retries = cfg.connect_retries > 0;
+    if retries
+        || cfg.transport.force_subprocess
+        || conn.use_kerberos
+        || conn.bastion.is_some()
+        || ssh_options
+    {
         TransportKind::Subprocess
     } else {
         TransportKind::Native
@@ -103,6 +121,38 @@ mod tests {
         assert_eq!(pick_kind(&conn, &simple_cfg()), TransportKind::Subprocess);
     }
 
+    #[test]
+    fn connect_retries_force_subprocess() {
+        let mut cfg = simple_cfg();
+        cfg.connect_retries = 2;
+        assert_eq!(pick_kind(&simple_conn(), &cfg), TransportKind::Subprocess);
+    }
+
+    #[test]
+    fn ssh_config_file_forces_subprocess() {
+        let mut conn = simple_conn();
+        conn.ssh_config_file = Some("/etc/ssh/lab_config".into());
+        assert_eq!(pick_kind(&conn, &simple_cfg()), TransportKind::Subprocess);
+
+        let mut cfg = simple_cfg();
+        cfg.default_ssh_config_file = Some("/etc/ssh/lab_config".into());
+        assert_eq!(pick_kind(&simple_conn(), &cfg), TransportKind::Subprocess);
+    }
+
+    #[test]
+    fn env_forwarding_forces_subprocess() {
+        let mut conn = simple_conn();
+        conn.send_env = vec!["LC_*".into()];
+        assert_eq!(pick_kind(&conn, &simple_cfg()), TransportKind::Subprocess);
+    }
+
+    #[test]
+    fn keyring_password_forces_subprocess() {
+        let mut conn = simple_conn();
+        conn.password_ref = Some("deploy@legacy".into());
+        assert_eq!(pick_kind(&conn, &simple_cfg()), TransportKind::Subprocess);
+    }
+
     #[test]
     fn force_subprocess_flag_wins() {
         let mut cfg = simple_cfg();
@@ -108,15 +108,17 @@ fn create_bastion_wrapper(bastion: &str) -> Result<PathBuf> {
 
 /// Upload `local` to `remote_path` on the target behind the bastion.
 ///
-/// `recursive` enables `-r` for directory trees.
+/// `recursive` enables `-r` for directory trees; `ssh_config` is passed
+/// to scp as `-F`.
 pub async fn scp_upload(
     conn: &Connection,
     local: &Path,
     remote_path: &str,
     recursive: bool,
+    ssh_config: Option<&Path>,
 ) -> Result<()> {
     let argv = build_scp_argv(conn, local, remote_path, recursive, Direction::Upload)?;
-    run_scp(&argv).await
+    run_scp(&with_ssh_config(argv, ssh_config)).await
 }
 
 /// Download `remote_path` from the target to `local`.
@@ -125,15 +127,23 @@ pub async fn scp_download(
     remote_path: &str,
     local: &Path,
     recursive: bool,
+    ssh_config: Option<&Path>,
 ) -> Result<()> {
     let argv = build_scp_argv(conn, local, remote_path, recursive, Direction::Download)?;
-    run_scp(&argv).await
+    run_scp(&with_ssh_config(argv, ssh_config)).await
 }
 
 // ──────────────────────────────────────────────────────────────────────────
 // Internals
 // ──────────────────────────────────────────────────────────────────────────
 
+fn with_ssh_config(mut argv: Vec<String>, ssh_config: Option<&Path>) -> Vec<String> {
+    if let Some(path) = ssh_config {
+        argv.splice(1..1, ["-F".to_string(), path.display().to_string()]);
+    }
+    argv
+}
+
 #[derive(Debug, Clone, Copy)]
 enum Direction {
     Upload,
@@ -201,11 +211,12 @@ fn build_scp_argv(
             }
         }
     } else {
-        // Direct connection — plain scp.
+        // Direct connection — plain scp, or through the ProxyCommand.
         if conn.use_kerberos {
             argv.push("-o".into());
             argv.push("GSSAPIAuthentication=yes".into());
         }
+        argv.extend(conn.proxy_options());
         if let Some(key) = &conn.key_path {
             argv.push("-i".into());
             argv.push(key.clone());
@@ -5,8 +5,10 @@
 #![allow(dead_code)]
 
 use crate::config::AppConfig;
-use crate::models::Connection;
+use crate::models::{Connection, SshCommandMode};
+use crate::services::ssh::shell_quote;
 use async_trait::async_trait;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use tokio::process::Command as TokioCommand;
 
@@ -15,11 +17,31 @@ use super::types::{ExecOutput, PtyIo, SftpSession, ShellHandle, SshTransport, Tr
 pub struct SubprocessTransport {
     #[allow(dead_code)]
     config: AppConfig,
+    /// Interactive sessions are recorded here through `script(1)`
+    transcript: Option<PathBuf>,
+    /// Extra variables for the local ssh process (`connect --env-file`)
+    env: Vec<(String, String)>,
 }
 
 impl SubprocessTransport {
     pub fn new(config: AppConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            transcript: None,
+            env: Vec::new(),
+        }
+    }
+
+    /// Record interactive sessions (terminal output included) to `path`
+    pub fn with_transcript(mut self, path: PathBuf) -> Self {
+        self.transcript = Some(path);
+        self
+    }
+
+    /// Set `vars` in the environment of interactive ssh sessions
+    pub fn with_env(mut self, vars: Vec<(String, String)>) -> Self {
+        self.env = vars;
+        self
     }
 
     /// Build the argv for a non-interactive exec call.
@@ -37,6 +59,8 @@ impl SubprocessTransport {
             argv.push("-i".into());
             argv.push(key.clone());
         }
+        argv.extend(conn.env_options());
+        argv.extend(conn.proxy_options());
         argv.push("-o".into());
         argv.push("BatchMode=yes".into());
         argv.push("-o".into());
@@ -71,6 +95,8 @@ impl SubprocessTransport {
             argv.push("-i".into());
             argv.push(key.clone());
         }
+        argv.extend(conn.env_options());
+        argv.extend(conn.proxy_options());
 
         if let Some(bastion) = &conn.bastion {
             let bu = conn.bastion_user.as_deref().unwrap_or(&conn.user);
@@ -93,6 +119,9 @@ impl SubprocessTransport {
             argv.push(conn.port.to_string());
             argv.push(format!("{}@{}", conn.user, conn.host));
         }
+        if let Some(attach) = conn.attach_command() {
+            argv.push(attach);
+        }
         argv
     }
 
@@ -112,6 +141,7 @@ impl SubprocessTransport {
             argv.push("-i".into());
             argv.push(key.clone());
         }
+        argv.extend(conn.proxy_options());
         if let Some(bastion) = &conn.bastion {
             let bu = conn.bastion_user.as_deref().unwrap_or(&conn.user);
             argv.push("-J".into());
@@ -128,6 +158,35 @@ impl SubprocessTransport {
         argv
     }
 
+    /// Build the argv to install `public_key` on the host with `ssh-copy-id`.
+    ///
+    /// ssh-copy-id has no `-J`, so a jump-host bastion goes in as
+    /// `-o ProxyJump=…`. An interactive bastion cannot be used at all.
+    pub(crate) fn build_copy_id_argv(conn: &Connection, public_key: &Path) -> Vec<String> {
+        let mut argv: Vec<String> = vec!["ssh-copy-id".into()];
+        argv.push("-i".into());
+        argv.push(public_key.display().to_string());
+        argv.extend(conn.proxy_options());
+        if let Some(bastion) = &conn.bastion {
+            let bu = conn.bastion_user.as_deref().unwrap_or(&conn.user);
+            argv.push("-o".into());
+            argv.push(format!("ProxyJump={bu}@{bastion}"));
+        }
+        argv.push("-p".into());
+        argv.push(conn.port.to_string());
+        argv.push(format!("{}@{}", conn.user, conn.host));
+        argv
+    }
+
+    /// Full argv of an `ssh-copy-id` run, with the connection's `-F` file
+    pub fn copy_id_argv(
+        &self,
+        conn: &Connection,
+        public_key: &Path,
+    ) -> Result<Vec<String>, TransportError> {
+        self.with_ssh_config_file(conn, Self::build_copy_id_argv(conn, public_key))
+    }
+
     /// Execute a command through an interactive bastion.
     ///
     /// Interactive bastions only accept the target as an argument — they
@@ -155,6 +214,7 @@ impl SubprocessTransport {
         if let Some(pos) = argv.iter().position(|a| a == "-t" || a == "-tt") {
             argv[pos] = "-tt".into();
         }
+        let argv = self.with_ssh_config_file(conn, argv)?;
         let (cmd_name, args) = argv.split_first().expect("argv non-empty");
 
         let mut child = TokioCommand::new(cmd_name)
@@ -279,11 +339,13 @@ impl SshTransport for SubprocessTransport {
             return self.run_interactive_exec(conn, command).await;
         }
 
-        let argv = Self::build_exec_argv(conn, command);
+        let argv = self.with_ssh_config_file(conn, Self::build_exec_argv(conn, command))?;
+        let argv = with_sshpass(conn, self.with_shared_bastion(conn, argv));
         let (cmd_name, args) = argv.split_first().expect("argv non-empty");
 
         let output = TokioCommand::new(cmd_name)
             .args(args)
+            .envs(sshpass_env(conn)?)
             .stdin(Stdio::null())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -312,7 +374,11 @@ impl SshTransport for SubprocessTransport {
         remote_host: &str,
         remote_port: u16,
     ) -> Result<crate::services::transport::types::ForwardHandle, TransportError> {
-        let argv = Self::build_forward_argv(conn, bind_host, bind_port, remote_host, remote_port);
+        let argv = self.with_ssh_config_file(
+            conn,
+            Self::build_forward_argv(conn, bind_host, bind_port, remote_host, remote_port),
+        )?;
+        let argv = self.with_shared_bastion(conn, argv);
         let (cmd_name, args) = argv.split_first().expect("argv non-empty");
 
         let mut child = TokioCommand::new(cmd_name)
@@ -342,7 +408,9 @@ impl SshTransport for SubprocessTransport {
         bind_host: &str,
         bind_port: u16,
     ) -> Result<crate::services::transport::types::ForwardHandle, TransportError> {
-        let argv = Self::build_dynamic_argv(conn, bind_host, bind_port);
+        let argv =
+            self.with_ssh_config_file(conn, Self::build_dynamic_argv(conn, bind_host, bind_port))?;
+        let argv = self.with_shared_bastion(conn, argv);
         let (cmd_name, args) = argv.split_first().expect("argv non-empty");
 
         let mut child = TokioCommand::new(cmd_name)
@@ -367,16 +435,23 @@ impl SshTransport for SubprocessTransport {
     }
 
     async fn run_interactive(&self, conn: &Connection) -> Result<i32, TransportError> {
-        let argv = Self::build_shell_argv(conn);
-        let (cmd_name, args) = argv.split_first().expect("argv non-empty");
+        self.run_interactive_with(conn, &[]).await
+    }
 
-        let mut child = TokioCommand::new(cmd_name)
-            .args(args)
-            .stdin(Stdio::inherit())
-            .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
-            .spawn()
-            .map_err(|e| TransportError::permanent(anyhow::Error::from(e)))?;
+    fn name(&self) -> &'static str {
+        "subprocess"
+    }
+}
+
+impl SubprocessTransport {
+    /// Interactive shell with `extra_args` (e.g. ad-hoc `-L`/`-R`/`-D`
+    /// forwards) inserted before the destination.
+    pub async fn run_interactive_with(
+        &self,
+        conn: &Connection,
+        extra_args: &[String],
+    ) -> Result<i32, TransportError> {
+        let mut child = self.spawn_interactive(conn, extra_args, Stdio::inherit())?;
 
         let status = child
             .wait()
@@ -386,12 +461,206 @@ impl SshTransport for SubprocessTransport {
         Ok(status.code().unwrap_or(-1))
     }
 
-    fn name(&self) -> &'static str {
-        "subprocess"
+    /// Like [`Self::run_interactive_with`], but ssh's stderr is also kept
+    /// (its last few KiB) and returned with the exit code, so a failed
+    /// attempt can be classified. The output still reaches the terminal.
+    pub async fn run_interactive_capture(
+        &self,
+        conn: &Connection,
+        extra_args: &[String],
+    ) -> Result<(i32, String), TransportError> {
+        use std::sync::{Arc, Mutex};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        const STDERR_TAIL: usize = 4096;
+
+        let mut child = self.spawn_interactive(conn, extra_args, Stdio::piped())?;
+        let mut stderr = child.stderr.take().expect("stderr piped");
+
+        let tail = Arc::new(Mutex::new(Vec::new()));
+        let tail_writer = Arc::clone(&tail);
+        let mut tee = tokio::spawn(async move {
+            let mut terminal = tokio::io::stderr();
+            let mut chunk = [0u8; 1024];
+            while let Ok(n) = stderr.read(&mut chunk).await {
+                if n == 0 {
+                    break;
+                }
+                let _ = terminal.write_all(&chunk[..n]).await;
+                let _ = terminal.flush().await;
+                let mut tail = tail_writer.lock().unwrap_or_else(|e| e.into_inner());
+                tail.extend_from_slice(&chunk[..n]);
+                let excess = tail.len().saturating_sub(STDERR_TAIL);
+                tail.drain(..excess);
+            }
+        });
+
+        let status = child
+            .wait()
+            .await
+            .map_err(|e| TransportError::permanent(anyhow::Error::from(e)))?;
+
+        // A backgrounded control master can hold the pipe open; don't wait on it.
+        if tokio::time::timeout(std::time::Duration::from_millis(500), &mut tee)
+            .await
+            .is_err()
+        {
+            tee.abort();
+        }
+
+        let tail = tail.lock().unwrap_or_else(|e| e.into_inner());
+        Ok((
+            status.code().unwrap_or(-1),
+            String::from_utf8_lossy(&tail).into_owned(),
+        ))
+    }
+
+    /// Full argv of an interactive session, as run by `run_interactive_with`
+    pub fn interactive_argv(
+        &self,
+        conn: &Connection,
+        extra_args: &[String],
+    ) -> Result<Vec<String>, TransportError> {
+        let mut argv = Self::build_shell_argv(conn);
+        // Right after `ssh -tt`, ahead of the destination argument(s).
+        argv.splice(2..2, extra_args.iter().cloned());
+        let argv = self.with_ssh_config_file(conn, argv)?;
+        let argv = with_sshpass(conn, self.with_shared_bastion(conn, argv));
+        Ok(match &self.transcript {
+            Some(path) => with_script(path, argv),
+            None => argv,
+        })
+    }
+
+    fn spawn_interactive(
+        &self,
+        conn: &Connection,
+        extra_args: &[String],
+        stderr: Stdio,
+    ) -> Result<tokio::process::Child, TransportError> {
+        let argv = self.interactive_argv(conn, extra_args)?;
+        let (cmd_name, args) = argv.split_first().expect("argv non-empty");
+
+        TokioCommand::new(cmd_name)
+            .args(args)
+            .envs(self.env.iter().map(|(key, value)| (key, value)))
+            .envs(sshpass_env(conn)?)
+            .stdin(Stdio::inherit())
+            .stdout(Stdio::inherit())
+            .stderr(stderr)
+            .spawn()
+            .map_err(|e| TransportError::permanent(anyhow::Error::from(e)))
+    }
+
+    /// Control socket shared by every session that jumps through the same
+    /// bastion endpoint (`bastion_user@bastion`).
+    pub(crate) fn bastion_control_path(conn: &Connection) -> Option<PathBuf> {
+        let bastion = conn.bastion.as_deref()?;
+        let bu = conn.bastion_user.as_deref().unwrap_or(&conn.user);
+        // FNV-1a keeps the name short (socket paths are length-limited) and
+        // stable across builds, so separate bssh invocations agree on it.
+        let hash = format!("{bu}@{bastion}")
+            .bytes()
+            .fold(0xcbf2_9ce4_8422_2325u64, |h, b| {
+                (h ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+            });
+        let dir = dirs::runtime_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("bayesian-ssh");
+        Some(dir.join(format!("bastion-{hash:016x}")))
+    }
+
+    /// Replace the `-J user@bastion` pair in `argv` with a `ProxyCommand` hop
+    /// that multiplexes over `control_path`.
+    ///
+    /// The first hop becomes the master and lingers for `persist_secs` after
+    /// its last client, so later jumps through the same bastion skip the
+    /// handshake. Argv without `-J` is returned unchanged.
+    pub(crate) fn share_bastion_hop(
+        mut argv: Vec<String>,
+        control_path: &Path,
+        persist_secs: u64,
+    ) -> Vec<String> {
+        let Some(pos) = argv.iter().position(|a| a == "-J") else {
+            return argv;
+        };
+        let Some(jump) = argv.get(pos + 1).cloned() else {
+            return argv;
+        };
+        let proxy = shared_bastion_proxy(&jump, &hop_options(&argv), control_path, persist_secs);
+        argv.splice(pos..pos + 2, ["-o".to_string(), proxy]);
+        argv
+    }
+
+    /// The ssh command `mode` runs for `conn`, for display: as
+    /// [`Connection::to_ssh_command`], but with the shared bastion hop in
+    /// place of `-J` when `share_bastion_connections` is on.
+    pub fn display_command(config: &AppConfig, conn: &Connection, mode: SshCommandMode) -> String {
+        let hop = config
+            .transport
+            .share_bastion_connections
+            .then(|| Self::bastion_control_path(conn))
+            .flatten()
+            .zip(conn.bastion.as_deref())
+            .map(|(control_path, bastion)| {
+                // The options to_ssh_command puts on the outer ssh
+                let mut outer: Vec<String> = Vec::new();
+                if let Some(config_file) = &conn.ssh_config_file {
+                    outer.extend(["-F".into(), config_file.display().to_string()]);
+                }
+                if mode == SshCommandMode::Exec {
+                    outer.extend(["-o".into(), "StrictHostKeyChecking=accept-new".into()]);
+                }
+                let bu = conn.bastion_user.as_deref().unwrap_or(&conn.user);
+                shared_bastion_proxy(
+                    &format!("{bu}@{bastion}"),
+                    &hop_options(&outer),
+                    &control_path,
+                    config.transport.bastion_control_persist,
+                )
+            });
+        conn.to_ssh_command_via(mode, hop.as_deref())
+    }
+
+    /// Apply bastion multiplexing to `argv` when enabled in the config.
+    fn with_shared_bastion(&self, conn: &Connection, argv: Vec<String>) -> Vec<String> {
+        if !self.config.transport.share_bastion_connections {
+            return argv;
+        }
+        let Some(control_path) = Self::bastion_control_path(conn) else {
+            return argv;
+        };
+        let Some(jump) = argv
+            .iter()
+            .position(|a| a == "-J")
+            .and_then(|pos| argv.get(pos + 1))
+        else {
+            return argv;
+        };
+        if let Err(e) = prepare_control_socket(&control_path, jump) {
+            tracing::warn!("Bastion multiplexing disabled ({e}); using a plain jump");
+            return argv;
+        }
+        Self::share_bastion_hop(
+            argv,
+            &control_path,
+            self.config.transport.bastion_control_persist,
+        )
+    }
+
+    /// Insert `-F <file>` right after the program name when the connection
+    /// (or the config default) names an OpenSSH config file.
+    fn with_ssh_config_file(
+        &self,
+        conn: &Connection,
+        mut argv: Vec<String>,
+    ) -> Result<Vec<String>, TransportError> {
+        if let Some(path) = ssh_config_file(conn, &self.config)? {
+            argv.splice(1..1, ["-F".to_string(), path.display().to_string()]);
+        }
+        Ok(argv)
     }
-}
 
-impl SubprocessTransport {
     /// Build the argv for a SOCKS5 dynamic proxy session (`ssh -D -N`).
     pub(crate) fn build_dynamic_argv(
         conn: &Connection,
@@ -406,6 +675,7 @@ impl SubprocessTransport {
             argv.push("-i".into());
             argv.push(key.clone());
         }
+        argv.extend(conn.proxy_options());
         if let Some(bastion) = &conn.bastion {
             let bu = conn.bastion_user.as_deref().unwrap_or(&conn.user);
             argv.push("-J".into());
@@ -426,10 +696,238 @@ impl SubprocessTransport {
     }
 }
 
+/// The master behind a control socket, as far as `ssh -O check` can tell
+#[cfg(unix)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MasterState {
+    /// No socket yet: the next hop becomes the master
+    Absent,
+    /// A live master answers on the socket; the next hop reuses it
+    Running,
+    /// The socket is left over from a master that died
+    Stale,
+}
+
+/// Ask ssh whether a master for `destination` is serving `control_path`.
+/// When ssh cannot be run, a socket that accepts connections counts as live.
+#[cfg(unix)]
+fn master_state(control_path: &Path, destination: &str) -> MasterState {
+    if !control_path.exists() {
+        return MasterState::Absent;
+    }
+    let running = control_command("check", control_path, destination)
+        .status()
+        .map(|status| status.success())
+        .unwrap_or_else(|_| std::os::unix::net::UnixStream::connect(control_path).is_ok());
+    if running {
+        MasterState::Running
+    } else {
+        MasterState::Stale
+    }
+}
+
+/// `ssh -O <command>` against the master on `control_path`, output discarded
+#[cfg(unix)]
+fn control_command(command: &str, control_path: &Path, destination: &str) -> std::process::Command {
+    let mut cmd = std::process::Command::new("ssh");
+    cmd.args(["-O", command, "-o"])
+        .arg(format!("ControlPath={}", control_path.display()))
+        .arg(destination)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    cmd
+}
+
+/// Create the private socket directory, then make sure at most one master
+/// serves `control_path`: a running one is reused, and a stale socket is
+/// shut down (`ssh -O exit`) and removed, since ssh would otherwise disable
+/// multiplexing or start a second master next to it.
+#[cfg(unix)]
+fn prepare_control_socket(control_path: &Path, destination: &str) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    if let Some(dir) = control_path.parent() {
+        std::fs::create_dir_all(dir)?;
+        std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700))?;
+    }
+    match master_state(control_path, destination) {
+        MasterState::Absent => {}
+        MasterState::Running => {
+            tracing::info!(
+                "Reusing the bastion master for {destination} at {}",
+                control_path.display()
+            );
+        }
+        MasterState::Stale => {
+            tracing::info!(
+                "Removing the stale bastion control socket {}",
+                control_path.display()
+            );
+            let _ = control_command("exit", control_path, destination).status();
+            if control_path.exists() {
+                std::fs::remove_file(control_path)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Control sockets are Unix domain sockets; elsewhere the hop stays a plain
+/// `-J` jump.
+#[cfg(not(unix))]
+fn prepare_control_socket(_control_path: &Path, _destination: &str) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "control sockets need a Unix platform",
+    ))
+}
+
+/// `ProxyCommand=…` option for a hop to `jump` (`user@bastion[:port]`, as
+/// given to `-J`) that multiplexes over `control_path`. `forwarded` options
+/// are repeated on the hop, since a ProxyCommand inherits none of them.
+fn shared_bastion_proxy(
+    jump: &str,
+    forwarded: &[String],
+    control_path: &Path,
+    persist_secs: u64,
+) -> String {
+    let (destination, port) = match jump.rsplit_once(':') {
+        Some((destination, port)) if !destination.contains(':') => match port.parse::<u16>() {
+            Ok(port) => (destination, Some(port)),
+            Err(_) => (jump, None),
+        },
+        _ => (jump, None),
+    };
+    let mut hop = vec!["ssh".to_string()];
+    hop.extend(forwarded.iter().cloned());
+    hop.push(format!(
+        "-o ControlMaster=auto -o ControlPath='{}' -o ControlPersist={persist_secs}",
+        control_path.display()
+    ));
+    if let Some(port) = port {
+        hop.push(format!("-p {port}"));
+    }
+    hop.push(format!("-W %h:%p {destination}"));
+    format!("ProxyCommand={}", hop.join(" "))
+}
+
+/// The `-F` file and host-key options of `argv`, which the bastion hop needs
+/// too, shell-quoted for a ProxyCommand
+fn hop_options(argv: &[String]) -> Vec<String> {
+    argv.windows(2)
+        .filter(|pair| {
+            let option = pair[1].to_ascii_lowercase();
+            pair[0] == "-F"
+                || (pair[0] == "-o"
+                    && (option.starts_with("stricthostkeychecking=")
+                        || option.starts_with("userknownhostsfile=")))
+        })
+        .map(|pair| format!("{} {}", pair[0], shell_quote(&pair[1])))
+        .collect()
+}
+
+/// OpenSSH config file to pass as `-F` for `conn`: its own, else the
+/// config default. Fails when the file does not exist, since ssh would
+/// otherwise abort with a less obvious message.
+pub(crate) fn ssh_config_file<'a>(
+    conn: &'a Connection,
+    config: &'a AppConfig,
+) -> Result<Option<&'a Path>, TransportError> {
+    let Some(path) = conn
+        .ssh_config_file
+        .as_deref()
+        .or(config.default_ssh_config_file.as_deref())
+    else {
+        return Ok(None);
+    };
+    if !path.is_file() {
+        return Err(TransportError::permanent(anyhow::anyhow!(
+            "SSH config file {} for '{}' does not exist",
+            path.display(),
+            conn.name
+        )));
+    }
+    Ok(Some(path))
+}
+
+/// Run `argv` under `sshpass -e` when the connection logs in with a
+/// keyring password
+fn with_sshpass(conn: &Connection, mut argv: Vec<String>) -> Vec<String> {
+    if conn.password_ref.is_some() {
+        argv.splice(0..0, ["sshpass".to_string(), "-e".to_string()]);
+    }
+    argv
+}
+
+/// Run `argv` under `script(1)` so the whole terminal session, not just
+/// ssh's own output, lands in `path`. The BSD `script` on macOS takes the
+/// command as arguments; util-linux wants a single shell string.
+fn with_script(path: &Path, argv: Vec<String>) -> Vec<String> {
+    let path = path.to_string_lossy().into_owned();
+    if cfg!(target_os = "macos") {
+        let mut wrapped = vec!["script".into(), "-q".into(), "-F".into(), path];
+        wrapped.extend(argv);
+        wrapped
+    } else {
+        let command: Vec<String> = argv
+            .iter()
+            .map(|arg| crate::services::ssh::shell_quote(arg))
+            .collect();
+        vec![
+            "script".into(),
+            "-q".into(),
+            "-f".into(),
+            "-e".into(),
+            "-c".into(),
+            command.join(" "),
+            path,
+        ]
+    }
+}
+
+/// `SSHPASS` for [`with_sshpass`], read from the keyring
+fn sshpass_env(conn: &Connection) -> Result<Option<(&'static str, String)>, TransportError> {
+    let Some(reference) = conn.password_ref.as_deref() else {
+        return Ok(None);
+    };
+    let password =
+        crate::services::secrets::get_password(reference).map_err(TransportError::permanent)?;
+    Ok(Some(("SSHPASS", password)))
+}
+
+/// Whether ssh's stderr says the server rejected our credentials, as
+/// opposed to the connection itself failing (both exit with 255).
+pub(crate) fn is_auth_failure(stderr: &str) -> bool {
+    const MARKERS: [&str; 4] = [
+        "Permission denied",
+        "Too many authentication failures",
+        "Authentication failed",
+        "No more authentication methods",
+    ];
+    MARKERS.iter().any(|marker| stderr.contains(marker))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn auth_failures_are_told_apart_from_connection_failures() {
+        assert!(is_auth_failure(
+            "alice@target.example: Permission denied (publickey,password).\r\n"
+        ));
+        assert!(is_auth_failure(
+            "Received disconnect from 10.0.0.1 port 22:2: Too many authentication failures"
+        ));
+        assert!(!is_auth_failure(
+            "ssh: connect to host jump.example port 22: Connection refused"
+        ));
+        assert!(!is_auth_failure(
+            "kex_exchange_identification: Connection closed by remote host"
+        ));
+    }
+
     fn c(kerb: bool, bastion: Option<&str>, key: Option<&str>) -> Connection {
         Connection::new(
             "x".into(),
@@ -492,6 +990,81 @@ mod tests {
         assert!(argv.contains(&"/k/id_ed25519".to_string()));
     }
 
+    #[test]
+    fn keyring_passwords_run_ssh_under_sshpass() {
+        let mut conn = c(false, None, None);
+        let transport = SubprocessTransport::new(AppConfig::default());
+        assert_eq!(transport.interactive_argv(&conn, &[]).unwrap()[0], "ssh");
+
+        conn.password_ref = Some("deploy@h".into());
+        let argv = transport.interactive_argv(&conn, &[]).unwrap();
+        assert_eq!(argv[..3], ["sshpass", "-e", "ssh"]);
+    }
+
+    #[test]
+    #[cfg(not(target_os = "macos"))]
+    fn transcripts_run_the_session_under_script() {
+        let conn = c(false, None, None);
+        let transport = SubprocessTransport::new(AppConfig::default())
+            .with_transcript(PathBuf::from("/tmp/my session.log"));
+        let argv = transport.interactive_argv(&conn, &[]).unwrap();
+        assert_eq!(argv[..5], ["script", "-q", "-f", "-e", "-c"]);
+        assert!(argv[5].starts_with("ssh -tt "), "{}", argv[5]);
+        assert_eq!(argv[6], "/tmp/my session.log");
+    }
+
+    #[test]
+    fn argv_env_uses_setenv_and_sendenv_options() {
+        let mut conn = c(false, None, None);
+        conn.env = vec![("LANG".into(), "en_US.UTF-8".into())];
+        conn.send_env = vec!["APP_*".into()];
+        for argv in [
+            SubprocessTransport::build_exec_argv(&conn, "uptime"),
+            SubprocessTransport::build_shell_argv(&conn),
+        ] {
+            assert!(argv.contains(&"SetEnv=LANG=\"en_US.UTF-8\"".to_string()));
+            assert!(argv.contains(&"SendEnv=APP_*".to_string()));
+        }
+    }
+
+    #[test]
+    fn copy_id_argv_routes_through_the_bastion() {
+        let argv = SubprocessTransport::build_copy_id_argv(
+            &c(false, Some("b.example"), Some("/k/id_ed25519")),
+            Path::new("/k/id_ed25519.pub"),
+        );
+        assert_eq!(
+            argv,
+            [
+                "ssh-copy-id",
+                "-i",
+                "/k/id_ed25519.pub",
+                "-o",
+                "ProxyJump=alice@b.example",
+                "-p",
+                "2222",
+                "alice@target.example"
+            ]
+        );
+    }
+
+    #[test]
+    fn argv_proxy_command_expands_tokens() {
+        let mut conn = c(false, None, None);
+        conn.proxy_command = Some("sso-proxy --to %h:%p --as %r --pct 100%%".into());
+        let expected =
+            "ProxyCommand=sso-proxy --to target.example:2222 --as alice --pct 100%%".to_string();
+        for argv in [
+            SubprocessTransport::build_exec_argv(&conn, "uptime"),
+            SubprocessTransport::build_shell_argv(&conn),
+            SubprocessTransport::build_forward_argv(&conn, "127.0.0.1", 8080, "localhost", 80),
+            SubprocessTransport::build_dynamic_argv(&conn, "127.0.0.1", 1080),
+        ] {
+            assert!(argv.contains(&expected), "{argv:?}");
+            assert!(!argv.contains(&"-J".to_string()));
+        }
+    }
+
     #[test]
     fn shell_argv_simple() {
         let argv = SubprocessTransport::build_shell_argv(&c(false, None, None));
@@ -507,4 +1080,98 @@ mod tests {
         assert!(argv.contains(&"-tt".to_string()));
         assert!(argv.contains(&"-K".to_string()));
     }
+
+    #[test]
+    fn bastion_control_path_is_per_endpoint() {
+        let a = c(false, Some("b.example"), None);
+        let mut other_target = c(false, Some("b.example"), None);
+        other_target.host = "other.example".into();
+        let mut other_user = c(false, Some("b.example"), None);
+        other_user.bastion_user = Some("ops".into());
+
+        let path = SubprocessTransport::bastion_control_path(&a).unwrap();
+        assert_eq!(
+            SubprocessTransport::bastion_control_path(&other_target).unwrap(),
+            path
+        );
+        assert_ne!(
+            SubprocessTransport::bastion_control_path(&other_user).unwrap(),
+            path
+        );
+        assert!(SubprocessTransport::bastion_control_path(&c(false, None, None)).is_none());
+    }
+
+    #[test]
+    fn shared_bastion_hop_replaces_j_flag() {
+        let argv = SubprocessTransport::build_shell_argv(&c(false, Some("b.example"), None));
+        let argv = SubprocessTransport::share_bastion_hop(argv, Path::new("/run/bssh/sock"), 600);
+        assert!(!argv.contains(&"-J".to_string()));
+        let proxy = argv
+            .iter()
+            .find(|a| a.starts_with("ProxyCommand="))
+            .expect("ProxyCommand should be set");
+        assert!(proxy.contains("ControlPath='/run/bssh/sock'"));
+        assert!(proxy.contains("ControlPersist=600"));
+        assert!(proxy.ends_with("-W %h:%p alice@b.example"));
+        assert_eq!(argv.last().unwrap(), "alice@target.example");
+    }
+
+    #[test]
+    fn shared_bastion_hop_keeps_port_config_and_host_key_options() {
+        let argv =
+            SubprocessTransport::build_exec_argv(&c(false, Some("b.example:2200"), None), "uptime");
+        let mut argv = [
+            vec![
+                "ssh".to_string(),
+                "-F".into(),
+                "/etc/ssh/corp config".into(),
+            ],
+            argv[1..].to_vec(),
+        ]
+        .concat();
+        argv.extend(["-o".into(), "UserKnownHostsFile=/tmp/known".into()]);
+        let argv = SubprocessTransport::share_bastion_hop(argv, Path::new("/run/bssh/sock"), 600);
+        let proxy = argv
+            .iter()
+            .find(|a| a.starts_with("ProxyCommand="))
+            .expect("ProxyCommand should be set");
+        assert!(
+            proxy.starts_with(
+                "ProxyCommand=ssh -F '/etc/ssh/corp config' -o StrictHostKeyChecking=accept-new -o UserKnownHostsFile=/tmp/known "
+            ),
+            "{proxy}"
+        );
+        assert!(
+            proxy.ends_with("-p 2200 -W %h:%p alice@b.example"),
+            "{proxy}"
+        );
+    }
+
+    #[test]
+    fn display_command_shows_the_shared_hop() {
+        let conn = c(false, Some("b.example"), None);
+        let mut config = AppConfig::default();
+        assert!(
+            SubprocessTransport::display_command(&config, &conn, SshCommandMode::Interactive)
+                .contains("-J alice@b.example")
+        );
+
+        config.transport.share_bastion_connections = true;
+        let shown =
+            SubprocessTransport::display_command(&config, &conn, SshCommandMode::Interactive);
+        assert!(!shown.contains("-J"), "{shown}");
+        assert!(
+            shown.contains("-o 'ProxyCommand=ssh -o ControlMaster=auto"),
+            "{shown}"
+        );
+        assert!(shown.ends_with("-p 2222 alice@target.example"), "{shown}");
+    }
+
+    #[test]
+    fn shared_bastion_hop_leaves_direct_argv_alone() {
+        let argv = SubprocessTransport::build_shell_argv(&c(false, None, None));
+        let shared =
+            SubprocessTransport::share_bastion_hop(argv.clone(), Path::new("/run/bssh/sock"), 600);
+        assert_eq!(shared, argv);
+    }
 }
@@ -27,6 +27,7 @@ pub async fn run_tui(config: AppConfig) -> Result<Option<(Connection, PendingAct
 
     // Create app
     let mut app = App::new(config)?;
+    app.handle_resize(terminal.size()?.height);
 
     // Main loop
     loop {
@@ -49,11 +50,23 @@ pub async fn run_tui(config: AppConfig) -> Result<Option<(Connection, PendingAct
 
         // Handle events
         if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                app.handle_key_event(key)?;
+            match event::read()? {
+                Event::Key(key) => app.handle_key_event(key)?,
+                Event::Resize(_, height) => {
+                    // Start the next frame from a blank screen so nothing
+                    // from the old layout survives; popups recenter on it
+                    terminal.autoresize()?;
+                    terminal.clear()?;
+                    app.handle_resize(height);
+                }
+                _ => {}
             }
         }
 
+        if matches!(app.pending_action, Some(PendingAction::OpenDetached)) {
+            app.open_detached();
+        }
+
         if app.should_quit {
             break;
         }
@@ -62,10 +75,23 @@ pub async fn run_tui(config: AppConfig) -> Result<Option<(Connection, PendingAct
     // Cancel any active tunnels before exit
     app.cancel_all_tunnels().await;
 
+    // A preference that cannot be written is not worth failing the exit for
+    if let Err(e) = app.save_tui_state() {
+        tracing::warn!("Could not save TUI state: {}", e);
+    }
+
     // Restore terminal
     disable_raw_mode()?;
     execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
 
+    // Headless sessions had no clipboard: hand the yanked commands over now.
+    if !app.yank_fallback.is_empty() {
+        println!("📋 Copied SSH commands (no clipboard was available):");
+        for cmd in &app.yank_fallback {
+            println!("  {}", cmd);
+        }
+    }
+
     // Return selected connection and action if any
     Ok(app.selected_connection.zip(app.pending_action))
 }
@@ -262,13 +262,11 @@ impl App {
             }
             KeyCode::Up | KeyCode::Char('k') => self.move_selection_up(),
             KeyCode::Down | KeyCode::Char('j') => self.move_selection_down(),
-            KeyCode::Enter => {
-                if !self.filtered_connections.is_empty() {
-                    self.selected_connection =
-                        Some(self.filtered_connections[self.selected_index].clone());
-                    self.pending_action = Some(PendingAction::Connect);
-                    self.should_quit = true;
-                }
+            KeyCode::Enter if !self.filtered_connections.is_empty() => {
+                self.selected_connection =
+                    Some(self.filtered_connections[self.selected_index].clone());
+                self.pending_action = Some(PendingAction::Connect);
+                self.should_quit = true;
             }
             KeyCode::Char('e') => {
                 self.enter_edit_mode();
@@ -434,7 +432,10 @@ impl App {
                         } else if idx < self.filtered_connections.len() {
                             let conn = &self.filtered_connections[idx];
                             let db = Database::new(&self.config)?;
-                            if db.remove_connection(&conn.name)? {
+                            if db.remove_connection(
+                                &conn.name,
+                                self.config.retain_sessions_on_delete,
+                            )? {
                                 self.set_status(format!("Deleted connection: {}", conn.name));
                                 self.refresh_connections()?;
                             }
@@ -450,7 +451,10 @@ impl App {
                         for idx in indices {
                             if idx < self.filtered_connections.len() {
                                 let conn = &self.filtered_connections[idx];
-                                if db.remove_connection(&conn.name)? {
+                                if db.remove_connection(
+                                    &conn.name,
+                                    self.config.retain_sessions_on_delete,
+                                )? {
                                     deleted += 1;
                                 }
                             }
@@ -521,14 +525,15 @@ impl App {
             KeyCode::Esc | KeyCode::Char('p') | KeyCode::Char('q') => {
                 self.mode = AppMode::Normal;
             }
-            KeyCode::Enter => {
+            KeyCode::Enter if !self.filtered_connections.is_empty() => {
                 // Connect directly from preview
-                if !self.filtered_connections.is_empty() {
-                    self.selected_connection =
-                        Some(self.filtered_connections[self.selected_index].clone());
-                    self.pending_action = Some(PendingAction::Connect);
-                    self.should_quit = true;
-                }
+                self.selected_connection =
+                    Some(self.filtered_connections[self.selected_index].clone());
+                self.pending_action = Some(PendingAction::Connect);
+                self.should_quit = true;
+            }
+            KeyCode::Char('y') => {
+                self.yank_ssh_command();
             }
             _ => {}
         }
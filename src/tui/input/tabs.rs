@@ -28,30 +28,37 @@ impl App {
             KeyCode::Up | KeyCode::Char('k') => self.move_selection_up(),
             KeyCode::Down | KeyCode::Char('j') => self.move_selection_down(),
             KeyCode::PageUp => {
-                for _ in 0..10 {
+                for _ in 0..self.page_step() {
                     self.move_selection_up();
                 }
             }
             KeyCode::PageDown => {
-                for _ in 0..10 {
+                for _ in 0..self.page_step() {
                     self.move_selection_down();
                 }
             }
             KeyCode::Home | KeyCode::Char('g') => {
                 self.selected_index = 0;
+                self.keep_selection_visible();
             }
             KeyCode::End | KeyCode::Char('G') => {
                 self.selected_index = self.filtered_connections.len().saturating_sub(1);
+                self.keep_selection_visible();
             }
 
             // Connect
-            KeyCode::Enter => {
-                if !self.filtered_connections.is_empty() {
-                    self.selected_connection =
-                        Some(self.filtered_connections[self.selected_index].clone());
-                    self.pending_action = Some(PendingAction::Connect);
-                    self.should_quit = true;
-                }
+            KeyCode::Enter if !self.filtered_connections.is_empty() => {
+                self.selected_connection =
+                    Some(self.filtered_connections[self.selected_index].clone());
+                self.pending_action = Some(PendingAction::Connect);
+                self.should_quit = true;
+            }
+
+            // Connect in a new terminal window, staying in the TUI
+            KeyCode::Char('n') if !self.filtered_connections.is_empty() => {
+                self.selected_connection =
+                    Some(self.filtered_connections[self.selected_index].clone());
+                self.pending_action = Some(PendingAction::OpenDetached);
             }
 
             // Select all (Ctrl+A) — must be before plain 'a'
@@ -76,10 +83,8 @@ impl App {
             }
 
             // Detail pane
-            KeyCode::Char('s') => {
-                if !self.filtered_connections.is_empty() {
-                    self.mode = AppMode::Detail;
-                }
+            KeyCode::Char('s') if !self.filtered_connections.is_empty() => {
+                self.mode = AppMode::Detail;
             }
 
             // Edit connection
@@ -88,10 +93,13 @@ impl App {
             }
 
             // SSH command preview
-            KeyCode::Char('p') => {
-                if !self.filtered_connections.is_empty() {
-                    self.mode = AppMode::CommandPreview;
-                }
+            KeyCode::Char('p') if !self.filtered_connections.is_empty() => {
+                self.mode = AppMode::CommandPreview;
+            }
+
+            // Copy the SSH command to the clipboard
+            KeyCode::Char('y') => {
+                self.yank_ssh_command();
             }
 
             // Search
@@ -139,6 +147,7 @@ impl App {
             // Compact/expanded view toggle
             KeyCode::Char('v') => {
                 self.compact_view = !self.compact_view;
+                self.keep_selection_visible();
                 let msg = if self.compact_view {
                     "Compact view"
                 } else {
@@ -147,6 +156,11 @@ impl App {
                 self.set_status(msg);
             }
 
+            // Color theme
+            KeyCode::Char('T') => {
+                self.cycle_theme();
+            }
+
             KeyCode::Char('f') => {
                 self.group_mode = match self.group_mode {
                     GroupMode::None => {
@@ -173,10 +187,8 @@ impl App {
             }
 
             // Batch delete
-            KeyCode::Char('x') => {
-                if self.multi_select.active {
-                    self.mode = AppMode::Confirm(ConfirmAction::BatchDelete);
-                }
+            KeyCode::Char('x') if self.multi_select.active => {
+                self.mode = AppMode::Confirm(ConfirmAction::BatchDelete);
             }
 
             // Quick connect
@@ -197,6 +209,11 @@ impl App {
                 }
             }
 
+            // Check reachability of every visible connection (Shift+H)
+            KeyCode::Char('H') if !self.filtered_connections.is_empty() => {
+                self.spawn_ping_all();
+            }
+
             // Open Files tab for the highlighted connection (Shift+F)
             KeyCode::Char('F') => {
                 if let Some(conn) = self.filtered_connections.get(self.selected_index).cloned() {
@@ -225,12 +242,12 @@ impl App {
             KeyCode::Up | KeyCode::Char('k') => self.move_selection_up(),
             KeyCode::Down | KeyCode::Char('j') => self.move_selection_down(),
             KeyCode::PageUp => {
-                for _ in 0..10 {
+                for _ in 0..self.page_step() {
                     self.move_selection_up();
                 }
             }
             KeyCode::PageDown => {
-                for _ in 0..10 {
+                for _ in 0..self.page_step() {
                     self.move_selection_down();
                 }
             }
@@ -249,6 +266,11 @@ impl App {
                         self.selected_connection = Some(conn);
                         self.pending_action = Some(PendingAction::Connect);
                         self.should_quit = true;
+                    } else if entry.ad_hoc {
+                        self.set_status(format!(
+                            "'{}' is not a saved connection",
+                            entry.connection_name
+                        ));
                     } else {
                         self.set_status(format!(
                             "Connection '{}' no longer exists",
@@ -449,10 +471,8 @@ impl App {
             KeyCode::Char('?') => {
                 self.mode = AppMode::Help;
             }
-            KeyCode::Down | KeyCode::Char('j') => {
-                if !self.tunnels.is_empty() {
-                    self.tunnel_selected = (self.tunnel_selected + 1).min(self.tunnels.len() - 1);
-                }
+            KeyCode::Down | KeyCode::Char('j') if !self.tunnels.is_empty() => {
+                self.tunnel_selected = (self.tunnel_selected + 1).min(self.tunnels.len() - 1);
             }
             KeyCode::Up | KeyCode::Char('k') => {
                 self.tunnel_selected = self.tunnel_selected.saturating_sub(1);
@@ -465,11 +485,9 @@ impl App {
                 // Open SOCKS5 dynamic proxy dialog
                 self.open_socks5_launch();
             }
-            KeyCode::Char('x') | KeyCode::Delete => {
-                if !self.tunnels.is_empty() {
-                    let idx = self.tunnel_selected;
-                    self.mode = AppMode::Confirm(ConfirmAction::StopTunnel(idx));
-                }
+            KeyCode::Char('x') | KeyCode::Delete if !self.tunnels.is_empty() => {
+                let idx = self.tunnel_selected;
+                self.mode = AppMode::Confirm(ConfirmAction::StopTunnel(idx));
             }
             _ => {}
         }
@@ -0,0 +1,134 @@
+//! Open a connection in a separate terminal window
+//!
+//! Backs the Connections tab's `n` key: the TUI keeps running while
+//! `bssh connect` runs in a new terminal emulator.
+
+use crate::config::AppConfig;
+use crate::models::Connection;
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// Emulators tried in order when `$TERMINAL` is unset, with the flag that
+/// introduces the command to run
+const KNOWN_TERMINALS: &[(&str, &[&str])] = &[
+    ("x-terminal-emulator", &["-e"]),
+    ("gnome-terminal", &["--"]),
+    ("konsole", &["-e"]),
+    ("alacritty", &["-e"]),
+    ("kitty", &[]),
+    ("wezterm", &["start", "--"]),
+    ("foot", &[]),
+    ("xterm", &["-e"]),
+];
+
+/// Spawn `bssh connect` for `connection` in a new terminal window and return
+/// the emulator used. The child is detached: its exit is reaped on a
+/// background thread and never reported back to the TUI.
+pub fn open_in_terminal(connection: &Connection, config: &AppConfig) -> Result<String> {
+    let launcher = terminal_launcher()
+        .context("No terminal emulator found; set $TERMINAL (e.g. TERMINAL=alacritty)")?;
+    let exe = std::env::current_exe().context("Could not locate the bssh executable")?;
+    let argv = launch_argv(&launcher, &exe, connection, config);
+
+    let (program, args) = argv.split_first().expect("launcher is non-empty");
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("Failed to start {}", program))?;
+    std::thread::spawn(move || child.wait());
+
+    Ok(program.clone())
+}
+
+/// `$TERMINAL -e`, or the first known emulator on `$PATH`
+fn terminal_launcher() -> Option<Vec<String>> {
+    if let Ok(terminal) = std::env::var("TERMINAL") {
+        let mut launcher: Vec<String> = terminal.split_whitespace().map(String::from).collect();
+        if !launcher.is_empty() {
+            launcher.push("-e".to_string());
+            return Some(launcher);
+        }
+    }
+
+    let path = std::env::var_os("PATH")?;
+    let dirs: Vec<PathBuf> = std::env::split_paths(&path).collect();
+    KNOWN_TERMINALS
+        .iter()
+        .find(|(name, _)| dirs.iter().any(|dir| dir.join(name).is_file()))
+        .map(|(name, flags)| {
+            std::iter::once(*name)
+                .chain(flags.iter().copied())
+                .map(String::from)
+                .collect()
+        })
+}
+
+/// Full argv: the launcher followed by a `bssh connect` pinned to the TUI's
+/// environment and database, so the new window opens the same connection.
+fn launch_argv(
+    launcher: &[String],
+    exe: &std::path::Path,
+    connection: &Connection,
+    config: &AppConfig,
+) -> Vec<String> {
+    let mut argv = launcher.to_vec();
+    argv.extend([
+        exe.display().to_string(),
+        "--env".to_string(),
+        config.environment.clone(),
+        "--database".to_string(),
+        config.database_path.display().to_string(),
+        "connect".to_string(),
+        connection.name.clone(),
+    ]);
+    argv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn launch_argv_runs_connect_against_the_same_database() {
+        let config = AppConfig {
+            environment: "work".into(),
+            database_path: PathBuf::from("/tmp/work.db"),
+            ..AppConfig::default()
+        };
+        let conn = Connection::new(
+            "web".into(),
+            "web.corp".into(),
+            "deploy".into(),
+            22,
+            None,
+            None,
+            false,
+            None,
+        );
+        let launcher = vec!["gnome-terminal".to_string(), "--".to_string()];
+
+        assert_eq!(
+            launch_argv(
+                &launcher,
+                std::path::Path::new("/usr/bin/bssh"),
+                &conn,
+                &config
+            ),
+            [
+                "gnome-terminal",
+                "--",
+                "/usr/bin/bssh",
+                "--env",
+                "work",
+                "--database",
+                "/tmp/work.db",
+                "connect",
+                "web"
+            ]
+        );
+    }
+}
@@ -9,10 +9,14 @@
 //! - `state`      — `App` struct and all state-management methods
 //! - `input`      — Keyboard input handlers (dispatched per tab/mode)
 //! - `event_loop` — Terminal setup/teardown and the main loop (`run_tui`)
+//! - `theme`      — Color presets selected by `config.theme`
+//! - `launcher`   — Opening connections in a new terminal window
 //! - `ui/`        — All rendering code, split into sub-modules
 
 pub mod event_loop;
 pub mod input;
+pub mod launcher;
 pub mod models;
 pub mod state;
+pub mod theme;
 pub mod ui;
@@ -136,6 +136,26 @@ impl SortField {
         }
     }
 
+    /// Name stored in `tui_state.sort_field`
+    pub fn key(&self) -> &'static str {
+        match self {
+            SortField::Name => "name",
+            SortField::Host => "host",
+            SortField::LastUsed => "last_used",
+            SortField::Created => "created",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "name" => Some(SortField::Name),
+            "host" => Some(SortField::Host),
+            "last_used" => Some(SortField::LastUsed),
+            "created" => Some(SortField::Created),
+            _ => None,
+        }
+    }
+
     pub fn next(&self) -> Self {
         match self {
             SortField::Name => SortField::Host,
@@ -333,10 +353,13 @@ impl TunnelEntry {
     }
 }
 
-/// Action to perform after TUI exits
+/// Action requested for `App::selected_connection`
 #[derive(Debug, Clone)]
 pub enum PendingAction {
+    /// Quit the TUI and connect in the foreground
     Connect,
+    /// Connect in a new terminal window and keep the TUI running
+    OpenDetached,
 }
 
 /// Sort field for history tab
@@ -418,7 +441,8 @@ impl MultiSelectState {
 pub enum PingStatus {
     Checking,
     Reachable(std::time::Duration),
-    Unreachable,
+    /// Refused, timed out or failed; the reason is shown in the status bar
+    Unreachable(String),
 }
 
 // ─── SFTP file browser ───────────────────────────────────────────────────────
@@ -2,18 +2,25 @@
 
 use crate::config::AppConfig;
 use crate::database::Database;
-use crate::models::connection::Connection;
+use crate::models::connection::{Connection, SshCommandMode};
 use crate::models::session::SessionHistoryEntry;
 use crate::services::ping;
+use crate::services::transport::SubprocessTransport;
+use crate::tui::launcher;
 use crate::tui::models::*;
+use crate::tui::theme::Theme;
 use anyhow::Result;
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, Semaphore};
 
 mod sftp;
 mod tunnels;
 
+/// Maximum number of reachability checks in flight at once
+const PING_CONCURRENCY: usize = 8;
+
 /// Main TUI application state
 pub struct App {
     // -- Active tab --
@@ -24,6 +31,8 @@ pub struct App {
     pub filtered_connections: Vec<Connection>,
     pub search_query: String,
     pub selected_index: usize,
+    /// First connection shown in the list viewport
+    pub scroll_offset: usize,
     pub mode: AppMode,
     pub should_quit: bool,
     pub selected_connection: Option<Connection>,
@@ -31,6 +40,8 @@ pub struct App {
     pub sort_field: SortField,
     pub sort_direction: SortDirection,
     pub compact_view: bool,
+    /// Rows the tab body shows inside its borders, updated on resize
+    pub body_rows: usize,
     pub edit_state: Option<EditState>,
     pub group_mode: GroupMode,
     pub grouped_connections: Vec<(String, Vec<Connection>)>,
@@ -42,6 +53,8 @@ pub struct App {
     pub ping_tx: mpsc::UnboundedSender<(String, PingStatus)>,
     /// Receiver half of the ping result channel (drained each event loop tick)
     pub ping_rx: mpsc::UnboundedReceiver<(String, PingStatus)>,
+    /// Caps how many ping tasks hold a socket open concurrently
+    pub ping_limiter: Arc<Semaphore>,
 
     // -- Files tab state --
     pub files_state: Option<FilesTabState>,
@@ -82,6 +95,12 @@ pub struct App {
     pub config_editing: Option<ConfigEditState>,
 
     // -- Shared --
+    /// System clipboard, opened on first yank (kept alive so X11 can serve it)
+    pub clipboard: Option<arboard::Clipboard>,
+    /// Yanked commands that could not reach a clipboard; printed after exit
+    pub yank_fallback: Vec<String>,
+    /// Color preset from `config.theme`
+    pub theme: Theme,
     pub status_message: Option<String>,
     pub status_set_at: Option<Instant>,
     pub config: AppConfig,
@@ -97,12 +116,13 @@ impl App {
     /// Create a new App instance
     pub fn new(config: AppConfig) -> Result<Self> {
         let db = Database::new(&config)?;
-        let connections = db.list_connections(None, false)?;
+        // Loaded with aliases, which the search matches too
+        let connections = db.load_search_candidates()?;
         let filtered_connections = connections.clone();
 
         // Load history
         let history_entries = db
-            .get_session_history(None, 100, None, false)
+            .get_session_history(None, 100, None, None, false, None)
             .unwrap_or_default();
 
         // Load environments
@@ -117,12 +137,13 @@ impl App {
         // Tunnel result channel
         let (tunnel_tx, tunnel_rx) = mpsc::unbounded_channel();
 
-        Ok(Self {
+        let mut app = Self {
             active_tab: Tab::Connections,
             connections,
             filtered_connections,
             search_query: String::new(),
             selected_index: 0,
+            scroll_offset: 0,
             mode: AppMode::Normal,
             should_quit: false,
             selected_connection: None,
@@ -130,6 +151,7 @@ impl App {
             sort_field: SortField::Name,
             sort_direction: SortDirection::Asc,
             compact_view: false,
+            body_rows: 10,
             edit_state: None,
             group_mode: GroupMode::None,
             grouped_connections: Vec::new(),
@@ -139,6 +161,7 @@ impl App {
             ping_statuses: HashMap::new(),
             ping_tx,
             ping_rx,
+            ping_limiter: Arc::new(Semaphore::new(PING_CONCURRENCY)),
 
             files_state: None,
             files_prompt_input: String::new(),
@@ -164,10 +187,105 @@ impl App {
             active_env,
             config_editing: None,
 
+            clipboard: None,
+            yank_fallback: Vec::new(),
+            theme: Theme::from_config(&config.theme),
             status_message: Some("Press ? for help, / to search, Tab to switch tabs".to_string()),
             status_set_at: Some(Instant::now()),
             config,
-        })
+        };
+        app.restore_tui_state();
+        Ok(app)
+    }
+
+    /// Reopen the connection list the way `tui_state` says it was left
+    fn restore_tui_state(&mut self) {
+        let state = self.config.tui_state.clone();
+        if let Some(field) = state.sort_field.as_deref() {
+            match SortField::from_key(field) {
+                Some(field) => {
+                    self.sort_field = field;
+                    if state.sort_descending {
+                        self.sort_direction = SortDirection::Desc;
+                    }
+                    self.apply_sort();
+                }
+                None => tracing::warn!("Unknown TUI sort field '{}', ignoring", field),
+            }
+        }
+        self.compact_view = state.compact_view;
+        if state.show_details && !self.filtered_connections.is_empty() {
+            self.mode = AppMode::Detail;
+        }
+    }
+
+    /// Remember the sort, detail pane, view and theme for the next start.
+    /// Only those keys are written: the rest of the config is re-read from
+    /// disk so one-off overrides such as `--database` are not persisted.
+    pub fn save_tui_state(&self) -> Result<()> {
+        let mut config = AppConfig::load(Some(self.config.environment.clone()))?;
+        config.tui_state = crate::config::TuiState {
+            sort_field: Some(self.sort_field.key().to_string()),
+            sort_descending: self.sort_direction == SortDirection::Desc,
+            show_details: self.mode == AppMode::Detail,
+            compact_view: self.compact_view,
+        };
+        config.theme = self.config.theme.clone();
+        config.save()
+    }
+
+    /// Switch to the next color preset
+    pub fn cycle_theme(&mut self) {
+        let names = Theme::NAMES;
+        let next = names
+            .iter()
+            .position(|name| *name == self.config.theme)
+            .map_or(0, |i| (i + 1) % names.len());
+        self.config.theme = names[next].to_string();
+        self.theme = Theme::from_config(&self.config.theme);
+        self.set_status(format!("Theme: {}", self.config.theme));
+    }
+
+    /// Track a new terminal height and keep every selection in range
+    pub fn handle_resize(&mut self, height: u16) {
+        // Header and status bar take 3 rows each, the body's borders 2
+        self.body_rows = (height as usize).saturating_sub(8).max(1);
+        self.selected_index = self
+            .selected_index
+            .min(self.filtered_connections.len().saturating_sub(1));
+        self.history_selected = self
+            .history_selected
+            .min(self.history_entries.len().saturating_sub(1));
+        self.env_selected = self.env_selected.min(self.env_list.len().saturating_sub(1));
+        self.keep_selection_visible();
+    }
+
+    /// How far PgUp/PgDn move: one screen of items
+    pub fn page_step(&self) -> usize {
+        match self.active_tab {
+            Tab::Connections => self.visible_connections(),
+            _ => self.body_rows.max(1),
+        }
+    }
+
+    /// Connections that fit in the list at once (expanded rows take two lines)
+    pub fn visible_connections(&self) -> usize {
+        let item_rows = if self.compact_view { 1 } else { 2 };
+        (self.body_rows / item_rows).max(1)
+    }
+
+    /// Move `scroll_offset` just enough to show the selected connection,
+    /// without leaving blank rows below the last one
+    pub fn keep_selection_visible(&mut self) {
+        let visible = self.visible_connections();
+        if self.selected_index < self.scroll_offset {
+            self.scroll_offset = self.selected_index;
+        } else if self.selected_index >= self.scroll_offset + visible {
+            self.scroll_offset = self.selected_index + 1 - visible;
+        }
+        self.scroll_offset = self
+            .scroll_offset
+            .min(self.filtered_connections.len().saturating_sub(visible));
     }
 
     /// Set a status message with auto-clear timer
@@ -195,8 +313,8 @@ impl App {
                 PingStatus::Reachable(dur) => {
                     self.set_status(format!("{}: reachable ({:.0?})", name, dur));
                 }
-                PingStatus::Unreachable => {
-                    self.set_status(format!("{}: unreachable", name));
+                PingStatus::Unreachable(reason) => {
+                    self.set_status(format!("{}: {}", name, reason));
                 }
                 _ => {}
             }
@@ -206,32 +324,52 @@ impl App {
 
     /// Spawn an async TCP ping for a connection.
     ///
-    /// For connections behind a bastion, pings the bastion host on port 22.
+    /// For connections behind a bastion, pings the bastion host.
     /// For direct connections, pings `host:port`.
     pub fn spawn_ping(&self, conn: &Connection) {
         let name = conn.name.clone();
-        let (host, port) = if let Some(bastion) = &conn.bastion {
-            (bastion.clone(), 22u16)
-        } else {
-            (conn.host.clone(), conn.port)
-        };
+        let (host, port) = conn.probe_endpoint();
         let tx = self.ping_tx.clone();
+        let limiter = Arc::clone(&self.ping_limiter);
 
         tokio::spawn(async move {
+            // The semaphore is never closed, so acquiring only fails on shutdown
+            let Ok(_permit) = limiter.acquire_owned().await else {
+                return;
+            };
             let result = ping::tcp_ping(&host, port, 5).await;
             let status = match result {
                 ping::PingResult::Reachable(dur) => PingStatus::Reachable(dur),
-                ping::PingResult::Unreachable => PingStatus::Unreachable,
+                other => PingStatus::Unreachable(other.to_string()),
             };
             // Ignore send errors (receiver dropped = app is shutting down)
             let _ = tx.send((name, status));
         });
     }
 
+    /// Check reachability of every visible connection in the background.
+    ///
+    /// Each connection is marked as checking immediately; results arrive
+    /// through the ping channel as they complete, at most
+    /// `PING_CONCURRENCY` at a time.
+    pub fn spawn_ping_all(&mut self) {
+        let conns = self.filtered_connections.clone();
+        for conn in &conns {
+            self.ping_statuses
+                .insert(conn.name.clone(), PingStatus::Checking);
+            self.spawn_ping(conn);
+        }
+        self.set_status(format!(
+            "Checking reachability of {} host(s)...",
+            conns.len()
+        ));
+    }
+
     /// Refresh connections from database
     pub fn refresh_connections(&mut self) -> Result<()> {
         let db = Database::new(&self.config)?;
-        self.connections = db.list_connections(None, false)?;
+        // Loaded with aliases, which the search matches too
+        self.connections = db.load_search_candidates()?;
         self.apply_filter();
         self.apply_sort();
         if self.group_mode != GroupMode::None {
@@ -249,7 +387,7 @@ impl App {
             Some(self.history_filter.as_str())
         };
         self.history_entries =
-            db.get_session_history(filter, 100, None, self.history_show_failed_only)?;
+            db.get_session_history(filter, 100, None, None, self.history_show_failed_only, None)?;
         if self.history_selected >= self.history_entries.len() {
             self.history_selected = self.history_entries.len().saturating_sub(1);
         }
@@ -308,32 +446,41 @@ impl App {
                     .collect();
             }
         } else {
-            let query = self.search_query.to_lowercase();
-            self.filtered_connections = self
-                .connections
-                .iter()
-                .filter(|c| {
-                    c.name.to_lowercase().contains(&query)
-                        || c.host.to_lowercase().contains(&query)
-                        || c.user.to_lowercase().contains(&query)
-                        || c.tags.iter().any(|t| t.to_lowercase().contains(&query))
-                })
-                .cloned()
-                .collect();
+            // Same matcher and ranking as the CLI, best match first
+            self.filtered_connections = match Database::new(&self.config) {
+                Ok(db) => db.search_candidates(
+                    &self.connections,
+                    &self.search_query,
+                    self.connections.len(),
+                    &self.config.search_mode,
+                ),
+                Err(e) => {
+                    self.set_status(format!("Search failed: {}", e));
+                    Vec::new()
+                }
+            };
         }
 
         if self.selected_index >= self.filtered_connections.len() {
             self.selected_index = self.filtered_connections.len().saturating_sub(1);
         }
+        self.keep_selection_visible();
 
         // Clear multi-select when filter changes
         self.multi_select.clear();
     }
 
-    /// Sort filtered connections based on current sort settings
+    /// Whether `filtered_connections` holds search results in score order
+    pub fn is_ranked_search(&self) -> bool {
+        !self.search_query.is_empty() && !self.search_query.starts_with("tag:")
+    }
+
+    /// Sort filtered connections based on current sort settings. Search
+    /// results keep their ranking; the sort applies again once it is cleared.
     pub fn apply_sort(&mut self) {
         let dir = self.sort_direction;
         match self.sort_field {
+            _ if self.is_ranked_search() => {}
             SortField::Name => {
                 self.filtered_connections.sort_by(|a, b| {
                     let cmp = a.name.to_lowercase().cmp(&b.name.to_lowercase());
@@ -379,6 +526,7 @@ impl App {
         if self.selected_index >= self.filtered_connections.len() {
             self.selected_index = self.filtered_connections.len().saturating_sub(1);
         }
+        self.keep_selection_visible();
     }
 
     /// Build grouped connection list by tags
@@ -397,7 +545,7 @@ impl App {
         }
 
         let mut result: Vec<(String, Vec<Connection>)> = groups.into_iter().collect();
-        result.sort_by(|a, b| a.0.to_lowercase().cmp(&b.0.to_lowercase()));
+        result.sort_by_key(|a| a.0.to_lowercase());
 
         if !ungrouped.is_empty() {
             result.push(("Ungrouped".to_string(), ungrouped));
@@ -413,6 +561,7 @@ impl App {
                 if self.selected_index > 0 {
                     self.selected_index -= 1;
                 }
+                self.keep_selection_visible();
             }
             Tab::History => {
                 if self.history_selected > 0 {
@@ -442,6 +591,7 @@ impl App {
                 if self.selected_index < self.filtered_connections.len().saturating_sub(1) {
                     self.selected_index += 1;
                 }
+                self.keep_selection_visible();
             }
             Tab::History => {
                 if self.history_selected < self.history_entries.len().saturating_sub(1) {
@@ -466,9 +616,139 @@ impl App {
         }
     }
 
+    /// Handle `PendingAction::OpenDetached`: launch the selected connection
+    /// in a new terminal window and clear the request.
+    pub fn open_detached(&mut self) {
+        self.pending_action = None;
+        let Some(connection) = self.selected_connection.take() else {
+            return;
+        };
+        match launcher::open_in_terminal(&connection, &self.config) {
+            Ok(terminal) => self.set_status(format!("Opened {} in {}", connection.name, terminal)),
+            Err(e) => self.set_status(format!("Could not open a terminal: {}", e)),
+        }
+    }
+
+    /// Copy the selected connection's ssh command to the clipboard, or keep
+    /// it for printing on exit when no clipboard is available (headless).
+    pub fn yank_ssh_command(&mut self) {
+        let Some(cmd) = self.get_selected_connection().map(|c| {
+            SubprocessTransport::display_command(&self.config, c, SshCommandMode::Interactive)
+        }) else {
+            return;
+        };
+
+        if self.clipboard.is_none() {
+            self.clipboard = arboard::Clipboard::new().ok();
+        }
+        let copied = self
+            .clipboard
+            .as_mut()
+            .is_some_and(|cb| cb.set_text(cmd.clone()).is_ok());
+
+        if copied {
+            self.set_status(format!("Copied: {}", cmd));
+        } else {
+            self.set_status("No clipboard available; the command will be printed on exit");
+            self.yank_fallback.push(cmd);
+        }
+    }
+
     /// Get the currently selected connection
-    #[allow(dead_code)]
     pub fn get_selected_connection(&self) -> Option<&Connection> {
         self.filtered_connections.get(self.selected_index)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app_with(count: usize, dir: &std::path::Path) -> App {
+        let config = AppConfig {
+            database_path: dir.join("history.db"),
+            ..AppConfig::default()
+        };
+        let mut app = App::new(config).expect("app should start");
+        app.filtered_connections = (0..count)
+            .map(|i| {
+                Connection::new(
+                    format!("web{i:02}"),
+                    "web.corp".into(),
+                    "deploy".into(),
+                    22,
+                    None,
+                    None,
+                    false,
+                    None,
+                )
+            })
+            .collect();
+        app
+    }
+
+    #[test]
+    fn the_viewport_follows_the_selection() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = app_with(50, dir.path());
+        app.compact_view = true;
+        app.handle_resize(18); // 10 rows of list
+
+        for _ in 0..12 {
+            app.move_selection_down();
+        }
+        assert_eq!((app.selected_index, app.scroll_offset), (12, 3));
+
+        // Going back up only scrolls once the selection leaves the top
+        for _ in 0..5 {
+            app.move_selection_up();
+        }
+        assert_eq!((app.selected_index, app.scroll_offset), (7, 3));
+
+        // Expanded rows take two lines, so half as many fit
+        app.compact_view = false;
+        app.selected_index = 49;
+        app.keep_selection_visible();
+        assert_eq!(app.scroll_offset, 45);
+
+        // A taller terminal never leaves blank rows under the last item
+        app.handle_resize(200);
+        assert_eq!(app.scroll_offset, 0);
+    }
+
+    #[test]
+    fn the_saved_view_is_restored() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = AppConfig {
+            database_path: dir.path().join("history.db"),
+            ..AppConfig::default()
+        };
+        let db = Database::new(&config).unwrap();
+        for (name, host) in [("api", "b.corp"), ("web", "a.corp")] {
+            let conn = Connection::new(
+                name.into(),
+                host.into(),
+                "deploy".into(),
+                22,
+                None,
+                None,
+                false,
+                None,
+            );
+            db.add_connection(&conn).unwrap();
+        }
+        config.tui_state = crate::config::TuiState {
+            sort_field: Some("host".into()),
+            sort_descending: true,
+            show_details: true,
+            compact_view: true,
+        };
+
+        let app = App::new(config).unwrap();
+        assert_eq!(app.sort_field, SortField::Host);
+        assert_eq!(app.sort_direction, SortDirection::Desc);
+        assert_eq!(app.filtered_connections[0].name, "api");
+        assert_eq!(app.mode, AppMode::Detail);
+        assert!(app.compact_view);
+    }
+}
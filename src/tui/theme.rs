@@ -0,0 +1,140 @@
+//! TUI color themes
+//!
+//! Render code picks colors by role (`theme.accent`, `theme.muted`, …)
+//! instead of literal `Color::` values, so a preset can restyle the whole UI.
+
+use ratatui::style::Color;
+
+/// Color roles used by the `ui/` render functions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Regular foreground text
+    pub text: Color,
+    /// Labels, borders and hints
+    pub muted: Color,
+    /// Secondary body text (status bar message)
+    pub subtle: Color,
+    /// Titles, focused borders, warnings
+    pub accent: Color,
+    /// Active tab, focused labels, headings
+    pub primary: Color,
+    pub success: Color,
+    pub error: Color,
+    /// Edit mode and tag highlights
+    pub special: Color,
+    /// Tunnel panel borders
+    pub info: Color,
+    /// Directories in the Files tab
+    pub directory: Color,
+    /// Text drawn on a `primary` background
+    pub on_primary: Color,
+    /// Background of the highlighted row
+    pub selection_bg: Color,
+    /// Background of the highlighted row when it is also multi-selected
+    pub multi_selection_bg: Color,
+    /// Background of multi-selected rows
+    pub marked_bg: Color,
+    /// Background of every other row
+    pub stripe_bg: Color,
+}
+
+impl Theme {
+    /// Names accepted by `config.theme`
+    pub const NAMES: &'static [&'static str] = &["default", "solarized", "mono"];
+
+    /// Look up a preset by name
+    pub fn named(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Self::default()),
+            "solarized" => Some(Self::solarized()),
+            "mono" => Some(Self::mono()),
+            _ => None,
+        }
+    }
+
+    /// Preset for `name`, falling back to the default theme for unknown names
+    pub fn from_config(name: &str) -> Self {
+        Self::named(name).unwrap_or_else(|| {
+            tracing::warn!("Unknown TUI theme '{}', using default", name);
+            Self::default()
+        })
+    }
+
+    /// Solarized dark palette
+    pub fn solarized() -> Self {
+        Self {
+            text: Color::Rgb(147, 161, 161),
+            muted: Color::Rgb(88, 110, 117),
+            subtle: Color::Rgb(131, 148, 150),
+            accent: Color::Rgb(181, 137, 0),
+            primary: Color::Rgb(42, 161, 152),
+            success: Color::Rgb(133, 153, 0),
+            error: Color::Rgb(220, 50, 47),
+            special: Color::Rgb(211, 54, 130),
+            info: Color::Rgb(38, 139, 210),
+            directory: Color::Rgb(38, 139, 210),
+            on_primary: Color::Rgb(0, 43, 54),
+            selection_bg: Color::Rgb(7, 54, 66),
+            multi_selection_bg: Color::Rgb(55, 50, 100),
+            marked_bg: Color::Rgb(30, 40, 70),
+            stripe_bg: Color::Rgb(0, 36, 46),
+        }
+    }
+
+    /// No hues at all: only the terminal's basic grays, so it works on
+    /// 16-color terminals and does not rely on telling colors apart.
+    pub fn mono() -> Self {
+        Self {
+            text: Color::White,
+            muted: Color::DarkGray,
+            subtle: Color::Gray,
+            accent: Color::White,
+            primary: Color::White,
+            success: Color::White,
+            error: Color::White,
+            special: Color::White,
+            info: Color::Gray,
+            directory: Color::White,
+            on_primary: Color::Black,
+            selection_bg: Color::DarkGray,
+            multi_selection_bg: Color::Gray,
+            marked_bg: Color::Black,
+            stripe_bg: Color::Reset,
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            text: Color::White,
+            muted: Color::DarkGray,
+            subtle: Color::Gray,
+            accent: Color::Yellow,
+            primary: Color::Cyan,
+            success: Color::Green,
+            error: Color::Red,
+            special: Color::Magenta,
+            info: Color::Blue,
+            directory: Color::LightBlue,
+            on_primary: Color::Black,
+            selection_bg: Color::Rgb(30, 60, 90),
+            multi_selection_bg: Color::Rgb(60, 40, 90),
+            marked_bg: Color::Rgb(40, 30, 60),
+            stripe_bg: Color::Rgb(20, 20, 30),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_listed_name_resolves() {
+        for name in Theme::NAMES {
+            assert!(Theme::named(name).is_some(), "{name}");
+        }
+        assert_eq!(Theme::from_config("nope"), Theme::default());
+    }
+}
@@ -20,6 +20,7 @@ pub fn draw_config_tab(frame: &mut Frame, area: Rect, app: &App) {
 }
 
 fn draw_env_list(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
     let items: Vec<ListItem> = app
         .env_list
         .iter()
@@ -31,9 +32,9 @@ fn draw_env_list(frame: &mut Frame, area: Rect, app: &App) {
             let marker = if is_active { " * " } else { "   " };
             let label = format!("{}{}", marker, env);
 
-            let mut style = item_style(i, is_selected, false);
+            let mut style = item_style(i, is_selected, false, theme);
             if is_active && !is_selected {
-                style = style.fg(Color::Green).bold();
+                style = style.fg(theme.success).bold();
             }
 
             ListItem::new(label).style(style)
@@ -44,15 +45,15 @@ fn draw_env_list(frame: &mut Frame, area: Rect, app: &App) {
         .block(
             Block::default()
                 .title(" Environments ")
-                .title_style(Style::default().fg(Color::Yellow).bold())
+                .title_style(Style::default().fg(theme.accent).bold())
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray))
+                .border_style(Style::default().fg(theme.muted))
                 .padding(Padding::horizontal(1)),
         )
         .highlight_style(
             Style::default()
-                .fg(Color::White)
-                .bg(Color::Rgb(30, 60, 90))
+                .fg(theme.text)
+                .bg(theme.selection_bg)
                 .bold(),
         )
         .highlight_symbol("▶ ");
@@ -64,47 +65,48 @@ fn draw_env_list(frame: &mut Frame, area: Rect, app: &App) {
 }
 
 fn draw_config_details(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
     let config = &app.config;
 
     let mut lines: Vec<Line> = Vec::new();
 
     lines.push(Line::from(""));
     lines.push(Line::from(vec![
-        Span::styled("  Environment:   ", Style::default().fg(Color::DarkGray)),
+        Span::styled("  Environment:   ", Style::default().fg(theme.muted)),
         Span::styled(
             &config.environment,
-            Style::default().fg(Color::Green).bold(),
+            Style::default().fg(theme.success).bold(),
         ),
     ]));
     lines.push(Line::from(""));
     lines.push(Line::from(vec![Span::styled(
         "  Connection Defaults",
-        Style::default().fg(Color::Cyan).bold(),
+        Style::default().fg(theme.primary).bold(),
     )]));
     lines.push(Line::from(vec![Span::styled(
         "  ────────────────────────────────",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme.muted),
     )]));
     lines.push(Line::from(vec![
-        Span::styled("  Default User:    ", Style::default().fg(Color::DarkGray)),
-        Span::styled(&config.default_user, Style::default().fg(Color::White)),
+        Span::styled("  Default User:    ", Style::default().fg(theme.muted)),
+        Span::styled(&config.default_user, Style::default().fg(theme.text)),
     ]));
     lines.push(Line::from(vec![
-        Span::styled("  Default Port:    ", Style::default().fg(Color::DarkGray)),
+        Span::styled("  Default Port:    ", Style::default().fg(theme.muted)),
         Span::styled(
             config.default_port.to_string(),
-            Style::default().fg(Color::White),
+            Style::default().fg(theme.text),
         ),
     ]));
     lines.push(Line::from(vec![
-        Span::styled("  Default Bastion: ", Style::default().fg(Color::DarkGray)),
+        Span::styled("  Default Bastion: ", Style::default().fg(theme.muted)),
         Span::styled(
             config.default_bastion.as_deref().unwrap_or("None"),
-            Style::default().fg(Color::White),
+            Style::default().fg(theme.text),
         ),
     ]));
     lines.push(Line::from(vec![
-        Span::styled("  Kerberos:        ", Style::default().fg(Color::DarkGray)),
+        Span::styled("  Kerberos:        ", Style::default().fg(theme.muted)),
         Span::styled(
             if config.use_kerberos_by_default {
                 "Enabled"
@@ -112,9 +114,9 @@ fn draw_config_details(frame: &mut Frame, area: Rect, app: &App) {
                 "Disabled"
             },
             Style::default().fg(if config.use_kerberos_by_default {
-                Color::Yellow
+                theme.accent
             } else {
-                Color::White
+                theme.text
             }),
         ),
     ]));
@@ -122,62 +124,62 @@ fn draw_config_details(frame: &mut Frame, area: Rect, app: &App) {
     lines.push(Line::from(""));
     lines.push(Line::from(vec![Span::styled(
         "  Search & Storage",
-        Style::default().fg(Color::Cyan).bold(),
+        Style::default().fg(theme.primary).bold(),
     )]));
     lines.push(Line::from(vec![Span::styled(
         "  ────────────────────────────────",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme.muted),
     )]));
     lines.push(Line::from(vec![
-        Span::styled("  Search Mode:     ", Style::default().fg(Color::DarkGray)),
-        Span::styled(&config.search_mode, Style::default().fg(Color::White)),
+        Span::styled("  Search Mode:     ", Style::default().fg(theme.muted)),
+        Span::styled(&config.search_mode, Style::default().fg(theme.text)),
     ]));
     lines.push(Line::from(vec![
-        Span::styled("  Log Level:       ", Style::default().fg(Color::DarkGray)),
-        Span::styled(&config.log_level, Style::default().fg(Color::White)),
+        Span::styled("  Log Level:       ", Style::default().fg(theme.muted)),
+        Span::styled(&config.log_level, Style::default().fg(theme.text)),
     ]));
     lines.push(Line::from(vec![
-        Span::styled("  Auto Save:       ", Style::default().fg(Color::DarkGray)),
+        Span::styled("  Auto Save:       ", Style::default().fg(theme.muted)),
         Span::styled(
             if config.auto_save_history {
                 "Yes"
             } else {
                 "No"
             },
-            Style::default().fg(Color::White),
+            Style::default().fg(theme.text),
         ),
     ]));
     lines.push(Line::from(vec![
-        Span::styled("  Max History:     ", Style::default().fg(Color::DarkGray)),
+        Span::styled("  Max History:     ", Style::default().fg(theme.muted)),
         Span::styled(
             config.max_history_size.to_string(),
-            Style::default().fg(Color::White),
+            Style::default().fg(theme.text),
         ),
     ]));
 
     lines.push(Line::from(""));
     lines.push(Line::from(vec![Span::styled(
         "  Database",
-        Style::default().fg(Color::Cyan).bold(),
+        Style::default().fg(theme.primary).bold(),
     )]));
     lines.push(Line::from(vec![Span::styled(
         "  ────────────────────────────────",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme.muted),
     )]));
     lines.push(Line::from(vec![
-        Span::styled("  DB Path:         ", Style::default().fg(Color::DarkGray)),
+        Span::styled("  DB Path:         ", Style::default().fg(theme.muted)),
         Span::styled(
             config.database_path.display().to_string(),
-            Style::default().fg(Color::DarkGray).italic(),
+            Style::default().fg(theme.muted).italic(),
         ),
     ]));
 
     let detail = Paragraph::new(Text::from(lines)).block(
         Block::default()
             .title(" Configuration ")
-            .title_style(Style::default().fg(Color::Yellow).bold())
+            .title_style(Style::default().fg(theme.accent).bold())
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::DarkGray))
+            .border_style(Style::default().fg(theme.muted))
             .padding(Padding::vertical(0)),
     );
 
@@ -1,6 +1,8 @@
 //! Connection detail pane rendering
 
 use crate::cli::utils::format_duration;
+use crate::models::SshCommandMode;
+use crate::services::transport::SubprocessTransport;
 use crate::tui::state::App;
 use ratatui::{
     prelude::*,
@@ -9,6 +11,7 @@ use ratatui::{
 
 /// Draw the detail pane for the selected connection
 pub fn draw_detail_pane(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
     let conn = match app.filtered_connections.get(app.selected_index) {
         Some(c) => c,
         None => return,
@@ -17,52 +20,52 @@ pub fn draw_detail_pane(frame: &mut Frame, area: Rect, app: &App) {
     let mut lines: Vec<Line> = Vec::new();
 
     lines.push(Line::from(vec![
-        Span::styled("  Name:     ", Style::default().fg(Color::DarkGray)),
-        Span::styled(&conn.name, Style::default().fg(Color::White).bold()),
+        Span::styled("  Name:     ", Style::default().fg(theme.muted)),
+        Span::styled(&conn.name, Style::default().fg(theme.text).bold()),
     ]));
     lines.push(Line::from(""));
     lines.push(Line::from(vec![
-        Span::styled("  Host:     ", Style::default().fg(Color::DarkGray)),
+        Span::styled("  Host:     ", Style::default().fg(theme.muted)),
         Span::styled(
             format!("{}:{}", conn.host, conn.port),
-            Style::default().fg(Color::White),
+            Style::default().fg(theme.text),
         ),
     ]));
     lines.push(Line::from(vec![
-        Span::styled("  User:     ", Style::default().fg(Color::DarkGray)),
-        Span::styled(&conn.user, Style::default().fg(Color::White)),
+        Span::styled("  User:     ", Style::default().fg(theme.muted)),
+        Span::styled(&conn.user, Style::default().fg(theme.text)),
     ]));
     lines.push(Line::from(""));
 
     if let Some(bastion) = &conn.bastion {
         let bastion_user = conn.bastion_user.as_deref().unwrap_or(&conn.user);
         lines.push(Line::from(vec![
-            Span::styled("  Bastion:  ", Style::default().fg(Color::DarkGray)),
+            Span::styled("  Bastion:  ", Style::default().fg(theme.muted)),
             Span::styled(
                 format!("{}@{}", bastion_user, bastion),
-                Style::default().fg(Color::Green),
+                Style::default().fg(theme.success),
             ),
         ]));
     } else {
         lines.push(Line::from(vec![
-            Span::styled("  Bastion:  ", Style::default().fg(Color::DarkGray)),
-            Span::styled("None", Style::default().fg(Color::DarkGray).italic()),
+            Span::styled("  Bastion:  ", Style::default().fg(theme.muted)),
+            Span::styled("None", Style::default().fg(theme.muted).italic()),
         ]));
     }
 
     lines.push(Line::from(vec![
-        Span::styled("  Kerberos: ", Style::default().fg(Color::DarkGray)),
+        Span::styled("  Kerberos: ", Style::default().fg(theme.muted)),
         if conn.use_kerberos {
-            Span::styled("Enabled", Style::default().fg(Color::Yellow))
+            Span::styled("Enabled", Style::default().fg(theme.accent))
         } else {
-            Span::styled("Disabled", Style::default().fg(Color::DarkGray).italic())
+            Span::styled("Disabled", Style::default().fg(theme.muted).italic())
         },
     ]));
 
     if let Some(key) = &conn.key_path {
         lines.push(Line::from(vec![
-            Span::styled("  SSH Key:  ", Style::default().fg(Color::DarkGray)),
-            Span::styled(key, Style::default().fg(Color::White)),
+            Span::styled("  SSH Key:  ", Style::default().fg(theme.muted)),
+            Span::styled(key, Style::default().fg(theme.text)),
         ]));
     }
 
@@ -70,62 +73,79 @@ pub fn draw_detail_pane(frame: &mut Frame, area: Rect, app: &App) {
 
     if !conn.tags.is_empty() {
         lines.push(Line::from(vec![
-            Span::styled("  Tags:     ", Style::default().fg(Color::DarkGray)),
+            Span::styled("  Tags:     ", Style::default().fg(theme.muted)),
             Span::styled(
                 conn.tags.join(", "),
-                Style::default().fg(Color::Magenta).italic(),
+                Style::default().fg(theme.special).italic(),
             ),
         ]));
     }
 
     if !conn.aliases.is_empty() {
         lines.push(Line::from(vec![
-            Span::styled("  Aliases:  ", Style::default().fg(Color::DarkGray)),
-            Span::styled(conn.aliases.join(", "), Style::default().fg(Color::Cyan)),
+            Span::styled("  Aliases:  ", Style::default().fg(theme.muted)),
+            Span::styled(conn.aliases.join(", "), Style::default().fg(theme.primary)),
         ]));
     }
 
     lines.push(Line::from(""));
     lines.push(Line::from(vec![
-        Span::styled("  Created:  ", Style::default().fg(Color::DarkGray)),
+        Span::styled("  Created:  ", Style::default().fg(theme.muted)),
         Span::styled(
             conn.created_at.format("%Y-%m-%d %H:%M UTC").to_string(),
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
         ),
     ]));
 
     if let Some(last_used) = conn.last_used {
         lines.push(Line::from(vec![
-            Span::styled("  Last use: ", Style::default().fg(Color::DarkGray)),
+            Span::styled("  Last use: ", Style::default().fg(theme.muted)),
+            Span::styled(format_duration(last_used), Style::default().fg(theme.text)),
+        ]));
+    }
+
+    if let Some(error) = &conn.last_error {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("  ✗ Error:  ", Style::default().fg(theme.error).bold()),
             Span::styled(
-                format_duration(last_used),
-                Style::default().fg(Color::White),
+                conn.last_error_at.map(format_duration).unwrap_or_default(),
+                Style::default().fg(theme.muted),
             ),
         ]));
+        for line in error.lines() {
+            lines.push(Line::from(Span::styled(
+                format!("    {}", line),
+                Style::default().fg(theme.error),
+            )));
+        }
     }
 
     lines.push(Line::from(""));
     lines.push(Line::from(vec![Span::styled(
         "  SSH Command:",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme.muted),
     )]));
     lines.push(Line::from(vec![Span::styled(
-        format!("  {}", conn.to_ssh_command()),
-        Style::default().fg(Color::Green),
+        format!(
+            "  {}",
+            SubprocessTransport::display_command(&app.config, conn, SshCommandMode::Interactive)
+        ),
+        Style::default().fg(theme.success),
     )]));
 
     lines.push(Line::from(""));
     lines.push(Line::from(vec![Span::styled(
         "  Enter connect | e edit | p preview | s close",
-        Style::default().fg(Color::DarkGray).italic(),
+        Style::default().fg(theme.muted).italic(),
     )]));
 
     let detail = Paragraph::new(Text::from(lines)).block(
         Block::default()
             .title(" Detail ")
-            .title_style(Style::default().fg(Color::Yellow).bold())
+            .title_style(Style::default().fg(theme.accent).bold())
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Cyan))
+            .border_style(Style::default().fg(theme.primary))
             .padding(Padding::vertical(1)),
     );
 
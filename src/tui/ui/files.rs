@@ -1,5 +1,6 @@
 //! Files tab — SFTP remote file browser rendering
 
+use crate::cli::utils::format_bytes;
 use crate::tui::models::FilesPromptKind;
 use crate::tui::state::App;
 use ratatui::{
@@ -9,6 +10,7 @@ use ratatui::{
 
 /// Draw the Files tab.
 pub fn draw_files_tab(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
     let fs = match app.files_state.as_ref() {
         Some(s) => s,
         None => {
@@ -16,7 +18,7 @@ pub fn draw_files_tab(frame: &mut Frame, area: Rect, app: &App) {
             let para = Paragraph::new(
                 "No connection selected.\n\nPress Shift+F on a connection to open the file browser.",
             )
-            .style(Style::default().fg(Color::DarkGray))
+            .style(Style::default().fg(theme.muted))
             .alignment(Alignment::Center)
             .wrap(Wrap { trim: true });
             frame.render_widget(para, area);
@@ -41,14 +43,14 @@ pub fn draw_files_tab(frame: &mut Frame, area: Rect, app: &App) {
     };
 
     let border_color = if fs.error.is_some() {
-        Color::Red
+        theme.error
     } else {
-        Color::Cyan
+        theme.primary
     };
 
     let block = Block::default()
         .title(title)
-        .title_style(Style::default().fg(Color::Cyan).bold())
+        .title_style(Style::default().fg(theme.primary).bold())
         .borders(Borders::ALL)
         .border_style(Style::default().fg(border_color))
         .padding(Padding::horizontal(1));
@@ -56,7 +58,7 @@ pub fn draw_files_tab(frame: &mut Frame, area: Rect, app: &App) {
     if fs.entries.is_empty() && !fs.is_loading {
         let msg = fs.error.as_deref().unwrap_or("Directory is empty.");
         let para = Paragraph::new(msg)
-            .style(Style::default().fg(Color::DarkGray))
+            .style(Style::default().fg(theme.muted))
             .alignment(Alignment::Center)
             .wrap(Wrap { trim: true })
             .block(block);
@@ -85,13 +87,13 @@ pub fn draw_files_tab(frame: &mut Frame, area: Rect, app: &App) {
 
                 let style = if i == fs.selected {
                     Style::default()
-                        .fg(Color::White)
-                        .bg(Color::Rgb(30, 60, 90))
+                        .fg(theme.text)
+                        .bg(theme.selection_bg)
                         .bold()
                 } else if entry.is_dir {
-                    Style::default().fg(Color::LightBlue)
+                    Style::default().fg(theme.directory)
                 } else {
-                    Style::default().fg(Color::White)
+                    Style::default().fg(theme.text)
                 };
 
                 ListItem::new(label).style(style)
@@ -109,7 +111,7 @@ pub fn draw_files_tab(frame: &mut Frame, area: Rect, app: &App) {
     let hints = Span::raw(
         " ↑/k up  ↓/j down  Enter enter  ←/h up  d dl  u upload  D delete  m mkdir  R rename  r refresh  ? help  q quit",
     );
-    let hint_para = Paragraph::new(hints).style(Style::default().fg(Color::DarkGray));
+    let hint_para = Paragraph::new(hints).style(Style::default().fg(theme.muted));
     frame.render_widget(hint_para, chunks[1]);
 }
 
@@ -117,6 +119,7 @@ pub fn draw_files_tab(frame: &mut Frame, area: Rect, app: &App) {
 
 /// Draw a floating prompt dialog for upload path / mkdir name / rename.
 pub fn draw_files_prompt_dialog(frame: &mut Frame, area: Rect, app: &App, kind: &FilesPromptKind) {
+    let theme = &app.theme;
     let popup = centered_rect(60, 8, area);
     frame.render_widget(Clear, popup);
 
@@ -150,9 +153,9 @@ pub fn draw_files_prompt_dialog(frame: &mut Frame, area: Rect, app: &App, kind:
 
     let block = Block::default()
         .title(title)
-        .title_style(Style::default().fg(Color::Yellow).bold())
+        .title_style(Style::default().fg(theme.accent).bold())
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Yellow));
+        .border_style(Style::default().fg(theme.accent));
 
     let inner = block.inner(popup);
     frame.render_widget(block, popup);
@@ -165,19 +168,19 @@ pub fn draw_files_prompt_dialog(frame: &mut Frame, area: Rect, app: &App, kind:
     .areas(inner);
 
     frame.render_widget(
-        Paragraph::new(hint_text).style(Style::default().fg(Color::DarkGray)),
+        Paragraph::new(hint_text).style(Style::default().fg(theme.muted)),
         hint_area,
     );
 
     let input_display = format!(" > {} ", app.files_prompt_input);
     frame.render_widget(
-        Paragraph::new(input_display).style(Style::default().fg(Color::White).bold()),
+        Paragraph::new(input_display).style(Style::default().fg(theme.text).bold()),
         input_area,
     );
 
     frame.render_widget(
         Paragraph::new("Enter to confirm  ·  Esc to cancel")
-            .style(Style::default().fg(Color::DarkGray)),
+            .style(Style::default().fg(theme.muted)),
         note_area,
     );
 }
@@ -193,18 +196,3 @@ fn centered_rect(percent_x: u16, height: u16, area: Rect) -> Rect {
         height.min(area.height),
     )
 }
-
-fn format_bytes(bytes: u64) -> String {
-    const KB: u64 = 1_024;
-    const MB: u64 = 1_024 * KB;
-    const GB: u64 = 1_024 * MB;
-    if bytes >= GB {
-        format!("{:.1} GB", bytes as f64 / GB as f64)
-    } else if bytes >= MB {
-        format!("{:.1} MB", bytes as f64 / MB as f64)
-    } else if bytes >= KB {
-        format!("{:.1} KB", bytes as f64 / KB as f64)
-    } else {
-        format!("{bytes} B")
-    }
-}
@@ -9,6 +9,7 @@ use ratatui::{
 
 /// Draw the header with tab bar
 pub fn draw_header(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
     // Split header into tab bar and info line
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -20,9 +21,9 @@ pub fn draw_header(frame: &mut Frame, area: Rect, app: &App) {
         .iter()
         .map(|t| {
             let style = if *t == app.active_tab {
-                Style::default().fg(Color::Cyan).bold()
+                Style::default().fg(theme.primary).bold()
             } else {
-                Style::default().fg(Color::DarkGray)
+                Style::default().fg(theme.muted)
             };
             Line::from(Span::styled(t.label(), style))
         })
@@ -30,8 +31,8 @@ pub fn draw_header(frame: &mut Frame, area: Rect, app: &App) {
 
     let tabs = Tabs::new(tab_titles)
         .select(app.active_tab.index())
-        .highlight_style(Style::default().fg(Color::Cyan).bold())
-        .divider(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
+        .highlight_style(Style::default().fg(theme.primary).bold())
+        .divider(Span::styled(" │ ", Style::default().fg(theme.muted)));
 
     frame.render_widget(tabs, chunks[0]);
 
@@ -124,16 +125,16 @@ pub fn draw_header(frame: &mut Frame, area: Rect, app: &App) {
     };
 
     let style = match app.mode {
-        AppMode::Search | AppMode::QuickConnect => Style::default().fg(Color::Green).bold(),
-        _ => Style::default().fg(Color::Cyan).bold(),
+        AppMode::Search | AppMode::QuickConnect => Style::default().fg(theme.success).bold(),
+        _ => Style::default().fg(theme.primary).bold(),
     };
 
     let header = Paragraph::new(title).style(style).block(
         Block::default()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(match app.mode {
-                AppMode::Search | AppMode::QuickConnect => Color::Green,
-                _ => Color::DarkGray,
+                AppMode::Search | AppMode::QuickConnect => theme.success,
+                _ => theme.muted,
             })),
     );
 
@@ -1,5 +1,6 @@
 //! UI helper functions and shared utilities
 
+use crate::tui::theme::Theme;
 use ratatui::prelude::*;
 
 /// Helper function to create a centered rect
@@ -10,23 +11,28 @@ pub fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
 }
 
 /// Compute item style with alternating row tint
-pub fn item_style(index: usize, is_selected: bool, is_multi_selected: bool) -> Style {
+pub fn item_style(
+    index: usize,
+    is_selected: bool,
+    is_multi_selected: bool,
+    theme: &Theme,
+) -> Style {
     if is_selected && is_multi_selected {
         Style::default()
-            .fg(Color::White)
-            .bg(Color::Rgb(60, 40, 90))
+            .fg(theme.text)
+            .bg(theme.multi_selection_bg)
             .bold()
     } else if is_selected {
         Style::default()
-            .fg(Color::White)
-            .bg(Color::Rgb(30, 60, 90))
+            .fg(theme.text)
+            .bg(theme.selection_bg)
             .bold()
     } else if is_multi_selected {
-        Style::default().fg(Color::White).bg(Color::Rgb(40, 30, 60))
+        Style::default().fg(theme.text).bg(theme.marked_bg)
     } else if index.is_multiple_of(2) {
-        Style::default().fg(Color::White).bg(Color::Rgb(20, 20, 30))
+        Style::default().fg(theme.text).bg(theme.stripe_bg)
     } else {
-        Style::default().fg(Color::White)
+        Style::default().fg(theme.text)
     }
 }
 
@@ -10,14 +10,15 @@ use ratatui::{
 
 /// Draw the history tab
 pub fn draw_history_tab(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
     let entries = &app.history_entries;
 
     if entries.is_empty() {
         let block = Block::default()
             .title(" Session History ")
-            .title_style(Style::default().fg(Color::Yellow).bold())
+            .title_style(Style::default().fg(theme.accent).bold())
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::DarkGray));
+            .border_style(Style::default().fg(theme.muted));
 
         frame.render_widget(block, area);
 
@@ -28,7 +29,7 @@ pub fn draw_history_tab(frame: &mut Frame, area: Rect, app: &App) {
         };
 
         let empty = Paragraph::new(msg)
-            .style(Style::default().fg(Color::DarkGray))
+            .style(Style::default().fg(theme.muted))
             .alignment(Alignment::Center)
             .wrap(Wrap { trim: true });
 
@@ -40,63 +41,70 @@ pub fn draw_history_tab(frame: &mut Frame, area: Rect, app: &App) {
         return;
     }
 
-    let items: Vec<ListItem> = entries
-        .iter()
-        .enumerate()
-        .map(|(i, entry)| {
-            let is_selected = i == app.history_selected;
-
-            let status_str = match &entry.status {
-                SessionStatus::Terminated => {
-                    match entry.exit_code {
-                        Some(0) => ("OK", Color::Green),
-                        Some(code) => {
-                            // We'll just show the exit code inline
-                            return ListItem::new(format!(
-                                "  {:<20} {:<20} {:>8}   exit:{}",
-                                entry.connection_name,
-                                entry.started_at.format("%Y-%m-%d %H:%M"),
-                                entry
-                                    .duration
-                                    .as_ref()
-                                    .map(format_chrono_duration)
-                                    .unwrap_or_else(|| "-".to_string()),
-                                code,
-                            ))
-                            .style(item_style(i, is_selected, false));
+    let items: Vec<ListItem> =
+        entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| {
+                let is_selected = i == app.history_selected;
+                let name = if entry.ad_hoc {
+                    format!("{} (ad-hoc)", entry.connection_name)
+                } else {
+                    entry.connection_name.clone()
+                };
+
+                let status_str =
+                    match &entry.status {
+                        SessionStatus::Terminated => {
+                            match entry.exit_code {
+                                Some(0) => ("OK", theme.success),
+                                Some(code) => {
+                                    // We'll just show the exit code inline
+                                    return ListItem::new(format!(
+                                        "  {:<20} {:<20} {:>8}   exit:{}",
+                                        name,
+                                        entry.started_at.format("%Y-%m-%d %H:%M"),
+                                        entry
+                                            .duration
+                                            .as_ref()
+                                            .map(format_chrono_duration)
+                                            .unwrap_or_else(|| "-".to_string()),
+                                        code,
+                                    ))
+                                    .style(item_style(i, is_selected, false, theme));
+                                }
+                                None => ("DONE", theme.success),
+                            }
                         }
-                        None => ("DONE", Color::Green),
-                    }
+                        SessionStatus::Active => ("ACTIVE", theme.primary),
+                        SessionStatus::Starting => ("START", theme.accent),
+                        SessionStatus::Disconnected => ("DISC", theme.accent),
+                        SessionStatus::Error(_) => ("ERROR", theme.error),
+                    };
+
+                let duration_str = entry
+                    .duration
+                    .as_ref()
+                    .map(format_chrono_duration)
+                    .unwrap_or_else(|| "-".to_string());
+
+                let line = format!(
+                    "  {:<20} {:<20} {:>8}   {}",
+                    name,
+                    entry.started_at.format("%Y-%m-%d %H:%M"),
+                    duration_str,
+                    status_str.0,
+                );
+
+                let mut style = item_style(i, is_selected, false, theme);
+                // Tint the status color
+                if !is_selected {
+                    style = style.fg(status_str.1);
                 }
-                SessionStatus::Active => ("ACTIVE", Color::Cyan),
-                SessionStatus::Starting => ("START", Color::Yellow),
-                SessionStatus::Disconnected => ("DISC", Color::Yellow),
-                SessionStatus::Error(_) => ("ERROR", Color::Red),
-            };
-
-            let duration_str = entry
-                .duration
-                .as_ref()
-                .map(format_chrono_duration)
-                .unwrap_or_else(|| "-".to_string());
-
-            let line = format!(
-                "  {:<20} {:<20} {:>8}   {}",
-                entry.connection_name,
-                entry.started_at.format("%Y-%m-%d %H:%M"),
-                duration_str,
-                status_str.0,
-            );
-
-            let mut style = item_style(i, is_selected, false);
-            // Tint the status color
-            if !is_selected {
-                style = style.fg(status_str.1);
-            }
-
-            ListItem::new(line).style(style)
-        })
-        .collect();
+
+                ListItem::new(line).style(style)
+            })
+            .collect();
 
     // Column header
     let header_line = format!(
@@ -109,7 +117,7 @@ pub fn draw_history_tab(frame: &mut Frame, area: Rect, app: &App) {
         .constraints([Constraint::Length(1), Constraint::Min(1)])
         .split(area);
 
-    let header = Paragraph::new(header_line).style(Style::default().fg(Color::DarkGray).bold());
+    let header = Paragraph::new(header_line).style(Style::default().fg(theme.muted).bold());
 
     frame.render_widget(header, inner_area[0]);
 
@@ -117,15 +125,15 @@ pub fn draw_history_tab(frame: &mut Frame, area: Rect, app: &App) {
         .block(
             Block::default()
                 .title(" Session History ")
-                .title_style(Style::default().fg(Color::Yellow).bold())
+                .title_style(Style::default().fg(theme.accent).bold())
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray))
+                .border_style(Style::default().fg(theme.muted))
                 .padding(Padding::horizontal(1)),
         )
         .highlight_style(
             Style::default()
-                .fg(Color::White)
-                .bg(Color::Rgb(30, 60, 90))
+                .fg(theme.text)
+                .bg(theme.selection_bg)
                 .bold(),
         )
         .highlight_symbol("▶ ");
@@ -3,6 +3,7 @@
 use crate::cli::utils::format_duration;
 use crate::tui::models::{AppMode, GroupMode, PingStatus};
 use crate::tui::state::App;
+use crate::tui::theme::Theme;
 use crate::tui::ui::helpers::item_style;
 use ratatui::{
     prelude::*,
@@ -10,19 +11,21 @@ use ratatui::{
 };
 
 /// Build a colored Span for the ping indicator
-fn ping_span(status: Option<&PingStatus>) -> Span<'static> {
+fn ping_span(status: Option<&PingStatus>, theme: &Theme) -> Span<'static> {
     match status {
-        Some(PingStatus::Reachable(dur)) => {
-            Span::styled(format!(" ● {:.0?}", dur), Style::default().fg(Color::Green))
-        }
-        Some(PingStatus::Unreachable) => Span::styled(" ●", Style::default().fg(Color::Red)),
-        Some(PingStatus::Checking) => Span::styled(" ◌", Style::default().fg(Color::Yellow)),
+        Some(PingStatus::Reachable(dur)) => Span::styled(
+            format!(" ● {:.0?}", dur),
+            Style::default().fg(theme.success),
+        ),
+        Some(PingStatus::Unreachable(_)) => Span::styled(" ●", Style::default().fg(theme.error)),
+        Some(PingStatus::Checking) => Span::styled(" ◌", Style::default().fg(theme.accent)),
         _ => Span::raw(""),
     }
 }
 
 /// Draw the connection list
 pub fn draw_connection_list(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
     if app.group_mode == GroupMode::ByTag {
         draw_grouped_list(frame, area, app);
         return;
@@ -40,10 +43,10 @@ pub fn draw_connection_list(frame: &mut Frame, area: Rect, app: &App) {
             let bastion_indicator = if conn.bastion.is_some() { " [B]" } else { "" };
             let kerberos_indicator = if conn.use_kerberos { " [K]" } else { "" };
 
-            let ping = ping_span(app.ping_statuses.get(&conn.name));
+            let ping = ping_span(app.ping_statuses.get(&conn.name), theme);
 
             let select_marker = if is_multi { "◆ " } else { "" };
-            let style = item_style(i, is_selected, is_multi);
+            let style = item_style(i, is_selected, is_multi, theme);
 
             if app.compact_view {
                 let tags_str = if conn.tags.is_empty() {
@@ -109,31 +112,33 @@ pub fn draw_connection_list(frame: &mut Frame, area: Rect, app: &App) {
         .block(
             Block::default()
                 .title(list_title)
-                .title_style(Style::default().fg(Color::Yellow).bold())
+                .title_style(Style::default().fg(theme.accent).bold())
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray))
+                .border_style(Style::default().fg(theme.muted))
                 .padding(Padding::horizontal(1)),
         )
         .highlight_style(
             Style::default()
-                .fg(Color::White)
-                .bg(Color::Rgb(30, 60, 90))
+                .fg(theme.text)
+                .bg(theme.selection_bg)
                 .bold(),
         )
         .highlight_symbol("▶ ");
 
-    let mut state = ListState::default();
-    state.select(Some(app.selected_index));
+    let mut state = ListState::default()
+        .with_offset(app.scroll_offset)
+        .with_selected(Some(app.selected_index));
 
     frame.render_stateful_widget(list, area, &mut state);
 
     // Show empty state message
     if connections.is_empty() {
-        draw_empty_state(frame, area, &app.search_query);
+        draw_empty_state(frame, area, &app.search_query, theme);
     }
 }
 
 fn draw_grouped_list(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
     let mut items: Vec<ListItem> = Vec::new();
 
     for (group_name, connections) in &app.grouped_connections {
@@ -143,14 +148,14 @@ fn draw_grouped_list(frame: &mut Frame, area: Rect, app: &App) {
         // Group header
         items.push(
             ListItem::new(format!("{} {} ({})", arrow, group_name, connections.len()))
-                .style(Style::default().fg(Color::Yellow).bold()),
+                .style(Style::default().fg(theme.accent).bold()),
         );
 
         if !collapsed {
             for conn in connections {
                 let bastion_indicator = if conn.bastion.is_some() { " [B]" } else { "" };
                 let kerberos_indicator = if conn.use_kerberos { " [K]" } else { "" };
-                let ping = ping_span(app.ping_statuses.get(&conn.name));
+                let ping = ping_span(app.ping_statuses.get(&conn.name), theme);
                 let line = Line::from(vec![
                     Span::styled(
                         format!(
@@ -162,7 +167,7 @@ fn draw_grouped_list(frame: &mut Frame, area: Rect, app: &App) {
                             bastion_indicator,
                             kerberos_indicator,
                         ),
-                        Style::default().fg(Color::White),
+                        Style::default().fg(theme.text),
                     ),
                     ping,
                 ]);
@@ -174,20 +179,20 @@ fn draw_grouped_list(frame: &mut Frame, area: Rect, app: &App) {
     let list = List::new(items).block(
         Block::default()
             .title(" Connections (grouped by tag) ")
-            .title_style(Style::default().fg(Color::Yellow).bold())
+            .title_style(Style::default().fg(theme.accent).bold())
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::DarkGray))
+            .border_style(Style::default().fg(theme.muted))
             .padding(Padding::horizontal(1)),
     );
 
     frame.render_widget(list, area);
 
     if app.grouped_connections.is_empty() {
-        draw_empty_state(frame, area, &app.search_query);
+        draw_empty_state(frame, area, &app.search_query, theme);
     }
 }
 
-fn draw_empty_state(frame: &mut Frame, area: Rect, search_query: &str) {
+fn draw_empty_state(frame: &mut Frame, area: Rect, search_query: &str, theme: &Theme) {
     let empty_msg = if search_query.is_empty() {
         "No connections found.\n\nUse 'bssh add' or press 'a' to create connections\nor 'bssh import' to import from ~/.ssh/config"
     } else {
@@ -195,7 +200,7 @@ fn draw_empty_state(frame: &mut Frame, area: Rect, search_query: &str) {
     };
 
     let empty_paragraph = Paragraph::new(empty_msg)
-        .style(Style::default().fg(Color::DarkGray))
+        .style(Style::default().fg(theme.muted))
         .alignment(Alignment::Center)
         .wrap(Wrap { trim: true });
 
@@ -98,7 +98,7 @@ fn draw_overlays(frame: &mut Frame, area: Rect, app: &App) {
             // Connection edit/add overlay (connections tab)
             if app.active_tab == Tab::Connections {
                 if let Some(ref edit) = app.edit_state {
-                    overlays::draw_edit_overlay(frame, area, edit, &app.mode);
+                    overlays::draw_edit_overlay(frame, area, edit, &app.mode, &app.theme);
                 }
             }
             // Config tab environment creation dialog
@@ -1,7 +1,10 @@
 //! Overlay dialogs: help, confirm, edit/add, command preview
 
+use crate::models::SshCommandMode;
+use crate::services::transport::SubprocessTransport;
 use crate::tui::models::{AppMode, ConfirmAction, EditState, Tab};
 use crate::tui::state::App;
+use crate::tui::theme::Theme;
 use crate::tui::ui::helpers::centered_rect;
 use ratatui::{
     prelude::*,
@@ -10,6 +13,7 @@ use ratatui::{
 
 /// Draw the help overlay
 pub fn draw_help_overlay(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
     let help_text = match app.active_tab {
         Tab::Connections => vec![
             "",
@@ -26,10 +30,12 @@ pub fn draw_help_overlay(frame: &mut Frame, area: Rect, app: &App) {
             "  Actions",
             "  ──────────────────────────────────────",
             "  Enter       Connect to selected",
+            "  n           Connect in a new terminal",
             "  a           Add new connection",
             "  s           Show detail pane",
             "  e           Edit connection",
             "  p           Preview SSH command",
+            "  y           Copy SSH command",
             "  d/Delete    Delete connection",
             "  r           Refresh list",
             "  :           Quick connect",
@@ -52,11 +58,13 @@ pub fn draw_help_overlay(frame: &mut Frame, area: Rect, app: &App) {
             "  O           Toggle sort direction",
             "  v           Toggle compact view",
             "  f           Toggle group by tag",
+            "  T           Cycle color theme",
             "",
             "  General",
             "  ──────────────────────────────────────",
             "  ?           Toggle this help",
             "  P           Ping selected host",
+            "  H           Ping all visible hosts",
             "  q/Esc       Quit",
             "",
             "  Indicators: [B] bastion  [K] kerberos",
@@ -168,13 +176,13 @@ pub fn draw_help_overlay(frame: &mut Frame, area: Rect, app: &App) {
     frame.render_widget(Clear, popup_area);
 
     let help = Paragraph::new(help_content)
-        .style(Style::default().fg(Color::White))
+        .style(Style::default().fg(theme.text))
         .block(
             Block::default()
                 .title(" Help ")
-                .title_style(Style::default().fg(Color::Yellow).bold())
+                .title_style(Style::default().fg(theme.accent).bold())
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan)),
+                .border_style(Style::default().fg(theme.primary)),
         );
 
     frame.render_widget(help, popup_area);
@@ -182,6 +190,7 @@ pub fn draw_help_overlay(frame: &mut Frame, area: Rect, app: &App) {
 
 /// Draw the confirm dialog
 pub fn draw_confirm_dialog(frame: &mut Frame, area: Rect, action: &ConfirmAction, app: &App) {
+    let theme = &app.theme;
     let (title, message) = match action {
         ConfirmAction::Delete(idx) => {
             if app.active_tab == Tab::Config {
@@ -246,15 +255,15 @@ pub fn draw_confirm_dialog(frame: &mut Frame, area: Rect, action: &ConfirmAction
     frame.render_widget(Clear, popup_area);
 
     let confirm = Paragraph::new(message)
-        .style(Style::default().fg(Color::White))
+        .style(Style::default().fg(theme.text))
         .alignment(Alignment::Center)
         .wrap(Wrap { trim: true })
         .block(
             Block::default()
                 .title(title)
-                .title_style(Style::default().fg(Color::Red).bold())
+                .title_style(Style::default().fg(theme.error).bold())
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Red))
+                .border_style(Style::default().fg(theme.error))
                 .padding(Padding::uniform(1)),
         );
 
@@ -262,7 +271,13 @@ pub fn draw_confirm_dialog(frame: &mut Frame, area: Rect, action: &ConfirmAction
 }
 
 /// Draw the edit/add overlay
-pub fn draw_edit_overlay(frame: &mut Frame, area: Rect, edit: &EditState, mode: &AppMode) {
+pub fn draw_edit_overlay(
+    frame: &mut Frame,
+    area: Rect,
+    edit: &EditState,
+    mode: &AppMode,
+    theme: &Theme,
+) {
     let popup_width = 64;
     let popup_height = 24;
     let popup_area = centered_rect(popup_width, popup_height, area);
@@ -283,15 +298,15 @@ pub fn draw_edit_overlay(frame: &mut Frame, area: Rect, edit: &EditState, mode:
         };
 
         let label_style = if is_active {
-            Style::default().fg(Color::Cyan).bold()
+            Style::default().fg(theme.primary).bold()
         } else {
-            Style::default().fg(Color::DarkGray)
+            Style::default().fg(theme.muted)
         };
 
         let value_style = if is_active {
-            Style::default().fg(Color::White).bg(Color::Rgb(30, 60, 90))
+            Style::default().fg(theme.text).bg(theme.selection_bg)
         } else {
-            Style::default().fg(Color::White)
+            Style::default().fg(theme.text)
         };
 
         let arrow = if is_active { "▶ " } else { "  " };
@@ -308,7 +323,7 @@ pub fn draw_edit_overlay(frame: &mut Frame, area: Rect, edit: &EditState, mode:
         if i == 7 && is_active {
             lines.push(Line::from(vec![Span::styled(
                 "                    (press any key to toggle)",
-                Style::default().fg(Color::DarkGray).italic(),
+                Style::default().fg(theme.muted).italic(),
             )]));
         }
     }
@@ -316,7 +331,7 @@ pub fn draw_edit_overlay(frame: &mut Frame, area: Rect, edit: &EditState, mode:
     lines.push(Line::from(""));
     lines.push(Line::from(vec![Span::styled(
         "  Tab/↓ next  Shift+Tab/↑ prev  Enter save  Esc cancel",
-        Style::default().fg(Color::DarkGray).italic(),
+        Style::default().fg(theme.muted).italic(),
     )]));
 
     let title = if *mode == AppMode::Add {
@@ -326,9 +341,9 @@ pub fn draw_edit_overlay(frame: &mut Frame, area: Rect, edit: &EditState, mode:
     };
 
     let border_color = if *mode == AppMode::Add {
-        Color::Green
+        theme.success
     } else {
-        Color::Magenta
+        theme.special
     };
 
     let title_style = Style::default().fg(border_color).bold();
@@ -347,71 +362,73 @@ pub fn draw_edit_overlay(frame: &mut Frame, area: Rect, edit: &EditState, mode:
 
 /// Draw the SSH command preview overlay
 pub fn draw_command_preview(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
     let conn = match app.filtered_connections.get(app.selected_index) {
         Some(c) => c,
         None => return,
     };
 
-    let ssh_cmd = conn.to_ssh_command();
+    let ssh_cmd =
+        SubprocessTransport::display_command(&app.config, conn, SshCommandMode::Interactive);
 
     let mut lines: Vec<Line> = Vec::new();
     lines.push(Line::from(""));
     lines.push(Line::from(vec![
-        Span::styled("  Connection: ", Style::default().fg(Color::DarkGray)),
-        Span::styled(&conn.name, Style::default().fg(Color::White).bold()),
+        Span::styled("  Connection: ", Style::default().fg(theme.muted)),
+        Span::styled(&conn.name, Style::default().fg(theme.text).bold()),
     ]));
     lines.push(Line::from(""));
     lines.push(Line::from(vec![Span::styled(
         "  Command:",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme.muted),
     )]));
     lines.push(Line::from(""));
     lines.push(Line::from(vec![Span::styled(
         format!("    {}", ssh_cmd),
-        Style::default().fg(Color::Green).bold(),
+        Style::default().fg(theme.success).bold(),
     )]));
     lines.push(Line::from(""));
 
     // Break down the components
     lines.push(Line::from(vec![Span::styled(
         "  Breakdown:",
-        Style::default().fg(Color::DarkGray),
+        Style::default().fg(theme.muted),
     )]));
     lines.push(Line::from(vec![
-        Span::styled("    Host:      ", Style::default().fg(Color::DarkGray)),
-        Span::styled(&conn.host, Style::default().fg(Color::White)),
+        Span::styled("    Host:      ", Style::default().fg(theme.muted)),
+        Span::styled(&conn.host, Style::default().fg(theme.text)),
     ]));
     lines.push(Line::from(vec![
-        Span::styled("    Port:      ", Style::default().fg(Color::DarkGray)),
-        Span::styled(conn.port.to_string(), Style::default().fg(Color::White)),
+        Span::styled("    Port:      ", Style::default().fg(theme.muted)),
+        Span::styled(conn.port.to_string(), Style::default().fg(theme.text)),
     ]));
     lines.push(Line::from(vec![
-        Span::styled("    User:      ", Style::default().fg(Color::DarkGray)),
-        Span::styled(&conn.user, Style::default().fg(Color::White)),
+        Span::styled("    User:      ", Style::default().fg(theme.muted)),
+        Span::styled(&conn.user, Style::default().fg(theme.text)),
     ]));
     if let Some(bastion) = &conn.bastion {
         lines.push(Line::from(vec![
-            Span::styled("    Bastion:   ", Style::default().fg(Color::DarkGray)),
-            Span::styled(bastion, Style::default().fg(Color::Green)),
+            Span::styled("    Bastion:   ", Style::default().fg(theme.muted)),
+            Span::styled(bastion, Style::default().fg(theme.success)),
         ]));
     }
     if conn.use_kerberos {
         lines.push(Line::from(vec![
-            Span::styled("    Kerberos:  ", Style::default().fg(Color::DarkGray)),
-            Span::styled("Enabled (-K -A -t)", Style::default().fg(Color::Yellow)),
+            Span::styled("    Kerberos:  ", Style::default().fg(theme.muted)),
+            Span::styled("Enabled (-K -A -t)", Style::default().fg(theme.accent)),
         ]));
     }
     if let Some(key) = &conn.key_path {
         lines.push(Line::from(vec![
-            Span::styled("    Key:       ", Style::default().fg(Color::DarkGray)),
-            Span::styled(key, Style::default().fg(Color::White)),
+            Span::styled("    Key:       ", Style::default().fg(theme.muted)),
+            Span::styled(key, Style::default().fg(theme.text)),
         ]));
     }
 
     lines.push(Line::from(""));
     lines.push(Line::from(vec![Span::styled(
-        "  Enter connect | Esc/p close",
-        Style::default().fg(Color::DarkGray).italic(),
+        "  Enter connect | y copy | Esc/p close",
+        Style::default().fg(theme.muted).italic(),
     )]));
 
     let popup_width = 64;
@@ -423,9 +440,9 @@ pub fn draw_command_preview(frame: &mut Frame, area: Rect, app: &App) {
     let preview = Paragraph::new(Text::from(lines)).block(
         Block::default()
             .title(" SSH Command Preview ")
-            .title_style(Style::default().fg(Color::Yellow).bold())
+            .title_style(Style::default().fg(theme.accent).bold())
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Yellow)),
+            .border_style(Style::default().fg(theme.accent)),
     );
 
     frame.render_widget(preview, popup_area);
@@ -433,6 +450,7 @@ pub fn draw_command_preview(frame: &mut Frame, area: Rect, app: &App) {
 
 /// Draw config tab environment creation dialog
 pub fn draw_config_edit_dialog(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
     if let Some(ref edit) = app.config_editing {
         let popup_area = centered_rect(50, 7, area);
         frame.render_widget(Clear, popup_area);
@@ -440,13 +458,13 @@ pub fn draw_config_edit_dialog(frame: &mut Frame, area: Rect, app: &App) {
         let content = format!("\n  Name: {}|", edit.field_value);
 
         let dialog = Paragraph::new(content)
-            .style(Style::default().fg(Color::White))
+            .style(Style::default().fg(theme.text))
             .block(
                 Block::default()
                     .title(" Create Environment ")
-                    .title_style(Style::default().fg(Color::Green).bold())
+                    .title_style(Style::default().fg(theme.success).bold())
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Green))
+                    .border_style(Style::default().fg(theme.success))
                     .padding(Padding::horizontal(1)),
             );
 
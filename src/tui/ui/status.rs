@@ -9,6 +9,7 @@ use ratatui::{
 
 /// Draw the status bar
 pub fn draw_status_bar(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
     let mode_str = match &app.mode {
         AppMode::Normal => "NORMAL",
         AppMode::Search => "SEARCH",
@@ -52,7 +53,7 @@ pub fn draw_status_bar(frame: &mut Frame, area: Rect, app: &App) {
             "Tab next | Shift+Tab prev | Enter save | Esc cancel"
         }
         (AppMode::QuickConnect, _) => "[user@]host[:port] | Enter connect | Esc cancel",
-        (AppMode::CommandPreview, _) => "Enter connect | Esc close",
+        (AppMode::CommandPreview, _) => "Enter connect | y copy | Esc close",
         (AppMode::FilesPrompt(_), _) => "Enter confirm | Esc cancel",
     };
 
@@ -63,14 +64,14 @@ pub fn draw_status_bar(frame: &mut Frame, area: Rect, app: &App) {
         .split(area);
 
     let mode_style = match &app.mode {
-        AppMode::Normal => Style::default().fg(Color::Cyan).bold(),
-        AppMode::Search | AppMode::QuickConnect => Style::default().fg(Color::Green).bold(),
-        AppMode::Detail | AppMode::CommandPreview => Style::default().fg(Color::Yellow).bold(),
-        AppMode::Edit | AppMode::Add => Style::default().fg(Color::Magenta).bold(),
-        AppMode::Confirm(_) => Style::default().fg(Color::Red).bold(),
-        AppMode::Help => Style::default().fg(Color::White).bold(),
-        AppMode::TunnelLaunch => Style::default().fg(Color::Cyan).bold(),
-        AppMode::FilesPrompt(_) => Style::default().fg(Color::Yellow).bold(),
+        AppMode::Normal => Style::default().fg(theme.primary).bold(),
+        AppMode::Search | AppMode::QuickConnect => Style::default().fg(theme.success).bold(),
+        AppMode::Detail | AppMode::CommandPreview => Style::default().fg(theme.accent).bold(),
+        AppMode::Edit | AppMode::Add => Style::default().fg(theme.special).bold(),
+        AppMode::Confirm(_) => Style::default().fg(theme.error).bold(),
+        AppMode::Help => Style::default().fg(theme.text).bold(),
+        AppMode::TunnelLaunch => Style::default().fg(theme.primary).bold(),
+        AppMode::FilesPrompt(_) => Style::default().fg(theme.accent).bold(),
     };
 
     let status_text = app.status_message.as_deref().unwrap_or("");
@@ -79,23 +80,23 @@ pub fn draw_status_bar(frame: &mut Frame, area: Rect, app: &App) {
 
     let left = Paragraph::new(Line::from(vec![
         Span::styled(format!(" [{}] ", mode_str), mode_style),
-        Span::styled(status_text, Style::default().fg(Color::Gray)),
+        Span::styled(status_text, Style::default().fg(theme.subtle)),
     ]))
     .block(
         Block::default()
             .borders(Borders::TOP | Borders::LEFT | Borders::BOTTOM)
-            .border_style(Style::default().fg(Color::DarkGray)),
+            .border_style(Style::default().fg(theme.muted)),
     );
 
     let right = Paragraph::new(Line::from(vec![
-        Span::styled(tab_hint, Style::default().fg(Color::DarkGray)),
-        Span::styled(help_hint, Style::default().fg(Color::DarkGray)),
+        Span::styled(tab_hint, Style::default().fg(theme.muted)),
+        Span::styled(help_hint, Style::default().fg(theme.muted)),
     ]))
     .alignment(Alignment::Right)
     .block(
         Block::default()
             .borders(Borders::TOP | Borders::RIGHT | Borders::BOTTOM)
-            .border_style(Style::default().fg(Color::DarkGray)),
+            .border_style(Style::default().fg(theme.muted)),
     );
 
     frame.render_widget(left, halves[0]);
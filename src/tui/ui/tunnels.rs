@@ -1,6 +1,7 @@
 //! Tunnels tab rendering — active port-forward tunnel management
 
 use crate::tui::state::App;
+use crate::tui::theme::Theme;
 use crate::tui::ui::helpers::{centered_rect, item_style};
 use ratatui::{
     prelude::*,
@@ -10,18 +11,18 @@ use ratatui::{
 /// Draw the Tunnels tab.
 pub fn draw_tunnels_tab(frame: &mut Frame, area: Rect, app: &App) {
     if app.tunnels.is_empty() {
-        draw_empty_state(frame, area);
+        draw_empty_state(frame, area, &app.theme);
     } else {
         draw_tunnel_list(frame, area, app);
     }
 }
 
-fn draw_empty_state(frame: &mut Frame, area: Rect) {
+fn draw_empty_state(frame: &mut Frame, area: Rect, theme: &Theme) {
     let block = Block::default()
         .title(" Active Tunnels ")
-        .title_style(Style::default().fg(Color::Yellow).bold())
+        .title_style(Style::default().fg(theme.accent).bold())
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::DarkGray))
+        .border_style(Style::default().fg(theme.muted))
         .padding(Padding::uniform(1));
 
     let msg = Paragraph::new(
@@ -30,7 +31,7 @@ fn draw_empty_state(frame: &mut Frame, area: Rect) {
          Press  d  to start a SOCKS5 dynamic proxy.\n\
          Press  ?  for help.",
     )
-    .style(Style::default().fg(Color::DarkGray))
+    .style(Style::default().fg(theme.muted))
     .alignment(Alignment::Center)
     .wrap(Wrap { trim: true })
     .block(block);
@@ -39,6 +40,7 @@ fn draw_empty_state(frame: &mut Frame, area: Rect) {
 }
 
 fn draw_tunnel_list(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
     // Header line inside the block
     let header = format!(
         "  {:<2}  {:<4}  {:<20}  {:<22}  {:<22}  {}",
@@ -69,15 +71,15 @@ fn draw_tunnel_list(frame: &mut Frame, area: Rect, app: &App) {
                 uptime,
             );
 
-            ListItem::new(line).style(item_style(i, is_selected, false))
+            ListItem::new(line).style(item_style(i, is_selected, false, theme))
         })
         .collect();
 
     let block = Block::default()
         .title(format!(" Active Tunnels ({}) ", app.tunnels.len()))
-        .title_style(Style::default().fg(Color::Yellow).bold())
+        .title_style(Style::default().fg(theme.accent).bold())
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Blue))
+        .border_style(Style::default().fg(theme.info))
         .padding(Padding::new(0, 0, 0, 0));
 
     // Split area: header row + list
@@ -91,13 +93,13 @@ fn draw_tunnel_list(frame: &mut Frame, area: Rect, app: &App) {
     }));
 
     let header_widget = Paragraph::new(header)
-        .style(Style::default().fg(Color::DarkGray).bold())
+        .style(Style::default().fg(theme.muted).bold())
         .block(
             Block::default()
                 .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT)
-                .border_style(Style::default().fg(Color::Blue))
+                .border_style(Style::default().fg(theme.info))
                 .title(" Active Tunnels ")
-                .title_style(Style::default().fg(Color::Yellow).bold()),
+                .title_style(Style::default().fg(theme.accent).bold()),
         );
 
     frame.render_widget(header_widget, header_area);
@@ -107,9 +109,14 @@ fn draw_tunnel_list(frame: &mut Frame, area: Rect, app: &App) {
         .block(
             Block::default()
                 .borders(Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
-                .border_style(Style::default().fg(Color::Blue)),
+                .border_style(Style::default().fg(theme.info)),
         )
-        .highlight_style(Style::default().fg(Color::Black).bg(Color::Cyan).bold());
+        .highlight_style(
+            Style::default()
+                .fg(theme.on_primary)
+                .bg(theme.primary)
+                .bold(),
+        );
     frame.render_stateful_widget(list, list_area, &mut list_state);
 
     let _ = block; // consumed above
@@ -117,6 +124,7 @@ fn draw_tunnel_list(frame: &mut Frame, area: Rect, app: &App) {
 
 /// Draw the TunnelLaunch input dialog (modal overlay).
 pub fn draw_tunnel_launch_dialog(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
     let popup = centered_rect(60, 10, area);
     frame.render_widget(Clear, popup);
 
@@ -138,9 +146,9 @@ pub fn draw_tunnel_launch_dialog(frame: &mut Frame, area: Rect, app: &App) {
     };
     let block = Block::default()
         .title(title)
-        .title_style(Style::default().fg(Color::Cyan).bold())
+        .title_style(Style::default().fg(theme.primary).bold())
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan));
+        .border_style(Style::default().fg(theme.primary));
 
     let inner = block.inner(popup);
     frame.render_widget(block, popup);
@@ -153,19 +161,18 @@ pub fn draw_tunnel_launch_dialog(frame: &mut Frame, area: Rect, app: &App) {
     .areas(inner);
 
     frame.render_widget(
-        Paragraph::new(hint_text).style(Style::default().fg(Color::DarkGray)),
+        Paragraph::new(hint_text).style(Style::default().fg(theme.muted)),
         hint_area,
     );
 
     let input_display = format!(" > {} ", app.tunnel_input);
     frame.render_widget(
-        Paragraph::new(input_display).style(Style::default().fg(Color::White).bold()),
+        Paragraph::new(input_display).style(Style::default().fg(theme.text).bold()),
         input_area,
     );
 
     frame.render_widget(
-        Paragraph::new("Enter to start  ·  Esc to cancel")
-            .style(Style::default().fg(Color::DarkGray)),
+        Paragraph::new("Enter to start  ·  Esc to cancel").style(Style::default().fg(theme.muted)),
         note_area,
     );
 }
@@ -184,9 +191,10 @@ fn format_uptime(d: std::time::Duration) -> String {
 }
 
 fn truncate(s: &str, max: usize) -> String {
-    if s.len() <= max {
+    if s.chars().count() <= max {
         s.to_string()
     } else {
-        format!("{}…", &s[..max.saturating_sub(1)])
+        let kept: String = s.chars().take(max.saturating_sub(1)).collect();
+        format!("{kept}…")
     }
 }
@@ -0,0 +1,52 @@
+mod common;
+
+use common::run_bssh;
+use std::path::Path;
+
+fn host_of(config_home: &Path, name: &str) -> String {
+    let output = run_bssh(config_home, &["show", name, "--json"]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let conn: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("show should be JSON");
+    conn["host"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn a_lone_host_gets_a_derived_unique_name() {
+    // Given: an empty database.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+
+    // When: adding two hosts whose first label is the same, and an IP.
+    for host in [
+        "web01.prod.example.com",
+        "web01.staging.example.com",
+        "10.0.1.5",
+    ] {
+        let add = run_bssh(home, &["add", host, "-u", "deploy"]);
+        assert!(add.status.success());
+    }
+
+    // Then: the names come from the hosts, the second one with a suffix.
+    assert_eq!(host_of(home, "web01"), "web01.prod.example.com");
+    assert_eq!(host_of(home, "web01-2"), "web01.staging.example.com");
+    assert_eq!(host_of(home, "10-0-1-5"), "10.0.1.5");
+}
+
+#[test]
+fn an_explicit_name_is_kept() {
+    // Given: an empty database.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+
+    // When: adding with both a name and a host.
+    let add = run_bssh(home, &["add", "frontend", "web01.prod.example.com"]);
+
+    // Then: the given name is used.
+    assert!(add.status.success());
+    assert_eq!(host_of(home, "frontend"), "web01.prod.example.com");
+}
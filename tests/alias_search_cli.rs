@@ -0,0 +1,55 @@
+mod common;
+
+use common::run_bssh;
+
+#[test]
+fn fuzzy_search_finds_connections_by_partial_alias() {
+    // Given: a connection whose name, host and tags do not contain "sho",
+    // but whose alias does.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    assert!(run_bssh(home, &["add", "frontend-eu-west", "10.0.3.7"])
+        .status
+        .success());
+    assert!(run_bssh(home, &["add", "backend", "10.0.3.8"])
+        .status
+        .success());
+    assert!(
+        run_bssh(home, &["alias", "add", "shop", "frontend-eu-west"])
+            .status
+            .success()
+    );
+
+    // When: looking it up by a fragment of the alias.
+    let show = run_bssh(home, &["show", "sho"]);
+
+    // Then: the aliased connection is the single match.
+    let stdout = String::from_utf8_lossy(&show.stdout);
+    assert!(show.status.success(), "{stdout}");
+    assert!(stdout.contains("frontend-eu-west"), "{stdout}");
+    assert!(!stdout.contains("backend"), "{stdout}");
+}
+
+#[test]
+fn duplicate_accepts_an_alias_as_the_source() {
+    // Given: a connection reachable through an alias.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    assert!(run_bssh(home, &["add", "frontend-eu-west", "10.0.3.7"])
+        .status
+        .success());
+    assert!(
+        run_bssh(home, &["alias", "add", "shop", "frontend-eu-west"])
+            .status
+            .success()
+    );
+
+    // When: duplicating it by its alias.
+    let duplicate = run_bssh(home, &["duplicate", "shop", "frontend-eu-east"]);
+
+    // Then: the copy points at the aliased connection's host.
+    assert!(duplicate.status.success());
+    let show = run_bssh(home, &["show", "frontend-eu-east"]);
+    let stdout = String::from_utf8_lossy(&show.stdout);
+    assert!(stdout.contains("10.0.3.7"), "{stdout}");
+}
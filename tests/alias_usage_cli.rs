@@ -0,0 +1,153 @@
+mod common;
+
+use common::run_bssh;
+
+#[test]
+fn connecting_through_an_alias_is_counted() {
+    // Given: a connection with two aliases and a stub ssh binary. The bastion
+    // makes the dispatcher pick the subprocess transport.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    common::install_stub_ssh(home);
+    assert!(run_bssh(
+        home,
+        &[
+            "add",
+            "web-prod",
+            "web.example.com",
+            "--bastion",
+            "jump.example.com"
+        ]
+    )
+    .status
+    .success());
+    assert!(run_bssh(home, &["alias", "add", "wp", "web-prod"])
+        .status
+        .success());
+    assert!(run_bssh(home, &["alias", "add", "web", "web-prod"])
+        .status
+        .success());
+
+    // When: the user connects once through `wp` and once by name.
+    assert!(run_bssh(home, &["connect", "wp"]).status.success());
+    assert!(run_bssh(home, &["connect", "web-prod"]).status.success());
+
+    // Then: only the alias that was actually typed is credited.
+    let list = run_bssh(home, &["alias", "list"]);
+    assert!(list.status.success());
+    let stdout = String::from_utf8_lossy(&list.stdout);
+    assert!(stdout.contains("wp (used once)"), "{stdout}");
+    assert!(stdout.contains("web (used 0 times)"), "{stdout}");
+}
+
+#[test]
+fn aliases_resolve_exactly_before_fuzzy_search() {
+    // Given: an alias `w` for web-prod and another host that also fuzzy-matches `w`.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    for (name, host) in [
+        ("web-prod", "web.example.com"),
+        ("wiki", "wiki.example.com"),
+    ] {
+        assert!(run_bssh(home, &["add", name, host]).status.success());
+    }
+    assert!(run_bssh(home, &["alias", "add", "w", "web-prod"])
+        .status
+        .success());
+
+    // When/Then: show, edit and remove all land on web-prod without a picker.
+    let show = run_bssh(home, &["show", "w", "--json"]);
+    assert!(show.status.success(), "{:?}", show);
+    let shown: serde_json::Value =
+        serde_json::from_slice(&show.stdout).expect("show --json should be valid JSON");
+    assert_eq!(shown["name"], "web-prod");
+
+    assert!(run_bssh(home, &["edit", "w", "--user", "deploy"])
+        .status
+        .success());
+    let show = run_bssh(home, &["show", "web-prod", "--json"]);
+    let shown: serde_json::Value =
+        serde_json::from_slice(&show.stdout).expect("show --json should be valid JSON");
+    assert_eq!(shown["user"], "deploy");
+
+    assert!(run_bssh(home, &["remove", "w", "--force"]).status.success());
+    let remaining = run_bssh(home, &["show", "wiki", "--json"]);
+    assert!(remaining.status.success());
+    assert!(!run_bssh(home, &["show", "web-prod", "--json"])
+        .status
+        .success());
+}
+
+#[test]
+fn alias_list_filters_by_pattern_and_prints_json() {
+    // Given: aliases on two connections.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    for (name, host) in [
+        ("web-prod", "web.example.com"),
+        ("db-prod", "db.example.com"),
+    ] {
+        assert!(run_bssh(home, &["add", name, host]).status.success());
+    }
+    for (alias, target) in [("wp", "web-prod"), ("www", "web-prod"), ("pg", "db-prod")] {
+        assert!(run_bssh(home, &["alias", "add", alias, target])
+            .status
+            .success());
+    }
+
+    // When: listing aliases matching "web" (a connection name) and "pg" (an alias).
+    let by_connection = run_bssh(home, &["alias", "list", "web", "--json"]);
+    let by_alias = run_bssh(home, &["alias", "list", "PG"]);
+
+    // Then: JSON carries just web-prod's aliases; the text filter is case-insensitive.
+    assert!(by_connection.status.success());
+    let rows: serde_json::Value = serde_json::from_slice(&by_connection.stdout)
+        .expect("alias list --json should be valid JSON");
+    let rows = rows
+        .as_array()
+        .expect("alias list --json should be an array");
+    let aliases: Vec<_> = rows.iter().map(|r| r["alias"].as_str().unwrap()).collect();
+    assert_eq!(aliases, vec!["wp", "www"]);
+    assert_eq!(rows[0]["connection"], "web-prod");
+    assert_eq!(rows[0]["uses"], 0);
+
+    let stdout = String::from_utf8_lossy(&by_alias.stdout);
+    assert!(
+        stdout.contains("db-prod") && stdout.contains("pg (used 0 times)"),
+        "{stdout}"
+    );
+    assert!(!stdout.contains("web-prod"), "{stdout}");
+}
+
+#[test]
+fn a_moved_alias_does_not_inherit_old_uses() {
+    // Given: two connections, and a session through `wp` while it pointed at web-prod.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    common::install_stub_ssh(home);
+    for (name, host) in [
+        ("web-prod", "web.example.com"),
+        ("web-preprod", "preprod.example.com"),
+    ] {
+        assert!(
+            run_bssh(home, &["add", name, host, "--bastion", "jump.example.com"])
+                .status
+                .success()
+        );
+    }
+    assert!(run_bssh(home, &["alias", "add", "wp", "web-prod"])
+        .status
+        .success());
+    assert!(run_bssh(home, &["connect", "wp"]).status.success());
+
+    // When: the alias is moved to the other connection.
+    assert!(run_bssh(home, &["alias", "remove", "wp"]).status.success());
+    assert!(run_bssh(home, &["alias", "add", "wp", "web-preprod"])
+        .status
+        .success());
+
+    // Then: the earlier session is not credited to the new target.
+    let list = run_bssh(home, &["alias", "list"]);
+    let stdout = String::from_utf8_lossy(&list.stdout);
+    assert!(stdout.contains("wp (used 0 times)"), "{stdout}");
+}
@@ -0,0 +1,47 @@
+mod common;
+
+use common::run_bssh;
+use std::path::Path;
+
+fn listed_names(config_home: &Path, args: &[&str]) -> Vec<String> {
+    let mut list_args = vec!["list", "--json"];
+    list_args.extend_from_slice(args);
+    let output = run_bssh(config_home, &list_args);
+    assert!(output.status.success());
+
+    let connections: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("list --json should print JSON");
+    connections
+        .as_array()
+        .expect("list --json should print an array")
+        .iter()
+        .map(|conn| conn["name"].as_str().unwrap_or_default().to_string())
+        .collect()
+}
+
+#[test]
+fn archived_connections_are_hidden_until_restored() {
+    // Given: two connections, one of them archived.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    assert!(run_bssh(home, &["add", "db-old", "db-old.internal"])
+        .status
+        .success());
+    assert!(run_bssh(home, &["add", "web", "web.internal"])
+        .status
+        .success());
+    assert!(run_bssh(home, &["archive", "db-old"]).status.success());
+
+    // Then: plain list skips it, --all and --archived show it.
+    assert_eq!(listed_names(home, &[]), vec!["web"]);
+    let mut all = listed_names(home, &["--all"]);
+    all.sort();
+    assert_eq!(all, vec!["db-old", "web"]);
+    assert_eq!(listed_names(home, &["--archived"]), vec!["db-old"]);
+
+    // And: the exact name still resolves, so it can be restored.
+    assert!(run_bssh(home, &["unarchive", "db-old"]).status.success());
+    let mut restored = listed_names(home, &[]);
+    restored.sort();
+    assert_eq!(restored, vec!["db-old", "web"]);
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use common::run_bssh;
+
+#[test]
+fn attach_flag_runs_tmux_as_the_remote_command() {
+    // Given: a plain saved connection.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    common::install_logging_ssh(home);
+    assert!(run_bssh(
+        home,
+        &["add", "web-prod", "web.example.com", "-u", "deploy"]
+    )
+    .status
+    .success());
+
+    // When: connecting with a bare --attach.
+    let connect = run_bssh(home, &["connect", "web-prod", "--attach"]);
+
+    // Then: ssh gets a forced TTY and attaches to the default tmux session.
+    assert!(
+        connect.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&connect.stderr)
+    );
+    let calls = common::ssh_calls(home);
+    assert!(calls.contains("-tt"), "calls: {calls}");
+    assert!(
+        calls
+            .trim_end()
+            .ends_with("deploy@web.example.com tmux new -A -s bssh"),
+        "calls: {calls}"
+    );
+}
+
+#[test]
+fn stored_auto_attach_applies_until_overridden_or_cleared() {
+    // Given: a connection that auto-attaches to a named session.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    common::install_logging_ssh(home);
+    assert!(run_bssh(
+        home,
+        &["add", "build01", "build01.corp", "--auto-attach", "ci"]
+    )
+    .status
+    .success());
+
+    // When/Then: connect uses it, and --no-attach skips it for one session.
+    let dry_run = run_bssh(home, &["connect", "build01", "--dry-run"]);
+    assert!(String::from_utf8_lossy(&dry_run.stdout).contains("'tmux new -A -s ci'"));
+    let plain = run_bssh(home, &["connect", "build01", "--no-attach", "--dry-run"]);
+    assert!(!String::from_utf8_lossy(&plain.stdout).contains("tmux"));
+
+    // When: clearing the preference.
+    assert!(run_bssh(home, &["edit", "build01", "--clear-auto-attach"])
+        .status
+        .success());
+
+    // Then: connect opens a plain shell again.
+    let cleared = run_bssh(home, &["connect", "build01", "--dry-run"]);
+    assert!(!String::from_utf8_lossy(&cleared.stdout).contains("tmux"));
+}
+
+#[test]
+fn attach_rejects_unsafe_session_names() {
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+
+    // Given/When: a session name with shell metacharacters.
+    let output = run_bssh(
+        temp_dir.path(),
+        &["connect", "web-prod", "--attach=x;rm", "--dry-run"],
+    );
+
+    // Then: clap rejects it before anything runs.
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("invalid tmux session"));
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use common::run_bssh;
+use std::path::{Path, PathBuf};
+
+/// Stub `ssh` that logs its arguments and answers `-O check` with
+/// `master_check`
+fn install_stub_ssh(home: &Path, master_check: u8) {
+    common::install_stub(
+        home,
+        "ssh",
+        &format!(
+            "echo \"$*\" >> '{}'\n[ \"$1 $2\" = \"-O check\" ] && exit {master_check}\nexit 0\n",
+            home.join("ssh.log").display()
+        ),
+    );
+}
+
+/// A connection behind a shared bastion, with a leftover control socket
+/// whose master answers `-O check` with `master_check`; returns the
+/// socket's path
+fn setup(home: &Path, master_check: u8) -> PathBuf {
+    install_stub_ssh(home, master_check);
+    assert!(run_bssh(
+        home,
+        &["add", "db01", "db01.corp", "--bastion", "jump.corp"]
+    )
+    .status
+    .success());
+    let config_file = common::env_dir(home).join("config.json");
+    let mut config: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&config_file).unwrap()).unwrap();
+    config["transport"]["share_bastion_connections"] = serde_json::Value::Bool(true);
+    std::fs::write(&config_file, config.to_string()).expect("config should be written");
+
+    let dry_run = run_bssh(home, &["connect", "db01", "--dry-run"]);
+    let printed = String::from_utf8_lossy(&dry_run.stdout).into_owned();
+    let control_path = printed
+        .split("ControlPath=")
+        .nth(1)
+        .and_then(|rest| rest.split('\'').find(|part| part.starts_with('/')))
+        .unwrap_or_else(|| panic!("dry run should show the control path: {printed}"));
+    let control_path = PathBuf::from(control_path);
+    std::fs::write(&control_path, "").expect("leftover socket should be created");
+    control_path
+}
+
+fn ssh_log(home: &Path) -> String {
+    std::fs::read_to_string(home.join("ssh.log")).unwrap_or_default()
+}
+
+#[test]
+fn a_running_master_is_reused() {
+    // Given: a control socket whose master answers `ssh -O check`.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    let control_path = setup(home, 0);
+
+    // When: connecting through the bastion.
+    let connect = run_bssh(home, &["connect", "db01"]);
+
+    // Then: the master was checked and left alone.
+    assert!(
+        connect.status.success(),
+        "{}",
+        String::from_utf8_lossy(&connect.stderr)
+    );
+    let log = ssh_log(home);
+    assert!(log.contains("-O check"), "{log}");
+    assert!(!log.contains("-O exit"), "{log}");
+    assert!(control_path.exists());
+}
+
+#[test]
+fn a_stale_socket_is_shut_down_and_removed() {
+    // Given: a control socket whose master no longer answers.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    let control_path = setup(home, 255);
+
+    // When: connecting through the bastion.
+    let connect = run_bssh(home, &["connect", "db01"]);
+
+    // Then: the stale master was told to exit and its socket removed.
+    assert!(
+        connect.status.success(),
+        "{}",
+        String::from_utf8_lossy(&connect.stderr)
+    );
+    let log = ssh_log(home);
+    assert!(log.contains("-O check"), "{log}");
+    assert!(log.contains("-O exit"), "{log}");
+    assert!(!control_path.exists());
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use common::run_bssh;
+use std::path::Path;
+
+fn bastions(config_home: &Path) -> Vec<(String, Option<String>)> {
+    let output = run_bssh(config_home, &["list", "--json"]);
+    let connections: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("list --json should print JSON");
+    let mut bastions: Vec<_> = connections
+        .as_array()
+        .expect("list --json should print an array")
+        .iter()
+        .map(|conn| {
+            (
+                conn["name"].as_str().unwrap_or_default().to_string(),
+                conn["bastion"].as_str().map(String::from),
+            )
+        })
+        .collect();
+    bastions.sort();
+    bastions
+}
+
+#[test]
+fn filter_applies_changes_to_every_tagged_connection() {
+    // Given: two staging hosts and one production host.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    for (name, tag) in [
+        ("api-stg", "staging"),
+        ("web-stg", "staging"),
+        ("web-prod", "prod"),
+    ] {
+        assert!(
+            run_bssh(home, &["add", name, &format!("{name}.corp"), "--tags", tag])
+                .status
+                .success()
+        );
+    }
+
+    // When: a bastion is set on every staging host.
+    let edit = run_bssh(
+        home,
+        &[
+            "--yes",
+            "edit",
+            "--filter",
+            "staging",
+            "--bastion",
+            "jump.stg.corp",
+        ],
+    );
+
+    // Then: both staging hosts changed and production did not.
+    assert!(
+        edit.status.success(),
+        "{}",
+        String::from_utf8_lossy(&edit.stderr)
+    );
+    assert!(String::from_utf8_lossy(&edit.stdout).contains("Updated 2 connection(s)"));
+    let stg = Some("jump.stg.corp".to_string());
+    assert_eq!(
+        bastions(home),
+        vec![
+            ("api-stg".to_string(), stg.clone()),
+            ("web-prod".to_string(), None),
+            ("web-stg".to_string(), stg),
+        ]
+    );
+}
+
+#[test]
+fn bulk_edit_refuses_to_rename() {
+    // Given: two hosts sharing a tag.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    for name in ["a", "b"] {
+        assert!(run_bssh(home, &["add", name, "h.corp", "--tags", "t"])
+            .status
+            .success());
+    }
+
+    // When: a rename is attempted in bulk mode.
+    let edit = run_bssh(home, &["--yes", "edit", "--all", "--name", "same"]);
+
+    // Then: nothing is changed.
+    assert!(!edit.status.success());
+    assert!(String::from_utf8_lossy(&edit.stderr).contains("--name cannot be used"));
+    assert_eq!(bastions(home).len(), 2);
+}
@@ -0,0 +1,104 @@
+mod common;
+
+use common::run_bssh;
+use std::path::Path;
+use std::process::{Child, Command};
+
+fn is_running(child: &mut Child) -> bool {
+    child
+        .try_wait()
+        .expect("child should be pollable")
+        .is_none()
+}
+
+/// Three live processes recorded as active sessions to web-prod, oldest first
+fn seed_active_sessions(home: &Path) -> Vec<Child> {
+    assert!(run_bssh(home, &["add", "web-prod", "web.example.com"])
+        .status
+        .success());
+    let db = rusqlite::Connection::open(common::database_path(home)).expect("database should open");
+    let connection_id: String = db
+        .query_row(
+            "SELECT id FROM connections WHERE name = 'web-prod'",
+            [],
+            |r| r.get(0),
+        )
+        .expect("connection should exist");
+
+    let children: Vec<Child> = (0..3)
+        .map(|_| {
+            Command::new("sleep")
+                .arg("60")
+                .spawn()
+                .expect("sleep should start")
+        })
+        .collect();
+    for (i, child) in children.iter().enumerate() {
+        db.execute(
+            "INSERT INTO sessions (id, connection_id, started_at, status, pid)
+             VALUES (?1, ?2, ?3, '\"Active\"', ?4)",
+            rusqlite::params![
+                format!("session-{i}"),
+                connection_id,
+                format!("2026-03-01T10:0{i}:00+00:00"),
+                child.id()
+            ],
+        )
+        .expect("session should be inserted");
+    }
+    children
+}
+
+#[test]
+fn close_with_number_stops_only_that_session() {
+    // Given: three active sessions to the same connection.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    let mut children = seed_active_sessions(home);
+
+    // When: the listing is shown and session #2 is closed.
+    let listing = run_bssh(home, &["close"]);
+    let close = run_bssh(home, &["close", "web-prod", "-n", "2", "-f"]);
+
+    // Then: only the second-oldest process is signalled.
+    let stdout = String::from_utf8_lossy(&listing.stdout);
+    assert!(stdout.contains("-n <#>"), "{stdout}");
+    assert!(
+        close.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&close.stderr)
+    );
+    children[1].wait().expect("closed session should exit");
+    assert!(is_running(&mut children[0]));
+    assert!(is_running(&mut children[2]));
+
+    for child in &mut children {
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+}
+
+#[test]
+fn close_without_a_choice_refuses_to_guess() {
+    // Given: three active sessions and no terminal to ask on.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    let mut children = seed_active_sessions(home);
+
+    // When: closing the connection without --number or --force.
+    let close = run_bssh(home, &["close", "web-prod"]);
+    let out_of_range = run_bssh(home, &["close", "web-prod", "-n", "4", "-f"]);
+
+    // Then: the sessions are listed, nothing is closed, and bad numbers fail.
+    assert!(!close.status.success());
+    let stdout = String::from_utf8_lossy(&close.stdout);
+    assert!(stdout.contains("3 active sessions to web-prod"), "{stdout}");
+    assert!(String::from_utf8_lossy(&close.stderr).contains("--number"));
+    assert!(!out_of_range.status.success());
+    assert!(children.iter_mut().all(is_running));
+
+    for child in &mut children {
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+}
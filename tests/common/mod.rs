@@ -0,0 +1,92 @@
+//! Fixtures shared by the CLI integration tests.
+//!
+//! Each test points `bssh` at its own temp `home`: config and data live
+//! under it, and `home/bin` comes first on PATH so stubs written with
+//! [`install_stub`] stand in for `ssh`, `ssh-keyscan` and the like, and
+//! for `$EDITOR` as `home/bin/editor`. Variables from the caller's session
+//! that would change behaviour (`KRB5CCNAME`, `BSSH_ASSUME_YES`) are cleared.
+//! Commands run in the [`ENV`] environment unless a test picks another.
+
+// Every test binary compiles this module but uses only some of it.
+#![allow(dead_code)]
+
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+/// Environment the tests of one file run in, named after the test binary
+pub const ENV: &str = env!("CARGO_CRATE_NAME");
+
+/// `bssh` isolated under `home`, with no arguments yet (not even `--env`)
+pub fn bssh(home: &Path) -> Command {
+    let path = format!(
+        "{}:{}",
+        home.join("bin").display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+    let mut command = Command::new(env!("CARGO_BIN_EXE_bayesian-ssh"));
+    command
+        .env("HOME", home)
+        .env("XDG_CONFIG_HOME", home)
+        .env("XDG_DATA_HOME", home.join("data"))
+        .env("XDG_RUNTIME_DIR", home.join("run"))
+        .env("PATH", path)
+        .env("EDITOR", home.join("bin/editor"))
+        .env_remove("VISUAL")
+        .env_remove("KRB5CCNAME")
+        .env_remove("BSSH_ASSUME_YES");
+    command
+}
+
+/// Run `bssh --env <ENV> <args>` under `home`
+pub fn run_bssh(home: &Path, args: &[&str]) -> Output {
+    bssh(home)
+        .args(["--env", ENV])
+        .args(args)
+        .output()
+        .expect("bssh command should run")
+}
+
+/// Config and data directory of [`ENV`] under `home`
+pub fn env_dir(home: &Path) -> PathBuf {
+    home.join("bayesian-ssh/environments").join(ENV)
+}
+
+/// SQLite database of [`ENV`] under `home`
+pub fn database_path(home: &Path) -> PathBuf {
+    env_dir(home).join("history.db")
+}
+
+/// Write `body` as the executable `/bin/sh` script `home/bin/<name>`
+pub fn install_stub(home: &Path, name: &str, body: &str) -> PathBuf {
+    let bin_dir = home.join("bin");
+    std::fs::create_dir_all(&bin_dir).expect("bin dir should be created");
+    let stub = bin_dir.join(name);
+    std::fs::write(&stub, format!("#!/bin/sh\n{body}"))
+        .unwrap_or_else(|e| panic!("stub {name} should be written: {e}"));
+    std::fs::set_permissions(&stub, std::fs::Permissions::from_mode(0o755))
+        .unwrap_or_else(|e| panic!("stub {name} should be executable: {e}"));
+    stub
+}
+
+/// Stub `ssh` that exits 0 straight away
+pub fn install_stub_ssh(home: &Path) {
+    install_stub(home, "ssh", "exit 0\n");
+}
+
+/// Stub `ssh` that appends its arguments to [`ssh_calls`] and exits 0
+pub fn install_logging_ssh(home: &Path) {
+    install_stub(
+        home,
+        "ssh",
+        &format!(
+            "echo \"$@\" >> '{}'\nexit 0\n",
+            home.join("calls").display()
+        ),
+    );
+}
+
+/// One line per `ssh` call recorded by [`install_logging_ssh`]
+pub fn ssh_calls(home: &Path) -> String {
+    std::fs::read_to_string(home.join("calls")).unwrap_or_default()
+}
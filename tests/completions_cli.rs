@@ -0,0 +1,47 @@
+mod common;
+
+use common::run_bssh;
+
+#[test]
+fn install_writes_detected_shell_completions_and_refuses_overwrite() {
+    // Given: a bash user with no completions installed yet.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    let target = home.join("data/bash-completion/completions/bayesian-ssh");
+    let run = |args: &[&str]| {
+        common::bssh(home)
+            .env("SHELL", "/bin/bash")
+            .args(["--env", common::ENV])
+            .args(args)
+            .output()
+            .expect("bssh command should run")
+    };
+
+    // When: completions are installed without naming the shell.
+    let first = run(&["completions", "--install"]);
+
+    // Then: the bash script lands in the XDG data dir and the path is reported.
+    assert!(first.status.success(), "{:?}", first);
+    assert!(String::from_utf8_lossy(&first.stdout).contains(&target.display().to_string()));
+    let script = std::fs::read_to_string(&target).expect("completion file should exist");
+    assert!(script.contains("bayesian-ssh"));
+
+    // And: a second install is refused unless --force is given.
+    let second = run(&["completions", "--install"]);
+    assert!(!second.status.success());
+    assert!(String::from_utf8_lossy(&second.stderr).contains("--force"));
+
+    let forced = run(&["completions", "--install", "--force"]);
+    assert!(forced.status.success(), "{:?}", forced);
+}
+
+#[test]
+fn install_honours_an_explicit_shell() {
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+
+    let output = run_bssh(home, &["completions", "--install", "fish"]);
+
+    assert!(output.status.success(), "{:?}", output);
+    assert!(home.join("fish/completions/bayesian-ssh.fish").exists());
+}
@@ -0,0 +1,41 @@
+mod common;
+
+use common::run_bssh;
+
+#[test]
+fn corrupt_config_is_backed_up_and_repaired() {
+    // Given: an environment whose config was hand-edited into a bad value.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    assert!(run_bssh(home, &["list"]).status.success());
+    let config_file = common::env_dir(home).join("config.json");
+    let backup = common::env_dir(home).join("config.json.bak");
+    std::fs::write(
+        &config_file,
+        r#"{"default_user": "deploy", "default_port": "ssh"}"#,
+    )
+    .expect("config should be written");
+
+    // When: any command runs.
+    let list = run_bssh(home, &["list"]);
+
+    // Then: it still works, warns, and keeps a copy of the bad file.
+    assert!(list.status.success(), "{:?}", list);
+    assert!(String::from_utf8_lossy(&list.stderr).contains("config repair"));
+    assert!(backup.exists());
+
+    // And: repair reports the bad field and rewrites a valid config.
+    let repair = run_bssh(home, &["config", "repair"]);
+    assert!(repair.status.success(), "{:?}", repair);
+    assert!(String::from_utf8_lossy(&repair.stdout).contains("default_port"));
+    let repaired: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(&config_file).expect("config should be readable"),
+    )
+    .expect("repaired config should be valid JSON");
+    assert_eq!(repaired["default_user"], "deploy");
+    assert_eq!(repaired["default_port"], 22);
+
+    let again = run_bssh(home, &["config", "repair"]);
+    assert!(String::from_utf8_lossy(&again.stdout).contains("nothing to repair"));
+    assert!(!String::from_utf8_lossy(&again.stderr).contains("Could not parse"));
+}
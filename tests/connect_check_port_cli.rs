@@ -0,0 +1,58 @@
+mod common;
+
+use common::run_bssh;
+use std::path::Path;
+
+/// Add `web-prod` behind a bastion on the local `port`
+fn add_behind_local_bastion(config_home: &Path, port: u16) {
+    let bastion = format!("127.0.0.1:{port}");
+    assert!(run_bssh(
+        config_home,
+        &["add", "web-prod", "web.internal", "--bastion", &bastion]
+    )
+    .status
+    .success());
+}
+
+#[test]
+fn check_port_fails_fast_when_the_bastion_port_is_closed() {
+    // Given: a bastion port nothing listens on.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    common::install_logging_ssh(home);
+    let closed_port = std::net::TcpListener::bind("127.0.0.1:0")
+        .and_then(|l| l.local_addr())
+        .expect("port should be allocated")
+        .port();
+    add_behind_local_bastion(home, closed_port);
+
+    // When: connecting with --check-port.
+    let connect = run_bssh(home, &["connect", "web-prod", "--check-port"]);
+
+    // Then: bssh reports the refused port and never starts ssh.
+    assert!(!connect.status.success());
+    let stderr = String::from_utf8_lossy(&connect.stderr);
+    assert!(stderr.contains("connection refused"), "stderr: {stderr}");
+    assert!(common::ssh_calls(home).is_empty());
+}
+
+#[test]
+fn check_port_lets_ssh_run_when_the_port_answers() {
+    // Given: a bastion port with a listener.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    common::install_logging_ssh(home);
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("listener should bind");
+    add_behind_local_bastion(home, listener.local_addr().unwrap().port());
+
+    // When: connecting with --check-port.
+    let connect = run_bssh(home, &["connect", "web-prod", "--check-port"]);
+
+    // Then: the probe passes and ssh is started.
+    assert!(
+        connect.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&connect.stderr)
+    );
+    assert!(common::ssh_calls(home).contains("web.internal"));
+}
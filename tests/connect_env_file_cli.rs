@@ -0,0 +1,128 @@
+mod common;
+
+use common::run_bssh;
+use std::path::Path;
+
+/// Stub `ssh` that records the `KRB5CCNAME` it was started with
+fn install_stub_ssh(config_home: &Path) {
+    common::install_stub(
+        config_home,
+        "ssh",
+        &format!(
+            "echo \"$KRB5CCNAME\" > '{}'\nexit 0\n",
+            config_home.join("ssh-env").display()
+        ),
+    );
+}
+
+fn seen_by_ssh(config_home: &Path) -> String {
+    std::fs::read_to_string(config_home.join("ssh-env"))
+        .expect("stub ssh should have run")
+        .trim()
+        .to_string()
+}
+
+#[test]
+fn env_file_variables_reach_the_ssh_process() {
+    // Given: a saved connection and an env file selecting a Kerberos cache.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    install_stub_ssh(home);
+    assert!(run_bssh(home, &["add", "web-prod", "web.corp"])
+        .status
+        .success());
+    let env_file = home.join("eu.env");
+    std::fs::write(
+        &env_file,
+        "# EU realm\nexport KRB5CCNAME=FILE:/tmp/krb5cc_eu\n",
+    )
+    .unwrap();
+
+    // When: connecting with --env-file.
+    let connect = run_bssh(
+        home,
+        &[
+            "connect",
+            "web-prod",
+            "--env-file",
+            env_file.to_str().unwrap(),
+        ],
+    );
+
+    // Then: ssh ran with the variable set.
+    assert!(
+        connect.status.success(),
+        "{}",
+        String::from_utf8_lossy(&connect.stderr)
+    );
+    assert_eq!(seen_by_ssh(home), "FILE:/tmp/krb5cc_eu");
+}
+
+#[test]
+fn configured_env_file_is_the_default() {
+    // Given: env_file set in the config, and a second file for one session.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    install_stub_ssh(home);
+    assert!(run_bssh(home, &["add", "web-prod", "web.corp"])
+        .status
+        .success());
+    let default_file = home.join("default.env");
+    std::fs::write(&default_file, "KRB5CCNAME=FILE:/tmp/krb5cc_default\n").unwrap();
+    let other_file = home.join("other.env");
+    std::fs::write(&other_file, "KRB5CCNAME='FILE:/tmp/krb5cc_other'\n").unwrap();
+    assert!(run_bssh(
+        home,
+        &["config", "--env-file", default_file.to_str().unwrap()]
+    )
+    .status
+    .success());
+
+    // When/Then: a plain connect uses the configured file...
+    assert!(run_bssh(home, &["connect", "web-prod"]).status.success());
+    assert_eq!(seen_by_ssh(home), "FILE:/tmp/krb5cc_default");
+
+    // ...and --env-file replaces it.
+    assert!(run_bssh(
+        home,
+        &[
+            "connect",
+            "web-prod",
+            "--env-file",
+            other_file.to_str().unwrap()
+        ]
+    )
+    .status
+    .success());
+    assert_eq!(seen_by_ssh(home), "FILE:/tmp/krb5cc_other");
+}
+
+#[test]
+fn a_malformed_env_file_stops_the_connection() {
+    // Given: an env file with a line that is not KEY=VALUE.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    install_stub_ssh(home);
+    assert!(run_bssh(home, &["add", "web-prod", "web.corp"])
+        .status
+        .success());
+    let env_file = home.join("broken.env");
+    std::fs::write(&env_file, "KRB5CCNAME\n").unwrap();
+
+    // When: connecting with it.
+    let connect = run_bssh(
+        home,
+        &[
+            "connect",
+            "web-prod",
+            "--env-file",
+            env_file.to_str().unwrap(),
+        ],
+    );
+
+    // Then: ssh never runs and the line is reported.
+    assert!(!connect.status.success());
+    let stderr = String::from_utf8_lossy(&connect.stderr);
+    assert!(stderr.contains("line 1"), "{stderr}");
+    assert!(!home.join("ssh-env").exists());
+}
@@ -0,0 +1,64 @@
+mod common;
+
+use common::run_bssh;
+use std::path::Path;
+
+fn install_recording_ssh(config_home: &Path) {
+    common::install_stub(
+        config_home,
+        "ssh",
+        &format!(
+            "echo \"$@\" > '{}'\nexit 0\n",
+            config_home.join("ssh-args").display()
+        ),
+    );
+}
+
+#[test]
+fn adhoc_forwards_are_passed_to_ssh_for_one_session() {
+    // Given: a direct connection, which would normally use the native transport.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    install_recording_ssh(home);
+    assert!(run_bssh(home, &["add", "db01", "db.example.com"])
+        .status
+        .success());
+
+    // When: connecting with one-off tunnels.
+    let output = run_bssh(
+        home,
+        &[
+            "connect",
+            "db01",
+            "-L",
+            "5432:localhost:5432",
+            "-R",
+            "127.0.0.1:9000:localhost:9000",
+            "-D",
+            "1080",
+        ],
+    );
+
+    // Then: ssh receives every forward.
+    assert!(output.status.success(), "{:?}", output);
+    let args = std::fs::read_to_string(home.join("ssh-args")).expect("ssh should have run");
+    assert!(args.contains("-L 5432:localhost:5432"), "{args}");
+    assert!(args.contains("-R 127.0.0.1:9000:localhost:9000"), "{args}");
+    assert!(args.contains("-D 1080"), "{args}");
+}
+
+#[test]
+fn invalid_forward_spec_is_rejected_before_ssh_runs() {
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    install_recording_ssh(home);
+    assert!(run_bssh(home, &["add", "db01", "db.example.com"])
+        .status
+        .success());
+
+    let output = run_bssh(home, &["connect", "db01", "-L", "5432:localhost"]);
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid -L spec"));
+    assert!(!home.join("ssh-args").exists());
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use common::run_bssh;
+use std::path::Path;
+
+/// Stub `ssh` that writes each argument on its own line and exits 0.
+fn install_recording_ssh(config_home: &Path) {
+    common::install_stub(
+        config_home,
+        "ssh",
+        &format!(
+            "printf '%s\\n' \"$@\" > '{}'\nexit 0\n",
+            config_home.join("args").display()
+        ),
+    );
+}
+
+fn ssh_args(config_home: &Path) -> Vec<String> {
+    std::fs::read_to_string(config_home.join("args"))
+        .unwrap_or_default()
+        .lines()
+        .map(String::from)
+        .collect()
+}
+
+fn show_json(config_home: &Path, name: &str) -> serde_json::Value {
+    let show = run_bssh(config_home, &["show", name, "--json"]);
+    serde_json::from_slice(&show.stdout).expect("show --json should print JSON")
+}
+
+#[test]
+fn connect_overrides_are_not_saved() {
+    // Given: a saved connection on a non-default port. The bastion
+    // selects the subprocess transport so the stub ssh is used.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    install_recording_ssh(home);
+    assert!(run_bssh(
+        home,
+        &[
+            "add",
+            "db01",
+            "10.0.1.5",
+            "-u",
+            "dba",
+            "-p",
+            "2222",
+            "-b",
+            "jump.corp"
+        ]
+    )
+    .status
+    .success());
+
+    // When: connecting once through an ad-hoc jump host as another user.
+    let connect = run_bssh(
+        home,
+        &["connect", "db01", "--jump", "ops@jump2.corp", "-u", "root"],
+    );
+
+    // Then: ssh used the overrides...
+    assert!(
+        connect.status.success(),
+        "{}",
+        String::from_utf8_lossy(&connect.stderr)
+    );
+    let args = ssh_args(home);
+    assert!(args.contains(&"ops@jump2.corp".to_string()), "{args:?}");
+    assert!(args.contains(&"root@10.0.1.5".to_string()), "{args:?}");
+
+    // ...but the saved connection is unchanged apart from last_used.
+    let saved = show_json(home, "db01");
+    assert_eq!(saved["user"], "dba");
+    assert_eq!(saved["port"], 2222);
+    assert_eq!(saved["bastion"], "jump.corp");
+    assert!(saved["bastion_user"].is_null());
+    assert!(saved["last_used"].is_string());
+
+    // When: connecting without overrides.
+    assert!(run_bssh(home, &["connect", "db01"]).status.success());
+
+    // Then: the stored port and bastion are used.
+    let args = ssh_args(home);
+    assert!(args.contains(&"2222".to_string()), "{args:?}");
+    assert!(args.contains(&"dba@jump.corp".to_string()), "{args:?}");
+}
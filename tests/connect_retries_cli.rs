@@ -0,0 +1,107 @@
+mod common;
+
+use common::run_bssh;
+use std::path::Path;
+
+/// Stub `ssh` that logs each call, prints `stderr` and exits 255.
+fn install_failing_ssh(config_home: &Path, stderr: &str) {
+    common::install_stub(
+        config_home,
+        "ssh",
+        &format!(
+            "echo call >> '{}'\necho '{}' >&2\nexit 255\n",
+            config_home.join("calls").display(),
+            stderr
+        ),
+    );
+}
+
+fn ssh_calls(config_home: &Path) -> usize {
+    std::fs::read_to_string(config_home.join("calls"))
+        .unwrap_or_default()
+        .lines()
+        .count()
+}
+
+fn add_bastion_connection(config_home: &Path) {
+    // The bastion selects the subprocess transport.
+    assert!(run_bssh(
+        config_home,
+        &[
+            "add",
+            "web-prod",
+            "web.example.com",
+            "-b",
+            "jump.example.com"
+        ]
+    )
+    .status
+    .success());
+}
+
+#[test]
+fn connection_failures_are_retried_into_one_session() {
+    // Given: an ssh that always fails to connect.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    install_failing_ssh(
+        home,
+        "ssh: connect to host jump.example.com port 22: Connection refused",
+    );
+    add_bastion_connection(home);
+
+    // When: connecting with one retry.
+    let connect = run_bssh(home, &["connect", "web-prod", "--retries", "1"]);
+
+    // Then: ssh ran twice, the retry was announced, and one session was recorded.
+    assert_eq!(ssh_calls(home), 2);
+    let stderr = String::from_utf8_lossy(&connect.stderr);
+    assert!(stderr.contains("retrying in 1s (1/1)"), "{stderr}");
+    let history = run_bssh(home, &["history"]);
+    let stdout = String::from_utf8_lossy(&history.stdout);
+    assert_eq!(
+        stdout.lines().filter(|l| l.contains("web-prod")).count(),
+        1,
+        "{stdout}"
+    );
+}
+
+#[test]
+fn direct_hosts_are_retried_too() {
+    // Given: a direct connection (no bastion) to a host that refuses.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    install_failing_ssh(
+        home,
+        "ssh: connect to host web.example.com port 22: Connection refused",
+    );
+    assert!(run_bssh(home, &["add", "web-prod", "web.example.com"])
+        .status
+        .success());
+
+    // When: connecting with one retry.
+    let connect = run_bssh(home, &["connect", "web-prod", "--retries", "1"]);
+
+    // Then: the retry ran through ssh as for bastion connections.
+    assert_eq!(ssh_calls(home), 2);
+    let stderr = String::from_utf8_lossy(&connect.stderr);
+    assert!(stderr.contains("retrying in 1s (1/1)"), "{stderr}");
+}
+
+#[test]
+fn authentication_failures_are_not_retried() {
+    // Given: an ssh whose credentials are rejected.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    install_failing_ssh(
+        home,
+        "deploy@web.example.com: Permission denied (publickey).",
+    );
+    add_bastion_connection(home);
+
+    // When: connecting with retries enabled.
+    run_bssh(home, &["connect", "web-prod", "--retries", "3"]);
+
+    // Then: ssh ran once.
+    assert_eq!(ssh_calls(home), 1);
+}
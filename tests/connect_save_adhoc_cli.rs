@@ -0,0 +1,120 @@
+mod common;
+
+use common::run_bssh;
+use std::path::Path;
+
+fn saved_names(config_home: &Path) -> Vec<String> {
+    let list = run_bssh(config_home, &["list", "--json"]);
+    assert!(list.status.success());
+    let json: serde_json::Value =
+        serde_json::from_slice(&list.stdout).expect("list --json should print JSON");
+    json.as_array()
+        .expect("list --json should print an array")
+        .iter()
+        .map(|c| c["name"].as_str().unwrap_or_default().to_string())
+        .collect()
+}
+
+#[test]
+fn save_flag_stores_an_unsaved_host_after_the_session() {
+    // Given: no saved connections.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    common::install_logging_ssh(home);
+
+    // When: connecting to a bare host with --save.
+    let connect = run_bssh(
+        home,
+        &[
+            "connect",
+            "10.0.0.9",
+            "-u",
+            "admin",
+            "--bastion",
+            "jump.corp",
+            "--save",
+        ],
+    );
+
+    // Then: the host is saved with the settings it was reached with, and the
+    // session it just had is counted.
+    assert!(
+        connect.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&connect.stderr)
+    );
+    assert!(String::from_utf8_lossy(&connect.stdout).contains("Saved connection '10.0.0.9'"));
+    assert_eq!(saved_names(home), vec!["10.0.0.9".to_string()]);
+
+    let show = run_bssh(home, &["show", "10.0.0.9"]);
+    let stdout = String::from_utf8_lossy(&show.stdout);
+    assert!(stdout.contains("admin"), "show: {stdout}");
+    assert!(stdout.contains("jump.corp"), "show: {stdout}");
+
+    let history = run_bssh(home, &["history", "-c", "10.0.0.9"]);
+    let stdout = String::from_utf8_lossy(&history.stdout);
+    assert!(stdout.contains("10.0.0.9"), "history: {stdout}");
+}
+
+#[test]
+fn unsaved_host_is_not_saved_with_no_save_or_without_a_terminal() {
+    // Given: no saved connections.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    common::install_logging_ssh(home);
+
+    // When: connecting ad hoc with --no-save, then with no terminal to ask on.
+    for args in [
+        &["connect", "10.0.0.9", "--bastion", "jump.corp", "--no-save"][..],
+        &["connect", "10.0.0.9", "--bastion", "jump.corp"][..],
+    ] {
+        let connect = run_bssh(home, args);
+        assert!(
+            connect.status.success(),
+            "stderr: {}",
+            String::from_utf8_lossy(&connect.stderr)
+        );
+    }
+
+    // Then: nothing was saved.
+    assert!(saved_names(home).is_empty());
+}
+
+#[test]
+fn ephemeral_connect_is_kept_in_history_as_ad_hoc_only() {
+    // Given: a saved connection whose name is also a resolvable hostname.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    common::install_logging_ssh(home);
+    assert!(run_bssh(home, &["add", "localhost", "web.example.com"])
+        .status
+        .success());
+
+    // When: connecting to that name with --ephemeral.
+    let connect = run_bssh(
+        home,
+        &[
+            "connect",
+            "--ephemeral",
+            "localhost",
+            "--bastion",
+            "jump.corp",
+        ],
+    );
+
+    // Then: ssh went to the name as a host, the saved connection is
+    // untouched, and history marks the session as ad-hoc.
+    assert!(
+        connect.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&connect.stderr)
+    );
+    let calls = std::fs::read_to_string(home.join("calls")).unwrap_or_default();
+    assert!(calls.contains("@localhost"), "calls: {calls}");
+    assert!(!calls.contains("web.example.com"), "calls: {calls}");
+    assert_eq!(saved_names(home), vec!["localhost".to_string()]);
+
+    let history = run_bssh(home, &["history"]);
+    let stdout = String::from_utf8_lossy(&history.stdout);
+    assert!(stdout.contains("localhost (ad-hoc)"), "history: {stdout}");
+}
@@ -0,0 +1,51 @@
+mod common;
+
+use common::run_bssh;
+use std::path::Path;
+
+fn add_connection(config_home: &Path) {
+    assert!(run_bssh(
+        config_home,
+        &["add", "web-prod", "web.example.com", "-u", "deploy"]
+    )
+    .status
+    .success());
+}
+
+#[test]
+fn repeated_v_flags_reach_ssh() {
+    // Given: a saved connection.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    common::install_logging_ssh(home);
+    add_connection(home);
+
+    // When: connecting with -vv.
+    let connect = run_bssh(home, &["connect", "web-prod", "-vv"]);
+
+    // Then: ssh itself ran with -vv.
+    assert!(connect.status.success());
+    let calls = common::ssh_calls(home);
+    assert!(calls.starts_with("-tt -vv "), "{calls}");
+}
+
+#[test]
+fn dry_run_prints_the_verbose_command_without_connecting() {
+    // Given: a saved connection.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    common::install_logging_ssh(home);
+    add_connection(home);
+
+    // When: previewing a -vvv connect.
+    let connect = run_bssh(home, &["connect", "web-prod", "-vvv", "--dry-run"]);
+
+    // Then: the command is printed, ssh never ran and no session was recorded.
+    let stdout = String::from_utf8_lossy(&connect.stdout);
+    assert!(connect.status.success(), "{stdout}");
+    assert!(stdout.starts_with("ssh -tt -vvv "), "{stdout}");
+    assert!(stdout.contains("deploy@web.example.com"), "{stdout}");
+    assert!(common::ssh_calls(home).is_empty());
+    let history = run_bssh(home, &["history"]);
+    assert!(!String::from_utf8_lossy(&history.stdout).contains("web-prod"));
+}
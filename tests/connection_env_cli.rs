@@ -0,0 +1,98 @@
+mod common;
+
+use common::run_bssh;
+use std::path::Path;
+
+/// Stub `ssh` that writes each argument on its own line and exits 0.
+fn install_recording_ssh(config_home: &Path) {
+    common::install_stub(
+        config_home,
+        "ssh",
+        &format!(
+            "printf '%s\\n' \"$@\" > '{}'\nexit 0\n",
+            config_home.join("args").display()
+        ),
+    );
+}
+
+fn ssh_args(config_home: &Path) -> Vec<String> {
+    std::fs::read_to_string(config_home.join("args"))
+        .unwrap_or_default()
+        .lines()
+        .map(String::from)
+        .collect()
+}
+
+#[test]
+fn connect_forwards_set_env_and_send_env() {
+    // Given: a connection with a remote variable and a SendEnv pattern.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    install_recording_ssh(home);
+    let add = run_bssh(
+        home,
+        &[
+            "add",
+            "app01",
+            "app01.corp",
+            "--set-env",
+            "APP_ENV=staging blue",
+            "--send-env",
+            "LC_*",
+        ],
+    );
+    assert!(
+        add.status.success(),
+        "{}",
+        String::from_utf8_lossy(&add.stderr)
+    );
+
+    // When: connecting.
+    assert!(run_bssh(home, &["connect", "app01"]).status.success());
+
+    // Then: ssh receives both options, with the value quoted.
+    let args = ssh_args(home);
+    assert!(
+        args.contains(&"SetEnv=APP_ENV=\"staging blue\"".to_string()),
+        "{args:?}"
+    );
+    assert!(args.contains(&"SendEnv=LC_*".to_string()), "{args:?}");
+
+    // When: the variable is replaced and the patterns cleared.
+    assert!(run_bssh(
+        home,
+        &[
+            "edit",
+            "app01",
+            "--set-env",
+            "APP_ENV=prod",
+            "--clear-send-env"
+        ]
+    )
+    .status
+    .success());
+    assert!(run_bssh(home, &["connect", "app01"]).status.success());
+
+    // Then: only the new value is passed.
+    let args = ssh_args(home);
+    assert!(
+        args.contains(&"SetEnv=APP_ENV=\"prod\"".to_string()),
+        "{args:?}"
+    );
+    assert!(!args.iter().any(|a| a.starts_with("SendEnv=")), "{args:?}");
+    let show = run_bssh(home, &["show", "app01"]);
+    assert!(String::from_utf8_lossy(&show.stdout).contains("SetEnv:   APP_ENV=prod"));
+}
+
+#[test]
+fn set_env_rejects_malformed_pairs() {
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    for bad in ["NOVALUE", "1ABC=x", "A-B=x"] {
+        let add = run_bssh(
+            temp_dir.path(),
+            &["add", "x", "x.internal", "--set-env", bad],
+        );
+        assert!(!add.status.success(), "{bad} should be rejected");
+        assert!(String::from_utf8_lossy(&add.stderr).contains("invalid variable"));
+    }
+}
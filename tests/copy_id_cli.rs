@@ -0,0 +1,97 @@
+mod common;
+
+use common::run_bssh;
+use std::path::Path;
+
+/// Stub `ssh-copy-id` that records its arguments and exits 0.
+fn install_logging_copy_id(config_home: &Path) {
+    common::install_stub(
+        config_home,
+        "ssh-copy-id",
+        &format!(
+            "echo \"$@\" >> '{}'\nexit 0\n",
+            config_home.join("calls").display()
+        ),
+    );
+}
+
+#[test]
+fn copy_id_installs_the_connection_key_through_its_bastion() {
+    // Given: a connection behind a bastion with a key that has a .pub.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    install_logging_copy_id(home);
+    let key = home.join("deploy");
+    std::fs::write(&key, "key").expect("key should be written");
+    std::fs::write(home.join("deploy.pub"), "ssh-ed25519 AAAA").expect("pub should be written");
+    assert!(run_bssh(
+        home,
+        &[
+            "add",
+            "web-prod",
+            "web.example.com",
+            "-u",
+            "deploy",
+            "--port",
+            "2222",
+            "--bastion",
+            "jump.corp",
+            "--key",
+            key.to_str().unwrap(),
+        ]
+    )
+    .status
+    .success());
+
+    // When: copying the default key.
+    let copy = run_bssh(home, &["copy-id", "web-prod"]);
+
+    // Then: ssh-copy-id gets the .pub, the jump, the port and the target,
+    // and the run is in history.
+    assert!(
+        copy.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&copy.stderr)
+    );
+    let calls = std::fs::read_to_string(home.join("calls")).unwrap_or_default();
+    assert_eq!(
+        calls.trim(),
+        format!(
+            "-i {} -o ProxyJump=deploy@jump.corp -p 2222 deploy@web.example.com",
+            home.join("deploy.pub").display()
+        )
+    );
+    let history = run_bssh(home, &["history", "--label", "copy-id"]);
+    let stdout = String::from_utf8_lossy(&history.stdout);
+    assert!(stdout.contains("web-prod"), "history: {stdout}");
+}
+
+#[test]
+fn copy_id_without_a_public_key_fails_before_running() {
+    // Given: a connection whose key has no .pub next to it.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    install_logging_copy_id(home);
+    let key = home.join("deploy");
+    std::fs::write(&key, "key").expect("key should be written");
+    assert!(run_bssh(
+        home,
+        &[
+            "add",
+            "web-prod",
+            "web.example.com",
+            "--key",
+            key.to_str().unwrap()
+        ]
+    )
+    .status
+    .success());
+
+    // When: copying without naming a key.
+    let copy = run_bssh(home, &["copy-id", "web-prod"]);
+
+    // Then: it asks for one and never runs ssh-copy-id.
+    assert!(!copy.status.success());
+    assert!(String::from_utf8_lossy(&copy.stderr).contains("pass one explicitly"));
+    assert!(!home.join("calls").exists());
+}
@@ -0,0 +1,33 @@
+mod common;
+
+use common::run_bssh;
+use std::path::Path;
+
+#[test]
+fn database_flag_uses_an_isolated_inventory() {
+    // Given: an alternate database path whose parent does not exist yet.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    let alt_db = home.join("inventories").join("work.db");
+    let alt_db = alt_db.to_str().expect("temp path should be UTF-8");
+
+    // When: a connection is added through --database.
+    assert!(run_bssh(
+        home,
+        &["--database", alt_db, "add", "web-prod", "web.example.com"]
+    )
+    .status
+    .success());
+
+    // Then: it is visible only when the same database is selected.
+    let with_flag = run_bssh(home, &["list", "--json", "--database", alt_db]);
+    let listed: serde_json::Value =
+        serde_json::from_slice(&with_flag.stdout).expect("list --json should be valid JSON");
+    assert_eq!(listed[0]["name"], "web-prod");
+
+    let without_flag = run_bssh(home, &["list", "--json"]);
+    let listed: serde_json::Value =
+        serde_json::from_slice(&without_flag.stdout).expect("list --json should be valid JSON");
+    assert_eq!(listed, serde_json::json!([]));
+    assert!(Path::new(alt_db).exists());
+}
@@ -0,0 +1,109 @@
+mod common;
+
+use common::run_bssh;
+
+#[test]
+fn db_check_reports_and_fixes_orphans() {
+    // Given: an alias left behind by a removed connection and a session for
+    // a connection id that never existed.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    assert!(run_bssh(home, &["add", "web-prod", "web.example.com"])
+        .status
+        .success());
+    assert!(run_bssh(home, &["add", "db01", "10.0.1.5"])
+        .status
+        .success());
+    assert!(run_bssh(home, &["alias", "add", "wp", "web-prod"])
+        .status
+        .success());
+    assert!(run_bssh(home, &["alias", "add", "db", "db01"])
+        .status
+        .success());
+    assert!(run_bssh(home, &["--yes", "remove", "web-prod"])
+        .status
+        .success());
+    let db = rusqlite::Connection::open(common::database_path(home)).expect("database should open");
+    // Older databases and other writers did not enforce the foreign keys.
+    db.execute_batch("PRAGMA foreign_keys = OFF")
+        .expect("foreign keys should be disabled");
+    db.execute(
+        "INSERT INTO sessions (id, connection_id, started_at, status)
+         VALUES ('0badc0de-0000-0000-0000-000000000000', 'missing-connection', '2026-01-01T00:00:00+00:00', 'Terminated')",
+        [],
+    )
+    .expect("session should be inserted");
+    db.execute(
+        "INSERT INTO aliases (alias, connection_id, created_at)
+         VALUES ('wp', 'missing-connection', '2026-01-01T00:00:00+00:00')",
+        [],
+    )
+    .expect("alias should be inserted");
+    drop(db);
+
+    // When: checking without --fix.
+    let check = run_bssh(home, &["db", "check"]);
+
+    // Then: both orphans are reported and nothing is deleted.
+    assert!(check.status.success());
+    let stdout = String::from_utf8_lossy(&check.stdout);
+    assert!(
+        stdout.contains("1 session(s) reference a missing connection"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("0badc0de (connection missing-"), "{stdout}");
+    assert!(
+        stdout.contains("1 alias(es) point at a missing connection"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("• wp → "), "{stdout}");
+    assert!(!stdout.contains("• db → "), "{stdout}");
+
+    // When: fixing.
+    let fix = run_bssh(home, &["db", "check", "--fix"]);
+
+    // Then: the orphans are gone and the live alias is kept.
+    assert!(String::from_utf8_lossy(&fix.stdout)
+        .contains("Deleted 1 orphaned session(s) and 1 alias(es)"));
+    let recheck = run_bssh(home, &["db", "check"]);
+    assert!(String::from_utf8_lossy(&recheck.stdout).contains("No orphaned sessions or aliases"));
+    let aliases = run_bssh(home, &["alias", "list"]);
+    assert!(String::from_utf8_lossy(&aliases.stdout).contains("db"));
+}
+
+#[test]
+fn db_optimize_reclaims_space_after_churn() {
+    // Given: a database that grew with session rows which were then deleted.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    assert!(run_bssh(home, &["add", "db01", "10.0.1.5"])
+        .status
+        .success());
+    let db = rusqlite::Connection::open(common::database_path(home)).expect("database should open");
+    let connection_id: String = db
+        .query_row("SELECT id FROM connections", [], |row| row.get(0))
+        .expect("connection should exist");
+    let label = "x".repeat(1024);
+    for i in 0..500 {
+        db.execute(
+            "INSERT INTO sessions (id, connection_id, started_at, status, label)
+             VALUES (?, ?, '2026-01-01T00:00:00+00:00', 'Terminated', ?)",
+            rusqlite::params![format!("session-{i}"), connection_id, label],
+        )
+        .expect("session should be inserted");
+    }
+    db.execute("DELETE FROM sessions", [])
+        .expect("sessions should be deleted");
+    drop(db);
+
+    // When: optimizing.
+    let optimize = run_bssh(home, &["db", "optimize"]);
+
+    // Then: the before/after sizes are reported and space was reclaimed.
+    assert!(optimize.status.success());
+    let stdout = String::from_utf8_lossy(&optimize.stdout);
+    assert!(stdout.contains("Before:"), "{stdout}");
+    assert!(stdout.contains("After:"), "{stdout}");
+    assert!(stdout.contains("Reclaimed"), "{stdout}");
+    assert!(run_bssh(home, &["show", "db01"]).status.success());
+}
@@ -0,0 +1,55 @@
+mod common;
+
+use common::run_bssh;
+
+#[test]
+fn diff_marks_only_fields_that_differ() {
+    // Given: staging and prod sharing a user but not a port or bastion.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    assert!(run_bssh(
+        home,
+        &[
+            "add",
+            "web-staging",
+            "web.staging",
+            "-u",
+            "deploy",
+            "-p",
+            "2222"
+        ]
+    )
+    .status
+    .success());
+    assert!(run_bssh(
+        home,
+        &[
+            "add",
+            "web-prod",
+            "web.prod",
+            "-u",
+            "deploy",
+            "-b",
+            "jump.prod"
+        ]
+    )
+    .status
+    .success());
+
+    // When: the two are compared.
+    let output = run_bssh(home, &["diff", "web-staging", "web-prod"]);
+
+    // Then: differing fields carry the marker, shared ones do not.
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let line = |field: &str| {
+        stdout
+            .lines()
+            .find(|l| l.trim_start_matches(['≠', ' ']).starts_with(field))
+            .unwrap_or_else(|| panic!("missing {field} row in {stdout}"))
+            .to_string()
+    };
+    assert!(line("port").contains('≠'), "{stdout}");
+    assert!(line("bastion ").contains('≠'), "{stdout}");
+    assert!(!line("user").contains('≠'), "{stdout}");
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use common::run_bssh;
+use std::path::Path;
+
+// A syntactically valid base64 key blob; discovery never inspects it
+const KEY: &str = "AAAAC3NzaC1lZDI1NTE5AAAAIB6pS4wq3Y2hE0tA7cZ5fW6v8H4pXkz1QmXo3rN9yDcE";
+
+fn list_json(config_home: &Path) -> Vec<serde_json::Value> {
+    let output = run_bssh(config_home, &["list", "--json"]);
+    let value: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("list --json should be valid JSON");
+    value
+        .as_array()
+        .expect("list --json should be an array")
+        .clone()
+}
+
+fn write_known_hosts(home: &Path) -> String {
+    let path = home.join("known_hosts");
+    std::fs::write(
+        &path,
+        format!(
+            "web.corp,10.0.0.5 ssh-ed25519 {KEY}\n\
+             [db.corp]:2222 ssh-ed25519 {KEY}\n\
+             saved.corp ssh-ed25519 {KEY}\n"
+        ),
+    )
+    .expect("known_hosts should be written");
+    path.to_str()
+        .expect("temp path should be UTF-8")
+        .to_string()
+}
+
+#[test]
+fn discover_adds_new_hosts_with_the_discovered_tag() {
+    // Given: a known_hosts file listing three hosts, one of them already saved.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    let known_hosts = write_known_hosts(home);
+    assert!(run_bssh(home, &["add", "saved", "saved.corp"])
+        .status
+        .success());
+
+    // When: discovery runs and the prompt is answered with --yes.
+    let output = run_bssh(home, &["--yes", "discover", "-f", &known_hosts]);
+
+    // Then: the two new hosts are saved and tagged, the duplicate is skipped.
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Added 2 connection(s)"), "{stdout}");
+    assert!(stdout.contains("Skipped 1 host(s)"), "{stdout}");
+
+    let connections = list_json(home);
+    let db = connections
+        .iter()
+        .find(|c| c["name"] == "db.corp-2222")
+        .expect("db.corp should be discovered under a port-suffixed name");
+    assert_eq!(db["host"], "db.corp");
+    assert_eq!(db["port"], 2222);
+    assert_eq!(db["tags"], serde_json::json!(["discovered"]));
+    assert!(connections.iter().any(|c| c["name"] == "web.corp"));
+    assert_eq!(connections.len(), 3);
+}
+
+#[test]
+fn discover_dry_run_lists_hosts_without_saving() {
+    // Given: a known_hosts file and an empty inventory.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    let known_hosts = write_known_hosts(home);
+
+    // When: discovery is previewed.
+    let output = run_bssh(home, &["discover", "--dry-run", "-f", &known_hosts]);
+
+    // Then: every host is listed and nothing is written.
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Found 3 new host(s)"), "{stdout}");
+    assert!(stdout.contains("Nothing was written"), "{stdout}");
+    assert!(list_json(home).is_empty());
+}
@@ -1,26 +1,7 @@
-use std::fs;
-use std::path::{Path, PathBuf};
-use std::process::{Command, Output};
-
-fn run_bssh(config_home: &Path, env_name: &str, args: &[&str]) -> Output {
-    let mut command = Command::new(env!("CARGO_BIN_EXE_bayesian-ssh"));
-    command
-        .env("XDG_CONFIG_HOME", config_home)
-        .env("XDG_DATA_HOME", config_home.join("data"))
-        .arg("--env")
-        .arg(env_name)
-        .args(args);
-
-    command.output().expect("bssh command should run")
-}
+mod common;
 
-fn config_file(config_home: &Path, env_name: &str) -> PathBuf {
-    config_home
-        .join("bayesian-ssh")
-        .join("environments")
-        .join(env_name)
-        .join("config.json")
-}
+use common::run_bssh;
+use std::fs;
 
 #[test]
 fn doctor_reports_actionable_health_when_environment_is_new() {
@@ -28,7 +9,7 @@ fn doctor_reports_actionable_health_when_environment_is_new() {
     let temp_dir = tempfile::tempdir().expect("temp dir should be created");
 
     // When: the user runs the real CLI doctor command.
-    let output = run_bssh(temp_dir.path(), "fresh", &["doctor"]);
+    let output = run_bssh(temp_dir.path(), &["doctor"]);
 
     // Then: the command succeeds and reports the key local health checks.
     assert!(
@@ -48,12 +29,9 @@ fn doctor_reports_actionable_health_when_environment_is_new() {
 fn doctor_fails_with_actionable_database_error_when_database_parent_is_file() {
     // Given: a valid config whose database parent path is occupied by a file.
     let temp_dir = tempfile::tempdir().expect("temp dir should be created");
-    let env_name = "broken-db";
-    let config_path = config_file(temp_dir.path(), env_name);
-    let env_dir = config_path
-        .parent()
-        .expect("config path should have parent");
-    fs::create_dir_all(env_dir).expect("env dir should be created");
+    let env_dir = common::env_dir(temp_dir.path());
+    let config_path = env_dir.join("config.json");
+    fs::create_dir_all(&env_dir).expect("env dir should be created");
 
     let blocked_parent = env_dir.join("blocked");
     fs::write(&blocked_parent, "not a directory").expect("blocked file should be created");
@@ -86,7 +64,7 @@ fn doctor_fails_with_actionable_database_error_when_database_parent_is_file() {
     fs::write(&config_path, config_json).expect("config should be written");
 
     // When: the user runs the real CLI doctor command.
-    let output = run_bssh(temp_dir.path(), env_name, &["doctor"]);
+    let output = run_bssh(temp_dir.path(), &["doctor"]);
 
     // Then: doctor fails with an actionable database diagnostic.
     assert!(
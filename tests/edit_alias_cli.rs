@@ -0,0 +1,101 @@
+mod common;
+
+use common::run_bssh;
+use std::path::Path;
+
+fn aliases(config_home: &Path) -> Vec<(String, String)> {
+    let output = run_bssh(config_home, &["alias", "list", "--json"]);
+    assert!(output.status.success());
+    let rows: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("alias list should be JSON");
+    let mut aliases: Vec<(String, String)> = rows
+        .as_array()
+        .expect("alias list should be an array")
+        .iter()
+        .map(|row| {
+            (
+                row["alias"].as_str().unwrap().to_string(),
+                row["connection"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect();
+    aliases.sort();
+    aliases
+}
+
+#[test]
+fn edit_adds_and_removes_aliases() {
+    // Given: a connection with one alias.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    assert!(run_bssh(home, &["add", "web-prod", "web.corp"])
+        .status
+        .success());
+    assert!(run_bssh(home, &["alias", "add", "wp", "web-prod"])
+        .status
+        .success());
+
+    // When: swapping the alias for two new ones in a single edit.
+    let edit = run_bssh(
+        home,
+        &[
+            "edit",
+            "web-prod",
+            "--remove-alias",
+            "wp",
+            "--add-alias",
+            "web",
+            "--add-alias",
+            "www",
+        ],
+    );
+
+    // Then: only the new aliases point at the connection.
+    assert!(
+        edit.status.success(),
+        "{}",
+        String::from_utf8_lossy(&edit.stderr)
+    );
+    assert_eq!(
+        aliases(home),
+        vec![
+            ("web".to_string(), "web-prod".to_string()),
+            ("www".to_string(), "web-prod".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn edit_rejects_taken_aliases_without_changing_anything() {
+    // Given: two connections, one of which owns the alias "db".
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    assert!(run_bssh(home, &["add", "db01", "db01.corp"])
+        .status
+        .success());
+    assert!(run_bssh(home, &["add", "db02", "db02.corp"])
+        .status
+        .success());
+    assert!(run_bssh(home, &["alias", "add", "db", "db01"])
+        .status
+        .success());
+
+    // When: claiming "db" or a connection name for db02, or removing db01's alias.
+    let taken = run_bssh(
+        home,
+        &["edit", "db02", "--port", "2222", "--add-alias", "db"],
+    );
+    let name = run_bssh(home, &["edit", "db02", "--add-alias", "db01"]);
+    let foreign = run_bssh(home, &["edit", "db02", "--remove-alias", "db"]);
+
+    // Then: every edit fails and nothing was written.
+    assert!(!taken.status.success());
+    assert!(String::from_utf8_lossy(&taken.stderr).contains("already exists"));
+    assert!(!name.status.success());
+    assert!(!foreign.status.success());
+    assert_eq!(aliases(home), vec![("db".to_string(), "db01".to_string())]);
+    let show = run_bssh(home, &["show", "db02", "--json"]);
+    let conn: serde_json::Value =
+        serde_json::from_slice(&show.stdout).expect("show should be JSON");
+    assert_eq!(conn["port"], 22);
+}
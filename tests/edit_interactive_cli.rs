@@ -0,0 +1,37 @@
+mod common;
+
+use common::run_bssh;
+
+#[test]
+fn interactive_edit_needs_a_terminal_and_changes_nothing_without_one() {
+    // Given: a saved connection.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    assert!(
+        run_bssh(home, &["add", "web-prod", "web.example.com", "-p", "22"])
+            .status
+            .success()
+    );
+
+    // When: the form is opened without a terminal on stdin.
+    let edit = run_bssh(home, &["edit", "web-prod", "-i", "--port", "2222"]);
+
+    // Then: it fails before saving, so the flag change is not applied either.
+    assert!(!edit.status.success());
+    assert!(String::from_utf8_lossy(&edit.stderr).contains("not a terminal"));
+    let show = run_bssh(home, &["show", "web-prod", "--json"]);
+    let conn: serde_json::Value =
+        serde_json::from_slice(&show.stdout).expect("show --json should print JSON");
+    assert_eq!(conn["port"], 22);
+}
+
+#[test]
+fn interactive_edit_is_for_a_single_connection() {
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+
+    // Given/When: the form is combined with a bulk filter.
+    let edit = run_bssh(temp_dir.path(), &["edit", "--filter", "prod", "-i"]);
+
+    // Then: clap rejects the combination.
+    assert!(!edit.status.success());
+}
@@ -1,23 +1,14 @@
-use std::process::{Command, Output};
+mod common;
 
-fn run_bssh(env_name: &str, args: &[&str]) -> Output {
-    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
-    let mut command = Command::new(env!("CARGO_BIN_EXE_bayesian-ssh"));
-    command
-        .env("XDG_CONFIG_HOME", temp_dir.path())
-        .env("XDG_DATA_HOME", temp_dir.path().join("data"))
-        .arg("--env")
-        .arg(env_name)
-        .args(args);
-
-    command.output().expect("bssh command should run")
-}
+use common::run_bssh;
 
 #[test]
 fn command_failure_prints_actionable_error_when_database_file_is_missing() {
     // Given: a fresh environment where no command has initialized the SQLite database yet.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+
     // When: the user asks bssh to back up that missing database.
-    let output = run_bssh("missing-db", &["backup"]);
+    let output = run_bssh(temp_dir.path(), &["backup"]);
 
     // Then: the real CLI exits with a clear error and recovery hint.
     assert!(!output.status.success());
@@ -27,3 +18,57 @@ fn command_failure_prints_actionable_error_when_database_file_is_missing() {
     assert!(stderr.contains("Database file does not exist"));
     assert!(stderr.contains("Suggestion:"));
 }
+
+#[test]
+fn destructive_prompt_without_tty_refuses_unless_assumed_yes() {
+    // Given: an environment with two saved connections and no terminal on stdin.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    assert!(run_bssh(home, &["add", "web", "web.example.com"])
+        .status
+        .success());
+    assert!(run_bssh(home, &["add", "db", "db.example.com"])
+        .status
+        .success());
+
+    // When: removal is attempted without, then with, an explicit yes.
+    let refused = run_bssh(home, &["remove", "web"]);
+    let with_flag = run_bssh(home, &["remove", "web", "--yes"]);
+    let with_env = common::bssh(home)
+        .env("BSSH_ASSUME_YES", "1")
+        .args(["--env", common::ENV, "remove", "db"])
+        .output()
+        .expect("bssh command should run");
+
+    // Then: the prompt is refused with a hint, and both opt-ins proceed.
+    assert!(!refused.status.success());
+    let stderr = String::from_utf8_lossy(&refused.stderr);
+    assert!(stderr.contains("Confirmation required"), "{stderr}");
+    assert!(stderr.contains("--yes"), "{stderr}");
+    assert!(with_flag.status.success());
+    assert!(with_env.status.success());
+    let list = run_bssh(home, &["list", "--json"]);
+    assert_eq!(String::from_utf8_lossy(&list.stdout).trim(), "[]");
+}
+
+#[test]
+fn single_fuzzy_match_is_not_taken_without_a_tty() {
+    // Given: one saved connection and no terminal on stdin.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    assert!(run_bssh(home, &["add", "web-prod", "web.example.com"])
+        .status
+        .success());
+
+    // When: an edit names the connection only approximately.
+    let edit = run_bssh(home, &["edit", "prod", "--port", "2"]);
+
+    // Then: the fuzzy match is not confirmed by default and nothing changes.
+    assert!(!edit.status.success());
+    let stderr = String::from_utf8_lossy(&edit.stderr);
+    assert!(stderr.contains("Confirmation required"), "{stderr}");
+    let show = run_bssh(home, &["show", "web-prod", "--json"]);
+    let connection: serde_json::Value =
+        serde_json::from_slice(&show.stdout).expect("show should be JSON");
+    assert_eq!(connection["port"], 22);
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use common::run_bssh;
+use std::path::Path;
+
+/// Stub `ssh` that fails on hosts whose name starts with "bad"
+fn install_stub_ssh(config_home: &Path) {
+    common::install_stub(
+        config_home,
+        "ssh",
+        "case \"$*\" in *@bad*) echo 'nginx: inactive' >&2; exit 3;; esac\necho active\n",
+    );
+}
+
+fn add(home: &Path, name: &str, host: &str, tag: &str) {
+    // The bastion selects the subprocess transport.
+    assert!(run_bssh(
+        home,
+        &["add", name, host, "-t", tag, "--bastion", "jump.corp"]
+    )
+    .status
+    .success());
+}
+
+#[test]
+fn every_tagged_host_runs_the_command() {
+    // Given: three web hosts and one db host.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    install_stub_ssh(home);
+    add(home, "web-1", "web1.corp", "web");
+    add(home, "web-2", "web2.corp", "web");
+    add(home, "web-3", "web3.corp", "web");
+    add(home, "db-1", "db1.corp", "db");
+
+    // When: running a check on the web tag, two at a time.
+    let exec = run_bssh(
+        home,
+        &[
+            "exec",
+            "--tag",
+            "web",
+            "--parallel",
+            "2",
+            "--",
+            "systemctl",
+            "is-active",
+            "nginx",
+        ],
+    );
+
+    // Then: each web host reports, the db host is skipped, and all passed.
+    let stdout = String::from_utf8_lossy(&exec.stdout);
+    assert!(
+        exec.status.success(),
+        "{stdout}{}",
+        String::from_utf8_lossy(&exec.stderr)
+    );
+    for host in ["web-1", "web-2", "web-3"] {
+        assert!(stdout.contains(&format!("── {host} ──")), "{stdout}");
+    }
+    assert!(!stdout.contains("db-1"), "{stdout}");
+    assert_eq!(stdout.matches("active\n").count(), 3, "{stdout}");
+    assert!(stdout.contains("3 passed, 0 failed"), "{stdout}");
+}
+
+#[test]
+fn one_failing_host_fails_the_run() {
+    // Given: a healthy and a broken host with the same tag.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    install_stub_ssh(home);
+    add(home, "web-1", "web1.corp", "web");
+    add(home, "web-bad", "bad.corp", "web");
+
+    // When: running the check.
+    let exec = run_bssh(home, &["exec", "-t", "web", "--", "true"]);
+
+    // Then: the table marks the broken host and the exit code is nonzero.
+    assert_eq!(exec.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&exec.stdout);
+    let row = stdout
+        .lines()
+        .find(|line| line.starts_with("web-bad "))
+        .unwrap_or_else(|| panic!("no table row for web-bad: {stdout}"));
+    assert!(row.contains("fail") && row.ends_with('3'), "{row}");
+    assert!(String::from_utf8_lossy(&exec.stderr).contains("nginx: inactive"));
+    assert!(stdout.contains("1 passed, 1 failed"), "{stdout}");
+}
+
+#[test]
+fn an_unknown_tag_is_an_error() {
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+
+    let exec = run_bssh(home, &["exec", "--tag", "nope", "--", "uptime"]);
+
+    assert!(!exec.status.success());
+    assert!(String::from_utf8_lossy(&exec.stderr).contains("No connections tagged 'nope'"));
+}
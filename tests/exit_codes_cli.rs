@@ -0,0 +1,87 @@
+mod common;
+
+use common::run_bssh;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Output, Stdio};
+
+fn run_bssh_with_input(config_home: &Path, args: &[&str], input: &str) -> Output {
+    let mut child = common::bssh(config_home)
+        .args(["--env", common::ENV])
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("bssh command should run");
+    child
+        .stdin
+        .take()
+        .expect("stdin should be piped")
+        .write_all(input.as_bytes())
+        .expect("input should be written");
+    child
+        .wait_with_output()
+        .expect("bssh command should finish")
+}
+
+/// Stub `ssh` that fails the way an unreachable host does.
+fn install_failing_ssh(config_home: &Path) {
+    common::install_stub(config_home, "ssh", "exit 255\n");
+}
+
+fn add(config_home: &Path, name: &str, host: &str) {
+    let output = run_bssh(config_home, &["add", name, host, "--bastion", "jump.corp"]);
+    assert!(output.status.success());
+}
+
+#[test]
+fn unknown_target_exits_3() {
+    // Given: no saved connections.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+
+    // When: removing something that does not exist.
+    let remove = run_bssh(home, &["remove", "ghost", "--yes"]);
+
+    // Then: the status says "not found".
+    assert_eq!(remove.status.code(), Some(3));
+}
+
+#[test]
+fn quitting_the_picker_exits_4_without_connecting() {
+    // Given: two connections that both match "prod".
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    install_failing_ssh(home);
+    add(home, "web-prod", "web.example.com");
+    add(home, "db-prod", "db.example.com");
+
+    // When: quitting the picker.
+    let connect = run_bssh_with_input(home, &["connect", "prod"], "q\n");
+
+    // Then: the status says "cancelled".
+    assert_eq!(connect.status.code(), Some(4));
+}
+
+#[test]
+fn failed_connect_and_ping_exit_5() {
+    // Given: a connection whose ssh cannot reach the host.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    install_failing_ssh(home);
+    add(home, "web-prod", "web.example.com");
+    assert!(
+        run_bssh(home, &["add", "closed", "127.0.0.1", "--port", "1"])
+            .status
+            .success()
+    );
+
+    // When: connecting, and pinging a closed port.
+    let connect = run_bssh(home, &["connect", "web-prod"]);
+    let ping = run_bssh(home, &["ping", "closed", "-t", "2"]);
+
+    // Then: both say "unreachable".
+    assert_eq!(connect.status.code(), Some(5));
+    assert_eq!(ping.status.code(), Some(5));
+}
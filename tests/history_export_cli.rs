@@ -0,0 +1,111 @@
+mod common;
+
+use common::run_bssh;
+use std::path::Path;
+
+fn install_stub_ssh(config_home: &Path, exit_code: i32) {
+    common::install_stub(config_home, "ssh", &format!("exit {}\n", exit_code));
+}
+
+/// One clean session on web-prod and one failed session on db-prod
+fn seed_sessions(home: &Path) {
+    for (name, host) in [
+        ("web-prod", "web.example.com"),
+        ("db-prod", "db.example.com"),
+    ] {
+        assert!(
+            run_bssh(home, &["add", name, host, "--bastion", "jump.example.com"])
+                .status
+                .success()
+        );
+    }
+    install_stub_ssh(home, 0);
+    assert!(run_bssh(home, &["connect", "web-prod"]).status.success());
+    install_stub_ssh(home, 3);
+    run_bssh(home, &["connect", "db-prod"]);
+}
+
+#[test]
+fn history_export_writes_filtered_sessions_as_json() {
+    let temp = tempfile::tempdir().expect("tempdir should be created");
+    let home = temp.path();
+    seed_sessions(home);
+    let file = home.join("audit/sessions.json");
+
+    // Given two recorded sessions
+    // When exporting only web-prod's history
+    let output = run_bssh(
+        home,
+        &[
+            "history",
+            "-c",
+            "web-prod",
+            "--export",
+            file.to_str().unwrap(),
+        ],
+    );
+
+    // Then the file holds just that session, with its exit code and status
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Exported 1 session(s)"));
+    let rows: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&file).expect("export should exist"))
+            .expect("export should be JSON");
+    let rows = rows.as_array().expect("export should be an array");
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["connection"], "web-prod");
+    assert_eq!(rows[0]["status"], "terminated");
+    assert_eq!(rows[0]["exit_code"], 0);
+    assert!(rows[0]["started_at"].as_str().is_some());
+    assert!(rows[0]["duration_secs"].as_i64().is_some());
+}
+
+#[test]
+fn history_export_writes_csv_with_a_header() {
+    let temp = tempfile::tempdir().expect("tempdir should be created");
+    let home = temp.path();
+    seed_sessions(home);
+    let file = home.join("sessions.csv");
+
+    // Given two recorded sessions
+    // When exporting every failed session as CSV
+    let output = run_bssh(
+        home,
+        &[
+            "history",
+            "--failed",
+            "--export",
+            file.to_str().unwrap(),
+            "--format",
+            "csv",
+        ],
+    );
+
+    // Then the CSV has a header and the failed session only
+    assert!(output.status.success());
+    let csv = std::fs::read_to_string(&file).expect("export should exist");
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        lines[0],
+        "connection,started_at,ended_at,duration_secs,status,error,exit_code,label,transcript"
+    );
+    assert_eq!(lines.len(), 2, "csv: {}", csv);
+    assert!(lines[1].starts_with("db-prod,"));
+    assert!(lines[1].contains(",3,"));
+}
+
+#[test]
+fn history_format_requires_export() {
+    let temp = tempfile::tempdir().expect("tempdir should be created");
+
+    // Given no --export destination
+    // When passing --format
+    let output = run_bssh(temp.path(), &["history", "--format", "csv"]);
+
+    // Then clap rejects the invocation
+    assert!(!output.status.success());
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use common::run_bssh;
+use std::path::Path;
+
+/// Three sessions: web-prod on March 1st and 2nd, db-prod on March 2nd
+fn seed_sessions(home: &Path) {
+    common::install_stub_ssh(home);
+    for (name, host) in [
+        ("web-prod", "web.example.com"),
+        ("db-prod", "db.example.com"),
+    ] {
+        assert!(
+            run_bssh(home, &["add", name, host, "--bastion", "jump.example.com"])
+                .status
+                .success()
+        );
+    }
+    for target in ["web-prod", "web-prod", "db-prod"] {
+        assert!(run_bssh(home, &["connect", target]).status.success());
+    }
+
+    // Backdate the sessions to fixed instants, in the order they were opened
+    let db = rusqlite::Connection::open(common::database_path(home)).expect("database should open");
+    let ids: Vec<String> = db
+        .prepare("SELECT id FROM sessions ORDER BY started_at")
+        .and_then(|mut stmt| {
+            stmt.query_map([], |row| row.get(0))?
+                .collect::<Result<_, _>>()
+        })
+        .expect("sessions should be listed");
+    for (id, started) in ids.iter().zip([
+        "2026-03-01T00:00:00+00:00",
+        "2026-03-02T15:00:00+00:00",
+        "2026-03-02T15:30:00+00:00",
+    ]) {
+        db.execute(
+            "UPDATE sessions SET started_at = ?1, ended_at = ?1 WHERE id = ?2",
+            [started, id],
+        )
+        .expect("session should be backdated");
+    }
+}
+
+#[test]
+fn since_and_until_bound_history_with_the_connection_filter() {
+    // Given: three sessions across two days.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    seed_sessions(home);
+
+    // When: history is scoped to an hour on March 2nd.
+    let window = run_bssh(
+        home,
+        &[
+            "history",
+            "--since",
+            "2026-03-02T14:00:00Z",
+            "--until",
+            "2026-03-02T16:00:00Z",
+        ],
+    );
+
+    // Then: both sessions in that window are listed, the March 1st one is not.
+    assert!(window.status.success());
+    let stdout = String::from_utf8_lossy(&window.stdout);
+    assert!(stdout.contains("Summary: 2 sessions"), "{stdout}");
+
+    // And combined with -c only that connection's session remains.
+    let scoped = run_bssh(
+        home,
+        &[
+            "history",
+            "-c",
+            "web-prod",
+            "--since",
+            "2026-03-02",
+            "--until",
+            "2026-03-02",
+        ],
+    );
+    let stdout = String::from_utf8_lossy(&scoped.stdout);
+    assert!(stdout.contains("Summary: 1 sessions"), "{stdout}");
+    assert!(!stdout.contains("db-prod"), "{stdout}");
+}
+
+#[test]
+fn inverted_or_malformed_windows_are_rejected() {
+    // Given: an empty environment.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+
+    // When/Then: --since after --until fails with a clear message.
+    let inverted = run_bssh(
+        home,
+        &["history", "--since", "2026-03-03", "--until", "2026-03-02"],
+    );
+    assert!(!inverted.status.success());
+    assert!(String::from_utf8_lossy(&inverted.stderr).contains("--since must not be later"));
+
+    // When/Then: an unparseable date is refused by the argument parser.
+    let malformed = run_bssh(home, &["history", "--since", "yesterday"]);
+    assert!(!malformed.status.success());
+    assert!(String::from_utf8_lossy(&malformed.stderr).contains("expected YYYY-MM-DD or RFC3339"));
+}
@@ -0,0 +1,171 @@
+mod common;
+
+use common::run_bssh;
+use std::path::Path;
+
+fn list_json(config_home: &Path) -> Vec<serde_json::Value> {
+    let output = run_bssh(config_home, &["list", "--json"]);
+    let value: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("list --json should be valid JSON");
+    value
+        .as_array()
+        .expect("list --json should be an array")
+        .clone()
+}
+
+#[test]
+fn reimport_after_rename_matches_by_endpoint() {
+    // Given: a host imported once, then renamed locally.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    let ssh_config = home.join("ssh_config");
+    std::fs::write(
+        &ssh_config,
+        "Host web\n  HostName web.example.com\n  User deploy\n",
+    )
+    .expect("ssh config should be written");
+    let ssh_config = ssh_config.to_str().expect("temp path should be UTF-8");
+    assert!(run_bssh(home, &["import", "-f", ssh_config])
+        .status
+        .success());
+    assert!(run_bssh(home, &["edit", "web", "--name", "web-renamed"])
+        .status
+        .success());
+
+    // When: the same file is imported again, then with a new key and --update-existing.
+    assert!(run_bssh(home, &["import", "-f", ssh_config])
+        .status
+        .success());
+    let after_reimport = list_json(home);
+    std::fs::write(
+        ssh_config,
+        "Host web\n  HostName web.example.com\n  User deploy\n  IdentityFile ~/.ssh/web\n",
+    )
+    .expect("ssh config should be rewritten");
+    assert!(
+        run_bssh(home, &["import", "-f", ssh_config, "--update-existing"])
+            .status
+            .success()
+    );
+    let after_update = list_json(home);
+
+    // Then: no duplicate is created and the renamed record is refreshed in place.
+    assert_eq!(after_reimport.len(), 1);
+    assert_eq!(after_reimport[0]["name"], "web-renamed");
+    assert_eq!(after_update.len(), 1);
+    assert_eq!(after_update[0]["name"], "web-renamed");
+    assert_eq!(after_update[0]["key_path"], "~/.ssh/web");
+}
+
+#[test]
+fn dry_run_previews_without_saving() {
+    // Given: one saved host, plus a config with a duplicate, a new host and a wildcard.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    assert!(
+        run_bssh(home, &["add", "db", "db.example.com", "--user", "admin"])
+            .status
+            .success()
+    );
+    let ssh_config = home.join("ssh_config");
+    std::fs::write(
+        &ssh_config,
+        "Host *.internal\n  User ops\n\
+         Host db\n  HostName db.example.com\n\
+         Host web\n  HostName web.example.com\n  User deploy\n  Port 2222\n  IdentityFile ~/.ssh/web\n",
+    )
+    .expect("ssh config should be written");
+    let ssh_config = ssh_config.to_str().expect("temp path should be UTF-8");
+
+    // When: the import is previewed.
+    let output = run_bssh(home, &["import", "-f", ssh_config, "--dry-run"]);
+
+    // Then: each host is classified with its resolved fields and nothing is saved.
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("deploy@web.example.com:2222"), "{stdout}");
+    assert!(stdout.contains("~/.ssh/web"), "{stdout}");
+    assert!(stdout.contains("already saved as 'db'"), "{stdout}");
+    assert!(stdout.contains("wildcard"), "{stdout}");
+    assert!(stdout.contains("1 host(s) would be imported"), "{stdout}");
+
+    let names: Vec<_> = list_json(home)
+        .iter()
+        .map(|c| c["name"].as_str().unwrap_or_default().to_string())
+        .collect();
+    assert_eq!(names, vec!["db".to_string()]);
+}
+
+#[test]
+fn import_without_a_terminal_draws_no_progress_bar() {
+    // Given: a config with a couple of hosts.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    let ssh_config = home.join("ssh_config");
+    std::fs::write(
+        &ssh_config,
+        "Host web\n  HostName web.example.com\nHost db\n  HostName db.example.com\n",
+    )
+    .expect("ssh config should be written");
+
+    // When: it is imported with stdout captured (not a TTY).
+    let output = run_bssh(
+        home,
+        &["import", "-f", ssh_config.to_str().expect("UTF-8 path")],
+    );
+
+    // Then: both hosts are imported and no progress bar is drawn.
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("imported 2 connection(s)"));
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("Importing ["));
+    assert_eq!(list_json(home).len(), 2);
+}
+
+#[test]
+fn update_refreshes_changed_hosts_and_keeps_tags() {
+    // Given: two imported hosts, one of them tagged locally.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    let ssh_config = home.join("ssh_config");
+    std::fs::write(
+        &ssh_config,
+        "Host web\n  HostName web.example.com\n  User deploy\nHost db\n  HostName db.example.com\n",
+    )
+    .expect("ssh config should be written");
+    let ssh_config_arg = ssh_config.to_str().expect("temp path should be UTF-8");
+    assert!(run_bssh(home, &["import", "-f", ssh_config_arg])
+        .status
+        .success());
+    assert!(run_bssh(home, &["edit", "web", "--add-tags", "frontend"])
+        .status
+        .success());
+
+    // When: web moves behind a jump host, a host is added, and the config is re-imported.
+    std::fs::write(
+        &ssh_config,
+        "Host web\n  HostName web.example.com\n  User deploy\n  Port 2222\n  ProxyJump ops@jump.example.com\n\
+         Host db\n  HostName db.example.com\n\
+         Host cache\n  HostName cache.example.com\n",
+    )
+    .expect("ssh config should be rewritten");
+    let output = run_bssh(home, &["import", "-f", ssh_config_arg, "--update"]);
+
+    // Then: the summary counts each outcome and web keeps its tags.
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("1 added, 1 updated, 1 unchanged"),
+        "{stdout}"
+    );
+    let connections = list_json(home);
+    let web = connections
+        .iter()
+        .find(|c| c["name"] == "web")
+        .expect("web should still exist");
+    assert_eq!(web["port"], 2222);
+    assert_eq!(web["bastion"], "jump.example.com");
+    assert_eq!(web["bastion_user"], "ops");
+    let tags = web["tags"].as_array().expect("tags should be a list");
+    assert!(tags.iter().any(|t| t == "frontend"), "{tags:?}");
+    assert!(tags.iter().any(|t| t == "imported"), "{tags:?}");
+}
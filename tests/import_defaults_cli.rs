@@ -0,0 +1,53 @@
+mod common;
+
+use common::run_bssh;
+use std::path::Path;
+
+fn show(config_home: &Path, name: &str) -> serde_json::Value {
+    let output = run_bssh(config_home, &["show", name, "--json"]);
+    assert!(output.status.success(), "{name} should have been imported");
+    serde_json::from_slice(&output.stdout).expect("show should be JSON")
+}
+
+#[test]
+fn wildcard_blocks_supply_defaults_to_matching_hosts() {
+    // Given: an ssh config with global and per-domain defaults, one host
+    // overriding them, and the `Host *` block last as ssh configs usually do.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    let ssh_config = home.join("ssh_config");
+    std::fs::write(
+        &ssh_config,
+        "Host *.internal\n  ProxyJump ops@jump.corp\n  Port 2222\n\
+         Host db.internal\n  User postgres\n\
+         Host web\n  HostName web.example.com\n  Port 8022\n\
+         Host *\n  User deploy\n  Port 22\n  IdentityFile ~/.ssh/corp\n",
+    )
+    .expect("ssh config should be written");
+
+    // When: importing it.
+    let import = run_bssh(home, &["import", "-f", ssh_config.to_str().unwrap()]);
+
+    // Then: each host gets its own settings first, then the first matching pattern's.
+    assert!(
+        import.status.success(),
+        "{}",
+        String::from_utf8_lossy(&import.stderr)
+    );
+    let db = show(home, "db.internal");
+    assert_eq!(db["user"], "postgres");
+    assert_eq!(db["port"], 2222);
+    assert_eq!(db["bastion"], "jump.corp");
+    assert_eq!(db["bastion_user"], "ops");
+    assert_eq!(db["key_path"], "~/.ssh/corp");
+
+    let web = show(home, "web");
+    assert_eq!(web["user"], "deploy");
+    assert_eq!(web["port"], 8022);
+    assert!(web["bastion"].is_null(), "{web}");
+
+    // The patterns themselves are not connections.
+    let list = run_bssh(home, &["list", "--json"]);
+    let rows: serde_json::Value = serde_json::from_slice(&list.stdout).unwrap();
+    assert_eq!(rows.as_array().unwrap().len(), 2, "{rows}");
+}
@@ -0,0 +1,146 @@
+mod common;
+
+use common::run_bssh;
+
+#[test]
+fn list_and_show_emit_parseable_json_with_aliases() {
+    // Given: an environment with one connection that has an alias.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    assert!(run_bssh(
+        home,
+        &["add", "web-prod", "web.example.com", "-u", "deploy"]
+    )
+    .status
+    .success());
+    assert!(run_bssh(home, &["alias", "add", "wp", "web-prod"])
+        .status
+        .success());
+
+    // When: the user requests JSON output from list and show.
+    let list = run_bssh(home, &["list", "--json"]);
+    let show = run_bssh(home, &["show", "web-prod", "--json"]);
+
+    // Then: stdout is pure JSON carrying the connection and its aliases.
+    assert!(list.status.success());
+    let listed: serde_json::Value =
+        serde_json::from_slice(&list.stdout).expect("list --json should be valid JSON");
+    let first = &listed.as_array().expect("list --json should be an array")[0];
+    assert_eq!(first["name"], "web-prod");
+    assert_eq!(first["aliases"][0], "wp");
+
+    assert!(show.status.success());
+    let shown: serde_json::Value =
+        serde_json::from_slice(&show.stdout).expect("show --json should be valid JSON");
+    assert_eq!(shown["host"], "web.example.com");
+    assert_eq!(shown["user"], "deploy");
+    assert_eq!(shown["aliases"][0], "wp");
+}
+
+#[test]
+fn stats_json_includes_session_aggregates() {
+    // Given: an environment with one connection and no sessions yet.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    assert!(run_bssh(home, &["add", "web-prod", "web.example.com"])
+        .status
+        .success());
+
+    // When: the user requests JSON statistics.
+    let stats = run_bssh(home, &["stats", "--json"]);
+
+    // Then: stdout is JSON carrying both connection and session aggregates.
+    assert!(stats.status.success());
+    let value: serde_json::Value =
+        serde_json::from_slice(&stats.stdout).expect("stats --json should be valid JSON");
+    assert_eq!(value["total_connections"], 1);
+    assert_eq!(value["total_sessions"], 0);
+    assert_eq!(value["success_rate"], 0.0);
+    assert!(value["average_session_secs"].is_null());
+    assert!(value["busiest_weekday"].is_null());
+}
+
+#[test]
+fn stats_json_counts_sessions_per_connection_window() {
+    // Given: one connection opened once (via a stub ssh) and one never opened.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    common::install_stub_ssh(home);
+    for (name, host) in [
+        ("web-prod", "web.example.com"),
+        ("db-old", "db.example.com"),
+    ] {
+        assert!(
+            run_bssh(home, &["add", name, host, "--bastion", "jump.example.com"])
+                .status
+                .success()
+        );
+    }
+    let connect = run_bssh(home, &["connect", "web-prod"]);
+    assert!(connect.status.success());
+
+    // When: the user requests JSON statistics.
+    let stats = run_bssh(home, &["stats", "--json"]);
+
+    // Then: usage lists the busy host first and keeps the idle one at zero.
+    assert!(stats.status.success());
+    let value: serde_json::Value =
+        serde_json::from_slice(&stats.stdout).expect("stats --json should be valid JSON");
+    let usage = value["usage"].as_array().expect("usage should be an array");
+    assert_eq!(usage.len(), 2);
+    assert_eq!(usage[0]["name"], "web-prod");
+    assert_eq!(usage[0]["week"], 1);
+    assert_eq!(usage[0]["month"], 1);
+    assert_eq!(usage[0]["all_time"], 1);
+    assert_eq!(usage[1]["name"], "db-old");
+    assert_eq!(usage[1]["all_time"], 0);
+}
+
+#[test]
+fn stats_json_ranks_most_used_by_count_and_splits_ad_hoc_sessions() {
+    // Given: web-prod opened twice, db-old once after it, then an unsaved host.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    common::install_stub_ssh(home);
+    for (name, host) in [
+        ("web-prod", "web.example.com"),
+        ("db-old", "db.example.com"),
+    ] {
+        assert!(
+            run_bssh(home, &["add", name, host, "--bastion", "jump.example.com"])
+                .status
+                .success()
+        );
+    }
+    for target in ["web-prod", "web-prod", "db-old"] {
+        assert!(run_bssh(home, &["connect", target]).status.success());
+    }
+    let adhoc = run_bssh(
+        home,
+        &[
+            "connect",
+            "10.0.0.9",
+            "--ephemeral",
+            "--bastion",
+            "jump.example.com",
+        ],
+    );
+    assert!(
+        adhoc.status.success(),
+        "{}",
+        String::from_utf8_lossy(&adhoc.stderr)
+    );
+
+    // When: the user requests JSON statistics.
+    let stats = run_bssh(home, &["stats", "--json"]);
+
+    // Then: the busiest connection wins over the latest one, and the
+    // ad-hoc session is counted apart from the direct ones.
+    let value: serde_json::Value =
+        serde_json::from_slice(&stats.stdout).expect("stats --json should be valid JSON");
+    assert_eq!(value["most_used"]["name"], "web-prod");
+    assert_eq!(value["total_sessions"], 4);
+    assert_eq!(value["kerberos_sessions"], 0);
+    assert_eq!(value["direct_sessions"], 3);
+    assert_eq!(value["adhoc_sessions"], 1);
+}
@@ -0,0 +1,90 @@
+mod common;
+
+use common::run_bssh;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+fn add_connection_with_key(config_home: &Path, key: &Path) {
+    assert!(run_bssh(
+        config_home,
+        &[
+            "add",
+            "web-prod",
+            "web.example.com",
+            "--bastion",
+            "jump.example.com",
+            "-i",
+            key.to_str().unwrap(),
+        ],
+    )
+    .status
+    .success());
+}
+
+fn mode(path: &Path) -> u32 {
+    std::fs::metadata(path).unwrap().permissions().mode() & 0o777
+}
+
+#[test]
+fn missing_key_stops_before_ssh_runs() {
+    // Given: a connection whose key file does not exist.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    common::install_logging_ssh(home);
+    add_connection_with_key(home, &home.join("gone_ed25519"));
+
+    // When: connecting.
+    let connect = run_bssh(home, &["connect", "web-prod"]);
+
+    // Then: bssh explains the problem and never starts ssh.
+    assert!(!connect.status.success());
+    let stderr = String::from_utf8_lossy(&connect.stderr);
+    assert!(stderr.contains("does not exist"), "stderr: {stderr}");
+    assert!(stderr.contains("--clear-key"), "stderr: {stderr}");
+    assert!(common::ssh_calls(home).is_empty());
+}
+
+#[test]
+fn open_key_is_restricted_when_confirmed() {
+    // Given: a world-readable key.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    common::install_logging_ssh(home);
+    let key = home.join("id_ed25519");
+    std::fs::write(&key, "key").unwrap();
+    std::fs::set_permissions(&key, std::fs::Permissions::from_mode(0o644)).unwrap();
+    add_connection_with_key(home, &key);
+
+    // When: connecting with prompts approved.
+    let connect = run_bssh(home, &["--yes", "connect", "web-prod"]);
+
+    // Then: the key is tightened to 0600 and ssh runs.
+    assert!(
+        connect.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&connect.stderr)
+    );
+    assert!(String::from_utf8_lossy(&connect.stderr).contains("permissions 0644"));
+    assert_eq!(mode(&key), 0o600);
+    assert!(common::ssh_calls(home).contains("-i"));
+}
+
+#[test]
+fn open_key_is_left_alone_without_a_terminal() {
+    // Given: a group-readable key and no terminal to ask on.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    common::install_logging_ssh(home);
+    let key = home.join("id_ed25519");
+    std::fs::write(&key, "key").unwrap();
+    std::fs::set_permissions(&key, std::fs::Permissions::from_mode(0o640)).unwrap();
+    add_connection_with_key(home, &key);
+
+    // When: connecting.
+    let connect = run_bssh(home, &["connect", "web-prod"]);
+
+    // Then: bssh warns, leaves the mode unchanged and lets ssh decide.
+    assert!(String::from_utf8_lossy(&connect.stderr).contains("permissions 0640"));
+    assert_eq!(mode(&key), 0o640);
+    assert!(!common::ssh_calls(home).is_empty());
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use common::run_bssh;
+use std::path::Path;
+
+/// Stub `ssh` that prints `stderr` and exits with `code`.
+fn install_ssh(config_home: &Path, stderr: &str, code: i32) {
+    common::install_stub(
+        config_home,
+        "ssh",
+        &format!("echo '{}' >&2\nexit {}\n", stderr, code),
+    );
+}
+
+fn show(config_home: &Path) -> String {
+    let show = run_bssh(config_home, &["show", "web-prod"]);
+    assert!(show.status.success());
+    String::from_utf8_lossy(&show.stdout).into_owned()
+}
+
+#[test]
+fn failed_connect_is_remembered_until_the_next_success() {
+    // Given: a saved connection and an ssh whose key is rejected.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    assert!(run_bssh(
+        home,
+        &[
+            "add",
+            "web-prod",
+            "web.example.com",
+            "-b",
+            "jump.example.com"
+        ]
+    )
+    .status
+    .success());
+    install_ssh(
+        home,
+        "alice@web.example.com: Permission denied (publickey).",
+        255,
+    );
+
+    // When: the connect fails.
+    run_bssh(home, &["connect", "web-prod"]);
+
+    // Then: show reports ssh's error.
+    let stdout = show(home);
+    assert!(stdout.contains("Last error"), "{stdout}");
+    assert!(
+        stdout.contains("Permission denied (publickey)."),
+        "{stdout}"
+    );
+
+    // When: the next connect succeeds.
+    install_ssh(home, "", 0);
+    assert!(run_bssh(home, &["connect", "web-prod"]).status.success());
+
+    // Then: the error is gone.
+    let stdout = show(home);
+    assert!(!stdout.contains("Last error"), "{stdout}");
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use common::run_bssh;
+
+#[test]
+fn list_oneline_prints_tab_separated_rows_only() {
+    // Given: two saved connections, one of them tagged.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    assert!(run_bssh(
+        home,
+        &[
+            "add",
+            "web-prod",
+            "web.example.com",
+            "-u",
+            "deploy",
+            "-t",
+            "prod"
+        ]
+    )
+    .status
+    .success());
+    assert!(run_bssh(
+        home,
+        &["add", "db01", "10.0.1.5", "-u", "dba", "-p", "2222"]
+    )
+    .status
+    .success());
+
+    // When: listing with --oneline.
+    let list = run_bssh(home, &["list", "--oneline"]);
+
+    // Then: one `name host user port` row per connection, nothing else.
+    assert!(list.status.success());
+    let stdout = String::from_utf8_lossy(&list.stdout);
+    let mut rows: Vec<_> = stdout.lines().collect();
+    rows.sort();
+    assert_eq!(
+        rows,
+        vec![
+            "db01\t10.0.1.5\tdba\t2222",
+            "web-prod\tweb.example.com\tdeploy\t22"
+        ]
+    );
+
+    // And: filters still apply.
+    let tagged = run_bssh(home, &["list", "--oneline", "-t", "prod"]);
+    assert_eq!(
+        String::from_utf8_lossy(&tagged.stdout),
+        "web-prod\tweb.example.com\tdeploy\t22\n"
+    );
+}
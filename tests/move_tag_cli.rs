@@ -0,0 +1,99 @@
+mod common;
+
+use common::run_bssh;
+use std::path::Path;
+
+fn tags(config_home: &Path) -> Vec<(String, Vec<String>)> {
+    let output = run_bssh(config_home, &["list", "--json"]);
+    let connections: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("list --json should print JSON");
+    let mut tags: Vec<_> = connections
+        .as_array()
+        .expect("list --json should print an array")
+        .iter()
+        .map(|conn| {
+            (
+                conn["name"].as_str().unwrap_or_default().to_string(),
+                conn["tags"]
+                    .as_array()
+                    .expect("tags should be an array")
+                    .iter()
+                    .map(|t| t.as_str().unwrap_or_default().to_string())
+                    .collect(),
+            )
+        })
+        .collect();
+    tags.sort();
+    tags
+}
+
+#[test]
+fn move_replaces_the_tag_on_every_matching_connection() {
+    // Given: two staging hosts (one already tagged preprod) and a production host.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    for (name, tags) in [
+        ("api-stg", vec!["staging", "web"]),
+        ("web-stg", vec!["staging", "preprod"]),
+        ("web-prod", vec!["prod"]),
+    ] {
+        let host = format!("{name}.corp");
+        let mut args = vec!["add", name, host.as_str()];
+        for tag in tags {
+            args.extend(["--tags", tag]);
+        }
+        assert!(run_bssh(home, &args).status.success());
+    }
+
+    // When: the staging group is moved to preprod.
+    let moved = run_bssh(
+        home,
+        &["move", "--from-tag", "staging", "--to-tag", "preprod"],
+    );
+
+    // Then: both staging hosts were retagged without duplicates, production untouched.
+    assert!(
+        moved.status.success(),
+        "{}",
+        String::from_utf8_lossy(&moved.stderr)
+    );
+    assert!(
+        String::from_utf8_lossy(&moved.stdout).contains("Moved 2 connection(s)"),
+        "{}",
+        String::from_utf8_lossy(&moved.stdout)
+    );
+    assert_eq!(
+        tags(home),
+        vec![
+            (
+                "api-stg".to_string(),
+                vec!["preprod".to_string(), "web".to_string()]
+            ),
+            ("web-prod".to_string(), vec!["prod".to_string()]),
+            ("web-stg".to_string(), vec!["preprod".to_string()]),
+        ]
+    );
+}
+
+#[test]
+fn move_with_no_matching_tag_changes_nothing() {
+    // Given: a single host tagged prod.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    assert!(
+        run_bssh(home, &["add", "web", "web.corp", "--tags", "prod"])
+            .status
+            .success()
+    );
+
+    // When: a tag that nobody carries (but is a substring of one) is moved.
+    let moved = run_bssh(home, &["move", "--from-tag", "pro", "--to-tag", "live"]);
+
+    // Then: the command succeeds and reports that nothing matched.
+    assert!(moved.status.success());
+    assert!(String::from_utf8_lossy(&moved.stdout).contains("No connections tagged 'pro'"));
+    assert_eq!(
+        tags(home),
+        vec![("web".to_string(), vec!["prod".to_string()])]
+    );
+}
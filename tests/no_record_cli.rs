@@ -0,0 +1,77 @@
+mod common;
+
+use common::run_bssh;
+use std::path::Path;
+
+fn assert_no_sessions(home: &Path, name: &str) {
+    let history = run_bssh(home, &["history"]);
+    assert!(history.status.success());
+    let stdout = String::from_utf8_lossy(&history.stdout);
+    assert!(!stdout.contains(name), "{stdout}");
+}
+
+fn show(home: &Path, name: &str) -> serde_json::Value {
+    let show = run_bssh(home, &["show", name, "--json"]);
+    serde_json::from_slice(&show.stdout).expect("show should be JSON")
+}
+
+#[test]
+fn no_record_leaves_no_history() {
+    // Given: a saved connection (the bastion selects the subprocess transport).
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    common::install_stub_ssh(home);
+    assert!(run_bssh(
+        home,
+        &["add", "web-prod", "web.corp", "--bastion", "jump.corp"]
+    )
+    .status
+    .success());
+
+    // When: connecting with --no-record.
+    let connect = run_bssh(home, &["connect", "web-prod", "--no-record"]);
+
+    // Then: the session ran but nothing about it was written.
+    assert!(
+        connect.status.success(),
+        "{}",
+        String::from_utf8_lossy(&connect.stderr)
+    );
+    assert_no_sessions(home, "web-prod");
+    let conn = show(home, "web-prod");
+    assert_eq!(conn["use_count"], 0, "{conn}");
+    assert!(conn["last_used"].is_null(), "{conn}");
+}
+
+#[test]
+fn auto_save_history_off_skips_session_rows() {
+    // Given: history recording turned off in the config file.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    common::install_stub_ssh(home);
+    assert!(run_bssh(
+        home,
+        &["add", "db01", "db01.corp", "--bastion", "jump.corp"]
+    )
+    .status
+    .success());
+    let config_file = common::env_dir(home).join("config.json");
+    let mut config: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&config_file).unwrap()).unwrap();
+    config["auto_save_history"] = serde_json::Value::Bool(false);
+    std::fs::write(&config_file, config.to_string()).expect("config should be written");
+
+    // When: connecting normally.
+    let connect = run_bssh(home, &["connect", "db01"]);
+
+    // Then: no session row was written, but the use still counts.
+    assert!(
+        connect.status.success(),
+        "{}",
+        String::from_utf8_lossy(&connect.stderr)
+    );
+    assert_no_sessions(home, "db01");
+    let conn = show(home, "db01");
+    assert_eq!(conn["use_count"], 1, "{conn}");
+    assert!(!conn["last_used"].is_null(), "{conn}");
+}
@@ -0,0 +1,116 @@
+mod common;
+
+use common::run_bssh;
+use std::path::Path;
+
+/// Make `$EDITOR` a script that runs `body` with the file to edit as `$1`
+fn set_editor(home: &Path, body: &str) {
+    common::install_stub(home, "editor", &format!("{body}\n"));
+}
+
+fn notes(home: &Path) -> serde_json::Value {
+    let show = run_bssh(home, &["show", "db-prod", "--json"]);
+    let connection: serde_json::Value =
+        serde_json::from_slice(&show.stdout).expect("show should be JSON");
+    connection["notes"].clone()
+}
+
+#[test]
+fn notes_are_edited_in_the_editor_and_shown() {
+    // Given: a saved connection and an editor that writes a runbook.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    set_editor(
+        home,
+        "printf 'Failover: promote db-replica\\nOn call: #dba\\n' > \"$1\"",
+    );
+    assert!(run_bssh(home, &["add", "db-prod", "db.corp"])
+        .status
+        .success());
+
+    // When: editing the notes.
+    let note = run_bssh(home, &["note", "db-prod"]);
+
+    // Then: they are saved and --show prints them.
+    assert!(
+        note.status.success(),
+        "{}",
+        String::from_utf8_lossy(&note.stderr)
+    );
+    assert_eq!(notes(home), "Failover: promote db-replica\nOn call: #dba");
+    let shown = run_bssh(home, &["note", "db-prod", "--show"]);
+    assert_eq!(
+        String::from_utf8_lossy(&shown.stdout),
+        "Failover: promote db-replica\nOn call: #dba\n"
+    );
+}
+
+#[test]
+fn an_aborted_edit_keeps_the_old_notes() {
+    // Given: a connection with notes.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    set_editor(home, "echo 'keep me' > \"$1\"");
+    assert!(run_bssh(home, &["add", "db-prod", "db.corp"])
+        .status
+        .success());
+    assert!(run_bssh(home, &["note", "db-prod"]).status.success());
+
+    // When: the editor changes the file but exits nonzero.
+    set_editor(home, "echo 'half-typed' > \"$1\"\nexit 1");
+    let note = run_bssh(home, &["note", "db-prod"]);
+
+    // Then: the command fails and nothing is saved.
+    assert!(!note.status.success());
+    assert!(String::from_utf8_lossy(&note.stderr).contains("notes not saved"));
+    assert_eq!(notes(home), "keep me");
+}
+
+#[test]
+fn emptying_the_file_removes_the_notes() {
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    set_editor(home, "echo 'temporary' > \"$1\"");
+    assert!(run_bssh(home, &["add", "db-prod", "db.corp"])
+        .status
+        .success());
+    assert!(run_bssh(home, &["note", "db-prod"]).status.success());
+
+    set_editor(home, ": > \"$1\"");
+    assert!(run_bssh(home, &["note", "db-prod"]).status.success());
+
+    assert_eq!(notes(home), serde_json::Value::Null);
+}
+
+#[test]
+fn edit_sets_and_clears_notes() {
+    // Given: a saved connection (the editor must not run for `edit`).
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    set_editor(home, "exit 1");
+    assert!(run_bssh(home, &["add", "db-prod", "db.corp"])
+        .status
+        .success());
+
+    // When: setting the notes from the command line.
+    let set = run_bssh(home, &["edit", "db-prod", "--notes", "On call: #dba"]);
+
+    // Then: they are stored as given.
+    assert!(
+        set.status.success(),
+        "{}",
+        String::from_utf8_lossy(&set.stderr)
+    );
+    assert_eq!(notes(home), "On call: #dba");
+
+    // When: clearing them.
+    let clear = run_bssh(home, &["edit", "db-prod", "--clear-notes"]);
+
+    // Then: the connection has no notes left.
+    assert!(
+        clear.status.success(),
+        "{}",
+        String::from_utf8_lossy(&clear.stderr)
+    );
+    assert_eq!(notes(home), serde_json::Value::Null);
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use common::run_bssh;
+use std::path::Path;
+use std::process::Output;
+
+fn stdout(output: &Output) -> String {
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn add_connections(home: &Path) {
+    assert!(
+        run_bssh(home, &["add", "web-prod", "web.corp", "-t", "prod"])
+            .status
+            .success()
+    );
+    assert!(
+        run_bssh(home, &["add", "db01", "db01.corp", "--port", "2222"])
+            .status
+            .success()
+    );
+}
+
+#[test]
+fn every_record_command_speaks_json() {
+    // Given: two saved connections.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    add_connections(home);
+
+    // When/Then: --output json prints parseable JSON for each command.
+    let list: serde_json::Value =
+        serde_json::from_str(&stdout(&run_bssh(home, &["list", "--output", "json"]))).unwrap();
+    assert_eq!(list.as_array().map(Vec::len), Some(2));
+
+    let show: serde_json::Value = serde_json::from_str(&stdout(&run_bssh(
+        home,
+        &["show", "db01", "--output", "json"],
+    )))
+    .unwrap();
+    assert_eq!(show["port"], 2222);
+
+    let stats: serde_json::Value =
+        serde_json::from_str(&stdout(&run_bssh(home, &["stats", "--output", "json"]))).unwrap();
+    assert_eq!(stats["total_connections"], 2);
+
+    let history: serde_json::Value =
+        serde_json::from_str(&stdout(&run_bssh(home, &["history", "--output", "json"]))).unwrap();
+    assert_eq!(history, serde_json::json!([]));
+
+    let sessions: serde_json::Value =
+        serde_json::from_str(&stdout(&run_bssh(home, &["sessions", "--output", "json"]))).unwrap();
+    assert_eq!(sessions, serde_json::json!([]));
+
+    // --json is the same as --output json.
+    assert_eq!(
+        stdout(&run_bssh(home, &["list", "--json"])),
+        stdout(&run_bssh(home, &["list", "--output", "json"]))
+    );
+}
+
+#[test]
+fn every_record_command_speaks_csv() {
+    // Given: two saved connections.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    add_connections(home);
+
+    // When/Then: --output csv prints a header row and one row per record.
+    let list = stdout(&run_bssh(home, &["list", "--output", "csv"]));
+    let lines: Vec<&str> = list.lines().collect();
+    assert!(lines[0].starts_with("name,host,user,port,"), "{list}");
+    assert_eq!(lines.len(), 3, "{list}");
+    assert!(lines.iter().any(|l| l.starts_with("db01,db01.corp,")));
+
+    let show = stdout(&run_bssh(home, &["show", "web-prod", "--output", "csv"]));
+    assert_eq!(show.lines().count(), 2, "{show}");
+    assert!(show.contains(",prod,"), "{show}");
+
+    let stats = stdout(&run_bssh(home, &["stats", "--output", "csv"]));
+    assert!(stats.starts_with("metric,value\n"), "{stats}");
+    assert!(stats.contains("total_connections,2\n"), "{stats}");
+
+    let history = stdout(&run_bssh(home, &["history", "--output", "csv"]));
+    assert!(history.starts_with("connection,started_at,"), "{history}");
+
+    let sessions = stdout(&run_bssh(home, &["sessions", "--output", "csv"]));
+    assert_eq!(sessions, "id,connection,pid,started,duration_secs,alive\n");
+
+    assert!(run_bssh(home, &["alias", "add", "wp", "web-prod"])
+        .status
+        .success());
+    let aliases = stdout(&run_bssh(home, &["alias", "list", "--output", "csv"]));
+    assert_eq!(aliases, "alias,connection,uses\nwp,web-prod,0\n");
+}
+
+#[test]
+fn json_and_output_cannot_be_combined() {
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+
+    let both = run_bssh(home, &["list", "--json", "--output", "csv"]);
+
+    assert!(!both.status.success());
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use common::run_bssh;
+use std::net::TcpListener;
+use std::path::Path;
+
+/// Stub `ssh` that fails on hosts whose name starts with "bad"
+fn install_stub_ssh(config_home: &Path) {
+    common::install_stub(
+        config_home,
+        "ssh",
+        "case \"$*\" in *@bad*) echo 'Permission denied (publickey).' >&2; exit 255;; esac\nexit 0\n",
+    );
+}
+
+fn add(home: &Path, name: &str, host: &str, bastion: &str) {
+    assert!(run_bssh(
+        home,
+        &["add", name, host, "-t", "web", "--bastion", bastion]
+    )
+    .status
+    .success());
+}
+
+#[test]
+fn every_tagged_host_is_tested() {
+    // Given: a bastion that accepts TCP, and three hosts behind it.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    install_stub_ssh(home);
+    let listener = TcpListener::bind("127.0.0.1:0").expect("listener should bind");
+    let bastion = listener.local_addr().unwrap().to_string();
+    add(home, "web-1", "web1.corp", &bastion);
+    add(home, "web-2", "web2.corp", &bastion);
+    add(home, "web-bad", "bad.corp", &bastion);
+
+    // When: pinging the tag, two at a time.
+    let ping = run_bssh(home, &["ping", "--tag", "web", "--parallel", "2"]);
+
+    // Then: each host has a row, and the failure makes the run unreachable.
+    let stdout = String::from_utf8_lossy(&ping.stdout);
+    assert_eq!(ping.status.code(), Some(5), "{stdout}");
+    for host in ["web-1", "web-2"] {
+        let row = stdout
+            .lines()
+            .find(|line| line.starts_with(&format!("{host} ")))
+            .unwrap_or_else(|| panic!("no table row for {host}: {stdout}"));
+        assert!(row.contains("ok"), "{row}");
+    }
+    let row = stdout
+        .lines()
+        .find(|line| line.starts_with("web-bad "))
+        .unwrap_or_else(|| panic!("no table row for web-bad: {stdout}"));
+    assert!(row.contains("fail") && row.ends_with("Permission denied (publickey)."));
+    assert!(stdout.contains("2 reachable, 1 unreachable"), "{stdout}");
+    assert!(String::from_utf8_lossy(&ping.stderr).contains("web-bad"));
+}
+
+#[test]
+fn a_closed_bastion_port_is_reported_without_ssh() {
+    // Given: a host behind a bastion port nothing listens on.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    install_stub_ssh(home);
+    let closed = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .to_string();
+    add(home, "web-1", "web1.corp", &closed);
+
+    // When: pinging the tag.
+    let ping = run_bssh(home, &["ping", "--tag", "web"]);
+
+    // Then: the row names the TCP endpoint that failed.
+    assert_eq!(ping.status.code(), Some(5));
+    let stdout = String::from_utf8_lossy(&ping.stdout);
+    assert!(stdout.contains(&format!("TCP {closed}")), "{stdout}");
+}
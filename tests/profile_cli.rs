@@ -0,0 +1,58 @@
+mod common;
+
+use std::process::Output;
+
+fn names(output: &Output) -> Vec<String> {
+    let listed: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("list --json should be valid JSON");
+    listed
+        .as_array()
+        .expect("list --json should be an array")
+        .iter()
+        .map(|c| c["name"].as_str().unwrap_or_default().to_string())
+        .collect()
+}
+
+#[test]
+fn profile_flag_and_subcommand_select_isolated_environments() {
+    // Given: two profiles created through the `profile` alias.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    // Each call picks its own environment, so none goes through `--env`.
+    let run = |args: &[&str]| {
+        common::bssh(home)
+            .args(args)
+            .output()
+            .expect("bssh command should run")
+    };
+    for name in ["client-a", "client-b"] {
+        assert!(run(&["profile", "create", name]).status.success());
+    }
+
+    // When: a connection is added under one --profile only.
+    assert!(
+        run(&["--profile", "client-a", "add", "web", "web.example.com"])
+            .status
+            .success()
+    );
+
+    // Then: each profile sees only its own inventory, same as --env.
+    assert_eq!(
+        names(&run(&["--profile", "client-a", "list", "--json"])),
+        vec!["web".to_string()]
+    );
+    assert!(names(&run(&["--env", "client-b", "list", "--json"])).is_empty());
+    assert!(home
+        .join("bayesian-ssh/environments/client-a/history.db")
+        .exists());
+
+    // And: profiles are listed and deleted like environments.
+    let list = run(&["profile", "list"]);
+    let stdout = String::from_utf8_lossy(&list.stdout);
+    assert!(
+        stdout.contains("client-a") && stdout.contains("client-b"),
+        "{stdout}"
+    );
+    assert!(run(&["profile", "delete", "client-b"]).status.success());
+    assert!(!home.join("bayesian-ssh/environments/client-b").exists());
+}
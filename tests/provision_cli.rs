@@ -0,0 +1,99 @@
+mod common;
+
+use common::run_bssh;
+use std::path::Path;
+
+fn show(config_home: &Path, name: &str) -> serde_json::Value {
+    let output = run_bssh(config_home, &["show", name, "--json"]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("show should be JSON")
+}
+
+#[test]
+fn hosts_are_expanded_and_copy_the_template() {
+    // Given: a template connection and one node that already exists.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    let add = run_bssh(
+        home,
+        &[
+            "add",
+            "web",
+            "web.prod",
+            "-u",
+            "deploy",
+            "-p",
+            "2222",
+            "-b",
+            "jump.corp",
+            "-t",
+            "web",
+        ],
+    );
+    assert!(add.status.success());
+    assert!(run_bssh(home, &["add", "web02", "old-web02.prod"])
+        .status
+        .success());
+
+    // When: provisioning three nodes from a quoted brace pattern.
+    let provision = run_bssh(
+        home,
+        &[
+            "provision",
+            "--template",
+            "web",
+            "--hosts",
+            "web{01..03}.prod",
+        ],
+    );
+
+    // Then: the free names are created with the template's settings and
+    // the taken one is skipped untouched.
+    assert!(
+        provision.status.success(),
+        "{}",
+        String::from_utf8_lossy(&provision.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&provision.stdout);
+    assert!(stdout.contains("Created 2 connection(s) from 'web', skipped 1"));
+    for name in ["web01", "web03"] {
+        let conn = show(home, name);
+        assert_eq!(conn["host"], format!("{}.prod", name));
+        assert_eq!(conn["user"], "deploy");
+        assert_eq!(conn["port"], 2222);
+        assert_eq!(conn["bastion"], "jump.corp");
+        assert_eq!(conn["tags"], serde_json::json!(["web"]));
+    }
+    assert_eq!(show(home, "web02")["host"], "old-web02.prod");
+}
+
+#[test]
+fn dry_run_saves_nothing() {
+    // Given: a template connection.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    assert!(run_bssh(home, &["add", "db", "db.corp"]).status.success());
+
+    // When: provisioning with --dry-run.
+    let provision = run_bssh(
+        home,
+        &[
+            "provision",
+            "--template",
+            "db",
+            "--hosts",
+            "db{1..2}.corp",
+            "--dry-run",
+        ],
+    );
+
+    // Then: the plan is printed but no connection is created.
+    assert!(provision.status.success());
+    assert!(String::from_utf8_lossy(&provision.stdout).contains("Would create db1 (db1.corp)"));
+    let missing = run_bssh(home, &["show", "db1", "--json"]);
+    assert!(!missing.status.success());
+}
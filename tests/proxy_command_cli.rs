@@ -0,0 +1,86 @@
+mod common;
+
+use common::run_bssh;
+
+#[test]
+fn proxy_command_is_expanded_into_the_ssh_invocation() {
+    // Given: a connection reached through a proxy binary, with a default
+    // bastion configured.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    assert!(
+        run_bssh(home, &["config", "--default-bastion", "jump.corp"])
+            .status
+            .success()
+    );
+    let add = run_bssh(
+        home,
+        &[
+            "add",
+            "vault",
+            "vault.corp",
+            "--proxy-command",
+            "sso-ssh-proxy --target %h:%p",
+        ],
+    );
+    assert!(add.status.success());
+
+    // When: previewing the connect.
+    let connect = run_bssh(home, &["connect", "vault", "--dry-run"]);
+
+    // Then: the tokens are filled in and the default bastion is not used.
+    let stdout = String::from_utf8_lossy(&connect.stdout);
+    assert!(
+        stdout.contains("'ProxyCommand=sso-ssh-proxy --target vault.corp:22'"),
+        "{stdout}"
+    );
+    assert!(!stdout.contains("-J"), "{stdout}");
+    let show = run_bssh(home, &["show", "vault"]);
+    let stdout = String::from_utf8_lossy(&show.stdout);
+    assert!(
+        stdout.contains("Proxy:    sso-ssh-proxy --target %h:%p"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn proxy_command_and_bastion_are_mutually_exclusive() {
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+
+    // Both on the command line is rejected.
+    let add = run_bssh(
+        home,
+        &[
+            "add",
+            "vault",
+            "vault.corp",
+            "-b",
+            "jump.corp",
+            "--proxy-command",
+            "sso-ssh-proxy %h",
+        ],
+    );
+    assert!(!add.status.success());
+
+    // Setting a bastion later replaces the ProxyCommand.
+    assert!(run_bssh(
+        home,
+        &[
+            "add",
+            "vault",
+            "vault.corp",
+            "--proxy-command",
+            "sso-ssh-proxy %h"
+        ]
+    )
+    .status
+    .success());
+    assert!(run_bssh(home, &["edit", "vault", "--bastion", "jump.corp"])
+        .status
+        .success());
+    let show = run_bssh(home, &["show", "vault"]);
+    let stdout = String::from_utf8_lossy(&show.stdout);
+    assert!(stdout.contains("jump.corp"), "{stdout}");
+    assert!(!stdout.contains("Proxy:"), "{stdout}");
+}
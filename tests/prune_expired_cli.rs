@@ -0,0 +1,59 @@
+mod common;
+
+use common::run_bssh;
+
+#[test]
+fn prune_removes_only_expired_connections() {
+    // Given: one host that expires almost immediately, one in two hours, one permanent.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    assert!(
+        run_bssh(home, &["add", "ci-runner", "10.0.0.5", "--expires", "1s"])
+            .status
+            .success()
+    );
+    assert!(
+        run_bssh(home, &["add", "spot-1", "10.0.0.6", "--expires", "2h"])
+            .status
+            .success()
+    );
+    assert!(run_bssh(home, &["add", "web", "web.internal"])
+        .status
+        .success());
+    std::thread::sleep(std::time::Duration::from_millis(1500));
+
+    // Then: list shows the expiry badges.
+    let list = run_bssh(home, &["list"]);
+    let stdout = String::from_utf8_lossy(&list.stdout);
+    assert!(stdout.contains("⏳ expired"), "{stdout}");
+    assert!(stdout.contains("⏳ 1h left"), "{stdout}");
+
+    // When: expired connections are pruned.
+    let prune = run_bssh(home, &["--yes", "prune", "--expired"]);
+
+    // Then: only the expired host is gone.
+    assert!(prune.status.success());
+    assert!(String::from_utf8_lossy(&prune.stdout).contains("Removed 1 expired connection(s)"));
+    let list = run_bssh(home, &["list", "--json"]);
+    let connections: serde_json::Value =
+        serde_json::from_slice(&list.stdout).expect("list --json should print JSON");
+    let mut names: Vec<_> = connections
+        .as_array()
+        .expect("list --json should print an array")
+        .iter()
+        .map(|c| c["name"].as_str().unwrap_or_default().to_string())
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["spot-1", "web"]);
+}
+
+#[test]
+fn expires_rejects_unknown_units() {
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let add = run_bssh(
+        temp_dir.path(),
+        &["add", "x", "x.internal", "--expires", "2y"],
+    );
+    assert!(!add.status.success());
+    assert!(String::from_utf8_lossy(&add.stderr).contains("unit must be s, m, h, d or w"));
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use common::run_bssh;
+use std::path::Path;
+
+fn recent_names(config_home: &Path, extra: &[&str]) -> Vec<String> {
+    let mut args = vec!["recent"];
+    args.extend_from_slice(extra);
+    let output = run_bssh(config_home, &args);
+    assert!(output.status.success());
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (_, rest) = line.trim().split_once(". ")?;
+            rest.split_whitespace().next().map(String::from)
+        })
+        .collect()
+}
+
+#[test]
+fn recent_keeps_distinct_targets_in_use_order() {
+    // Given: two saved connections (the bastion selects the subprocess transport).
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    common::install_stub_ssh(home);
+    for (name, host) in [("alpha", "a.example.com"), ("beta", "b.example.com")] {
+        assert!(
+            run_bssh(home, &["add", name, host, "--bastion", "jump.example.com"])
+                .status
+                .success()
+        );
+    }
+
+    // When: the user connects alpha, beta, then alpha again.
+    for target in ["alpha", "beta", "alpha"] {
+        assert!(run_bssh(home, &["connect", target]).status.success());
+    }
+
+    // Then: each target appears once, newest first, or oldest first with --order.
+    assert_eq!(recent_names(home, &[]), ["alpha", "beta"]);
+    assert_eq!(recent_names(home, &["--order"]), ["beta", "alpha"]);
+}
+
+#[test]
+fn failed_connect_does_not_count_as_recent() {
+    // Given: a saved connection whose ssh fails before a session exists.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    common::install_stub_ssh(home);
+    std::fs::write(home.join("bin").join("ssh"), "#!/bin/sh\nexit 255\n")
+        .expect("failing stub ssh should be written");
+    assert!(run_bssh(
+        home,
+        &[
+            "add",
+            "alpha",
+            "a.example.com",
+            "--bastion",
+            "jump.example.com"
+        ]
+    )
+    .status
+    .success());
+
+    // When: the user tries to connect.
+    run_bssh(home, &["connect", "alpha"]);
+
+    // Then: neither the recent ring nor last_used records the attempt.
+    assert!(recent_names(home, &[]).is_empty());
+    let list = run_bssh(home, &["list", "--json"]);
+    let listed: serde_json::Value =
+        serde_json::from_slice(&list.stdout).expect("list --json should be valid JSON");
+    assert!(listed[0]["last_used"].is_null());
+}
@@ -0,0 +1,120 @@
+mod common;
+
+use common::run_bssh;
+use std::path::Path;
+
+fn history(config_home: &Path) -> String {
+    let history = run_bssh(config_home, &["history"]);
+    String::from_utf8_lossy(&history.stdout).into_owned()
+}
+
+/// Add `web-prod` (behind a bastion, so the stub ssh is used) and open one session.
+fn connect_once(config_home: &Path) {
+    common::install_stub_ssh(config_home);
+    assert!(run_bssh(
+        config_home,
+        &[
+            "add",
+            "web-prod",
+            "web.example.com",
+            "-b",
+            "jump.example.com"
+        ]
+    )
+    .status
+    .success());
+    assert!(run_bssh(config_home, &["connect", "web-prod"])
+        .status
+        .success());
+}
+
+#[test]
+fn sessions_are_deleted_with_the_connection_by_default() {
+    // Given: a connection with one session.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    connect_once(home);
+
+    // When: removing it.
+    assert!(run_bssh(home, &["--yes", "remove", "web-prod"])
+        .status
+        .success());
+
+    // Then: its history is gone.
+    assert!(!history(home).contains("web-prod"));
+}
+
+#[test]
+fn retained_sessions_stay_in_history_under_the_old_name() {
+    // Given: retention enabled and a connection with one session.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    assert!(
+        run_bssh(home, &["config", "--retain-sessions-on-delete", "true"])
+            .status
+            .success()
+    );
+    connect_once(home);
+
+    // When: removing it.
+    assert!(run_bssh(home, &["--yes", "remove", "web-prod"])
+        .status
+        .success());
+
+    // Then: history still lists the session, filterable by name, and
+    // db check does not treat it as an orphan.
+    assert!(history(home).contains("web-prod"));
+    let filtered = run_bssh(home, &["history", "--connection", "web"]);
+    assert!(String::from_utf8_lossy(&filtered.stdout).contains("web-prod"));
+    let check = run_bssh(home, &["db", "check"]);
+    let stdout = String::from_utf8_lossy(&check.stdout);
+    assert!(!stdout.contains("session(s) reference"), "{stdout}");
+}
+
+#[test]
+fn older_session_tables_are_migrated_in_place() {
+    // Given: a database whose sessions table still requires connection_id.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    connect_once(home);
+    let db = rusqlite::Connection::open(common::database_path(home)).expect("database should open");
+    db.execute_batch(
+        "ALTER TABLE sessions RENAME TO sessions_current;
+         CREATE TABLE sessions (
+             id TEXT PRIMARY KEY,
+             connection_id TEXT NOT NULL,
+             started_at TEXT NOT NULL,
+             ended_at TEXT,
+             status TEXT NOT NULL,
+             pid INTEGER,
+             exit_code INTEGER,
+             transport TEXT,
+             alias_used TEXT,
+             label TEXT,
+             FOREIGN KEY (connection_id) REFERENCES connections (id)
+         );
+         INSERT INTO sessions
+         SELECT id, connection_id, started_at, ended_at, status, pid, exit_code, transport, alias_used, label
+         FROM sessions_current;
+         DROP TABLE sessions_current;",
+    )
+    .expect("old schema should be recreated");
+    drop(db);
+
+    // When: bssh opens it with retention enabled and a connection is removed.
+    assert!(
+        run_bssh(home, &["config", "--retain-sessions-on-delete", "true"])
+            .status
+            .success()
+    );
+    assert!(history(home).contains("web-prod"));
+    let remove = run_bssh(home, &["--yes", "remove", "web-prod"]);
+
+    // Then: the old session survives the removal.
+    assert!(
+        remove.status.success(),
+        "{}",
+        String::from_utf8_lossy(&remove.stderr)
+    );
+    assert!(history(home).contains("web-prod"));
+}
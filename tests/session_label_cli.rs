@@ -0,0 +1,44 @@
+mod common;
+
+use common::run_bssh;
+
+#[test]
+fn history_filters_sessions_by_label() {
+    // Given: two connections (the bastion selects the subprocess transport).
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    common::install_stub_ssh(home);
+    for (name, host) in [
+        ("web-prod", "web.example.com"),
+        ("db-prod", "db.example.com"),
+    ] {
+        assert!(
+            run_bssh(home, &["add", name, host, "--bastion", "jump.example.com"])
+                .status
+                .success()
+        );
+    }
+
+    // When: only one session is opened with a label.
+    assert!(
+        run_bssh(home, &["connect", "web-prod", "--label", "incident-4821"])
+            .status
+            .success()
+    );
+    assert!(run_bssh(home, &["connect", "db-prod"]).status.success());
+
+    // Then: the label filter returns just that session, and the label is shown.
+    let labelled = run_bssh(home, &["history", "--label", "incident-4821"]);
+    assert!(labelled.status.success());
+    let stdout = String::from_utf8_lossy(&labelled.stdout);
+    assert!(stdout.contains("web-prod"), "{stdout}");
+    assert!(stdout.contains("incident-4821"), "{stdout}");
+    assert!(!stdout.contains("db-prod"), "{stdout}");
+
+    let all = run_bssh(home, &["history"]);
+    let stdout = String::from_utf8_lossy(&all.stdout);
+    assert!(
+        stdout.contains("web-prod") && stdout.contains("db-prod"),
+        "{stdout}"
+    );
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use common::run_bssh;
+use std::path::Path;
+
+fn install_stub_ssh(config_home: &Path) {
+    common::install_stub(
+        config_home,
+        "ssh",
+        "echo hello from the remote\necho warning from ssh >&2\nexit 0\n",
+    );
+}
+
+#[test]
+fn connect_log_writes_a_transcript_and_remembers_it() {
+    // Given: a saved connection.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    install_stub_ssh(home);
+    assert!(run_bssh(home, &["add", "web-prod", "web.corp"])
+        .status
+        .success());
+
+    // When: connecting with --log.
+    let log = home.join("web-prod.log");
+    let connect = run_bssh(
+        home,
+        &["connect", "web-prod", "--log", log.to_str().unwrap()],
+    );
+
+    // Then: the session output is in the transcript and history points at it.
+    assert!(
+        connect.status.success(),
+        "{}",
+        String::from_utf8_lossy(&connect.stderr)
+    );
+    let transcript = std::fs::read_to_string(&log).expect("transcript should exist");
+    assert!(transcript.contains("hello from the remote"), "{transcript}");
+
+    let export = home.join("history.json");
+    assert!(
+        run_bssh(home, &["history", "--export", export.to_str().unwrap()])
+            .status
+            .success()
+    );
+    let sessions: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&export).unwrap()).unwrap();
+    assert_eq!(sessions[0]["transcript"], log.to_str().unwrap());
+}
+
+#[test]
+fn exec_log_captures_stdout_and_stderr() {
+    // Given: a connection (the bastion selects the subprocess transport).
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    install_stub_ssh(home);
+    assert!(run_bssh(
+        home,
+        &["add", "db01", "db01.corp", "--bastion", "jump.corp"]
+    )
+    .status
+    .success());
+
+    // When: running a command with --log.
+    let log = home.join("uptime.log");
+    let exec = run_bssh(
+        home,
+        &[
+            "exec",
+            "db01",
+            "--log",
+            log.to_str().unwrap(),
+            "--",
+            "uptime",
+        ],
+    );
+
+    // Then: both streams are printed and written to the file.
+    assert!(
+        exec.status.success(),
+        "{}",
+        String::from_utf8_lossy(&exec.stderr)
+    );
+    assert!(String::from_utf8_lossy(&exec.stdout).contains("hello from the remote"));
+    let written = std::fs::read_to_string(&log).expect("log should exist");
+    assert!(written.contains("hello from the remote"), "{written}");
+    assert!(written.contains("warning from ssh"), "{written}");
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use common::run_bssh;
+
+#[test]
+fn sessions_json_is_empty_array_and_unknown_kill_fails() {
+    // Given: a fresh environment with no recorded sessions.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+
+    // When: a script asks for the session list and kills a missing id.
+    let list = run_bssh(home, &["sessions", "--active", "--json"]);
+    let kill = run_bssh(home, &["sessions", "--kill", "deadbeef"]);
+
+    // Then: the list is valid empty JSON and the kill reports the bad id.
+    assert!(list.status.success());
+    let listed: serde_json::Value =
+        serde_json::from_slice(&list.stdout).expect("sessions --json should be valid JSON");
+    assert_eq!(listed, serde_json::json!([]));
+
+    assert!(!kill.status.success());
+    let stderr = String::from_utf8_lossy(&kill.stderr);
+    assert!(stderr.contains("deadbeef"), "{stderr}");
+}
+
+#[test]
+fn ad_hoc_sessions_are_listed_while_active() {
+    // Given: a stub ssh that lists active sessions while it "runs".
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    let script = format!(
+        "'{}' --env {} sessions --json > '{}'\nexit 0\n",
+        env!("CARGO_BIN_EXE_bayesian-ssh"),
+        common::ENV,
+        home.join("listed.json").display()
+    );
+    common::install_stub(home, "ssh", &script);
+
+    // When: connecting to a host that is not saved.
+    let connect = run_bssh(home, &["connect", "10.0.0.9", "--bastion", "jump.corp"]);
+
+    // Then: the session showed up under the host it was opened for.
+    assert!(
+        connect.status.success(),
+        "{}",
+        String::from_utf8_lossy(&connect.stderr)
+    );
+    let listed: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(home.join("listed.json")).expect("ssh should have run"),
+    )
+    .expect("sessions --json should be valid JSON");
+    assert_eq!(listed[0]["connection"], "10.0.0.9", "{listed}");
+}
@@ -0,0 +1,61 @@
+mod common;
+
+use common::run_bssh;
+use std::path::Path;
+
+/// Stub `ssh-keyscan` that reports one ed25519 key with the given base64 body.
+fn install_stub_keyscan(config_home: &Path, key_b64: &str) {
+    let script = format!(
+        "echo '# web.example.com:22 SSH-2.0-OpenSSH_9.6' >&2\necho 'web.example.com ssh-ed25519 {}'\n",
+        key_b64
+    );
+    common::install_stub(config_home, "ssh-keyscan", &script);
+}
+
+#[test]
+fn host_key_fingerprints_are_cached_and_changes_flagged() {
+    // Given: a connection whose host key is scanned once.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    assert!(run_bssh(home, &["add", "web-prod", "web.example.com"])
+        .status
+        .success());
+    install_stub_keyscan(
+        home,
+        "AAAAC3NzaC1lZDI1NTE5AAAAIAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA",
+    );
+    let first = run_bssh(home, &["show", "web-prod", "--host-key"]);
+    assert!(first.status.success());
+    let stdout = String::from_utf8_lossy(&first.stdout);
+    assert!(
+        stdout.contains("Host keys: ssh-ed25519 SHA256:"),
+        "{stdout}"
+    );
+    assert!(!String::from_utf8_lossy(&first.stderr).contains("changed"));
+
+    // Then: plain `show` displays the cached value without scanning.
+    let cached = run_bssh(home, &["show", "web-prod", "--json"]);
+    let value: serde_json::Value =
+        serde_json::from_slice(&cached.stdout).expect("show --json should be valid JSON");
+    let fingerprint = value["host_key_fingerprint"]
+        .as_str()
+        .expect("fingerprint should be cached")
+        .to_string();
+    assert!(fingerprint.starts_with("ssh-ed25519 SHA256:"));
+
+    // When: the server presents a different key.
+    install_stub_keyscan(
+        home,
+        "AAAAC3NzaC1lZDI1NTE5AAAAIP//////////////////////////////////////////",
+    );
+    let second = run_bssh(home, &["show", "web-prod", "--host-key"]);
+
+    // Then: the change is reported with the previously cached fingerprint.
+    assert!(second.status.success());
+    let stderr = String::from_utf8_lossy(&second.stderr);
+    assert!(
+        stderr.contains("changed since it was last checked"),
+        "{stderr}"
+    );
+    assert!(stderr.contains(&fingerprint), "{stderr}");
+}
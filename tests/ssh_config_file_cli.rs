@@ -0,0 +1,82 @@
+mod common;
+
+use common::run_bssh;
+use std::path::Path;
+
+/// Stub `ssh` that records its arguments and exits 0.
+fn install_recording_ssh(config_home: &Path) {
+    common::install_stub(
+        config_home,
+        "ssh",
+        &format!(
+            "echo \"$@\" >> '{}'\nexit 0\n",
+            config_home.join("args").display()
+        ),
+    );
+}
+
+fn ssh_args(config_home: &Path) -> String {
+    std::fs::read_to_string(config_home.join("args")).unwrap_or_default()
+}
+
+#[test]
+fn connect_passes_the_connection_config_file_as_f() {
+    // Given: a connection with its own ssh config file.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    install_recording_ssh(home);
+    let lab_config = home.join("lab_config");
+    std::fs::write(&lab_config, "Host *\n  ServerAliveInterval 30\n").unwrap();
+    let lab_config = lab_config.to_string_lossy().into_owned();
+    assert!(run_bssh(
+        home,
+        &["add", "gpu01", "gpu01.lab", "--ssh-config", &lab_config]
+    )
+    .status
+    .success());
+
+    // When: connecting.
+    let output = run_bssh(home, &["connect", "gpu01"]);
+
+    // Then: ssh gets `-F <file>` and show lists it.
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(ssh_args(home).starts_with(&format!("-F {lab_config} ")));
+    let show = run_bssh(home, &["show", "gpu01"]);
+    assert!(String::from_utf8_lossy(&show.stdout).contains(&format!("SSH config: {lab_config}")));
+}
+
+#[test]
+fn connect_uses_the_default_config_file_and_rejects_missing_ones() {
+    // Given: a global default config file and a plain connection.
+    let temp_dir = tempfile::tempdir().expect("temp dir should be created");
+    let home = temp_dir.path();
+    install_recording_ssh(home);
+    let default_config = home.join("bssh_config");
+    std::fs::write(&default_config, "Host *\n").unwrap();
+    let default_config = default_config.to_string_lossy().into_owned();
+    assert!(
+        run_bssh(home, &["config", "--default-ssh-config", &default_config])
+            .status
+            .success()
+    );
+    assert!(run_bssh(home, &["add", "web", "web.internal"])
+        .status
+        .success());
+
+    // When/Then: the default is passed as -F.
+    assert!(run_bssh(home, &["connect", "web"]).status.success());
+    assert!(ssh_args(home).starts_with(&format!("-F {default_config} ")));
+
+    // When: the file disappears.
+    std::fs::remove_file(&default_config).unwrap();
+    let output = run_bssh(home, &["connect", "web"]);
+
+    // Then: bssh fails before running ssh again.
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("does not exist"));
+    assert_eq!(ssh_args(home).lines().count(), 1);
+}